@@ -0,0 +1,247 @@
+// Server configuration from environment variables and command-line flags, loaded once at startup.
+
+use std::sync::OnceLock;
+
+/// Offline mode: DATAFLOW_MCP_OFFLINE=1|true or the --offline flag.
+const OFFLINE_ENV: &str = "DATAFLOW_MCP_OFFLINE";
+/// Catalog description language: DATAFLOW_MCP_LOCALE=ru or --locale=ru (LANG-style values like ru_RU.UTF-8 work too).
+const LOCALE_ENV: &str = "DATAFLOW_MCP_LOCALE";
+
+/// Directories path inputs may be read from: DATAFLOW_MCP_ALLOWED_PATHS (comma-separated) or --allow-path=DIR.
+const ALLOWED_PATHS_ENV: &str = "DATAFLOW_MCP_ALLOWED_PATHS";
+/// URL prefixes url inputs may be fetched from: DATAFLOW_MCP_ALLOWED_URLS (comma-separated) or --allow-url=PREFIX.
+const ALLOWED_URLS_ENV: &str = "DATAFLOW_MCP_ALLOWED_URLS";
+/// Largest tool output in characters before it is split into parts: DATAFLOW_MCP_MAX_OUTPUT_CHARS or --max-output-chars=N.
+const MAX_OUTPUT_ENV: &str = "DATAFLOW_MCP_MAX_OUTPUT_CHARS";
+/// Organizational policy rules (YAML/JSON) checked by validation: DATAFLOW_MCP_POLICY_FILE or --policy-file=PATH.
+const POLICY_FILE_ENV: &str = "DATAFLOW_MCP_POLICY_FILE";
+/// Naming conventions (prefix / suffix / pattern for names, topics and tables): DATAFLOW_MCP_NAMING_FILE or
+/// --naming-file=PATH.
+const NAMING_FILE_ENV: &str = "DATAFLOW_MCP_NAMING_FILE";
+
+/// Locales the catalogs carry descriptions for; anything else falls back to DEFAULT_LOCALE.
+pub const SUPPORTED_LOCALES: [&str; 2] = ["en", "ru"];
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Features that reach the network (the what passed to Config::ensure_online); all of them fail in offline mode.
+pub const NETWORK_FEATURES: [&str; 7] = [
+    "Kafka Connect REST import",
+    "Live CRD schema validation",
+    "Bulk apply",
+    "Orphan report",
+    "DataFlow export",
+    "Operator capability query",
+    "Reading input from a URL",
+];
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// No network access: tools that would reach the network fail fast, validation uses bundled schemas only.
+    pub offline: bool,
+    /// Language of catalog descriptions, one of SUPPORTED_LOCALES (empty: DEFAULT_LOCALE).
+    pub locale: String,
+    /// Directories path inputs may be read from; empty: path inputs are disabled.
+    pub allowed_paths: Vec<String>,
+    /// URL prefixes url inputs may be fetched from; empty: url inputs are disabled.
+    pub allowed_urls: Vec<String>,
+    /// Outputs longer than this many characters are returned in parts (fetch_result_chunk); 0: no limit.
+    pub max_output_chars: usize,
+    /// Policy rules file loaded at startup; None: no policies.
+    pub policy_file: Option<String>,
+    /// Naming conventions file loaded at startup; None: no conventions.
+    pub naming_file: Option<String>,
+}
+
+/// "ru_RU.UTF-8" / "ru-RU" -> "ru" when supported, else DEFAULT_LOCALE.
+fn normalize_locale(value: &str) -> String {
+    let lang = value
+        .split(['_', '-', '.'])
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    if SUPPORTED_LOCALES.contains(&lang.as_str()) {
+        lang
+    } else {
+        DEFAULT_LOCALE.to_string()
+    }
+}
+
+impl Config {
+    /// Reads the environment and the process arguments.
+    pub fn from_env_and_args(env: impl Fn(&str) -> Option<String>, args: &[String]) -> Self {
+        let flag = |v: Option<String>| {
+            matches!(
+                v.as_deref().map(str::to_lowercase).as_deref(),
+                Some("1" | "true" | "yes")
+            )
+        };
+        let arg_locale = args
+            .iter()
+            .position(|a| a == "--locale")
+            .and_then(|i| args.get(i + 1).cloned())
+            .or_else(|| {
+                args.iter()
+                    .find_map(|a| a.strip_prefix("--locale=").map(str::to_string))
+            });
+        let list = |var: &str, flag: &str| -> Vec<String> {
+            let from_env = env(var).unwrap_or_default();
+            from_env
+                .split(',')
+                .map(str::to_string)
+                .chain(
+                    args.iter()
+                        .filter_map(|a| a.strip_prefix(flag).map(str::to_string)),
+                )
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        let max_output = args
+            .iter()
+            .find_map(|a| a.strip_prefix("--max-output-chars=").map(str::to_string))
+            .or_else(|| env(MAX_OUTPUT_ENV));
+        let file = |var: &str, flag: &str| {
+            args.iter()
+                .find_map(|a| a.strip_prefix(flag).map(str::to_string))
+                .or_else(|| env(var))
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+        };
+        Config {
+            offline: flag(env(OFFLINE_ENV)) || args.iter().any(|a| a == "--offline"),
+            locale: normalize_locale(&arg_locale.or_else(|| env(LOCALE_ENV)).unwrap_or_default()),
+            allowed_paths: list(ALLOWED_PATHS_ENV, "--allow-path="),
+            allowed_urls: list(ALLOWED_URLS_ENV, "--allow-url="),
+            max_output_chars: max_output.and_then(|v| v.trim().parse().ok()).unwrap_or(0),
+            policy_file: file(POLICY_FILE_ENV, "--policy-file="),
+            naming_file: file(NAMING_FILE_ENV, "--naming-file="),
+        }
+    }
+
+    /// Locale for catalog descriptions.
+    pub fn locale(&self) -> &str {
+        if self.locale.is_empty() {
+            DEFAULT_LOCALE
+        } else {
+            &self.locale
+        }
+    }
+
+    /// Err with a clear message when what (e.g. "Kafka Connect REST import") needs the network in offline mode.
+    pub fn ensure_online(&self, what: &str) -> Result<(), String> {
+        if self.offline {
+            return Err(format!(
+                "{} needs network access, but the server runs in offline mode ({} / --offline)",
+                what, OFFLINE_ENV
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Loads the configuration; later calls keep the first value.
+pub fn init() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let args: Vec<String> = std::env::args().collect();
+        Config::from_env_and_args(|k| std::env::var(k).ok(), &args)
+    })
+}
+
+/// Current configuration (defaults if init was not called, e.g. in tests).
+pub fn current() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_from_env_and_args() {
+        let none = |_: &str| None;
+        assert!(!Config::from_env_and_args(none, &[]).offline);
+        assert!(
+            Config::from_env_and_args(none, &["dataflow-mcp".to_string(), "--offline".to_string()])
+                .offline
+        );
+        let env = |k: &str| (k == OFFLINE_ENV).then(|| "TRUE".to_string());
+        let config = Config::from_env_and_args(env, &[]);
+        let err = config
+            .ensure_online("Kafka Connect REST import")
+            .unwrap_err();
+        assert!(err.starts_with("Kafka Connect REST import needs network access"));
+        assert!(Config::default().ensure_online("x").is_ok());
+    }
+
+    #[test]
+    fn test_locale_from_env_and_args() {
+        let env = |k: &str| (k == LOCALE_ENV).then(|| "ru_RU.UTF-8".to_string());
+        assert_eq!(Config::from_env_and_args(env, &[]).locale(), "ru");
+        let args = [
+            "dataflow-mcp".to_string(),
+            "--locale".to_string(),
+            "en".to_string(),
+        ];
+        assert_eq!(Config::from_env_and_args(env, &args).locale(), "en");
+        assert_eq!(
+            Config::from_env_and_args(|_| None, &["--locale=de".to_string()]).locale(),
+            "en"
+        );
+        assert_eq!(Config::default().locale(), "en");
+    }
+
+    #[test]
+    fn test_allow_lists_from_env_and_args() {
+        let env =
+            |k: &str| (k == ALLOWED_PATHS_ENV).then(|| "/srv/manifests, /tmp/exports".to_string());
+        let config = Config::from_env_and_args(
+            env,
+            &["--allow-url=https://git.example.com/raw/".to_string()],
+        );
+        assert_eq!(config.allowed_paths, vec!["/srv/manifests", "/tmp/exports"]);
+        assert_eq!(config.allowed_urls, vec!["https://git.example.com/raw/"]);
+        assert!(Config::default().allowed_paths.is_empty());
+    }
+
+    #[test]
+    fn test_max_output_chars() {
+        let env = |k: &str| (k == MAX_OUTPUT_ENV).then(|| "20000".to_string());
+        assert_eq!(Config::from_env_and_args(env, &[]).max_output_chars, 20000);
+        assert_eq!(
+            Config::from_env_and_args(env, &["--max-output-chars=500".to_string()])
+                .max_output_chars,
+            500
+        );
+        assert_eq!(
+            Config::from_env_and_args(|_| None, &["--max-output-chars=lots".to_string()])
+                .max_output_chars,
+            0
+        );
+    }
+
+    #[test]
+    fn test_policy_and_naming_files() {
+        let env =
+            |k: &str| (k == POLICY_FILE_ENV).then(|| "/etc/dataflow/policies.yaml".to_string());
+        assert_eq!(
+            Config::from_env_and_args(env, &[]).policy_file.as_deref(),
+            Some("/etc/dataflow/policies.yaml")
+        );
+        let args = ["--policy-file=team.yaml".to_string()];
+        assert_eq!(
+            Config::from_env_and_args(env, &args).policy_file.as_deref(),
+            Some("team.yaml")
+        );
+        assert_eq!(Config::from_env_and_args(|_| None, &[]).policy_file, None);
+        let naming = Config::from_env_and_args(
+            |_| None,
+            &["--naming-file=/etc/dataflow/naming.yaml".to_string()],
+        );
+        assert_eq!(
+            naming.naming_file.as_deref(),
+            Some("/etc/dataflow/naming.yaml")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! DataFlow MCP server: generate manifests and migrate Kafka Connect to DataFlow.
 
+mod config;
 mod tools;
 mod types;
 
@@ -12,48 +13,580 @@ use rmcp::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tools::sarif::{FindingFormat, FindingKind};
+
+// Where a tool reads its main input from instead of the inline field (see tools::input::resolve_input).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct InputSource {
+    /// Read the input from this file instead (must be under a directory allowed by DATAFLOW_MCP_ALLOWED_PATHS)
+    #[serde(default)]
+    path: Option<String>,
+    /// Fetch the input from this URL instead (must match a prefix allowed by DATAFLOW_MCP_ALLOWED_URLS)
+    #[serde(default)]
+    url: Option<String>,
+}
+
+// Output format of generation and migration tools (see DataFlowMcpService::formatted).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct OutputOptions {
+    /// Output format: yaml (default, with comment headers) or json (a List for several manifests; comments are returned as a separate note)
+    #[serde(default, alias = "outputFormat")]
+    output_format: Option<String>,
+}
+
+// Credentials handling of generation and migration tools (see with_secrets).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct SecretsOption {
+    /// Replace passwords, tokens and connection strings with secretKeyRef references to <name>-credentials and list the Secret to create
+    #[serde(default, alias = "useSecrets")]
+    use_secrets: bool,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 struct GenerateParams {
-    /// Short description of the data flow
+    /// Short description of the data flow, e.g. "from kafka topic orders at k1:9092, mask email, to postgres table
+    /// analytics.orders"; types, config fields and transformations not given are inferred from it
     #[serde(default)]
     description: Option<String>,
-    /// Source type: kafka, postgresql, trino
-    source_type: String,
-    /// Sink type: kafka, postgresql, trino
-    sink_type: String,
-    /// Source config as JSON object string (optional)
+    /// Source type: kafka, postgresql, trino, clickhouse, syslog (optional when the description names it)
     #[serde(default)]
-    source_config: Option<String>,
-    /// Sink config as JSON object string (optional)
+    source_type: Option<String>,
+    /// Sink type: kafka, postgresql, trino, clickhouse, elasticsearch, s3 (optional when the description names it)
     #[serde(default)]
-    sink_config: Option<String>,
-    /// Transformations as JSON array string (optional)
+    sink_type: Option<String>,
+    /// Source connector fields as a JSON object (optional)
     #[serde(default)]
-    transformations: Option<String>,
+    #[schemars(schema_with = "source_config_schema")]
+    source_config: Option<serde_json::Value>,
+    /// Sink connector fields as a JSON object (optional)
+    #[serde(default)]
+    #[schemars(schema_with = "sink_config_schema")]
+    sink_config: Option<serde_json::Value>,
+    /// Transformations as a JSON array (optional)
+    #[serde(default)]
+    #[schemars(schema_with = "transformations_schema")]
+    transformations: Option<serde_json::Value>,
     /// DataFlow resource name (optional)
     #[serde(default)]
     name: Option<String>,
     /// Kubernetes namespace (optional)
     #[serde(default)]
     namespace: Option<String>,
+    /// Naming: exact (default), hash-suffix (name-7f3a, stable hash of the spec) or sequence (name-1)
+    #[serde(default, alias = "nameStrategy")]
+    name_strategy: Option<String>,
+    /// Number of flow pods, spec.parallelism (1-128); DataFlow v1 has no spec.replicas, so replicas is accepted as an alias
+    #[serde(default, alias = "replicas")]
+    parallelism: Option<u32>,
+    /// CPU request, e.g. 250m (spec.resources.requests.cpu); without any cpu/memory value defaults are filled in
+    #[serde(default, alias = "cpuRequest")]
+    cpu_request: Option<String>,
+    /// CPU limit, e.g. 1 (spec.resources.limits.cpu)
+    #[serde(default, alias = "cpuLimit")]
+    cpu_limit: Option<String>,
+    /// Memory request, e.g. 256Mi (spec.resources.requests.memory)
+    #[serde(default, alias = "memoryRequest")]
+    memory_request: Option<String>,
+    /// Memory limit, e.g. 512Mi (spec.resources.limits.memory)
+    #[serde(default, alias = "memoryLimit")]
+    memory_limit: Option<String>,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    /// Like use_secrets, and also emit a Kubernetes Secret document (stringData) holding the given credentials
+    #[serde(default, alias = "secretManifest")]
+    secret_manifest: bool,
+    /// Dead-letter queue for failed messages: kafka (topic <name>-dlq) or postgresql (table <sink table>_dlq); transformations without onError send failures there
+    #[serde(default)]
+    dlq: Option<String>,
+    /// Schema Registry URL; adds a schemaRegistry block to every kafka (avro, protobuf, jsonschema) and s3 (avro, parquet) connector
+    #[serde(default, alias = "schemaRegistryUrl")]
+    schema_registry_url: Option<String>,
+    /// Schema Registry auth: basic (username and password) or bearer (token), read from the <name>-credentials Secret
+    #[serde(default, alias = "schemaRegistryAuth")]
+    schema_registry_auth: Option<String>,
+    /// Subject naming strategy: TopicNameStrategy (default), RecordNameStrategy or TopicRecordNameStrategy
+    #[serde(default, alias = "subjectNameStrategy")]
+    subject_name_strategy: Option<String>,
+    /// Also emit a NetworkPolicy limiting the flow pods' egress to DNS and the brokers, hosts and URLs of the manifest
+    #[serde(default, alias = "networkPolicy")]
+    network_policy: bool,
+    /// Git repository URL of the manifests; when set, an ArgoCD Application syncing the DataFlow from it follows the manifest
+    #[serde(default, alias = "argocdRepoUrl")]
+    argocd_repo_url: Option<String>,
+    /// Directory of the DataFlow manifest in the repository (default dataflows/<name>)
+    #[serde(default, alias = "argocdPath")]
+    argocd_path: Option<String>,
+    /// Git revision ArgoCD tracks (default HEAD)
+    #[serde(default, alias = "argocdRevision")]
+    argocd_revision: Option<String>,
+    /// Namespace ArgoCD deploys the DataFlow to (default the manifest namespace)
+    #[serde(default, alias = "argocdNamespace")]
+    argocd_namespace: Option<String>,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+/// Generated or migrated YAML with its credentials moved to secretKeyRef references when use_secrets is set.
+fn with_secrets(out: Result<String, String>, use_secrets: bool) -> Result<String, String> {
+    match out {
+        Ok(yaml) if use_secrets => tools::credentials::use_secrets(&yaml, false),
+        other => other,
+    }
+}
+
+fn source_config_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::Schema::try_from(tools::docs::generate_param_schema("source_config"))
+        .unwrap_or_default()
+}
+
+fn sink_config_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::Schema::try_from(tools::docs::generate_param_schema("sink_config"))
+        .unwrap_or_default()
+}
+
+fn transformations_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::Schema::try_from(tools::docs::generate_param_schema("transformations"))
+        .unwrap_or_default()
+}
+
+fn transformation_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::Schema::try_from(tools::docs::generate_param_schema("transformation"))
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct TemplateParams {
+    /// Template profile: kafka-to-warehouse, cdc-replication or pii-masking-pipeline
+    profile: String,
+    /// Template inputs as a JSON object, e.g. {"brokers": "k1:9092", "topic": "orders", "warehouse": "clickhouse"}
+    #[serde(default)]
+    inputs: serde_json::Map<String, serde_json::Value>,
+    /// DataFlow resource name (optional; derived from the inputs, e.g. orders-to-clickhouse)
+    #[serde(default)]
+    name: Option<String>,
+    /// Kubernetes namespace (optional)
+    #[serde(default)]
+    namespace: Option<String>,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct BulkParams {
+    /// Flows to generate: a JSON array of rows ({"name", "source", "sink", "options"}; source/sink a type or {"type", ...config}) or a CSV table with columns name, source, sink, source.<field>, sink.<field>, namespace, description, transformations, parallelism, dlq, name_strategy (or give path or url)
+    #[serde(default)]
+    rows: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// Columns shared by every row, e.g. {"namespace": "shop", "source.brokers": ["k:9092"]}; a row's own values win
+    #[serde(default)]
+    defaults: serde_json::Map<String, serde_json::Value>,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct KustomizeEnvironment {
+    /// Environment name, the overlay directory (dev, stage, prod)
+    name: String,
+    /// Namespace of the environment (optional)
+    #[serde(default)]
+    namespace: Option<String>,
+    /// Kafka brokers of the environment; replace the brokers of every kafka source, sink and DLQ (optional)
+    #[serde(default)]
+    brokers: Vec<String>,
+    /// Suffix added to every resource name, e.g. -dev (optional)
+    #[serde(default, alias = "nameSuffix")]
+    name_suffix: Option<String>,
+    /// spec.parallelism in this environment (optional)
+    #[serde(default)]
+    parallelism: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct KustomizeParams {
+    /// Base DataFlow YAML, one or more documents (or give path or url)
+    #[serde(default)]
+    config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// One overlay per environment
+    environments: Vec<KustomizeEnvironment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct MonitoringParams {
+    /// DataFlow YAML, one or more documents (or give path or url)
+    #[serde(default)]
+    config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// service (ServiceMonitor, default) or pod (PodMonitor)
+    #[serde(default)]
+    monitor: Option<String>,
+    /// Scrape interval (default 30s)
+    #[serde(default)]
+    interval: Option<String>,
+    /// Consumer lag in messages above which the example lag alert fires (default 10000)
+    #[serde(default, alias = "lagThreshold")]
+    lag_threshold: Option<u64>,
+    /// Labels for the generated resources, e.g. {"release": "prometheus"} for the Prometheus instance's selector
+    #[serde(default)]
+    labels: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct MapSchemaParams {
+    /// YAML manifest to extend (or give path or url)
+    #[serde(default)]
+    config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// Schema of the source messages: a JSON Schema with properties or an Avro record with fields
+    #[serde(alias = "sourceSchema")]
+    source_schema: serde_json::Value,
+    /// Target table: a column list (names, "name TYPE" strings or {name, type} objects) or a JSON Schema / Avro record
+    target: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct InferTransformationsParams {
+    /// Sample input messages: a JSON object or an array of them
+    samples: serde_json::Value,
+    /// Desired output of the first sample: a JSON object, or an array of objects when one input becomes several messages
+    expected: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct AddTransformationParams {
+    /// YAML manifest to extend (or give path or url)
+    #[serde(default)]
+    config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// Transformation to add, e.g. {"type": "mask", "mask": {"fields": ["$.email"]}}
+    #[schemars(schema_with = "transformation_schema")]
+    transformation: serde_json::Value,
+    /// Add to spec.branches.<branch>.transformations instead of spec.transformations (optional)
+    #[serde(default)]
+    branch: Option<String>,
+    /// 0-based index to insert at; appended when omitted
+    #[serde(default)]
+    position: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 struct ValidateParams {
-    /// YAML manifest to validate
+    /// YAML manifest to validate (or give path or url)
+    #[serde(default)]
+    config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// Output format: text (default), sarif (SARIF 2.1.0 JSON for code review tools) or json (findings with severity, path, line and column)
+    #[serde(default)]
+    format: Option<String>,
+    /// Manifest path in the repository, used as the SARIF artifact URI (default dataflow.yaml)
+    #[serde(default, alias = "artifactUri")]
+    artifact_uri: Option<String>,
+    /// Also reject fields outside the manifest schema: spec, errorHandling, branches and transformation blocks (default false)
+    #[serde(default)]
+    strict: bool,
+    /// Also check against the openAPIV3Schema of the DataFlow CRD installed in the cluster (kubectl; default false)
+    #[serde(default, alias = "liveSchema")]
+    live_schema: bool,
+    /// Optional kubectl context for live_schema (default: current context)
+    #[serde(default)]
+    context: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct PolicyParams {
+    /// YAML manifest to check (or give path or url)
+    #[serde(default)]
+    config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// Policy rules (YAML or JSON, same format as the policy file) to use instead of the rules loaded at startup
+    #[serde(default)]
+    policies: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct LintParams {
+    /// YAML manifest to lint (or give path or url)
+    #[serde(default)]
     config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    /// Output format: text (default), sarif (SARIF 2.1.0 JSON for code review tools) or json (findings with severity, path, line and column)
+    #[serde(default)]
+    format: Option<String>,
+    /// Manifest path in the repository, used as the SARIF artifact URI (default dataflow.yaml)
+    #[serde(default, alias = "artifactUri")]
+    artifact_uri: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct SignParams {
+    /// YAML manifest to sign or verify
+    config: String,
+    /// Optional HMAC key; when set the digest is signed (sign) or the signature is checked (verify)
+    #[serde(default)]
+    key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ChangeLogParams {
+    /// Previous YAML manifest revision
+    old: String,
+    /// New YAML manifest revision
+    new: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct CapabilitiesParams {
+    /// Optional operator version endpoint URL returning {version, sources, sinks, transformations}; default reads the DataFlow CRD via kubectl
+    #[serde(default)]
+    endpoint: Option<String>,
+    /// Optional kubectl context (default: current context)
+    #[serde(default)]
+    context: Option<String>,
+    /// Optional YAML manifest to check against the operator's capabilities
+    #[serde(default)]
+    config: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ApplyParams {
+    /// Multi-document YAML: DataFlows plus the Secrets, ConfigMaps, topics etc. they need
+    manifests: String,
+    /// Optional kubectl context (default: current context)
+    #[serde(default)]
+    context: Option<String>,
+    /// Only return the apply order, do not touch the cluster (default false)
+    #[serde(default, alias = "dryRun")]
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct OrphanReportParams {
+    /// Directory with the manifests from git (.yaml/.yml, recursive; must be under a directory allowed by DATAFLOW_MCP_ALLOWED_PATHS); default: manifests saved in the session workspace
+    #[serde(default)]
+    directory: Option<String>,
+    /// Namespace to compare (also the default for manifests without metadata.namespace); default: all namespaces
+    #[serde(default)]
+    namespace: Option<String>,
+    /// Optional kubectl context (default: current context)
+    #[serde(default)]
+    context: Option<String>,
+    /// Confirmed orphans ("namespace/name") to generate deletion manifests for
+    #[serde(default)]
+    confirmed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ExportDeployedParams {
+    /// DataFlow name (metadata.name)
+    name: String,
+    /// Namespace (default: default)
+    #[serde(default)]
+    namespace: Option<String>,
+    /// Optional kubectl context (default: current context)
+    #[serde(default)]
+    context: Option<String>,
+}
+
+// Options of a Kafka Connect migration shared by the migration and its report, so both describe the same flows.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ConnectMigrationOptions {
+    /// Naming: exact (default), hash-suffix (name-7f3a, stable hash of the spec) or sequence (name-1, name-2 across the batch)
+    #[serde(default, alias = "nameStrategy")]
+    name_strategy: Option<String>,
+    /// Optional worker.properties content: brokers, converters and security settings connectors inherit from the worker
+    #[serde(default, alias = "workerProperties")]
+    worker_properties: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 struct MigrateParams {
-    /// Kafka Connect connector config(s) as JSON: single object or array of two (source, sink)
+    /// Kafka Connect connector config(s) as JSON (single object or array), one connector .properties file or Strimzi KafkaConnector resource YAML (or give path or url)
+    #[serde(default)]
+    kafka_connect_config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    #[serde(flatten)]
+    connect: ConnectMigrationOptions,
+    /// Also return a JSON companion mapping every manifest field to the connector property it came from
+    #[serde(default)]
+    provenance: bool,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct NifiMigrateParams {
+    /// NiFi flow definition JSON (registry export / "Download flow definition", flowContents with processors and controllerServices)
+    #[serde(alias = "nifiFlow")]
+    nifi_flow: String,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct AirbyteMigrateParams {
+    /// Airbyte connection export JSON: source and destination (with configuration), schedule, syncCatalog or configurations.streams
+    #[serde(alias = "airbyteConnection")]
+    airbyte_connection: String,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct BenthosMigrateParams {
+    /// Benthos / Redpanda Connect pipeline YAML (input, pipeline.processors, output)
+    #[serde(alias = "benthosConfig")]
+    benthos_config: String,
+    /// metadata.name of the generated DataFlow (default benthos-pipeline)
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct VectorMigrateParams {
+    /// Vector configuration, TOML or YAML (sources, transforms, sinks)
+    #[serde(alias = "vectorConfig")]
+    vector_config: String,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct FluentBitMigrateParams {
+    /// Fluent Bit configuration: classic [INPUT]/[FILTER]/[OUTPUT] sections or YAML (pipeline.inputs/filters/outputs)
+    #[serde(alias = "fluentbitConfig")]
+    fluentbit_config: String,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct TelegrafMigrateParams {
+    /// telegraf.conf content (TOML with [[inputs.*]] and [[outputs.*]] plugins)
+    #[serde(alias = "telegrafConfig")]
+    telegraf_config: String,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct KafkaStreamsMigrateParams {
+    /// Output of Kafka Streams Topology#describe() (Sub-topology / Source / Processor / Sink lines)
+    topology: String,
+    /// Kafka bootstrap servers, comma-separated (default localhost:9092, marked as filled)
+    #[serde(default, alias = "bootstrapServers")]
+    bootstrap_servers: Option<String>,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct MigrateReportParams {
+    /// Kafka Connect connector config(s) as JSON (single object or array), one connector .properties file or Strimzi KafkaConnector resource YAML (or give path or url)
+    #[serde(default)]
     kafka_connect_config: String,
+    #[serde(flatten)]
+    source: InputSource,
+    #[serde(flatten)]
+    connect: ConnectMigrationOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct MigrateClusterParams {
+    /// Kafka Connect REST base URL, e.g. http://connect:8083
+    url: String,
+    /// Optional auth: "user:password" (Basic) or "Bearer <token>"
+    #[serde(default)]
+    auth: Option<String>,
+    /// Naming: exact (default), hash-suffix (name-7f3a, stable hash of the spec) or sequence (name-1, name-2 across the batch)
+    #[serde(default, alias = "nameStrategy")]
+    name_strategy: Option<String>,
+    #[serde(flatten)]
+    secrets: SecretsOption,
+    #[serde(flatten)]
+    output: OutputOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct WorkspaceSaveParams {
+    /// Entry kind: manifest, template or answer
+    kind: String,
+    /// Entry key (e.g. manifest name or question id)
+    key: String,
+    /// Content to save (YAML manifest, template JSON, answer text)
+    content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct WorkspaceImportParams {
+    /// JSON bundle produced by export_workspace
+    bundle: String,
+    /// Replace the current workspace instead of merging into it (default false)
+    #[serde(default)]
+    replace: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ConvertApiVersionParams {
+    /// DataFlow YAML manifest(s) in an older apiVersion; other documents are kept as is
+    config: String,
+    /// Target apiVersion (default dataflow.dataflow.io/v1)
+    #[serde(default, alias = "targetApiVersion")]
+    target_api_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct JsonSchemaParams {
+    /// apiVersion to describe (default dataflow.dataflow.io/v1)
+    #[serde(default, alias = "apiVersion")]
+    api_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct FetchChunkParams {
+    /// Continuation token from the footer of a chunked result (e.g. "r3")
+    token: String,
+    /// 0-based part index; the footer names the next one
+    index: usize,
 }
 
 #[derive(Clone)]
 struct DataFlowMcpService {
     tool_router: rmcp::handler::server::tool::ToolRouter<Self>,
+    workspace: Arc<Mutex<tools::workspace::Workspace>>,
+    chunks: Arc<Mutex<tools::chunks::ResultChunks>>,
+    stats: Arc<Mutex<tools::session_stats::SessionStats>>,
 }
 
 #[tool_router]
@@ -61,52 +594,590 @@ impl DataFlowMcpService {
     fn new() -> Self {
         Self {
             tool_router: Self::tool_router(),
+            workspace: Arc::new(Mutex::new(tools::workspace::Workspace::default())),
+            chunks: Arc::new(Mutex::new(tools::chunks::ResultChunks::default())),
+            stats: Arc::new(Mutex::new(tools::session_stats::SessionStats::default())),
         }
     }
 
-    #[tool(description = "Generate a DataFlow YAML manifest from source/sink types and optional configs")]
+    /// Text content, split into parts when longer than the configured max output (see fetch_result_chunk).
+    fn paged(&self, out: String) -> Content {
+        let mut chunks = self.chunks.lock().unwrap_or_else(|e| e.into_inner());
+        Content::text(chunks.paginate(out, config::current().max_output_chars))
+    }
+
+    /// Generated or migrated manifests in the requested output_format; JSON header comments follow as a second content.
+    fn formatted(&self, out: String, output_format: Option<&str>) -> CallToolResult {
+        match tools::output::to_output_format(&out, output_format) {
+            Ok((text, notes)) => {
+                let mut content = vec![self.paged(text)];
+                if !notes.is_empty() {
+                    content.push(Content::text(notes));
+                }
+                CallToolResult::success(content)
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(e)]),
+        }
+    }
+
+    #[tool(
+        description = "Generate a DataFlow YAML manifest from source/sink types and optional configs, or infer them from a plain-language description"
+    )]
     async fn generate_dataflow_manifest(
         &self,
         params: rmcp::handler::server::wrapper::Parameters<GenerateParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         let p = params.0;
-        match tools::manifest::generate_dataflow_manifest(
+        let resources = tools::resources::resources_block(&[
+            ("requests", "cpu", p.cpu_request.as_deref()),
+            ("requests", "memory", p.memory_request.as_deref()),
+            ("limits", "cpu", p.cpu_limit.as_deref()),
+            ("limits", "memory", p.memory_limit.as_deref()),
+        ]);
+        let generated = tools::manifest::generate_dataflow_manifest(
             p.description.as_deref(),
-            &p.source_type,
-            &p.sink_type,
-            p.source_config.as_deref(),
-            p.sink_config.as_deref(),
-            p.transformations.as_deref(),
+            p.source_type.as_deref(),
+            p.sink_type.as_deref(),
+            p.source_config.as_ref(),
+            p.sink_config.as_ref(),
+            p.transformations.as_ref(),
             p.name.as_deref(),
             p.namespace.as_deref(),
+            p.name_strategy.as_deref(),
+            p.parallelism,
+            resources.as_ref(),
+            p.dlq.as_deref(),
+        );
+        let generated = match (
+            generated,
+            p.schema_registry_url.as_deref().filter(|u| !u.is_empty()),
         ) {
-            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            (Ok(yaml), Some(url)) => tools::schema_registry::with_schema_registry(
+                &yaml,
+                url,
+                p.schema_registry_auth.as_deref(),
+                p.subject_name_strategy.as_deref(),
+            ),
+            (other, _) => other,
+        };
+        let generated = match generated {
+            Ok(yaml) if p.secret_manifest => tools::credentials::use_secrets(&yaml, true),
+            other => with_secrets(other, p.secrets.use_secrets),
+        };
+        let generated = match generated {
+            Ok(yaml) if p.network_policy => tools::network_policy::with_network_policy(&yaml),
+            other => other,
+        };
+        let generated = match (generated, p.argocd_repo_url) {
+            (Ok(yaml), Some(repo_url)) => {
+                let target = tools::gitops::ArgoCdTarget {
+                    repo_url,
+                    path: p.argocd_path,
+                    revision: p.argocd_revision,
+                    namespace: p.argocd_namespace,
+                };
+                tools::gitops::with_argocd_application(&yaml, &target)
+            }
+            (other, _) => other,
+        };
+        match generated {
+            Ok(out) => Ok(self.formatted(out, p.output.output_format.as_deref())),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
         }
     }
 
-    #[tool(description = "Validate a DataFlow YAML manifest (apiVersion, kind, spec.source, spec.sink)")]
+    #[tool(
+        description = "Validate a DataFlow YAML manifest (apiVersion, kind, spec.source, spec.sink); multi-document YAML is validated per document"
+    )]
     async fn validate_dataflow_manifest(
         &self,
         params: rmcp::handler::server::wrapper::Parameters<ValidateParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
-        let config = params.0.config;
-        match tools::manifest::validate_dataflow_manifest(&config) {
-            Ok(()) => Ok(CallToolResult::success(vec![Content::text("Конфигурация валидна.")])),
-            Err(errors) => Ok(CallToolResult::error(vec![Content::text(format!(
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let format = match tools::sarif::finding_format(p.format.as_deref()) {
+            Ok(f) => f,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let mut reports = tools::manifest::validate_dataflow_documents(&config, p.strict);
+        tools::policy::add_policy_findings(&config, tools::policy::active(), &mut reports);
+        tools::naming::add_convention_findings(&config, tools::naming::conventions(), &mut reports);
+        if p.live_schema {
+            match tools::crd_schema::fetch_crd(p.context.as_deref()).await {
+                Ok(crd) => tools::crd_schema::add_crd_findings(&config, &crd, &mut reports),
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            }
+        }
+        let valid = !reports.iter().any(|r| r.has_errors());
+        if format == FindingFormat::Json {
+            let out =
+                serde_json::to_string_pretty(&json!({ "valid": valid, "documents": reports }))
+                    .unwrap_or_default();
+            return Ok(if valid {
+                CallToolResult::success(vec![Content::text(out)])
+            } else {
+                CallToolResult::error(vec![Content::text(out)])
+            });
+        }
+        if format == FindingFormat::Sarif {
+            let findings: Vec<tools::manifest::Diagnostic> = reports
+                .iter()
+                .flat_map(|r| r.diagnostics.iter().cloned())
+                .collect();
+            let uri = p.artifact_uri.as_deref().or(p.source.path.as_deref());
+            let sarif = tools::sarif::to_sarif(FindingKind::Validate, &findings, uri);
+            return Ok(if valid {
+                CallToolResult::success(vec![Content::text(sarif)])
+            } else {
+                CallToolResult::error(vec![Content::text(sarif)])
+            });
+        }
+        let multi = reports.len() > 1;
+        let groups = tools::manifest::render_reports(&reports);
+        if !valid {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
                 "Ошибки валидации:\n{}",
-                errors.join("\n")
+                groups.join("\n\n")
+            ))]));
+        }
+        let mut out = if multi {
+            format!("Все документы валидны ({}).", reports.len())
+        } else {
+            "Конфигурация валидна.".to_string()
+        };
+        if !groups.is_empty() {
+            out.push_str(&format!("\n\nПредупреждения:\n{}", groups.join("\n\n")));
+        }
+        Ok(CallToolResult::success(vec![Content::text(out)]))
+    }
+
+    #[tool(
+        description = "Check DataFlow manifests against the organizational policy rules (the policy file loaded at startup, or inline policies), e.g. all sinks use TLS or the namespace matches the team label"
+    )]
+    async fn check_dataflow_policies(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<PolicyParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let inline = match p
+            .policies
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(tools::policy::parse_policies)
+        {
+            Some(Ok(policies)) => Some(policies),
+            Some(Err(e)) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            None => None,
+        };
+        let policies = inline.as_deref().unwrap_or(tools::policy::active());
+        if policies.is_empty() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "No policies configured: start the server with DATAFLOW_MCP_POLICY_FILE / --policy-file=PATH or pass policies".to_string(),
+            )]));
+        }
+        let reports = tools::policy::policy_reports(&config, policies);
+        let groups = tools::manifest::render_reports(&reports);
+        if groups.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "Все политики соблюдены (правил: {}).",
+                policies.len()
+            ))]));
+        }
+        let out = format!("Нарушения политик:\n{}", groups.join("\n\n"));
+        Ok(if reports.iter().any(|r| r.has_errors()) {
+            CallToolResult::error(vec![Content::text(out)])
+        } else {
+            CallToolResult::success(vec![Content::text(out)])
+        })
+    }
+
+    #[tool(
+        description = "Lint a DataFlow YAML manifest for non-fatal issues (e.g. trivial scripts that a declarative transformation could replace)"
+    )]
+    async fn lint_dataflow_manifest(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<LintParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let format = match tools::sarif::finding_format(p.format.as_deref()) {
+            Ok(f) => f,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match tools::lint::lint_dataflow_manifest(&config) {
+            Ok(warnings) if format == FindingFormat::Json => {
+                let diagnostics: Vec<tools::manifest::Diagnostic> = warnings
+                    .into_iter()
+                    .map(|w| {
+                        tools::manifest::Diagnostic::located(
+                            tools::manifest::Severity::Warning,
+                            w,
+                            &config,
+                        )
+                    })
+                    .collect();
+                let out = serde_json::to_string_pretty(&json!({ "diagnostics": diagnostics }))
+                    .unwrap_or_default();
+                Ok(CallToolResult::success(vec![Content::text(out)]))
+            }
+            Ok(warnings) if format == FindingFormat::Sarif => {
+                let findings: Vec<tools::manifest::Diagnostic> = warnings
+                    .into_iter()
+                    .map(|w| {
+                        tools::manifest::Diagnostic::located(
+                            tools::manifest::Severity::Warning,
+                            w,
+                            &config,
+                        )
+                    })
+                    .collect();
+                let sarif = tools::sarif::to_sarif(
+                    FindingKind::Lint,
+                    &findings,
+                    p.artifact_uri.as_deref().or(p.source.path.as_deref()),
+                );
+                Ok(CallToolResult::success(vec![Content::text(sarif)]))
+            }
+            Ok(warnings) if warnings.is_empty() => {
+                Ok(CallToolResult::success(vec![Content::text(
+                    "Замечаний нет.",
+                )]))
+            }
+            Ok(warnings) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Предупреждения:\n{}",
+                warnings.join("\n")
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Embed a canonical-content SHA-256 digest (and optionally an HMAC-SHA256 signature with the given key) as manifest annotations"
+    )]
+    async fn sign_dataflow_manifest(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<SignParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match tools::digest::sign_dataflow_manifest(&p.config, p.key.as_deref()) {
+            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Verify the content digest (and signature, when a key is given) embedded by sign_dataflow_manifest"
+    )]
+    async fn verify_dataflow_manifest(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<SignParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match tools::digest::verify_dataflow_manifest(&p.config, p.key.as_deref()) {
+            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            Err(problems) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Проверка не пройдена:\n{}",
+                problems.join("\n")
             ))])),
         }
     }
 
+    #[tool(
+        description = "Changelog entry (markdown) between two manifest revisions: changed fields, risk classification and a rollback hint"
+    )]
+    async fn generate_change_log(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<ChangeLogParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match tools::changelog::generate_change_log(&p.old, &p.new) {
+            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
     #[tool(description = "Migrate Kafka Connect connector config(s) to DataFlow YAML manifest")]
     async fn migrate_kafka_connect_to_dataflow(
         &self,
         params: rmcp::handler::server::wrapper::Parameters<MigrateParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
-        match tools::kafka_connect::migrate_kafka_connect_to_dataflow(&params.0.kafka_connect_config) {
-            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+        let p = params.0;
+        let input = match tools::input::resolve_input(
+            "kafka_connect_config",
+            &p.kafka_connect_config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let migrated = tools::kafka_connect::migrate_kafka_connect_to_dataflow(
+            &input,
+            p.connect.worker_properties.as_deref(),
+            p.connect.name_strategy.as_deref(),
+        );
+        let out = match with_secrets(migrated, p.secrets.use_secrets) {
+            Ok(out) => out,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        if !p.provenance {
+            return Ok(self.formatted(out, p.output.output_format.as_deref()));
+        }
+        match tools::migration_report::migration_provenance(
+            &input,
+            p.connect.worker_properties.as_deref(),
+            &out,
+        ) {
+            Ok(provenance) => {
+                let mut result = self.formatted(out, p.output.output_format.as_deref());
+                if result.is_error != Some(true) {
+                    result.content.push(self.paged(provenance));
+                }
+                Ok(result)
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Audit report (JSON) for a Kafka Connect migration: every connector property, where it was mapped in the DataFlow spec or that it was dropped, with a confidence level"
+    )]
+    async fn migrate_kafka_connect_report(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<MigrateReportParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let input = match tools::input::resolve_input(
+            "kafka_connect_config",
+            &p.kafka_connect_config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let report = tools::migration_report::migrate_kafka_connect_report(
+            &input,
+            p.connect.worker_properties.as_deref(),
+            p.connect.name_strategy.as_deref(),
+        );
+        match report {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Migrate an Apache NiFi flow definition (ConsumeKafka, PublishKafka, PutDatabaseRecord processors) to a DataFlow YAML manifest with notes for unmapped processors"
+    )]
+    async fn migrate_nifi_to_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<NifiMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        match with_secrets(
+            tools::nifi::migrate_nifi_to_dataflow(&params.0.nifi_flow),
+            params.0.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, params.0.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Migrate an Airbyte connection export (Postgres, Kafka, ClickHouse sources and destinations) to a DataFlow YAML manifest; the sync schedule becomes pollInterval"
+    )]
+    async fn migrate_airbyte_to_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<AirbyteMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        match with_secrets(
+            tools::airbyte::migrate_airbyte_to_dataflow(&params.0.airbyte_connection),
+            params.0.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, params.0.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Migrate a Benthos / Redpanda Connect pipeline (kafka input; sql_insert, kafka, elasticsearch outputs) to a DataFlow YAML manifest; simple bloblang and jq processors become filter/select/remove/timestamp transformations, the rest is listed in notes"
+    )]
+    async fn migrate_benthos_to_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<BenthosMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match with_secrets(
+            tools::benthos::migrate_benthos_to_dataflow(&p.benthos_config, p.name.as_deref()),
+            p.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, p.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Migrate a Vector.dev configuration (TOML or YAML) to DataFlow YAML manifests, one per sink; filter and simple remap transforms become filter/select/remove/timestamp transformations"
+    )]
+    async fn migrate_vector_to_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<VectorMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        match with_secrets(
+            tools::vector::migrate_vector_to_dataflow(&params.0.vector_config),
+            params.0.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, params.0.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Migrate a Fluent Bit configuration (classic or YAML) to DataFlow YAML manifests, one per kafka or elasticsearch output; modify, record_modifier, grep and nest filters become select/remove/filter/unwrap transformations"
+    )]
+    async fn migrate_fluentbit_to_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<FluentBitMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        match with_secrets(
+            tools::fluentbit::migrate_fluentbit_to_dataflow(&params.0.fluentbit_config),
+            params.0.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, params.0.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Migrate a telegraf.conf to DataFlow YAML manifests, one per input/output pair; kafka_consumer and postgresql inputs, kafka and postgresql outputs are mapped, fieldpass/fielddrop become select/remove"
+    )]
+    async fn migrate_telegraf_to_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<TelegrafMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        match with_secrets(
+            tools::telegraf::migrate_telegraf_to_dataflow(&params.0.telegraf_config),
+            params.0.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, params.0.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Generate skeleton DataFlow YAML manifests from a Kafka Streams Topology#describe() dump, one per source-to-sink path; filters become placeholder filter transformations, stateful operations (aggregations, joins, windows, stores) are listed in notes"
+    )]
+    async fn migrate_kafka_streams_topology(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<KafkaStreamsMigrateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match with_secrets(
+            tools::kafka_streams::migrate_kafka_streams_topology(
+                &p.topology,
+                p.bootstrap_servers.as_deref(),
+            ),
+            p.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, p.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Fetch all connectors from a Kafka Connect REST endpoint and migrate them to DataFlow YAML manifests (one document per connector)"
+    )]
+    async fn migrate_from_kafka_connect_cluster(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<MigrateClusterParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let migrated = tools::connect_rest::migrate_from_kafka_connect_cluster(
+            &p.url,
+            p.auth.as_deref(),
+            p.name_strategy.as_deref(),
+        )
+        .await;
+        match with_secrets(migrated, p.secrets.use_secrets) {
+            Ok(out) => Ok(self.formatted(out, p.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Save a manifest, template or answer into the session workspace (overwrites the same key)"
+    )]
+    async fn save_to_workspace(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<WorkspaceSaveParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let mut ws = self.workspace.lock().unwrap_or_else(|e| e.into_inner());
+        match ws.save(&p.kind, &p.key, &p.content) {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Saved {} '{}'",
+                p.kind, p.key
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Export the session workspace (saved manifests, templates, answers) as a single JSON bundle"
+    )]
+    async fn export_workspace(&self) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = self.workspace.lock().unwrap_or_else(|e| e.into_inner());
+        let out = ws.export();
+        drop(ws);
+        Ok(CallToolResult::success(vec![self.paged(out)]))
+    }
+
+    #[tool(
+        description = "Import a workspace bundle from export_workspace to resume a previous session (merges unless replace is true)"
+    )]
+    async fn import_workspace(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<WorkspaceImportParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let mut ws = self.workspace.lock().unwrap_or_else(|e| e.into_inner());
+        match ws.import(&p.bundle, p.replace) {
+            Ok(summary) => Ok(CallToolResult::success(vec![Content::text(summary)])),
             Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
         }
     }
@@ -114,33 +1185,428 @@ impl DataFlowMcpService {
     #[tool(description = "List supported DataFlow connectors (sources and sinks) with fields")]
     async fn list_dataflow_connectors(&self) -> Result<CallToolResult, rmcp::ErrorData> {
         let out = tools::reference::list_dataflow_connectors_json();
-        Ok(CallToolResult::success(vec![Content::text(out)]))
+        Ok(CallToolResult::success(vec![self.paged(out)]))
+    }
+
+    #[tool(
+        description = "Query the deployed DataFlow operator (CRD annotations/schema or a version endpoint) for supported connectors and transformations; returns catalogs filtered to them and checks an optional manifest"
+    )]
+    async fn get_operator_capabilities(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<CapabilitiesParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let caps = match tools::capabilities::fetch_capabilities(
+            p.endpoint.as_deref(),
+            p.context.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match tools::capabilities::capabilities_report(&caps, p.config.as_deref()) {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Apply multiple manifests in dependency order (namespaces, Secrets/ConfigMaps, topics, then DataFlows upstream before downstream) via kubectl, with a result per resource"
+    )]
+    async fn apply_dataflow_manifests(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<ApplyParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match tools::apply::apply_in_order(&p.manifests, p.context.as_deref(), p.dry_run).await {
+            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Report orphaned DataFlows: deployed but not in the manifest directory/workspace, and in git but not deployed; optionally generates deletion manifests for confirmed orphans"
+    )]
+    async fn report_orphaned_dataflows(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<OrphanReportParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let sources = match p.directory.as_deref().filter(|d| !d.is_empty()) {
+            Some(dir) => match tools::input::allowed_path(crate::config::current(), dir)
+                .and_then(|d| tools::gc::read_manifest_dir(&d.display().to_string()))
+            {
+                Ok(s) => s,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            },
+            None => {
+                let ws = self.workspace.lock().unwrap_or_else(|e| e.into_inner());
+                ws.manifests
+                    .iter()
+                    .map(|(k, v)| (format!("workspace:{}", k), v.clone()))
+                    .collect()
+            }
+        };
+        let report = tools::gc::gc_report_from_cluster(
+            &sources,
+            p.namespace.as_deref(),
+            p.context.as_deref(),
+            &p.confirmed,
+        );
+        match report.await {
+            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Fetch a deployed DataFlow, strip status, server-side metadata and operator defaults, save it to the session workspace and return clean YAML ready to commit"
+    )]
+    async fn export_deployed_dataflow(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<ExportDeployedParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let exported = tools::export::export_deployed_dataflow(
+            &p.name,
+            p.namespace.as_deref(),
+            p.context.as_deref(),
+        )
+        .await;
+        match exported {
+            Ok(yaml) => {
+                let mut ws = self.workspace.lock().unwrap_or_else(|e| e.into_inner());
+                let key = tools::export::workspace_key(&p.name, p.namespace.as_deref());
+                if let Err(e) = ws.save("manifest", &key, &yaml) {
+                    return Ok(CallToolResult::error(vec![Content::text(e)]));
+                }
+                Ok(CallToolResult::success(vec![Content::text(yaml)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
     }
 
     #[tool(description = "List DataFlow transformations with examples")]
     async fn list_dataflow_transformations(&self) -> Result<CallToolResult, rmcp::ErrorData> {
         let out = tools::reference::list_dataflow_transformations_json();
-        Ok(CallToolResult::success(vec![Content::text(out)]))
+        Ok(CallToolResult::success(vec![self.paged(out)]))
+    }
+
+    #[tool(
+        description = "Export a machine-readable documentation bundle: JSON Schema of the manifest per apiVersion, connector field tables (required, type, allowed values) and transformation examples, generated from the validator's catalogs"
+    )]
+    async fn export_docs_bundle(&self) -> Result<CallToolResult, rmcp::ErrorData> {
+        match tools::docs::docs_bundle() {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Generate a fully populated DataFlow manifest from a named template profile (kafka-to-warehouse, cdc-replication, pii-masking-pipeline) and a few inputs, with recommended transformations and settings"
+    )]
+    async fn generate_from_template(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<TemplateParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match tools::templates::generate_from_template(
+            &p.profile,
+            &p.inputs,
+            p.name.as_deref(),
+            p.namespace.as_deref(),
+        ) {
+            Ok(out) => Ok(self.formatted(out, p.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Generate DataFlow manifests in bulk from a JSON or CSV list of (name, source, sink, options) rows with shared defaults, as one multi-document YAML"
+    )]
+    async fn generate_dataflows_bulk(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<BulkParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let rows = match tools::input::resolve_input(
+            "rows",
+            &p.rows,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match with_secrets(
+            tools::bulk::generate_dataflows_bulk(&rows, &p.defaults),
+            p.secrets.use_secrets,
+        ) {
+            Ok(out) => Ok(self.formatted(out, p.output.output_format.as_deref())),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Generate a Kustomize layout (JSON map of file path to content) for DataFlow manifests: base/ with the manifests and overlays/<env>/kustomization.yaml per environment with namespace, name suffix and patches for Kafka brokers and parallelism"
+    )]
+    async fn generate_kustomize_overlays(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<KustomizeParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let environments: Vec<tools::kustomize::Environment> = p
+            .environments
+            .into_iter()
+            .map(|e| tools::kustomize::Environment {
+                name: e.name,
+                namespace: e.namespace,
+                brokers: e.brokers,
+                name_suffix: e.name_suffix,
+                parallelism: e.parallelism,
+            })
+            .collect();
+        match tools::kustomize::generate_kustomize_overlays(&config, &environments) {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Generate a Prometheus ServiceMonitor (or PodMonitor) and a PrometheusRule with example lag and error alerts for every DataFlow of a manifest, selecting the flow by its labels"
+    )]
+    async fn generate_monitoring_manifests(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<MonitoringParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        let options = tools::monitoring::MonitoringOptions {
+            monitor: p.monitor,
+            interval: p.interval,
+            lag_threshold: p.lag_threshold,
+            labels: p.labels,
+        };
+        match tools::monitoring::generate_monitoring_manifests(&config, &options) {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Map a source schema (JSON Schema or Avro) to a target table's columns: append the snakeCase/camelCase, rename and type-cast script and select transformations that turn source messages into table rows, with a note per decision"
+    )]
+    async fn map_schema_to_table(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<MapSchemaParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match tools::mapping::map_schema_to_table(&config, &p.source_schema, &p.target) {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Propose a transformation chain (unwrap, flatten, snakeCase/camelCase, mask, select/remove) that turns sample input messages into a desired output example, with an explanation per step and whether the chain reproduces the example"
+    )]
+    async fn infer_transformations(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<InferTransformationsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        match tools::sample_inference::infer_transformations(&params.0.samples, &params.0.expected)
+        {
+            Ok(inferred) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&inferred).unwrap_or_default(),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Add one transformation to an existing DataFlow manifest (spec.transformations or a branch, appended or at a position), validate it in place and return the updated YAML, to build a pipeline step by step"
+    )]
+    async fn add_transformation_to_manifest(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<AddTransformationParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let config = match tools::input::resolve_input(
+            "config",
+            &p.config,
+            p.source.path.as_deref(),
+            p.source.url.as_deref(),
+        )
+        .await
+        {
+            Ok(c) => c,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        match tools::builder::add_transformation(
+            &config,
+            &p.transformation,
+            p.branch.as_deref(),
+            p.position,
+        ) {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Upgrade DataFlow manifests to a newer CRD apiVersion (e.g. dataflow.dataflow.io/v1alpha1 to v1): renames and moves fields and lists what needs manual attention in comments above each document"
+    )]
+    async fn convert_dataflow_apiversion(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<ConvertApiVersionParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        match tools::conversion::convert_dataflow_apiversion(
+            &p.config,
+            p.target_api_version.as_deref(),
+        ) {
+            Ok(out) => Ok(CallToolResult::success(vec![self.paged(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Get the JSON Schema (draft 2020-12) of a DataFlow manifest: metadata, every source/sink type and every transformation, generated from the validator's catalogs, for offline validation in editors and CI"
+    )]
+    async fn get_dataflow_json_schema(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<JsonSchemaParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let api_version = params
+            .0
+            .api_version
+            .unwrap_or_else(|| types::DATAFLOW_API_VERSION.to_string());
+        match tools::docs::manifest_json_schema(&api_version) {
+            Ok(schema) => {
+                Ok(CallToolResult::success(vec![self.paged(
+                    serde_json::to_string_pretty(&schema).unwrap_or_default(),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Fetch the next part of a result that was split because it exceeded the configured output size (token and index from the part footer)"
+    )]
+    async fn fetch_result_chunk(
+        &self,
+        params: rmcp::handler::server::wrapper::Parameters<FetchChunkParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let p = params.0;
+        let chunks = self.chunks.lock().unwrap_or_else(|e| e.into_inner());
+        match chunks.fetch(&p.token, p.index) {
+            Ok(out) => Ok(CallToolResult::success(vec![Content::text(out)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    #[tool(
+        description = "Show tool usage in this session: calls and errors per tool, the last error with its arguments, and which tools failed on their latest call (to avoid repeating failing calls)"
+    )]
+    async fn get_session_stats(&self) -> Result<CallToolResult, rmcp::ErrorData> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(CallToolResult::success(vec![Content::text(stats.report())]))
     }
 }
 
-#[tool_handler]
+#[tool_handler(router = self.tool_router)]
 impl ServerHandler for DataFlowMcpService {
+    /// Routes the call and records it in the session stats.
+    async fn call_tool(
+        &self,
+        request: rmcp::model::CallToolRequestParams,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let name = request.name.to_string();
+        let arguments = request
+            .arguments
+            .as_ref()
+            .map(|a| serde_json::Value::Object(a.clone()));
+        let tcc = rmcp::handler::server::tool::ToolCallContext::new(self, request, context);
+        let result = self.tool_router.call(tcc).await;
+        let error = match &result {
+            Ok(r) if r.is_error == Some(true) => Some(
+                r.content
+                    .iter()
+                    .filter_map(|c| c.as_text().map(|t| t.text.clone()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            Ok(_) => None,
+            Err(e) => Some(e.message.to_string()),
+        };
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats.record(&name, arguments.as_ref(), error.as_deref());
+        result
+    }
+
     fn get_info(&self) -> rmcp::model::ServerInfo {
-        rmcp::model::ServerInfo {
-            // name: Some("dataflow".to_string()),
-            // version: Some("0.1.0".to_string()),
-            instructions: Some("MCP for DataFlow: generate manifests and migrate Kafka Connect to DataFlow.".to_string()),
-            capabilities: rmcp::model::ServerCapabilities::builder()
+        rmcp::model::ServerInfo::new(
+            rmcp::model::ServerCapabilities::builder()
                 .enable_tools()
                 .build(),
-            ..Default::default()
-        }
+        )
+        .with_instructions(if config::current().offline {
+            format!(
+                "MCP for DataFlow: generate manifests and migrate Kafka Connect to DataFlow. Offline mode: tools that need the network ({}) are disabled.",
+                config::NETWORK_FEATURES.join(", ")
+            )
+        } else {
+            "MCP for DataFlow: generate manifests and migrate Kafka Connect to DataFlow.".to_string()
+        })
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let config = config::init();
+    if let Some(path) = &config.policy_file {
+        tools::policy::init(path)?;
+    }
+    if let Some(path) = &config.naming_file {
+        tools::naming::init_conventions(path)?;
+    }
     let service = DataFlowMcpService::new();
     let transport = stdio();
     let server = service.serve(transport).await?;
@@ -0,0 +1,592 @@
+// Airbyte connection migration: source/destination configs (Postgres, Kafka, ClickHouse), the selected stream
+// and the sync schedule mapped to a DataFlow manifest.
+
+use crate::tools::migration::{block, render_migrated, sanitize_name};
+use crate::tools::provenance::{annotate, or_filled, GenerationNote};
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_KIND, KAFKA_OFFSET_RESETS};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// Connector type of a source or destination: sourceType / destinationType (public API, also inside
+/// configuration), else the connector name ("Postgres", "ClickHouse").
+fn connector_type(v: &Value, field: &str) -> String {
+    v[field]
+        .as_str()
+        .or_else(|| v["configuration"][field].as_str())
+        .or_else(|| v[field.replace("Type", "Name")].as_str())
+        .or_else(|| v["name"].as_str())
+        .unwrap_or("")
+        .to_lowercase()
+        .replace(['-', ' '], "")
+}
+
+/// The first selected stream: (namespace, name, sync mode, cursor field, primary key).
+struct Stream {
+    namespace: Option<String>,
+    name: String,
+    sync_mode: String,
+    cursor: Option<String>,
+    primary_key: Option<String>,
+}
+
+impl Stream {
+    fn table(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{}.{}", ns, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    fn incremental(&self) -> bool {
+        self.sync_mode.starts_with("incremental")
+    }
+}
+
+/// Selected streams from syncCatalog.streams (config API) or configurations.streams (public API).
+fn selected_streams(conn: &Value) -> Vec<Stream> {
+    let first = |v: &Value| -> Option<String> {
+        match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Array(a) => a.first().and_then(|f| match f {
+                Value::Array(inner) => inner.first().and_then(Value::as_str).map(str::to_string),
+                other => other.as_str().map(str::to_string),
+            }),
+            _ => None,
+        }
+    };
+    if let Some(streams) = conn["syncCatalog"]["streams"].as_array() {
+        return streams
+            .iter()
+            .filter(|s| s["config"]["selected"].as_bool() != Some(false))
+            .map(|s| Stream {
+                namespace: s["stream"]["namespace"].as_str().map(str::to_string),
+                name: s["stream"]["name"].as_str().unwrap_or("").to_string(),
+                sync_mode: s["config"]["syncMode"]
+                    .as_str()
+                    .unwrap_or("full_refresh")
+                    .to_string(),
+                cursor: first(&s["config"]["cursorField"]),
+                primary_key: first(&s["config"]["primaryKey"]),
+            })
+            .collect();
+    }
+    conn["configurations"]["streams"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|s| Stream {
+            namespace: None,
+            name: s["name"].as_str().unwrap_or("").to_string(),
+            sync_mode: s["syncMode"].as_str().unwrap_or("full_refresh").to_string(),
+            cursor: first(&s["cursorField"]),
+            primary_key: first(&s["primaryKey"]),
+        })
+        .collect()
+}
+
+/// Schedule -> pollInterval: basic schedules ({timeUnit, units} or "Every 24 hours"); cron and manual get a note.
+fn poll_interval(conn: &Value, notes: &mut Vec<String>) -> Option<String> {
+    let schedule = if conn["scheduleData"].is_object() {
+        &conn["scheduleData"]
+    } else {
+        &conn["schedule"]
+    };
+    let kind = conn["scheduleType"]
+        .as_str()
+        .or_else(|| schedule["scheduleType"].as_str())
+        .unwrap_or("basic")
+        .to_lowercase();
+    match kind.as_str() {
+        "cron" => {
+            let cron = schedule["cronExpression"]
+                .as_str()
+                .or_else(|| schedule["cron"]["cronExpression"].as_str())
+                .unwrap_or("?");
+            notes.push(format!(
+                "Cron schedule '{}' has no DataFlow equivalent; the flow polls at pollInterval. Set it to the cron period.",
+                cron
+            ));
+            return None;
+        }
+        "manual" => {
+            notes.push("Manual sync schedule: DataFlow runs continuously; pollInterval left at the operator default.".to_string());
+            return None;
+        }
+        _ => {}
+    }
+    let basic = if schedule["basicSchedule"].is_object() {
+        &schedule["basicSchedule"]
+    } else {
+        schedule
+    };
+    let (units, unit) = match (basic["units"].as_u64(), basic["timeUnit"].as_str()) {
+        (Some(n), Some(u)) => (n, u.to_lowercase()),
+        _ => {
+            // Public API: "basicTiming": "Every 24 hours".
+            let timing = schedule["basicTiming"].as_str()?.to_lowercase();
+            let mut words = timing.split_whitespace().skip_while(|w| *w == "every");
+            let n = words.next()?.parse().ok()?;
+            (n, words.next()?.to_string())
+        }
+    };
+    let unit = unit.trim_end_matches('s');
+    Some(match unit {
+        "minute" => format!("{}m", units),
+        "hour" => format!("{}h", units),
+        "day" => format!("{}h", units * 24),
+        "week" => format!("{}h", units * 24 * 7),
+        "month" => {
+            notes.push(format!(
+                "Schedule every {} month(s) approximated as 30 days each.",
+                units
+            ));
+            format!("{}h", units * 24 * 30)
+        }
+        other => {
+            notes.push(format!(
+                "Schedule unit '{}' not recognized; pollInterval not set.",
+                other
+            ));
+            return None;
+        }
+    })
+}
+
+fn jdbc_string(scheme: &str, cfg: &Value, default_port: u64) -> Option<String> {
+    let host = cfg["host"].as_str()?;
+    let port = cfg["port"].as_u64().unwrap_or(default_port);
+    let db = cfg["database"].as_str().unwrap_or("");
+    let user = cfg["username"]
+        .as_str()
+        .map(|u| format!("{}@", u))
+        .unwrap_or_default();
+    Some(format!("{}://{}{}:{}/{}", scheme, user, host, port, db))
+}
+
+/// Postgres / ClickHouse source: polling on the first selected stream (incremental on its cursor), or
+/// logical replication for Postgres CDC.
+fn database_source(
+    kind: &str,
+    cfg: &Value,
+    stream: Option<&Stream>,
+    interval: Option<String>,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> JsonMap<String, Value> {
+    let path = format!("spec.source.{}", kind);
+    let (scheme, port) = if kind == "postgresql" {
+        ("postgres", 5432)
+    } else {
+        ("clickhouse", 9000)
+    };
+    let mut inner = JsonMap::new();
+    let conn = or_filled(
+        jdbc_string(scheme, cfg, port),
+        &format!("{}://user@localhost:{}/db", scheme, port),
+        &format!("{}.connectionString", path),
+        "host not set in the source configuration",
+        filled,
+    );
+    inner.insert("connectionString".to_string(), Value::String(conn));
+    notes.push(
+        "The source password was not copied; add it to connectionString from a Secret.".to_string(),
+    );
+    let table = or_filled(
+        stream.map(Stream::table),
+        "input_table",
+        &format!("{}.table", path),
+        "no selected stream in the connection",
+        filled,
+    );
+    inner.insert("table".to_string(), Value::String(table));
+    let cdc = cfg["replication_method"]["method"]
+        .as_str()
+        .is_some_and(|m| m.eq_ignore_ascii_case("CDC"));
+    if cdc && kind == "postgresql" {
+        let mut replication = JsonMap::new();
+        replication.insert(
+            "slotName".to_string(),
+            Value::String(
+                cfg["replication_method"]["replication_slot"]
+                    .as_str()
+                    .unwrap_or("airbyte_slot")
+                    .to_string(),
+            ),
+        );
+        replication.insert(
+            "publication".to_string(),
+            Value::String(
+                cfg["replication_method"]["publication"]
+                    .as_str()
+                    .unwrap_or("airbyte_publication")
+                    .to_string(),
+            ),
+        );
+        replication.insert("plugin".to_string(), Value::String("pgoutput".to_string()));
+        inner.insert("replication".to_string(), Value::Object(replication));
+        notes.push("Airbyte CDC became logical replication; stop the Airbyte connection first, two readers cannot share the slot.".to_string());
+        if interval.is_some() {
+            notes.push(
+                "The sync schedule does not apply to replication, which streams continuously."
+                    .to_string(),
+            );
+        }
+        return block(kind, inner);
+    }
+    if let Some(i) = interval {
+        inner.insert("pollInterval".to_string(), Value::String(i));
+    }
+    match stream {
+        Some(s) if s.incremental() => match &s.cursor {
+            Some(cursor) => {
+                inner.insert("mode".to_string(), Value::String("incremental".to_string()));
+                inner.insert("incrementalColumn".to_string(), Value::String(cursor.clone()));
+                inner.insert("cursorStorage".to_string(), Value::String("configmap".to_string()));
+                notes.push("Airbyte's saved cursor state was not migrated; set initialValue to resume instead of re-reading the table.".to_string());
+            }
+            None => notes.push(format!("Stream {} syncs incrementally without a cursor field; polling reads the whole table.", s.name)),
+        },
+        _ => {}
+    }
+    block(kind, inner)
+}
+
+fn kafka_source(
+    cfg: &Value,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> JsonMap<String, Value> {
+    let mut inner = JsonMap::new();
+    let servers = or_filled(
+        cfg["bootstrap_servers"].as_str().map(str::to_string),
+        "localhost:9092",
+        "spec.source.kafka.brokers",
+        "bootstrap_servers not set in the source configuration",
+        filled,
+    );
+    inner.insert(
+        "brokers".to_string(),
+        json!(servers.split(',').map(str::trim).collect::<Vec<_>>()),
+    );
+    let subscription = &cfg["subscription"];
+    match subscription["topic_pattern"].as_str() {
+        Some(p) if p.contains(['*', '|', '[', '(']) => {
+            inner.insert("topicPattern".to_string(), Value::String(p.to_string()));
+        }
+        Some(p) => {
+            inner.insert("topic".to_string(), Value::String(p.to_string()));
+        }
+        None => {
+            let topic = subscription["topic_partitions"]
+                .as_str()
+                .and_then(|tp| tp.split(',').next())
+                .map(|tp| tp.split(':').next().unwrap_or(tp).trim().to_string());
+            if topic.is_some() {
+                notes.push(
+                    "Assigned topic partitions became a topic subscription over all partitions."
+                        .to_string(),
+                );
+            }
+            let topic = or_filled(
+                topic,
+                "input-topic",
+                "spec.source.kafka.topic",
+                "no topic subscription",
+                filled,
+            );
+            inner.insert("topic".to_string(), Value::String(topic));
+        }
+    }
+    if let Some(group) = cfg["group_id"].as_str() {
+        inner.insert(
+            "consumerGroup".to_string(),
+            Value::String(group.to_string()),
+        );
+    }
+    if let Some(reset) = cfg["auto_offset_reset"]
+        .as_str()
+        .filter(|r| KAFKA_OFFSET_RESETS.contains(r))
+    {
+        inner.insert("offsetReset".to_string(), Value::String(reset.to_string()));
+    }
+    if cfg["MessageFormat"]["deserialization_type"]
+        .as_str()
+        .is_some_and(|t| t.eq_ignore_ascii_case("AVRO"))
+    {
+        inner.insert("format".to_string(), Value::String("avro".to_string()));
+    }
+    block("kafka", inner)
+}
+
+fn database_sink(
+    kind: &str,
+    cfg: &Value,
+    stream: Option<&Stream>,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> JsonMap<String, Value> {
+    let path = format!("spec.sink.{}", kind);
+    let (scheme, port) = if kind == "postgresql" {
+        ("postgres", 5432)
+    } else {
+        ("clickhouse", 9000)
+    };
+    let mut inner = JsonMap::new();
+    let conn = or_filled(
+        jdbc_string(scheme, cfg, port),
+        &format!("{}://user@localhost:{}/db", scheme, port),
+        &format!("{}.connectionString", path),
+        "host not set in the destination configuration",
+        filled,
+    );
+    inner.insert("connectionString".to_string(), Value::String(conn));
+    notes.push(
+        "The destination password was not copied; add it to connectionString from a Secret."
+            .to_string(),
+    );
+    // Airbyte names destination tables after the stream, in the destination's default schema.
+    let schema = cfg["schema"].as_str();
+    let table = stream.map(|s| match schema {
+        Some(schema) => format!("{}.{}", schema, s.name),
+        None => s.name.clone(),
+    });
+    let table = or_filled(
+        table,
+        "output_table",
+        &format!("{}.table", path),
+        "no selected stream in the connection",
+        filled,
+    );
+    inner.insert("table".to_string(), Value::String(table));
+    inner.insert("autoCreateTable".to_string(), Value::Bool(true));
+    if let Some(s) = stream {
+        if s.sync_mode.contains("dedup") || s.sync_mode.contains("append_dedup") {
+            if kind == "postgresql" {
+                inner.insert("upsertMode".to_string(), Value::Bool(true));
+                if let Some(pk) = &s.primary_key {
+                    inner.insert("conflictKey".to_string(), Value::String(pk.clone()));
+                }
+            } else {
+                notes.push("Deduplicated sync mode: use a ReplacingMergeTree table in ClickHouse to keep one row per key.".to_string());
+            }
+        }
+    }
+    notes.push("Airbyte's _airbyte_* metadata columns and raw tables are not produced; adjust readers that rely on them.".to_string());
+    block(kind, inner)
+}
+
+fn kafka_sink(
+    cfg: &Value,
+    stream: Option<&Stream>,
+    filled: &mut Vec<GenerationNote>,
+) -> JsonMap<String, Value> {
+    let mut inner = JsonMap::new();
+    let servers = or_filled(
+        cfg["bootstrap_servers"].as_str().map(str::to_string),
+        "localhost:9092",
+        "spec.sink.kafka.brokers",
+        "bootstrap_servers not set in the destination configuration",
+        filled,
+    );
+    inner.insert(
+        "brokers".to_string(),
+        json!(servers.split(',').map(str::trim).collect::<Vec<_>>()),
+    );
+    // topic_pattern may use {namespace} and {stream}.
+    let topic = cfg["topic_pattern"].as_str().map(|p| {
+        let (ns, name) = stream
+            .map(|s| (s.namespace.clone().unwrap_or_default(), s.name.clone()))
+            .unwrap_or_default();
+        p.replace("{namespace}", &ns)
+            .replace("{stream}", &name)
+            .trim_matches(['.', '_', '-'])
+            .to_string()
+    });
+    let topic = or_filled(
+        topic.filter(|t| !t.is_empty()),
+        "output-topic",
+        "spec.sink.kafka.topic",
+        "topic_pattern not set in the destination configuration",
+        filled,
+    );
+    inner.insert("topic".to_string(), Value::String(topic));
+    block("kafka", inner)
+}
+
+/// Migrates an Airbyte connection export (JSON with source, destination, schedule and the stream catalog)
+/// to a DataFlow manifest with migration notes as a comment header. Only the first selected stream is mapped.
+pub fn migrate_airbyte_to_dataflow(connection_json: &str) -> Result<String, String> {
+    let conn: Value =
+        serde_json::from_str(connection_json).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let (source, destination) = (&conn["source"], &conn["destination"]);
+    if !source.is_object() || !destination.is_object() {
+        return Err(
+            "Expected an Airbyte connection export with source and destination objects".to_string(),
+        );
+    }
+    let mut notes = Vec::new();
+    let mut filled = Vec::new();
+    let streams = selected_streams(&conn);
+    if streams.len() > 1 {
+        notes.push(format!(
+            "The connection syncs {} streams; only '{}' was mapped. Create one DataFlow per stream for: {}.",
+            streams.len(),
+            streams[0].name,
+            streams[1..].iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let stream = streams.first();
+    let interval = poll_interval(&conn, &mut notes);
+
+    let (source_cfg, dest_cfg) = (&source["configuration"], &destination["configuration"]);
+    let source_type = connector_type(source, "sourceType");
+    let source_block = match source_type.as_str() {
+        t if t.contains("postgres") => database_source(
+            "postgresql",
+            source_cfg,
+            stream,
+            interval,
+            &mut filled,
+            &mut notes,
+        ),
+        t if t.contains("clickhouse") => database_source(
+            "clickhouse",
+            source_cfg,
+            stream,
+            interval,
+            &mut filled,
+            &mut notes,
+        ),
+        t if t.contains("kafka") => kafka_source(source_cfg, &mut filled, &mut notes),
+        other => {
+            notes.push(format!(
+                "Airbyte source '{}' is not supported; the kafka source is a placeholder.",
+                other
+            ));
+            let placeholder = block(
+                "kafka",
+                json!({ "brokers": ["localhost:9092"], "topic": "input-topic" })
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            filled.push(GenerationNote::new(
+                "spec.source",
+                Value::Object(placeholder.clone()),
+                "unsupported Airbyte source",
+            ));
+            placeholder
+        }
+    };
+    let dest_type = connector_type(destination, "destinationType");
+    let sink_block = match dest_type.as_str() {
+        t if t.contains("postgres") => {
+            database_sink("postgresql", dest_cfg, stream, &mut filled, &mut notes)
+        }
+        t if t.contains("clickhouse") => {
+            database_sink("clickhouse", dest_cfg, stream, &mut filled, &mut notes)
+        }
+        t if t.contains("kafka") => kafka_sink(dest_cfg, stream, &mut filled),
+        other => {
+            notes.push(format!(
+                "Airbyte destination '{}' is not supported; the kafka sink is a placeholder.",
+                other
+            ));
+            let placeholder = block(
+                "kafka",
+                json!({ "brokers": ["localhost:9092"], "topic": "output-topic" })
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            filled.push(GenerationNote::new(
+                "spec.sink",
+                Value::Object(placeholder.clone()),
+                "unsupported Airbyte destination",
+            ));
+            placeholder
+        }
+    };
+
+    let name = sanitize_name(conn["name"].as_str().unwrap_or(""));
+    let mut metadata = JsonMap::new();
+    metadata.insert(
+        "name".to_string(),
+        Value::String(if name.is_empty() {
+            "airbyte-connection".to_string()
+        } else {
+            name
+        }),
+    );
+    annotate(&mut metadata, &filled);
+    let mut spec = JsonMap::new();
+    spec.insert("source".to_string(), Value::Object(source_block));
+    spec.insert("sink".to_string(), Value::Object(sink_block));
+    let mut manifest = JsonMap::new();
+    manifest.insert(
+        "apiVersion".to_string(),
+        Value::String(DATAFLOW_API_VERSION.to_string()),
+    );
+    manifest.insert("kind".to_string(), Value::String(DATAFLOW_KIND.to_string()));
+    manifest.insert("metadata".to_string(), Value::Object(metadata));
+    manifest.insert("spec".to_string(), Value::Object(spec));
+    render_migrated("Airbyte connection", &manifest, notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_airbyte_postgres_to_clickhouse() {
+        let conn = r#"{
+            "name": "Orders to CH",
+            "scheduleType": "basic",
+            "scheduleData": {"basicSchedule": {"timeUnit": "hours", "units": 6}},
+            "source": {"sourceName": "Postgres", "configuration": {"host": "pg", "port": 5433, "database": "shop", "username": "airbyte",
+                       "replication_method": {"method": "Standard"}}},
+            "destination": {"destinationName": "ClickHouse", "configuration": {"host": "ch", "port": 9000, "database": "dwh"}},
+            "syncCatalog": {"streams": [
+                {"stream": {"name": "orders", "namespace": "public"},
+                 "config": {"syncMode": "incremental", "destinationSyncMode": "append", "cursorField": ["updated_at"], "selected": true}},
+                {"stream": {"name": "users", "namespace": "public"}, "config": {"syncMode": "full_refresh", "selected": false}}
+            ]}
+        }"#;
+        let out = migrate_airbyte_to_dataflow(conn).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&out).unwrap();
+        let pg = &yaml["spec"]["source"]["postgresql"];
+        assert_eq!(yaml["metadata"]["name"], "orders-to-ch");
+        assert_eq!(pg["connectionString"], "postgres://airbyte@pg:5433/shop");
+        assert_eq!(pg["table"], "public.orders");
+        assert_eq!(pg["pollInterval"], "6h");
+        assert_eq!(pg["mode"], "incremental");
+        assert_eq!(pg["incrementalColumn"], "updated_at");
+        assert_eq!(yaml["spec"]["sink"]["clickhouse"]["table"], "orders");
+        assert!(!out.contains("Validation error"));
+    }
+
+    #[test]
+    fn test_migrate_airbyte_public_api_kafka_to_postgres() {
+        let conn = r#"{
+            "name": "events",
+            "schedule": {"scheduleType": "basic", "basicTiming": "Every 30 minutes"},
+            "source": {"configuration": {"sourceType": "kafka", "bootstrap_servers": "k1:9092,k2:9092",
+                       "subscription": {"subscription_type": "subscribe", "topic_pattern": "events"}, "group_id": "ab", "auto_offset_reset": "earliest"}},
+            "destination": {"configuration": {"destinationType": "postgres", "host": "pg", "database": "dwh", "schema": "raw"}},
+            "configurations": {"streams": [{"name": "events", "syncMode": "incremental_deduped_history", "primaryKey": [["id"]]}]}
+        }"#;
+        let out = migrate_airbyte_to_dataflow(conn).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(yaml["spec"]["source"]["kafka"]["brokers"][1], "k2:9092");
+        assert_eq!(yaml["spec"]["source"]["kafka"]["offsetReset"], "earliest");
+        let pg = &yaml["spec"]["sink"]["postgresql"];
+        assert_eq!(pg["table"], "raw.events");
+        assert_eq!(pg["upsertMode"], true);
+        assert_eq!(pg["conflictKey"], "id");
+        let mut notes = Vec::new();
+        assert_eq!(
+            poll_interval(&serde_json::from_str(conn).unwrap(), &mut notes).as_deref(),
+            Some("30m")
+        );
+        assert!(migrate_airbyte_to_dataflow("{}").is_err());
+    }
+}
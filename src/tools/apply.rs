@@ -0,0 +1,226 @@
+// Bulk apply: orders a set of resources by dependency (namespaces, then Secrets/ConfigMaps, then topics and
+// other supporting resources, then DataFlows upstream before downstream) and applies them one by one.
+
+use crate::tools::kube::kubectl_apply;
+use crate::tools::output::to_canonical_yaml;
+use crate::types::DATAFLOW_KIND;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// One resource of the bundle in apply order.
+#[derive(Debug, Clone)]
+pub struct PlannedResource {
+    pub kind: String,
+    pub name: String,
+    pub yaml: String,
+    /// Names of the DataFlows this one reads from (same topic), for the plan output.
+    pub after: Vec<String>,
+}
+
+impl PlannedResource {
+    fn label(&self) -> String {
+        format!("{}/{}", self.kind, self.name)
+    }
+}
+
+/// Apply tier by kind: lower tiers are applied first.
+fn tier(kind: &str) -> u8 {
+    match kind {
+        "Namespace" => 0,
+        "Secret" | "ConfigMap" | "ServiceAccount" => 1,
+        DATAFLOW_KIND => 3,
+        _ => 2,
+    }
+}
+
+/// Kafka topics a flow writes to (main sink and branch sinks).
+fn written_topics(flow: &Value) -> Vec<String> {
+    let spec = &flow["spec"];
+    let mut sinks = vec![&spec["sink"]];
+    if let Some(branches) = spec["branches"].as_object() {
+        sinks.extend(branches.values().map(|b| &b["sink"]));
+    }
+    sinks
+        .into_iter()
+        .filter(|s| s["type"].as_str() == Some("kafka"))
+        .filter_map(|s| s["kafka"]["topic"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Whether a flow's kafka source reads the topic (topic or topicPattern).
+fn reads_topic(flow: &Value, topic: &str) -> bool {
+    let source = &flow["spec"]["source"];
+    if source["type"].as_str() != Some("kafka") {
+        return false;
+    }
+    let kafka = &source["kafka"];
+    if kafka["topic"].as_str() == Some(topic) {
+        return true;
+    }
+    kafka["topicPattern"]
+        .as_str()
+        .and_then(|p| regex::Regex::new(&format!("^(?:{})$", p)).ok())
+        .is_some_and(|re| re.is_match(topic))
+}
+
+/// Splits a multi-document bundle and returns its resources in dependency order.
+/// Err when a document is not a resource or DataFlows form a topic cycle.
+pub fn plan_apply(bundle_yaml: &str) -> Result<Vec<PlannedResource>, String> {
+    let mut docs: Vec<(Value, PlannedResource)> = Vec::new();
+    for (i, doc) in serde_yaml::Deserializer::from_str(bundle_yaml).enumerate() {
+        let v = Value::deserialize(doc)
+            .map_err(|e| format!("document {}: YAML parse error: {}", i + 1, e))?;
+        if v.is_null() {
+            continue;
+        }
+        let kind = v["kind"]
+            .as_str()
+            .ok_or_else(|| format!("document {}: kind is required", i + 1))?;
+        let name = v["metadata"]["name"]
+            .as_str()
+            .ok_or_else(|| format!("document {}: metadata.name is required", i + 1))?;
+        let yaml = to_canonical_yaml(&v)?;
+        let resource = PlannedResource {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            yaml,
+            after: Vec::new(),
+        };
+        docs.push((v, resource));
+    }
+
+    // Edges between DataFlows: upstream writes a topic downstream reads.
+    let flows: Vec<usize> = (0..docs.len())
+        .filter(|&i| docs[i].1.kind == DATAFLOW_KIND)
+        .collect();
+    for &down in &flows {
+        let mut after = Vec::new();
+        for &up in &flows {
+            if up != down
+                && written_topics(&docs[up].0)
+                    .iter()
+                    .any(|t| reads_topic(&docs[down].0, t))
+            {
+                after.push(docs[up].1.name.clone());
+            }
+        }
+        docs[down].1.after = after;
+    }
+
+    // Stable: tiers in order, input order within a tier, DataFlows after all their upstreams.
+    let mut ordered: Vec<PlannedResource> = Vec::new();
+    let mut pending: Vec<PlannedResource> = docs.into_iter().map(|(_, r)| r).collect();
+    pending.sort_by_key(|r| tier(&r.kind));
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|r| {
+            r.after.iter().all(|up| {
+                ordered
+                    .iter()
+                    .any(|o| o.kind == DATAFLOW_KIND && &o.name == up)
+            })
+        });
+        match ready {
+            Some(i) => ordered.push(pending.remove(i)),
+            None => {
+                let cycle: Vec<String> = pending.iter().map(PlannedResource::label).collect();
+                return Err(format!(
+                    "DataFlows read each other's output topics in a cycle, no apply order exists: {}",
+                    cycle.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(ordered)
+}
+
+fn describe(resource: &PlannedResource) -> String {
+    if resource.after.is_empty() {
+        resource.label()
+    } else {
+        format!("{} (after {})", resource.label(), resource.after.join(", "))
+    }
+}
+
+/// Applies the bundle in dependency order and reports one line per resource. After the first failure the
+/// remaining resources are skipped, since they may depend on it. dry_run only returns the order.
+pub async fn apply_in_order(
+    bundle_yaml: &str,
+    context: Option<&str>,
+    dry_run: bool,
+) -> Result<String, String> {
+    let plan = plan_apply(bundle_yaml)?;
+    let mut lines = Vec::new();
+    let mut failed = false;
+    for (i, resource) in plan.iter().enumerate() {
+        let result = if dry_run {
+            "planned".to_string()
+        } else if failed {
+            "skipped (an earlier resource failed)".to_string()
+        } else {
+            match kubectl_apply("Bulk apply", &resource.yaml, context).await {
+                Ok(out) => format!("ok: {}", out),
+                Err(e) => {
+                    failed = true;
+                    format!("FAILED: {}", e)
+                }
+            }
+        };
+        lines.push(format!("{}. {} — {}", i + 1, describe(resource), result));
+    }
+    if failed {
+        return Err(lines.join("\n"));
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_apply_orders_secrets_and_upstream_flows_first() {
+        let bundle = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata: {name: enrich}
+spec:
+  source: {type: kafka, kafka: {brokers: ["k:9092"], topicPattern: "raw-.*"}}
+  sink: {type: postgresql, postgresql: {connectionString: "${PG}", table: orders}}
+---
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata: {name: ingest}
+spec:
+  source: {type: kafka, kafka: {brokers: ["k:9092"], topic: orders}}
+  sink: {type: kafka, kafka: {brokers: ["k:9092"], topic: raw-orders}}
+---
+apiVersion: v1
+kind: Secret
+metadata: {name: pg-credentials}
+stringData: {password: x}
+---
+apiVersion: kafka.strimzi.io/v1beta2
+kind: KafkaTopic
+metadata: {name: raw-orders}
+"#;
+        let plan = plan_apply(bundle).unwrap();
+        let labels: Vec<String> = plan.iter().map(PlannedResource::label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Secret/pg-credentials",
+                "KafkaTopic/raw-orders",
+                "DataFlow/ingest",
+                "DataFlow/enrich"
+            ]
+        );
+        assert_eq!(plan[3].after, vec!["ingest"]);
+        assert_eq!(describe(&plan[3]), "DataFlow/enrich (after ingest)");
+
+        let cycle = bundle.replace("topic: orders}", "topic: raw-x}").replace(
+            "table: orders}}",
+            "table: orders}}\n  branches: {b: {sink: {type: kafka, kafka: {topic: raw-x}}}}",
+        );
+        assert!(plan_apply(&cycle).unwrap_err().contains("cycle"));
+    }
+}
@@ -0,0 +1,220 @@
+// Avro schema checks for inline avroSchema in kafka blocks.
+
+use apache_avro::Schema;
+use serde_json::Value;
+
+/// Logical types the DataFlow operator can decode; anything else falls back to the base type silently.
+const SUPPORTED_LOGICAL_TYPES: [&str; 9] = [
+    "decimal",
+    "uuid",
+    "date",
+    "time-millis",
+    "time-micros",
+    "timestamp-millis",
+    "timestamp-micros",
+    "local-timestamp-millis",
+    "local-timestamp-micros",
+];
+
+const PRIMITIVE_TYPES: [&str; 8] = [
+    "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+];
+
+/// Parses an inline avroSchema (JSON string or YAML/JSON object) and returns the problems found: JSON syntax,
+/// a named record at the top (messages decode to objects), names, duplicate fields, then the Avro parser itself.
+pub fn check_avro_schema(schema: &Value) -> Vec<String> {
+    let json = match schema {
+        Value::String(s) => match serde_json::from_str::<Value>(s) {
+            Ok(v) => v,
+            Err(e) => return vec![format!("is not valid JSON: {}", e)],
+        },
+        Value::Object(_) | Value::Array(_) => schema.clone(),
+        _ => return vec!["must be an Avro schema (JSON string or object)".to_string()],
+    };
+    let mut problems = Vec::new();
+    if json["type"] != "record" {
+        let got = json
+            .get("type")
+            .map(Value::to_string)
+            .unwrap_or_else(|| json.to_string());
+        problems.push(format!(
+            "top-level type must be a named record (messages decode to objects), got {}",
+            got
+        ));
+    }
+    check_names(&json, "schema", &mut problems);
+    if problems.is_empty() {
+        if let Err(e) = Schema::parse_str(&json.to_string()) {
+            problems.push(format!("invalid Avro schema: {}", e));
+        }
+    }
+    collect_unsupported_logical_types(&json, &mut problems);
+    problems
+}
+
+/// Avro names: [A-Za-z_][A-Za-z0-9_]*; namespaces are dot-separated names.
+fn valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Named types (record, enum, fixed) need a valid name; records need fields with unique valid names and a type.
+fn check_names(v: &Value, path: &str, problems: &mut Vec<String>) {
+    match v {
+        Value::Array(branches) => {
+            for (i, branch) in branches.iter().enumerate() {
+                check_names(branch, &format!("{}[{}]", path, i), problems);
+            }
+        }
+        Value::Object(map) => {
+            let t = map.get("type").and_then(Value::as_str).unwrap_or("");
+            if matches!(t, "record" | "error" | "enum" | "fixed") {
+                match map.get("name").and_then(Value::as_str) {
+                    Some(name) if name.split('.').all(valid_name) => {}
+                    Some(name) => {
+                        problems.push(format!("{}: '{}' is not a valid Avro name", path, name))
+                    }
+                    None => problems.push(format!("{}: {} needs a name", path, t)),
+                }
+                if let Some(ns) = map
+                    .get("namespace")
+                    .and_then(Value::as_str)
+                    .filter(|ns| !ns.is_empty())
+                {
+                    if !ns.split('.').all(valid_name) {
+                        problems.push(format!("{}: '{}' is not a valid Avro namespace", path, ns));
+                    }
+                }
+            }
+            match t {
+                "record" | "error" => {
+                    let Some(fields) = map.get("fields").and_then(Value::as_array) else {
+                        problems.push(format!("{}: record needs a fields array", path));
+                        return;
+                    };
+                    let mut seen = Vec::new();
+                    for (i, field) in fields.iter().enumerate() {
+                        let f_path = format!("{}.fields[{}]", path, i);
+                        match field.get("name").and_then(Value::as_str) {
+                            Some(name) if seen.contains(&name) => problems
+                                .push(format!("{}: duplicate field name '{}'", f_path, name)),
+                            Some(name) if !valid_name(name) => problems
+                                .push(format!("{}: '{}' is not a valid Avro name", f_path, name)),
+                            Some(name) => seen.push(name),
+                            None => problems.push(format!("{}: field needs a name", f_path)),
+                        }
+                        match field.get("type") {
+                            Some(ft) => check_names(ft, &format!("{}.type", f_path), problems),
+                            None => problems.push(format!("{}: field needs a type", f_path)),
+                        }
+                    }
+                }
+                "array" => check_names(&map["items"], &format!("{}.items", path), problems),
+                "map" => check_names(&map["values"], &format!("{}.values", path), problems),
+                _ => {}
+            }
+        }
+        Value::String(t)
+            if !PRIMITIVE_TYPES.contains(&t.as_str()) && !t.split('.').all(valid_name) =>
+        {
+            problems.push(format!("{}: '{}' is not a valid Avro type name", path, t));
+        }
+        _ => {}
+    }
+}
+
+fn collect_unsupported_logical_types(v: &Value, problems: &mut Vec<String>) {
+    match v {
+        Value::Object(map) => {
+            if let Some(lt) = map.get("logicalType").and_then(Value::as_str) {
+                if !SUPPORTED_LOGICAL_TYPES.contains(&lt) {
+                    problems.push(format!(
+                        "unsupported logicalType '{}'; supported: {}",
+                        lt,
+                        SUPPORTED_LOGICAL_TYPES.join(", ")
+                    ));
+                }
+            }
+            for child in map.values() {
+                collect_unsupported_logical_types(child, problems);
+            }
+        }
+        Value::Array(items) => {
+            for child in items {
+                collect_unsupported_logical_types(child, problems);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_avro_schema_valid_record() {
+        let schema = Value::String(
+            r#"{"type":"record","name":"Order","fields":[{"name":"id","type":"long"},{"name":"at","type":{"type":"long","logicalType":"timestamp-millis"}}]}"#
+                .to_string(),
+        );
+        assert!(check_avro_schema(&schema).is_empty());
+    }
+
+    #[test]
+    fn test_check_avro_schema_syntax_and_logical_type_errors() {
+        let broken = Value::String(
+            r#"{"type":"record","name":"Order","fields":[{"name":"id","type":"lnog"}]}"#
+                .to_string(),
+        );
+        let problems = check_avro_schema(&broken);
+        assert!(problems
+            .iter()
+            .any(|p| p.starts_with("invalid Avro schema")));
+
+        let duration = serde_json::json!({
+            "type": "record",
+            "name": "Span",
+            "fields": [{ "name": "d", "type": { "type": "fixed", "name": "D", "size": 12, "logicalType": "duration" } }]
+        });
+        let problems = check_avro_schema(&duration);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("unsupported logicalType 'duration'"));
+    }
+
+    #[test]
+    fn test_check_avro_schema_json_and_names() {
+        let not_json = Value::String(r#"{"type":"record","name":"Order",}"#.to_string());
+        assert!(check_avro_schema(&not_json)[0].starts_with("is not valid JSON"));
+        let long = Value::String(r#""long""#.to_string());
+        assert_eq!(
+            check_avro_schema(&long),
+            vec![
+                r#"top-level type must be a named record (messages decode to objects), got "long""#
+                    .to_string()
+            ]
+        );
+        let named = serde_json::json!({
+            "type": "record",
+            "name": "order-v1",
+            "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "id", "type": "string" },
+                { "name": "status" },
+                { "name": "item", "type": { "type": "array", "items": { "type": "enum", "symbols": ["A"] } } }
+            ]
+        });
+        assert_eq!(
+            check_avro_schema(&named),
+            vec![
+                "schema: 'order-v1' is not a valid Avro name".to_string(),
+                "schema.fields[1]: duplicate field name 'id'".to_string(),
+                "schema.fields[2]: field needs a type".to_string(),
+                "schema.fields[3].type.items: enum needs a name".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// Incremental pipeline building: inserts one transformation into an existing manifest and validates it in place,
+// so a flow can be built step by step instead of being regenerated from scratch.
+
+use crate::tools::manifest::{split_documents, strict_unknown_fields, validate_dataflow_manifest};
+use crate::tools::output::to_canonical_yaml;
+use serde_json::Value;
+
+/// Inserts transformation into spec.transformations (or spec.branches.<branch>.transformations) at position
+/// (default: appended) and returns the updated YAML. Fails with the findings about the new transformation — its
+/// config, unknown fields and compatibility with the flow — while other errors of the manifest are listed as
+/// warnings in the header.
+pub fn add_transformation(
+    yaml: &str,
+    transformation: &Value,
+    branch: Option<&str>,
+    position: Option<usize>,
+) -> Result<String, String> {
+    let docs = split_documents(yaml);
+    let [(_, doc)] = docs.as_slice() else {
+        return Err(format!(
+            "expected one DataFlow manifest, got {} documents",
+            docs.len()
+        ));
+    };
+    let mut manifest: Value =
+        serde_yaml::from_str(doc).map_err(|e| format!("YAML parse error: {}", e))?;
+    let transformation = match transformation {
+        Value::String(s) => {
+            serde_json::from_str(s).map_err(|e| format!("transformation invalid JSON: {}", e))?
+        }
+        v => v.clone(),
+    };
+    let Some(kind) = transformation["type"].as_str().map(str::to_string) else {
+        return Err("transformation must be an object with a type, e.g. {\"type\": \"filter\", \"filter\": {\"condition\": \"$.status == 'active'\"}}".to_string());
+    };
+    let Some(spec) = manifest.get_mut("spec").and_then(Value::as_object_mut) else {
+        return Err("spec is required".to_string());
+    };
+    let (owner, list_path) = match branch.filter(|b| !b.is_empty()) {
+        Some(name) => {
+            let branches = spec.get_mut("branches").and_then(Value::as_object_mut);
+            let known: Vec<String> = branches
+                .as_ref()
+                .map(|b| b.keys().cloned().collect())
+                .unwrap_or_default();
+            let Some(owner) = branches
+                .and_then(|b| b.get_mut(name))
+                .and_then(Value::as_object_mut)
+            else {
+                return Err(format!(
+                    "spec.branches.{} does not exist; branches: {}",
+                    name,
+                    if known.is_empty() {
+                        "none".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                ));
+            };
+            (owner, format!("spec.branches.{}.transformations", name))
+        }
+        None => (spec, "spec.transformations".to_string()),
+    };
+    let list = owner
+        .entry("transformations")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    let Some(list) = list.as_array_mut() else {
+        return Err(format!("{} must be a list", list_path));
+    };
+    let index = position.unwrap_or(list.len());
+    if index > list.len() {
+        return Err(format!(
+            "position {} is out of range; {} has {} transformations",
+            index,
+            list_path,
+            list.len()
+        ));
+    }
+    list.insert(index, transformation);
+    let path = format!("{}[{}]", list_path, index);
+
+    let updated = to_canonical_yaml(&manifest)?;
+    let mut errors = validate_dataflow_manifest(&updated)
+        .err()
+        .unwrap_or_default();
+    errors.extend(strict_unknown_fields(&updated));
+    // Paths of the new transformation are followed by '.', ' ' or '(' (not by a digit of a longer index).
+    let about_new = |e: &String| {
+        e.strip_prefix(&path)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+    };
+    let (rejected, existing): (Vec<String>, Vec<String>) = errors.into_iter().partition(about_new);
+    if !rejected.is_empty() {
+        return Err(format!(
+            "{} transformation not added:\n- {}",
+            kind,
+            rejected.join("\n- ")
+        ));
+    }
+    let mut out = format!("# Added {} transformation at {}\n", kind, path);
+    for e in &existing {
+        out.push_str(&format!("# Warning: {}\n", e));
+    }
+    out.push_str(&updated);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MANIFEST: &str = r#"apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: orders
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["k:9092"]
+      topic: orders
+      consumerGroup: orders
+  transformations:
+    - type: remove
+      remove:
+        fields: ["$.password"]
+  sink:
+    type: postgresql
+    postgresql:
+      connectionString: "postgres://app@pg:5432/shop"
+      table: orders
+  branches:
+    audit:
+      sink:
+        type: kafka
+        kafka:
+          brokers: ["k:9092"]
+          topic: audit
+"#;
+
+    #[test]
+    fn test_add_transformation_in_order() {
+        let mask = json!({ "type": "mask", "mask": { "fields": ["$.email"] } });
+        let out = add_transformation(MANIFEST, &mask, None, Some(0)).unwrap();
+        assert!(out.starts_with("# Added mask transformation at spec.transformations[0]\n"));
+        let doc: Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(doc["spec"]["transformations"][0]["type"], "mask");
+        assert_eq!(doc["spec"]["transformations"][1]["type"], "remove");
+        let filter = json!(r#"{"type": "filter", "filter": {"condition": "$.total > 100"}}"#);
+        let out = add_transformation(MANIFEST, &filter, Some("audit"), None).unwrap();
+        let doc: Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(
+            doc["spec"]["branches"]["audit"]["transformations"][0]["filter"]["condition"],
+            "$.total > 100"
+        );
+    }
+
+    #[test]
+    fn test_add_transformation_rejects_invalid() {
+        let typo = json!({ "type": "filter", "filter": { "conditon": "$.x" } });
+        let err = add_transformation(MANIFEST, &typo, None, None).unwrap_err();
+        assert!(err.starts_with("filter transformation not added:\n- spec.transformations[1]"));
+        assert!(
+            add_transformation(MANIFEST, &json!({ "mask": {} }), None, None)
+                .unwrap_err()
+                .contains("with a type")
+        );
+        let select = json!({ "type": "select", "select": { "fields": ["$.id"] } });
+        assert_eq!(
+            add_transformation(MANIFEST, &select, Some("missing"), None).unwrap_err(),
+            "spec.branches.missing does not exist; branches: audit"
+        );
+        assert!(add_transformation(MANIFEST, &select, None, Some(5))
+            .unwrap_err()
+            .starts_with("position 5 is out of range"));
+    }
+}
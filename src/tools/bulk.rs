@@ -0,0 +1,389 @@
+// Bulk manifest generation: a JSON or CSV list of flows (name, source, sink, options) becomes one multi-document
+// YAML, each row generated like generate_dataflow_manifest.
+
+use crate::tools::docs::field_type;
+use crate::tools::manifest::generate_dataflow_manifest;
+use serde_json::{Map as JsonMap, Value};
+use std::collections::BTreeSet;
+
+/// Per-row options besides name, source and sink.
+const ROW_OPTIONS: [&str; 6] = [
+    "namespace",
+    "description",
+    "transformations",
+    "parallelism",
+    "dlq",
+    "name_strategy",
+];
+
+/// One flow to generate: types, connector config and options.
+#[derive(Debug, Clone, Default)]
+struct Row {
+    name: Option<String>,
+    source_type: Option<String>,
+    source_config: JsonMap<String, Value>,
+    sink_type: Option<String>,
+    sink_config: JsonMap<String, Value>,
+    options: JsonMap<String, Value>,
+}
+
+/// Records of CSV text (RFC 4180: quoted fields may hold commas, newlines and "" quotes); blank lines are skipped.
+fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|f| !f.trim().is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("CSV ends inside a quoted field".to_string());
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.trim().is_empty()) {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Value of a CSV cell for a connector field, typed by the catalog: arrays are comma-separated, integers and
+/// booleans parsed, objects JSON.
+fn cell_value(field: &str, cell: &str) -> Result<Value, String> {
+    match field_type(field) {
+        Some("array") => Ok(Value::Array(
+            cell.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_string()))
+                .collect(),
+        )),
+        Some("integer") => cell
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("{} must be an integer, got '{}'", field, cell)),
+        Some("boolean") => cell
+            .parse::<bool>()
+            .map(Value::Bool)
+            .map_err(|_| format!("{} must be true or false, got '{}'", field, cell)),
+        Some("object") => {
+            serde_json::from_str(cell).map_err(|e| format!("{} invalid JSON: {}", field, e))
+        }
+        _ => Ok(Value::String(cell.to_string())),
+    }
+}
+
+/// Rows of a CSV table: the header names the columns (name, source, sink, source.<field>, sink.<field> and the
+/// ROW_OPTIONS); empty cells are unset.
+fn csv_rows(text: &str) -> Result<Vec<JsonMap<String, Value>>, String> {
+    let mut records = parse_csv(text)?.into_iter();
+    let header: Vec<String> = records
+        .next()
+        .ok_or("the CSV has no header row")?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    let mut rows = Vec::new();
+    for (i, record) in records.enumerate() {
+        if record.len() > header.len() {
+            return Err(format!(
+                "row {}: {} cells but the header has {} columns",
+                i + 1,
+                record.len(),
+                header.len()
+            ));
+        }
+        let mut row = JsonMap::new();
+        for (column, cell) in header
+            .iter()
+            .zip(&record)
+            .filter(|(_, c)| !c.trim().is_empty())
+        {
+            let cell = cell.trim();
+            let value = match column.split_once('.') {
+                Some(("source" | "sink", field)) => cell_value(field, cell).map_err(|e| {
+                    format!(
+                        "row {}: {}.{}",
+                        i + 1,
+                        column.split('.').next().unwrap_or(""),
+                        e
+                    )
+                })?,
+                _ if column == "parallelism" => {
+                    cell.parse::<u32>().map(Value::from).map_err(|_| {
+                        format!(
+                            "row {}: parallelism must be a positive integer, got '{}'",
+                            i + 1,
+                            cell
+                        )
+                    })?
+                }
+                _ => Value::String(cell.to_string()),
+            };
+            row.insert(column.clone(), value);
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Rows of a JSON list: an array of row objects, or {"rows": [...]}.
+fn json_rows(text: &str) -> Result<Vec<JsonMap<String, Value>>, String> {
+    let parsed: Value =
+        serde_json::from_str(text).map_err(|e| format!("rows invalid JSON: {}", e))?;
+    let items = match parsed {
+        Value::Array(items) => items,
+        Value::Object(mut map) => match map.remove("rows") {
+            Some(Value::Array(items)) => items,
+            _ => {
+                return Err(
+                    "rows must be a JSON array of rows or an object with a rows array".to_string(),
+                )
+            }
+        },
+        _ => {
+            return Err(
+                "rows must be a JSON array of rows or an object with a rows array".to_string(),
+            )
+        }
+    };
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| match item {
+            Value::Object(map) => Ok(map),
+            _ => Err(format!(
+                "row {}: must be an object with name, source and sink",
+                i + 1
+            )),
+        })
+        .collect()
+}
+
+/// Applies the columns of one row (or of the defaults) onto row. source and sink take a type or an object with type
+/// and config fields; options may be nested under options.
+fn apply(row: &mut Row, columns: &JsonMap<String, Value>) -> Result<(), String> {
+    for (key, value) in columns {
+        match (key.as_str(), value) {
+            ("name", Value::String(s)) => row.name = Some(s.clone()),
+            ("source" | "sink", Value::String(s)) => *connector_type(row, key) = Some(s.clone()),
+            ("source" | "sink", Value::Object(block)) => {
+                for (field, v) in block {
+                    match (field.as_str(), v) {
+                        ("type", Value::String(t)) => *connector_type(row, key) = Some(t.clone()),
+                        _ => {
+                            connector_config(row, key).insert(field.clone(), v.clone());
+                        }
+                    }
+                }
+            }
+            ("options", Value::Object(options)) => apply(row, options)?,
+            (column, v) => match column.split_once('.') {
+                Some((side @ ("source" | "sink"), field)) => {
+                    connector_config(row, side).insert(field.to_string(), v.clone());
+                }
+                _ if ROW_OPTIONS.contains(&column) => {
+                    row.options.insert(column.to_string(), v.clone());
+                }
+                _ if ["name", "source", "sink", "options"].contains(&column) => return Err(format!("{} has an unsupported value {}", column, v)),
+                _ => {
+                    return Err(format!(
+                        "unknown column '{}'; columns: name, source, sink, source.<field>, sink.<field>, {}",
+                        column,
+                        ROW_OPTIONS.join(", ")
+                    ))
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+fn connector_type<'a>(row: &'a mut Row, side: &str) -> &'a mut Option<String> {
+    if side == "source" {
+        &mut row.source_type
+    } else {
+        &mut row.sink_type
+    }
+}
+
+fn connector_config<'a>(row: &'a mut Row, side: &str) -> &'a mut JsonMap<String, Value> {
+    if side == "source" {
+        &mut row.source_config
+    } else {
+        &mut row.sink_config
+    }
+}
+
+fn generate_row(row: &Row) -> Result<String, String> {
+    let option = |key: &str| row.options.get(key).and_then(Value::as_str);
+    let config = |c: &JsonMap<String, Value>| (!c.is_empty()).then(|| Value::Object(c.clone()));
+    let parallelism = match row.options.get("parallelism") {
+        None => None,
+        Some(v) => Some(
+            v.as_u64()
+                .and_then(|p| u32::try_from(p).ok())
+                .ok_or_else(|| format!("parallelism must be a positive integer, got {}", v))?,
+        ),
+    };
+    generate_dataflow_manifest(
+        option("description"),
+        row.source_type.as_deref(),
+        row.sink_type.as_deref(),
+        config(&row.source_config).as_ref(),
+        config(&row.sink_config).as_ref(),
+        row.options.get("transformations"),
+        row.name.as_deref(),
+        option("namespace"),
+        option("name_strategy"),
+        parallelism,
+        None,
+        option("dlq"),
+    )
+}
+
+/// Generates one DataFlow per row of rows (a JSON array or a CSV table, see csv_rows) as multi-document YAML.
+/// defaults (same columns as a row) apply to every row; a row's own values win and connector configs merge field by
+/// field. Every row needs a name, unique per namespace; the errors of all rows are reported together.
+pub fn generate_dataflows_bulk(
+    rows: &str,
+    defaults: &JsonMap<String, Value>,
+) -> Result<String, String> {
+    let text = rows.trim();
+    let rows = if text.starts_with('[') || text.starts_with('{') {
+        json_rows(text)?
+    } else {
+        csv_rows(text)?
+    };
+    if rows.is_empty() {
+        return Err("rows has no flows".to_string());
+    }
+    let mut base = Row::default();
+    apply(&mut base, defaults).map_err(|e| format!("defaults: {}", e))?;
+    let mut docs = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (i, columns) in rows.iter().enumerate() {
+        let mut row = base.clone();
+        let label = columns
+            .get("name")
+            .and_then(Value::as_str)
+            .map(|n| format!("row {} ({})", i + 1, n))
+            .unwrap_or_else(|| format!("row {}", i + 1));
+        if let Err(e) = apply(&mut row, columns) {
+            errors.push(format!("{}: {}", label, e));
+            continue;
+        }
+        let Some(name) = row.name.clone().filter(|n| !n.is_empty()) else {
+            errors.push(format!("{}: name is required", label));
+            continue;
+        };
+        let namespace = row
+            .options
+            .get("namespace")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+        if !seen.insert((namespace, name.clone())) {
+            errors.push(format!(
+                "{}: name {} is used by an earlier row in the same namespace",
+                label, name
+            ));
+            continue;
+        }
+        match generate_row(&row) {
+            Ok(yaml) => {
+                let (first, rest) = yaml.split_once('\n').unwrap_or((&yaml, ""));
+                docs.push(format!(
+                    "{}\n# Bulk row {} of {}\n{}",
+                    first,
+                    i + 1,
+                    rows.len(),
+                    rest
+                ));
+            }
+            Err(e) => errors.push(format!("{}: {}", label, e)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(format!(
+            "{} of {} rows failed:\n{}",
+            errors.len(),
+            rows.len(),
+            errors.join("\n")
+        ));
+    }
+    Ok(docs.join("---\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::manifest::{split_documents, validate_dataflow_manifest};
+    use serde_json::json;
+
+    #[test]
+    fn test_generate_dataflows_bulk_csv() {
+        let csv = "name,source,sink,source.topic,sink.table,parallelism\norders,kafka,postgresql,orders,orders,2\n\n\"payments\",kafka,postgresql,\"pay,ments\",payments,\n";
+        let defaults = json!({ "namespace": "shop", "source.brokers": ["k1:9092", "k2:9092"], "sink": { "connectionString": "postgres://pg:5432/shop" } });
+        let out = generate_dataflows_bulk(csv, defaults.as_object().unwrap()).unwrap();
+        let docs: Vec<Value> = split_documents(&out)
+            .iter()
+            .map(|(_, d)| serde_yaml::from_str(d).unwrap())
+            .collect();
+        assert_eq!(docs.len(), 2);
+        assert!(out.contains("# Bulk row 2 of 2\n"));
+        assert_eq!(docs[0]["metadata"]["namespace"], "shop");
+        assert_eq!(docs[0]["spec"]["parallelism"], 2);
+        assert_eq!(docs[1]["spec"]["source"]["kafka"]["topic"], "pay,ments");
+        assert_eq!(
+            docs[1]["spec"]["source"]["kafka"]["brokers"],
+            json!(["k1:9092", "k2:9092"])
+        );
+        for (_, doc) in split_documents(&out) {
+            assert_eq!(validate_dataflow_manifest(&doc), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_generate_dataflows_bulk_json_errors() {
+        let rows = r#"{"rows": [
+            {"name": "a", "source": {"type": "kafka", "brokers": ["k:9092"], "topic": "a"}, "sink": "s3", "options": {"dlq": "kafka"}},
+            {"name": "a", "source": "kafka", "sink": "kafka"},
+            {"source": "kafka", "sink": "kafka"},
+            {"name": "b", "source": "kafka", "sink": "kafka", "replicas": 2}
+        ]}"#;
+        let err = generate_dataflows_bulk(rows, &JsonMap::new()).unwrap_err();
+        assert_eq!(
+            err,
+            "3 of 4 rows failed:\nrow 2 (a): name a is used by an earlier row in the same namespace\nrow 3: name is required\nrow 4 (b): unknown column 'replicas'; columns: name, source, sink, source.<field>, sink.<field>, namespace, description, transformations, parallelism, dlq, name_strategy"
+        );
+        assert_eq!(
+            parse_csv("a,\"b\"\"c\"\r\n").unwrap(),
+            vec![vec!["a".to_string(), "b\"c".to_string()]]
+        );
+        assert!(
+            generate_dataflows_bulk("name,source\n\"x,kafka\n", &JsonMap::new())
+                .unwrap_err()
+                .contains("inside a quoted field")
+        );
+    }
+}
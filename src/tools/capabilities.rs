@@ -0,0 +1,303 @@
+// Capabilities of the deployed DataFlow operator (CRD annotations / schema or a version endpoint), used to
+// filter the compiled-in catalogs and check manifests against what the cluster actually supports.
+
+use crate::tools::kube::kubectl_json;
+use crate::tools::reference::{default_connectors, default_transformations};
+use crate::types::DATAFLOW_CRD_NAME;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// CRD annotations the operator sets: comma-separated type lists and its version.
+const SOURCES_ANNOTATION: &str = "dataflow.io/supported-sources";
+const SINKS_ANNOTATION: &str = "dataflow.io/supported-sinks";
+const TRANSFORMATIONS_ANNOTATION: &str = "dataflow.io/supported-transformations";
+const VERSION_ANNOTATION: &str = "dataflow.io/operator-version";
+
+/// What the running operator supports. An empty list means unknown: that category is not filtered.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OperatorCapabilities {
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+    #[serde(default)]
+    pub sinks: Vec<String>,
+    #[serde(default)]
+    pub transformations: Vec<String>,
+}
+
+fn split_list(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn schema_enum(v: &Value) -> Vec<String> {
+    v["enum"]
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads capabilities from a DataFlow CRD: the operator's annotations first, else the type enums of the
+/// served version's openAPIV3Schema.
+pub fn capabilities_from_crd(crd: &Value) -> OperatorCapabilities {
+    let annotations = &crd["metadata"]["annotations"];
+    let annotation = |name: &str| {
+        annotations[name]
+            .as_str()
+            .map(split_list)
+            .unwrap_or_default()
+    };
+    let versions = crd["spec"]["versions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let served = versions
+        .iter()
+        .find(|v| v["storage"].as_bool() == Some(true))
+        .or_else(|| versions.first());
+    let spec = served
+        .map(|v| v["schema"]["openAPIV3Schema"]["properties"]["spec"]["properties"].clone())
+        .unwrap_or(Value::Null);
+    let or_schema = |list: Vec<String>, schema: &Value| {
+        if list.is_empty() {
+            schema_enum(schema)
+        } else {
+            list
+        }
+    };
+    OperatorCapabilities {
+        version: annotations[VERSION_ANNOTATION].as_str().map(str::to_string),
+        sources: or_schema(
+            annotation(SOURCES_ANNOTATION),
+            &spec["source"]["properties"]["type"],
+        ),
+        sinks: or_schema(
+            annotation(SINKS_ANNOTATION),
+            &spec["sink"]["properties"]["type"],
+        ),
+        transformations: or_schema(
+            annotation(TRANSFORMATIONS_ANNOTATION),
+            &spec["transformations"]["items"]["properties"]["type"],
+        ),
+    }
+}
+
+/// Queries the operator: a version endpoint returning OperatorCapabilities JSON when given, else the DataFlow CRD via kubectl.
+pub async fn fetch_capabilities(
+    endpoint: Option<&str>,
+    context: Option<&str>,
+) -> Result<OperatorCapabilities, String> {
+    if let Some(url) = endpoint.filter(|u| !u.is_empty()) {
+        crate::config::current().ensure_online("Operator capability query")?;
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let resp = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+        if !resp.status().is_success() {
+            return Err(format!("GET {} returned {}", url, resp.status()));
+        }
+        return resp
+            .json::<OperatorCapabilities>()
+            .await
+            .map_err(|e| format!("GET {}: invalid capabilities response: {}", url, e));
+    }
+    let crd = kubectl_json(
+        "Operator capability query",
+        &["get", "crd", DATAFLOW_CRD_NAME],
+        context,
+    )
+    .await?;
+    Ok(capabilities_from_crd(&crd))
+}
+
+fn supports(list: &[String], t: &str) -> bool {
+    list.is_empty() || list.iter().any(|s| s == t)
+}
+
+/// Keeps only the catalog entries the operator supports; returns (filtered, dropped names).
+fn filter_catalog(
+    catalog: &JsonMap<String, Value>,
+    supported: &[String],
+) -> (JsonMap<String, Value>, Vec<String>) {
+    let mut kept = JsonMap::new();
+    let mut dropped = Vec::new();
+    for (name, entry) in catalog {
+        if supports(supported, name) {
+            kept.insert(name.clone(), entry.clone());
+        } else {
+            dropped.push(name.clone());
+        }
+    }
+    (kept, dropped)
+}
+
+/// Source, sink and transformation types a manifest uses that the operator does not support.
+pub fn check_manifest(
+    caps: &OperatorCapabilities,
+    config_yaml: &str,
+) -> Result<Vec<String>, String> {
+    let v: Value =
+        serde_yaml::from_str(config_yaml).map_err(|e| format!("YAML parse error: {}", e))?;
+    let mut errors = Vec::new();
+    let mut check = |path: String, t: Option<&str>, list: &[String], what: &str| {
+        if let Some(t) = t {
+            if !supports(list, t) {
+                errors.push(format!(
+                    "{}: {} '{}' is not supported by the deployed operator (supported: {})",
+                    path,
+                    what,
+                    t,
+                    list.join(", ")
+                ));
+            }
+        }
+    };
+    let spec = &v["spec"];
+    check(
+        "spec.source.type".to_string(),
+        spec["source"]["type"].as_str(),
+        &caps.sources,
+        "source",
+    );
+    check(
+        "spec.sink.type".to_string(),
+        spec["sink"]["type"].as_str(),
+        &caps.sinks,
+        "sink",
+    );
+    let mut lists = vec![("spec.transformations".to_string(), &spec["transformations"])];
+    if let Some(branches) = spec["branches"].as_object() {
+        for (name, branch) in branches {
+            lists.push((
+                format!("spec.branches.{}.transformations", name),
+                &branch["transformations"],
+            ));
+            check(
+                format!("spec.branches.{}.sink.type", name),
+                branch["sink"]["type"].as_str(),
+                &caps.sinks,
+                "sink",
+            );
+        }
+    }
+    for (path, list) in lists {
+        for (i, t) in list.as_array().into_iter().flatten().enumerate() {
+            check(
+                format!("{}[{}].type", path, i),
+                t["type"].as_str(),
+                &caps.transformations,
+                "transformation",
+            );
+        }
+    }
+    Ok(errors)
+}
+
+/// JSON report: the operator's capabilities, catalogs filtered to them, compiled-in entries the operator
+/// lacks and, when a manifest is given, its unsupported types.
+pub fn capabilities_report(
+    caps: &OperatorCapabilities,
+    config_yaml: Option<&str>,
+) -> Result<String, String> {
+    let connectors = default_connectors();
+    let empty = JsonMap::new();
+    let catalog = |key: &str| {
+        connectors
+            .get(key)
+            .and_then(Value::as_object)
+            .unwrap_or(&empty)
+    };
+    let (sources, dropped_sources) = filter_catalog(catalog("sources"), &caps.sources);
+    let (sinks, dropped_sinks) = filter_catalog(catalog("sinks"), &caps.sinks);
+    let (transformations, dropped_transformations) =
+        filter_catalog(&default_transformations(), &caps.transformations);
+    let mut report = json!({
+        "operator": caps,
+        "connectors": { "sources": sources, "sinks": sinks },
+        "transformations": transformations,
+        "not_supported_by_operator": {
+            "sources": dropped_sources,
+            "sinks": dropped_sinks,
+            "transformations": dropped_transformations
+        }
+    });
+    if let Some(yaml) = config_yaml.filter(|y| !y.trim().is_empty()) {
+        report["manifest_errors"] = json!(check_manifest(caps, yaml)?);
+    }
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_from_crd_annotations_and_schema() {
+        let crd = json!({
+            "metadata": { "annotations": { SOURCES_ANNOTATION: "kafka, postgresql", VERSION_ANNOTATION: "0.9.1" } },
+            "spec": { "versions": [{ "name": "v1", "storage": true, "schema": { "openAPIV3Schema": { "properties": { "spec": { "properties": {
+                "sink": { "properties": { "type": { "enum": ["kafka", "postgresql"] } } },
+                "transformations": { "items": { "properties": { "type": { "enum": ["filter", "mask"] } } } }
+            } } } } } }] }
+        });
+        let caps = capabilities_from_crd(&crd);
+        assert_eq!(caps.version.as_deref(), Some("0.9.1"));
+        assert_eq!(caps.sources, vec!["kafka", "postgresql"]);
+        assert_eq!(caps.sinks, vec!["kafka", "postgresql"]);
+        assert_eq!(caps.transformations, vec!["filter", "mask"]);
+    }
+
+    #[test]
+    fn test_capabilities_report_filters_catalog_and_checks_manifest() {
+        let caps = OperatorCapabilities {
+            version: None,
+            sources: vec!["kafka".to_string()],
+            sinks: vec!["kafka".to_string(), "postgresql".to_string()],
+            transformations: vec![],
+        };
+        let manifest = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata: {name: orders}
+spec:
+  source: {type: kafka, kafka: {brokers: ["k:9092"], topic: orders}}
+  sink: {type: s3, s3: {bucket: lake, region: eu-west-1}}
+  transformations:
+    - {type: filter, filter: {condition: "$.ok"}}
+"#;
+        let report: Value =
+            serde_json::from_str(&capabilities_report(&caps, Some(manifest)).unwrap()).unwrap();
+        assert!(report["connectors"]["sinks"].get("postgresql").is_some());
+        assert!(report["connectors"]["sinks"].get("s3").is_none());
+        assert!(report["not_supported_by_operator"]["sources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|s| s == "trino"));
+        // Unknown transformation support: nothing is filtered.
+        assert!(report["not_supported_by_operator"]["transformations"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+        let errors = report["manifest_errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .as_str()
+            .unwrap()
+            .starts_with("spec.sink.type: sink 's3' is not supported"));
+    }
+}
@@ -0,0 +1,298 @@
+// Changelog entry between two revisions of a DataFlow manifest: field-level changes, risk, data-position
+// warnings for consumer group / topic / table renames and a rollback hint.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Risk of a change when the new revision is applied to a running flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl Risk {
+    fn as_str(self) -> &'static str {
+        match self {
+            Risk::Low => "low",
+            Risk::Medium => "medium",
+            Risk::High => "high",
+        }
+    }
+}
+
+/// Path fragments whose change redirects or re-reads data (high) or changes message shape/throughput (medium).
+const HIGH_RISK: [&str; 13] = [
+    "metadata.name",
+    "metadata.namespace",
+    ".type",
+    ".topic",
+    ".topicPattern",
+    ".consumerGroup",
+    ".connectionString",
+    ".table",
+    ".replication",
+    ".mode",
+    ".incrementalColumn",
+    ".brokers",
+    ".index",
+];
+const MEDIUM_RISK: [&str; 5] = [
+    "spec.parallelism",
+    ".format",
+    ".columnMapping",
+    ".payloadMode",
+    ".batchTransformations",
+];
+
+fn classify(path: &str) -> Risk {
+    if path.starts_with("metadata.annotations") || path.starts_with("metadata.labels") {
+        Risk::Low
+    } else if [
+        "spec.transformations",
+        "spec.branches",
+        "spec.errorHandling",
+    ]
+    .iter()
+    .any(|p| path.starts_with(p))
+    {
+        Risk::Medium
+    } else if HIGH_RISK
+        .iter()
+        .any(|p| path.starts_with(p) || path.contains(&format!("{}.", p)) || path.ends_with(p))
+    {
+        Risk::High
+    } else if MEDIUM_RISK
+        .iter()
+        .any(|p| path.starts_with(p) || path.contains(p))
+    {
+        Risk::Medium
+    } else {
+        Risk::Low
+    }
+}
+
+/// Flattens a manifest into path -> scalar (or empty container) entries: spec.source.kafka.topic, spec.transformations[0].type.
+pub(crate) fn flatten(v: &Value, path: &str, out: &mut BTreeMap<String, Value>) {
+    match v {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, child) in map {
+                let p = if path.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", path, k)
+                };
+                flatten(child, &p, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (i, child) in items.iter().enumerate() {
+                flatten(child, &format!("{}[{}]", path, i), out);
+            }
+        }
+        _ => {
+            out.insert(path.to_string(), v.clone());
+        }
+    }
+}
+
+fn show(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn parse(yaml: &str, which: &str) -> Result<Value, String> {
+    serde_yaml::from_str(yaml).map_err(|e| format!("{}: YAML parse error: {}", which, e))
+}
+
+/// Markdown changelog entry for going from old to new, suitable for a PR description or release notes.
+pub fn generate_change_log(old_yaml: &str, new_yaml: &str) -> Result<String, String> {
+    let (old, new) = (parse(old_yaml, "old")?, parse(new_yaml, "new")?);
+    let (mut before, mut after) = (BTreeMap::new(), BTreeMap::new());
+    flatten(&old, "", &mut before);
+    flatten(&new, "", &mut after);
+
+    let mut changes: Vec<(Risk, String)> = Vec::new();
+    for (path, value) in &after {
+        match before.get(path) {
+            None => changes.push((
+                classify(path),
+                format!("added `{}`: `{}`", path, show(value)),
+            )),
+            Some(old_value) if old_value != value => changes.push((
+                classify(path),
+                format!(
+                    "changed `{}`: `{}` → `{}`",
+                    path,
+                    show(old_value),
+                    show(value)
+                ),
+            )),
+            _ => {}
+        }
+    }
+    for (path, value) in &before {
+        if !after.contains_key(path) {
+            changes.push((
+                classify(path),
+                format!("removed `{}` (was `{}`)", path, show(value)),
+            ));
+        }
+    }
+
+    let name = new["metadata"]["name"].as_str().unwrap_or("dataflow");
+    let mut out = format!("### DataFlow `{}`\n\n", name);
+    if changes.is_empty() {
+        out.push_str("No changes.\n");
+        return Ok(out);
+    }
+    let risk = changes.iter().map(|(r, _)| *r).max().unwrap_or(Risk::Low);
+    out.push_str(&format!("**Risk:** {}\n\n**Changes:**\n", risk.as_str()));
+    changes.sort_by_key(|c| std::cmp::Reverse(c.0));
+    for (r, line) in &changes {
+        out.push_str(&format!("- [{}] {}\n", r.as_str(), line));
+    }
+    let warnings = position_warnings(&before, &after);
+    if !warnings.is_empty() {
+        out.push_str("\n**Data position warnings:**\n");
+        for w in &warnings {
+            out.push_str(&format!("- {}\n", w));
+        }
+    }
+    out.push_str(&format!("\n**Rollback:** {}\n", rollback_hint(&changes)));
+    Ok(out)
+}
+
+/// Renames that lose the flow's data position: a consumer group without committed offsets, a topic the
+/// offsets do not apply to, a table that starts empty or without the stored cursor. Each comes with remediation.
+fn position_warnings(
+    before: &BTreeMap<String, Value>,
+    after: &BTreeMap<String, Value>,
+) -> Vec<String> {
+    let mut paths: Vec<&String> = before.keys().chain(after.keys()).collect();
+    paths.sort();
+    paths.dedup();
+    let mut warnings = Vec::new();
+    for path in paths {
+        let field = path.rsplit('.').next().unwrap_or("");
+        let (old, new) = (before.get(path).map(show), after.get(path).map(show));
+        if old == new {
+            continue;
+        }
+        let source = path.starts_with("spec.source.");
+        let warning = match (field, old, new) {
+            ("consumerGroup", old, new) => {
+                let old = old.unwrap_or_else(|| "(default group)".to_string());
+                let new = new.unwrap_or_else(|| "(default group)".to_string());
+                format!(
+                    "consumer group `{}` → `{}` (`{}`): the new group has no committed offsets, so the flow starts at offsetReset (latest by default) and skips or re-reads messages. Before applying, export the old group's offsets (`kafka-consumer-groups --describe --group {}`) and set them for the new group (`kafka-consumer-groups --reset-offsets --group {} --from-file offsets.csv --execute`), or keep the old name.",
+                    old, new, path, old, new
+                )
+            }
+            ("topic", Some(old), Some(new)) if source => format!(
+                "source topic `{}` → `{}` (`{}`): committed offsets belong to the old topic; the new one is read from offsetReset. Make sure `{}` holds the data (mirror or backfill it), drain `{}` before switching, and set offsetReset: earliest if the new topic must be read from the start.",
+                old, new, path, new, old
+            ),
+            ("topic", Some(old), Some(new)) => format!(
+                "sink topic `{}` → `{}` (`{}`): consumers of `{}` stop receiving messages. Move downstream consumers to `{}` and copy retained messages if they need the history.",
+                old, new, path, old, new
+            ),
+            ("table", Some(old), Some(new)) if source => format!(
+                "source table `{}` → `{}` (`{}`): the stored cursor (cursorStorage) tracks the old table, so `{}` is read from initialValue. Set initialValue to the last processed cursor value, or reset the cursor deliberately.",
+                old, new, path, new
+            ),
+            ("table", Some(old), Some(new)) => format!(
+                "sink table `{}` → `{}` (`{}`): existing rows stay in `{}` and new writes go to `{}`. Migrate the data first (`ALTER TABLE {} RENAME TO {}`, or `INSERT INTO {} SELECT * FROM {}`) and update readers.",
+                old, new, path, old, new, old, new, new, old
+            ),
+            _ => continue,
+        };
+        warnings.push(warning);
+    }
+    warnings
+}
+
+fn rollback_hint(changes: &[(Risk, String)]) -> String {
+    let touches = |needle: &str| {
+        changes
+            .iter()
+            .any(|(r, l)| *r == Risk::High && l.contains(needle))
+    };
+    let mut hint = String::from("re-apply the previous manifest revision.");
+    if touches("metadata.name") || touches("metadata.namespace") {
+        hint.push_str(" The resource was renamed or moved: delete the new DataFlow, rolling back does not remove it.");
+    }
+    if touches(".consumerGroup")
+        || touches(".topic")
+        || touches(".replication")
+        || touches(".incrementalColumn")
+    {
+        hint.push_str(" Read position changed (topic, consumer group, replication slot or cursor): after rollback the old position resumes where it stopped, so messages processed by the new revision may be read again.");
+    }
+    if touches("spec.sink") {
+        hint.push_str(" Data already written to the new sink target stays there; clean it up manually if needed.");
+    }
+    hint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_change_log() {
+        let old = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: orders
+spec:
+  parallelism: 2
+  source:
+    type: kafka
+    kafka: {brokers: ["k:9092"], topic: orders}
+  sink:
+    type: postgresql
+    postgresql: {connectionString: "postgres://pg/shop", table: orders, batchSize: 100}
+"#;
+        let new = old
+            .replace("topic: orders", "topic: orders-v2")
+            .replace("batchSize: 100", "batchSize: 500")
+            .replace("parallelism: 2", "parallelism: 4");
+        let log = generate_change_log(old, &new).unwrap();
+        assert!(log.contains("**Risk:** high"));
+        assert!(log.contains("- [high] changed `spec.source.kafka.topic`: `orders` → `orders-v2`"));
+        assert!(log.contains("- [medium] changed `spec.parallelism`: `2` → `4`"));
+        assert!(log.contains("- [low] changed `spec.sink.postgresql.batchSize`: `100` → `500`"));
+        assert!(log.contains("may be read again"));
+        assert!(log.contains("**Data position warnings:**\n- source topic `orders` → `orders-v2`"));
+        assert!(generate_change_log(old, old)
+            .unwrap()
+            .contains("No changes."));
+    }
+
+    #[test]
+    fn test_change_log_warns_on_consumer_group_and_table_renames() {
+        let old = r#"
+metadata: {name: orders}
+spec:
+  source: {type: kafka, kafka: {brokers: ["k:9092"], topic: orders, consumerGroup: orders-v1}}
+  sink: {type: postgresql, postgresql: {connectionString: "postgres://pg/shop", table: orders}}
+"#;
+        let new = old
+            .replace("consumerGroup: orders-v1", "consumerGroup: orders-v2")
+            .replace("table: orders", "table: orders_new");
+        let log = generate_change_log(old, &new).unwrap();
+        assert!(log.contains("- consumer group `orders-v1` → `orders-v2` (`spec.source.kafka.consumerGroup`): the new group has no committed offsets"));
+        assert!(log.contains("--reset-offsets --group orders-v2"));
+        assert!(log.contains("- sink table `orders` → `orders_new` (`spec.sink.postgresql.table`)"));
+        assert!(log.contains("ALTER TABLE orders RENAME TO orders_new"));
+        let dropped = old.replace(", consumerGroup: orders-v1", "");
+        assert!(generate_change_log(old, &dropped)
+            .unwrap()
+            .contains("consumer group `orders-v1` → `(default group)`"));
+    }
+}
@@ -0,0 +1,133 @@
+// Chunked tool output: results over the configured size are split into parts; the first part is returned with a
+// continuation token and the rest are fetched with fetch_result_chunk.
+
+use std::collections::VecDeque;
+
+/// How many chunked results are kept; older ones are dropped (their tokens expire).
+const MAX_STORED_RESULTS: usize = 16;
+
+#[derive(Debug, Default)]
+pub struct ResultChunks {
+    next_id: u64,
+    stored: VecDeque<(String, Vec<String>)>,
+}
+
+/// Splits text into parts of at most max_chars characters, preferring to cut after a newline.
+pub fn split_chunks(text: &str, max_chars: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > max_chars {
+        let limit = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(i, _)| i);
+        let cut = match rest[..limit].rfind('\n') {
+            Some(nl) if nl > 0 => nl + 1,
+            _ => limit,
+        };
+        parts.push(rest[..cut].to_string());
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(rest.to_string());
+    }
+    parts
+}
+
+fn with_footer(part: &str, token: &str, index: usize, total: usize) -> String {
+    if index + 1 < total {
+        format!(
+            "{}\n[part {}/{}; call fetch_result_chunk with token \"{}\" and index {} for the next part]",
+            part,
+            index + 1,
+            total,
+            token,
+            index + 1
+        )
+    } else {
+        format!(
+            "{}\n[part {}/{}; end of result \"{}\"]",
+            part,
+            index + 1,
+            total,
+            token
+        )
+    }
+}
+
+impl ResultChunks {
+    /// Returns text unchanged when it fits in max_chars (or max_chars is 0), otherwise stores its parts and
+    /// returns the first one with the continuation footer.
+    pub fn paginate(&mut self, text: String, max_chars: usize) -> String {
+        if max_chars == 0 || text.chars().count() <= max_chars {
+            return text;
+        }
+        let parts = split_chunks(&text, max_chars);
+        self.next_id += 1;
+        let token = format!("r{}", self.next_id);
+        let first = with_footer(&parts[0], &token, 0, parts.len());
+        if self.stored.len() == MAX_STORED_RESULTS {
+            self.stored.pop_front();
+        }
+        self.stored.push_back((token, parts));
+        first
+    }
+
+    /// Part index (0-based) of a stored result, with the continuation footer.
+    pub fn fetch(&self, token: &str, index: usize) -> Result<String, String> {
+        let (_, parts) = self
+            .stored
+            .iter()
+            .find(|(t, _)| t == token)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown or expired result token '{}'; call the original tool again",
+                    token
+                )
+            })?;
+        let part = parts.get(index).ok_or_else(|| {
+            format!(
+                "Result '{}' has {} parts (index 0..{})",
+                token,
+                parts.len(),
+                parts.len() - 1
+            )
+        })?;
+        Ok(with_footer(part, token, index, parts.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_chunks_prefers_line_breaks() {
+        let text = "apiVersion: v1\nkind: DataFlow\n---\nапи: ключ\n";
+        let parts = split_chunks(text, 20);
+        assert_eq!(parts.concat(), text);
+        assert_eq!(parts[0], "apiVersion: v1\n");
+        assert!(parts.iter().all(|p| p.chars().count() <= 20));
+        assert_eq!(split_chunks("", 5), vec![""]);
+        assert_eq!(split_chunks("abcdefg", 3), vec!["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn test_paginate_and_fetch() {
+        let mut chunks = ResultChunks::default();
+        assert_eq!(chunks.paginate("short".to_string(), 100), "short");
+        assert_eq!(chunks.paginate("x".repeat(500), 0).len(), 500);
+        let first = chunks.paginate("line1\nline2\nline3\n".to_string(), 12);
+        assert!(first.starts_with("line1\nline2\n") && first.contains("token \"r1\" and index 1"));
+        let last = chunks.fetch("r1", 1).unwrap();
+        assert!(last.starts_with("line3\n") && last.ends_with("[part 2/2; end of result \"r1\"]"));
+        assert!(chunks.fetch("r1", 2).unwrap_err().contains("has 2 parts"));
+        for _ in 0..MAX_STORED_RESULTS {
+            chunks.paginate("a\nb\n".to_string(), 2);
+        }
+        assert!(chunks
+            .fetch("r1", 0)
+            .unwrap_err()
+            .starts_with("Unknown or expired"));
+    }
+}
@@ -0,0 +1,180 @@
+// Source/sink format compatibility: whether what the source emits can be read by the transformations and written
+// by each sink, with the change that makes an incompatible flow work.
+
+use serde_json::Value;
+
+/// Transformations that read or change message fields, so they need records rather than raw strings.
+const FIELD_TRANSFORMATIONS: [&str; 12] = [
+    "filter",
+    "mask",
+    "select",
+    "remove",
+    "flatten",
+    "unwrap",
+    "unwrapCloudEvent",
+    "snakeCase",
+    "camelCase",
+    "timestamp",
+    "router",
+    "enrich",
+];
+
+/// Kafka formats that encode records with a schema and cannot carry a raw string.
+const SCHEMA_FORMATS: [&str; 3] = ["avro", "protobuf", "jsonschema"];
+
+const RAW_FIX: &str = "read the source with format json (or avro / protobuf with a schema), or parse the message in a script transformation first";
+
+/// Checks the flow against the source format: a kafka source with format string delivers raw strings without
+/// fields, which field transformations cannot read and structured sinks cannot write. A script transformation may
+/// turn the string into a record, so checks stop after one.
+pub fn validate_format_compatibility(spec: &Value, errors: &mut Vec<String>) {
+    let source = &spec["source"];
+    if source["type"] != "kafka" || source["kafka"]["format"] != "string" {
+        return;
+    }
+    if check_chain(&spec["transformations"], "spec.transformations", errors) {
+        check_sink(&spec["sink"], "spec.sink", errors);
+        for (name, branch) in spec["branches"].as_object().into_iter().flatten() {
+            let path = format!("spec.branches.{}", name);
+            if check_chain(
+                &branch["transformations"],
+                &format!("{}.transformations", path),
+                errors,
+            ) {
+                check_sink(&branch["sink"], &format!("{}.sink", path), errors);
+            }
+        }
+    }
+}
+
+/// Reports field transformations and router sinks in a chain of raw strings; false once a script may have parsed
+/// them.
+fn check_chain(chain: &Value, path: &str, errors: &mut Vec<String>) -> bool {
+    for (i, t) in chain.as_array().into_iter().flatten().enumerate() {
+        let kind = t["type"].as_str().unwrap_or("");
+        if kind == "script" {
+            return false;
+        }
+        if FIELD_TRANSFORMATIONS.contains(&kind) {
+            errors.push(format!(
+                "{}[{}] ({}) reads message fields, but spec.source.kafka.format string delivers raw strings without fields; {}",
+                path, i, kind, RAW_FIX
+            ));
+        }
+        for (j, route) in t["router"]["routes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            if route["sink"].is_object() {
+                check_sink(
+                    &route["sink"],
+                    &format!("{}[{}].router.routes[{}].sink", path, i, j),
+                    errors,
+                );
+            }
+        }
+    }
+    true
+}
+
+/// Reports a sink that cannot write raw strings.
+fn check_sink(sink: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(kind) = sink["type"].as_str() else {
+        return;
+    };
+    let block = &sink[kind];
+    let problem = match kind {
+        "postgresql" => match block["payloadMode"].as_str().unwrap_or("columns") {
+            "bytea" => None,
+            "jsonb" => Some(format!(
+                "{}.postgresql.payloadMode jsonb needs JSON messages, but spec.source.kafka.format string delivers raw strings; use payloadMode: bytea to store each message as is, or {}",
+                path, RAW_FIX
+            )),
+            _ => Some(format!(
+                "{}.postgresql writes one column per field, but spec.source.kafka.format string delivers raw strings without fields; use payloadMode: bytea to store each message as is, or {}",
+                path, RAW_FIX
+            )),
+        },
+        "clickhouse" | "trino" => Some(format!(
+            "{}.{} writes one column per field, but spec.source.kafka.format string delivers raw strings without fields; {}",
+            path, kind, RAW_FIX
+        )),
+        "elasticsearch" => Some(format!(
+            "{}.elasticsearch indexes JSON documents, but spec.source.kafka.format string delivers raw strings; {}",
+            path, RAW_FIX
+        )),
+        "s3" => match block["format"].as_str() {
+            Some(format @ ("avro" | "parquet")) => Some(format!(
+                "{}.s3.format {} needs records with fields, but spec.source.kafka.format string delivers raw strings; use s3 format json, or {}",
+                path, format, RAW_FIX
+            )),
+            _ => None,
+        },
+        "kafka" => match block["format"].as_str() {
+            Some(format) if SCHEMA_FORMATS.contains(&format) => Some(format!(
+                "{}.kafka.format {} encodes records with a schema, but spec.source.kafka.format string delivers raw strings; use format string or json, or {}",
+                path, format, RAW_FIX
+            )),
+            _ => None,
+        },
+        _ => None,
+    };
+    errors.extend(problem);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_raw_string_source_compatibility() {
+        let spec = json!({
+            "source": { "type": "kafka", "kafka": { "topic": "logs", "format": "string" } },
+            "transformations": [
+                { "type": "filter", "filter": { "condition": "$.level == 'error'" } },
+                { "type": "router", "router": { "routes": [{ "condition": "$.x", "sink": { "type": "s3", "s3": { "format": "parquet" } } }] } }
+            ],
+            "sink": { "type": "postgresql", "postgresql": { "table": "logs" } },
+            "branches": { "raw": { "sink": { "type": "postgresql", "postgresql": { "table": "raw", "payloadMode": "bytea" } } } }
+        });
+        let mut errors = Vec::new();
+        validate_format_compatibility(&spec, &mut errors);
+        let paths: Vec<&str> = errors
+            .iter()
+            .map(|e| e.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "spec.transformations[0]",
+                "spec.transformations[1]",
+                "spec.transformations[1].router.routes[0].sink.s3.format",
+                "spec.sink.postgresql",
+            ]
+        );
+        assert!(errors[3].contains("use payloadMode: bytea to store each message as is"));
+    }
+
+    #[test]
+    fn test_script_or_structured_source_is_compatible() {
+        let mut spec = json!({
+            "source": { "type": "kafka", "kafka": { "topic": "logs", "format": "string" } },
+            "transformations": [{ "type": "script", "script": { "language": "lua", "inline": "return parse(msg)" } }, { "type": "select" }],
+            "sink": { "type": "trino", "trino": { "table": "logs" } }
+        });
+        let mut errors = Vec::new();
+        validate_format_compatibility(&spec, &mut errors);
+        assert!(errors.is_empty());
+        spec["transformations"] = json!([]);
+        validate_format_compatibility(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("spec.sink.trino writes one column per field"));
+        spec["source"]["kafka"]["format"] = json!("json");
+        errors.clear();
+        validate_format_compatibility(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+// Confluent Cloud fully-managed connector configs: rewritten to the self-managed property shape before migration.
+
+use crate::tools::kafka_connect::{get, KafkaConnectConnector};
+use std::collections::HashMap;
+
+/// Original managed connector class, kept so the migration can explain what was translated.
+const CLOUD_CLASS_KEY: &str = "confluent.cloud.connector.class";
+
+/// Managed connector class -> self-managed class handled by the migrator.
+const CLOUD_CONNECTOR_CLASSES: [(&str, &str); 8] = [
+    (
+        "PostgresCdcSource",
+        "io.debezium.connector.postgresql.PostgresConnector",
+    ),
+    (
+        "PostgresCdcSourceV2",
+        "io.debezium.connector.postgresql.PostgresConnector",
+    ),
+    (
+        "MySqlCdcSource",
+        "io.debezium.connector.mysql.MySqlConnector",
+    ),
+    (
+        "MySqlCdcSourceV2",
+        "io.debezium.connector.mysql.MySqlConnector",
+    ),
+    (
+        "PostgresSource",
+        "io.confluent.connect.jdbc.JdbcSourceConnector",
+    ),
+    (
+        "PostgresSink",
+        "io.confluent.connect.jdbc.JdbcSinkConnector",
+    ),
+    (
+        "ElasticsearchSink",
+        "io.confluent.connect.elasticsearch.ElasticsearchSinkConnector",
+    ),
+    ("S3_SINK", "io.confluent.connect.s3.S3SinkConnector"),
+];
+
+/// input.data.format / output.data.format -> value.converter.
+const DATA_FORMAT_CONVERTERS: [(&str, &str); 6] = [
+    ("AVRO", "io.confluent.connect.avro.AvroConverter"),
+    (
+        "PROTOBUF",
+        "io.confluent.connect.protobuf.ProtobufConverter",
+    ),
+    ("JSON_SR", "io.confluent.connect.json.JsonSchemaConverter"),
+    ("JSON", "org.apache.kafka.connect.json.JsonConverter"),
+    ("STRING", "org.apache.kafka.connect.storage.StringConverter"),
+    (
+        "BYTES",
+        "org.apache.kafka.connect.converters.ByteArrayConverter",
+    ),
+];
+
+/// S3 output.data.format -> format.class.
+const S3_FORMAT_CLASSES: [(&str, &str); 3] = [
+    ("AVRO", "io.confluent.connect.s3.format.avro.AvroFormat"),
+    ("JSON", "io.confluent.connect.s3.format.json.JsonFormat"),
+    (
+        "PARQUET",
+        "io.confluent.connect.s3.format.parquet.ParquetFormat",
+    ),
+];
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| *v)
+}
+
+/// Managed configs use short class names (PostgresSink, S3_SINK) and kafka.api.key / kafka.auth.mode.
+fn is_cloud_config(config: &HashMap<String, String>) -> bool {
+    let class = get(config, "connector.class").unwrap_or_default();
+    (!class.is_empty() && !class.contains('.'))
+        || config.contains_key("kafka.api.key")
+        || config.contains_key("kafka.auth.mode")
+}
+
+/// Rewrites a managed connector config to the self-managed shape. Self-managed properties already present win;
+/// the managed ones are kept, so notes can be derived later. Other configs are returned unchanged.
+pub(crate) fn normalize_connector(conn: &KafkaConnectConnector) -> KafkaConnectConnector {
+    let mut out = conn.clone();
+    let Some(config) = out.config.as_mut() else {
+        return out;
+    };
+    if !is_cloud_config(config) || config.contains_key(CLOUD_CLASS_KEY) {
+        return out;
+    }
+    let class = get(config, "connector.class").unwrap_or_default();
+    config.insert(CLOUD_CLASS_KEY.to_string(), class.clone());
+    if let Some(managed) = lookup(&CLOUD_CONNECTOR_CLASSES, &class) {
+        config.insert("connector.class".to_string(), managed.to_string());
+    }
+    if let Some(key) = get(config, "kafka.api.key") {
+        config
+            .entry("security.protocol".to_string())
+            .or_insert_with(|| "SASL_SSL".to_string());
+        config
+            .entry("sasl.mechanism".to_string())
+            .or_insert_with(|| "PLAIN".to_string());
+        config.entry("sasl.jaas.config".to_string()).or_insert_with(|| {
+            format!("org.apache.kafka.common.security.plain.PlainLoginModule required username=\"{}\";", key)
+        });
+    }
+    // Sources write output.data.format to Kafka, sinks read input.data.format from it.
+    let data_format =
+        get(config, "output.data.format").or_else(|| get(config, "input.data.format"));
+    let is_s3 = class.eq_ignore_ascii_case("S3_SINK");
+    if let Some(format) = &data_format {
+        if is_s3 {
+            if let Some(f) = lookup(&S3_FORMAT_CLASSES, format) {
+                config
+                    .entry("format.class".to_string())
+                    .or_insert_with(|| f.to_string());
+            }
+        }
+    }
+    let kafka_format = if is_s3 {
+        get(config, "input.data.format")
+    } else {
+        data_format
+    };
+    if let Some(converter) = kafka_format.and_then(|f| lookup(&DATA_FORMAT_CONVERTERS, &f)) {
+        config
+            .entry("value.converter".to_string())
+            .or_insert_with(|| converter.to_string());
+    }
+    if let Some(interval) = get(config, "time.interval") {
+        let partitioner = match interval.to_uppercase().as_str() {
+            "HOURLY" => Some("io.confluent.connect.storage.partitioner.HourlyPartitioner"),
+            "DAILY" => Some("io.confluent.connect.storage.partitioner.DailyPartitioner"),
+            _ => None,
+        };
+        if let Some(p) = partitioner {
+            config
+                .entry("partitioner.class".to_string())
+                .or_insert_with(|| p.to_string());
+        }
+    }
+    // JDBC connectors take host/port/db.name instead of a connection.url.
+    if let (Some(host), Some(db)) = (get(config, "connection.host"), get(config, "db.name")) {
+        let port = get(config, "connection.port").unwrap_or_else(|| "5432".to_string());
+        config
+            .entry("connection.url".to_string())
+            .or_insert_with(|| format!("jdbc:postgresql://{}:{}/{}", host, port, db));
+    }
+    if let Some(batch) = get(config, "batch.sizes") {
+        config.entry("batch.size".to_string()).or_insert(batch);
+    }
+    out
+}
+
+/// Migration notes for a config produced by normalize_connector (empty for self-managed configs).
+pub(crate) fn cloud_notes(config: &HashMap<String, String>) -> Vec<String> {
+    let Some(class) = get(config, CLOUD_CLASS_KEY) else {
+        return Vec::new();
+    };
+    let mut notes = Vec::new();
+    match lookup(&CLOUD_CONNECTOR_CLASSES, &class) {
+        Some(managed) => notes.push(format!(
+            "Confluent Cloud connector {} was migrated as its self-managed equivalent {}.",
+            class, managed
+        )),
+        None => notes.push(format!(
+            "Confluent Cloud connector class '{}' has no self-managed mapping; migrate it manually.",
+            class
+        )),
+    }
+    notes.push(
+        "Confluent Cloud connector configs do not include the cluster bootstrap endpoint; set the kafka brokers to the cluster's bootstrap server."
+            .to_string(),
+    );
+    if get(config, "kafka.auth.mode").is_some_and(|m| m.eq_ignore_ascii_case("SERVICE_ACCOUNT")) {
+        notes.push(
+            "kafka.auth.mode SERVICE_ACCOUNT has no DataFlow equivalent; create an API key for the service account and use SASL/PLAIN."
+                .to_string(),
+        );
+    }
+    if get(config, "kafka.api.key").is_some() {
+        notes.push(
+            "kafka.api.key became the SASL/PLAIN username; kafka.api.secret was not copied."
+                .to_string(),
+        );
+    }
+    if let Some(format) =
+        get(config, "output.data.format").or_else(|| get(config, "input.data.format"))
+    {
+        if matches!(
+            format.to_uppercase().as_str(),
+            "AVRO" | "PROTOBUF" | "JSON_SR"
+        ) {
+            notes.push(format!(
+                "Data format {} used the managed Schema Registry of the Confluent Cloud environment; set kafka.schemaRegistry.url and its API key.",
+                format
+            ));
+        }
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::kafka_connect::migrate_kafka_connect_to_dataflow;
+
+    #[test]
+    fn test_migrate_confluent_cloud_postgres_sink() {
+        let input = r#"{
+            "name": "orders-pg",
+            "config": {
+                "connector.class": "PostgresSink",
+                "kafka.auth.mode": "KAFKA_API_KEY",
+                "kafka.api.key": "ABCDEF",
+                "kafka.api.secret": "s3cret",
+                "input.data.format": "AVRO",
+                "topics": "orders",
+                "connection.host": "pg.example.com",
+                "connection.port": "5433",
+                "connection.user": "app",
+                "db.name": "shop",
+                "table.name.format": "orders"
+            }
+        }"#;
+        let yaml = migrate_kafka_connect_to_dataflow(input, None, None).unwrap();
+        assert!(yaml.contains("connectionString: postgres://pg.example.com:5433/shop"));
+        assert!(yaml.contains("format: avro"));
+        assert!(yaml.contains("mechanism: PLAIN"));
+        assert!(yaml.contains("username: ABCDEF"));
+        assert!(!yaml.contains("s3cret"));
+        assert!(yaml.contains(
+            "migrated as its self-managed equivalent io.confluent.connect.jdbc.JdbcSinkConnector"
+        ));
+    }
+
+    #[test]
+    fn test_normalize_confluent_cloud_s3_sink() {
+        let conn = KafkaConnectConnector {
+            name: Some("lake".to_string()),
+            config: Some(
+                [
+                    ("connector.class", "S3_SINK"),
+                    ("input.data.format", "JSON"),
+                    ("output.data.format", "PARQUET"),
+                    ("time.interval", "HOURLY"),
+                ]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ),
+        };
+        let config = normalize_connector(&conn).config.unwrap();
+        assert_eq!(
+            config["connector.class"],
+            "io.confluent.connect.s3.S3SinkConnector"
+        );
+        assert_eq!(
+            config["format.class"],
+            "io.confluent.connect.s3.format.parquet.ParquetFormat"
+        );
+        assert_eq!(
+            config["value.converter"],
+            "org.apache.kafka.connect.json.JsonConverter"
+        );
+        assert!(config["partitioner.class"].ends_with("HourlyPartitioner"));
+        assert!(!cloud_notes(&config).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+// Kafka Connect worker.properties: worker-level client settings and converters folded into each connector config.
+
+use crate::tools::kafka_connect::{get, read_properties, KafkaConnectConnector};
+use std::collections::HashMap;
+
+/// Comma-separated keys a connector config inherited from worker.properties, kept for the migration notes.
+pub(crate) const WORKER_KEYS_KEY: &str = "worker.properties.inherited";
+
+/// Worker properties (or prefixes) that connectors inherit. Others (group.id, offset/config/status storage,
+/// plugin.path, listeners) configure the worker itself and are ignored.
+const INHERITED: [&str; 9] = [
+    "bootstrap.servers",
+    "key.converter",
+    "value.converter",
+    "header.converter",
+    "security.protocol",
+    "sasl.",
+    "ssl.",
+    "consumer.",
+    "producer.",
+];
+
+/// Adds the inherited worker.properties settings each connector does not set itself.
+pub(crate) fn apply_worker_properties(
+    connectors: &[KafkaConnectConnector],
+    worker_properties: &str,
+) -> Vec<KafkaConnectConnector> {
+    let worker = read_properties(worker_properties);
+    let mut inherited: Vec<(&String, &String)> = worker
+        .iter()
+        .filter(|(k, _)| INHERITED.iter().any(|p| k.to_lowercase().starts_with(p)))
+        .collect();
+    inherited.sort();
+    connectors
+        .iter()
+        .map(|conn| {
+            let mut out = conn.clone();
+            let config = out.config.get_or_insert_with(HashMap::new);
+            let mut keys = Vec::new();
+            for (k, v) in &inherited {
+                if get(config, k).is_none() {
+                    config.insert(k.to_string(), v.to_string());
+                    keys.push(k.as_str());
+                }
+            }
+            if !keys.is_empty() {
+                config.insert(WORKER_KEYS_KEY.to_string(), keys.join(","));
+            }
+            out
+        })
+        .collect()
+}
+
+/// Keys of a connector config that came from worker.properties.
+pub(crate) fn inherited_keys(config: &HashMap<String, String>) -> Vec<String> {
+    get(config, WORKER_KEYS_KEY)
+        .map(|keys| keys.split(',').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Migration note listing the inherited settings (empty when nothing was inherited).
+pub(crate) fn worker_notes(config: &HashMap<String, String>) -> Vec<String> {
+    let keys = inherited_keys(config);
+    if keys.is_empty() {
+        return Vec::new();
+    }
+    vec![format!(
+        "Inherited from worker.properties: {}.",
+        keys.join(", ")
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::kafka_connect::{migrate_kafka_connect_to_dataflow, parse_input};
+
+    const WORKER: &str = "bootstrap.servers=kafka-1:9092,kafka-2:9092\ngroup.id=connect-cluster\nvalue.converter=io.confluent.connect.avro.AvroConverter\nvalue.converter.schema.registry.url=http://sr:8081\noffset.storage.topic=connect-offsets\nconsumer.auto.offset.reset=earliest\n";
+
+    #[test]
+    fn test_apply_worker_properties_keeps_connector_settings() {
+        let connectors = parse_input(r#"{"name": "orders", "config": {"connector.class": "io.confluent.connect.jdbc.JdbcSinkConnector", "topics": "orders", "value.converter": "org.apache.kafka.connect.json.JsonConverter"}}"#).unwrap();
+        let config = apply_worker_properties(&connectors, WORKER)
+            .remove(0)
+            .config
+            .unwrap();
+        assert_eq!(config["bootstrap.servers"], "kafka-1:9092,kafka-2:9092");
+        assert_eq!(
+            config["value.converter"],
+            "org.apache.kafka.connect.json.JsonConverter"
+        );
+        assert!(!config.contains_key("group.id") && !config.contains_key("offset.storage.topic"));
+        assert_eq!(
+            inherited_keys(&config),
+            vec![
+                "bootstrap.servers",
+                "consumer.auto.offset.reset",
+                "value.converter.schema.registry.url"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrate_with_worker_properties() {
+        let input = r#"{"name": "orders", "config": {"connector.class": "io.confluent.connect.jdbc.JdbcSinkConnector", "connection.url": "jdbc:postgresql://pg:5432/shop", "topics": "orders", "table.name.format": "orders"}}"#;
+        let out = migrate_kafka_connect_to_dataflow(input, Some(WORKER), None).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&out).unwrap();
+        let kafka = &yaml["spec"]["source"]["kafka"];
+        assert_eq!(kafka["brokers"][1], "kafka-2:9092");
+        assert_eq!(kafka["format"], "avro");
+        assert_eq!(kafka["offsetReset"], "earliest");
+        assert!(kafka["consumerGroup"].is_null());
+        assert!(out.contains("Inherited from worker.properties: bootstrap.servers"));
+        assert!(!out.contains("localhost:9092"));
+
+        let provenance =
+            crate::tools::migration_report::migration_provenance(input, Some(WORKER), &out)
+                .unwrap();
+        let provenance: serde_json::Value = serde_json::from_str(&provenance).unwrap();
+        assert_eq!(
+            provenance["flows"][0]["fields"]["spec.source.kafka.brokers[0]"][0],
+            "worker.properties: bootstrap.servers"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// Connection string checks and normalization for relational connectors: postgresql / clickhouse
+// connectionString and trino serverURL, including JDBC URLs copied from Kafka Connect or other tools.
+
+use reqwest::Url;
+use serde_json::Value;
+
+/// Connection field per connector type and the URL schemes the operator accepts for it.
+const CONNECTION_FIELDS: [(&str, &str, &[&str]); 3] = [
+    (
+        "postgresql",
+        "connectionString",
+        &["postgres", "postgresql"],
+    ),
+    (
+        "clickhouse",
+        "connectionString",
+        &["clickhouse", "tcp", "http", "https"],
+    ),
+    ("trino", "serverURL", &["http", "https"]),
+];
+
+/// A connection field rewritten by normalization: manifest path, original and normalized value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normalized {
+    pub path: String,
+    pub from: String,
+    pub to: String,
+}
+
+fn connection_field(connector: &str) -> Option<(&'static str, &'static [&'static str])> {
+    CONNECTION_FIELDS
+        .iter()
+        .find(|(c, _, _)| *c == connector)
+        .map(|(_, f, s)| (*f, *s))
+}
+
+/// Checks that value is a URL with a scheme supported for connector and a host.
+pub fn check_connection(connector: &str, value: &str) -> Result<(), String> {
+    let Some((_, schemes)) = connection_field(connector) else {
+        return Ok(());
+    };
+    if let Some(normalized) = normalize_connection(connector, value) {
+        return Err(format!("'{}' is not supported; use {}", value, normalized));
+    }
+    let url = Url::parse(value).map_err(|e| format!("'{}' is not a valid URL: {}", value, e))?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(format!(
+            "scheme '{}' is not supported; use one of: {}",
+            url.scheme(),
+            schemes.join(", ")
+        ));
+    }
+    if url.host_str().map(str::is_empty).unwrap_or(true) {
+        return Err(format!("'{}' has no host", value));
+    }
+    Ok(())
+}
+
+/// Rewrites a JDBC URL (or a postgresql:// URL) into the form the operator expects; None when value is fine as is.
+/// jdbc:postgresql://h/db -> postgres://h/db (ssl=true -> sslmode=require), jdbc:clickhouse://h:8123/db ->
+/// http://h:8123/db (the JDBC driver speaks HTTP), jdbc:trino://h:8080/catalog -> http://h:8080 (https with SSL=true).
+pub fn normalize_connection(connector: &str, value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let normalized = match connector {
+        "postgresql" => {
+            let rest = trimmed.strip_prefix("jdbc:").unwrap_or(trimmed);
+            let rest = match rest.strip_prefix("postgresql:") {
+                Some(r) if r.starts_with("//") => r.to_string(),
+                // jdbc:postgresql:db is the local default server.
+                Some(db) if !db.is_empty() && trimmed.starts_with("jdbc:") => {
+                    format!("//localhost:5432/{}", db)
+                }
+                _ => return (trimmed != value).then(|| trimmed.to_string()),
+            };
+            format!("postgres:{}", rest).replace("ssl=true", "sslmode=require")
+        }
+        "clickhouse" => {
+            let rest = trimmed
+                .strip_prefix("jdbc:clickhouse:")
+                .or_else(|| trimmed.strip_prefix("jdbc:ch:"));
+            match rest {
+                Some(r) if r.starts_with("http://") || r.starts_with("https://") => r.to_string(),
+                Some(r) if r.starts_with("//") => format!("http:{}", r),
+                _ => trimmed.to_string(),
+            }
+        }
+        "trino" => match trimmed
+            .strip_prefix("jdbc:trino:")
+            .or_else(|| trimmed.strip_prefix("jdbc:presto:"))
+        {
+            Some(r) if r.starts_with("//") => {
+                let (address, params) = r[2..].split_once('?').unwrap_or((&r[2..], ""));
+                let host = address.split('/').next().unwrap_or("");
+                let tls = params
+                    .split('&')
+                    .any(|p| p.eq_ignore_ascii_case("SSL=true"));
+                format!("{}://{}", if tls { "https" } else { "http" }, host)
+            }
+            _ => trimmed.to_string(),
+        },
+        _ => return None,
+    };
+    (normalized != value).then_some(normalized)
+}
+
+/// Normalizes every connection field under spec (source, sinks, branches, DLQ, router route sinks) in place.
+pub fn normalize_spec(spec: &mut Value) -> Vec<Normalized> {
+    let mut changed = Vec::new();
+    normalize_value(spec, "spec", &mut changed);
+    changed
+}
+
+fn normalize_value(v: &mut Value, path: &str, changed: &mut Vec<Normalized>) {
+    match v {
+        Value::Object(map) => {
+            let connector = map.get("type").and_then(Value::as_str).map(str::to_string);
+            if let Some((connector, (field, _))) = connector
+                .as_deref()
+                .and_then(|c| connection_field(c).map(|f| (c, f)))
+            {
+                if let Some(Value::String(s)) =
+                    map.get_mut(connector).and_then(|b| b.get_mut(field))
+                {
+                    if let Some(to) = normalize_connection(connector, s) {
+                        changed.push(Normalized {
+                            path: format!("{}.{}.{}", path, connector, field),
+                            from: s.clone(),
+                            to: to.clone(),
+                        });
+                        *s = to;
+                    }
+                }
+            }
+            for (k, child) in map.iter_mut() {
+                normalize_value(child, &format!("{}.{}", path, k), changed);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter_mut().enumerate() {
+                normalize_value(child, &format!("{}[{}]", path, i), changed);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_connection() {
+        assert_eq!(
+            normalize_connection("postgresql", "jdbc:postgresql://pg:5432/shop?ssl=true")
+                .as_deref(),
+            Some("postgres://pg:5432/shop?sslmode=require")
+        );
+        assert_eq!(
+            normalize_connection("postgresql", "jdbc:postgresql:shop").as_deref(),
+            Some("postgres://localhost:5432/shop")
+        );
+        assert_eq!(
+            normalize_connection("postgresql", "postgres://pg/shop"),
+            None
+        );
+        assert_eq!(
+            normalize_connection("clickhouse", "jdbc:clickhouse://ch:8123/db").as_deref(),
+            Some("http://ch:8123/db")
+        );
+        assert_eq!(
+            normalize_connection("trino", "jdbc:trino://trino:8443/hive/sales?SSL=true").as_deref(),
+            Some("https://trino:8443")
+        );
+    }
+
+    #[test]
+    fn test_check_connection() {
+        assert!(check_connection("postgresql", "postgres://app@pg:5432/shop").is_ok());
+        assert!(check_connection("postgresql", "jdbc:postgresql://pg/shop")
+            .unwrap_err()
+            .contains("use postgres://pg/shop"));
+        assert!(check_connection("postgresql", "mysql://db/shop")
+            .unwrap_err()
+            .contains("scheme 'mysql' is not supported"));
+        assert!(check_connection("postgresql", "host=pg dbname=shop")
+            .unwrap_err()
+            .contains("not a valid URL"));
+        assert!(check_connection("trino", "http://trino:8080").is_ok());
+        assert!(check_connection("clickhouse", "tcp://ch:9000?database=logs").is_ok());
+    }
+
+    #[test]
+    fn test_normalize_spec_walks_branches() {
+        let mut spec = json!({
+            "source": { "type": "postgresql", "postgresql": { "connectionString": "jdbc:postgresql://pg/a", "table": "t" } },
+            "branches": { "b": { "sink": { "type": "trino", "trino": { "serverURL": "jdbc:trino://t:8080/hive" } } } }
+        });
+        let mut changed = normalize_spec(&mut spec);
+        changed.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[1].path, "spec.source.postgresql.connectionString");
+        assert_eq!(changed[1].from, "jdbc:postgresql://pg/a");
+        assert_eq!(
+            spec["branches"]["b"]["sink"]["trino"]["serverURL"],
+            "http://t:8080"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+// apiVersion conversion between DataFlow CRD versions: each step renames and moves fields and records what
+// could not be converted automatically, so old manifests are upgraded instead of only rejected.
+
+use crate::tools::manifest::{split_documents, validate_dataflow_manifest};
+use crate::tools::output::to_canonical_yaml;
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_KIND, SINK_TYPES, SOURCE_TYPES};
+use serde_json::{Map as JsonMap, Value};
+
+const V1ALPHA1: &str = "dataflow.dataflow.io/v1alpha1";
+
+/// Conversion steps: (from apiVersion, to apiVersion, conversion of the spec; notes collect manual work).
+type Step = (
+    &'static str,
+    &'static str,
+    fn(&mut JsonMap<String, Value>, &mut Vec<String>),
+);
+const STEPS: [Step; 1] = [(V1ALPHA1, DATAFLOW_API_VERSION, v1alpha1_to_v1)];
+
+/// Spec fields that moved in v1: (v1alpha1 path, v1 path), dot-separated under spec.
+const V1ALPHA1_SPEC_MOVES: [(&str, &str); 2] = [
+    ("replicas", "parallelism"),
+    ("deadLetterQueue", "errorHandling.dlq"),
+];
+
+/// Connector fields renamed in v1: (connector, v1alpha1 name, v1 name).
+const V1ALPHA1_FIELD_RENAMES: [(&str, &str, &str); 5] = [
+    ("kafka", "groupId", "consumerGroup"),
+    ("kafka", "autoOffsetReset", "offsetReset"),
+    ("postgresql", "connectionURL", "connectionString"),
+    ("clickhouse", "connectionURL", "connectionString"),
+    ("trino", "serverUrl", "serverURL"),
+];
+
+fn known_versions() -> Vec<&'static str> {
+    let mut versions: Vec<&str> = STEPS
+        .iter()
+        .flat_map(|(from, to, _)| [*from, *to])
+        .collect();
+    versions.dedup();
+    versions
+}
+
+/// True when an apiVersion has a conversion path to the current one.
+pub fn can_convert(api_version: &str) -> bool {
+    STEPS.iter().any(|(from, _, _)| *from == api_version)
+}
+
+/// Upgrades every DataFlow document of yaml to target (default the current apiVersion); other documents are
+/// kept as is. Each converted document starts with comments listing what changed and what needs attention.
+pub fn convert_dataflow_apiversion(yaml: &str, target: Option<&str>) -> Result<String, String> {
+    let target = target
+        .filter(|t| !t.is_empty())
+        .unwrap_or(DATAFLOW_API_VERSION);
+    let known = known_versions();
+    if !known.contains(&target) {
+        return Err(format!(
+            "Unknown target apiVersion '{}'; known: {}",
+            target,
+            known.join(", ")
+        ));
+    }
+    let docs = split_documents(yaml);
+    if docs.is_empty() {
+        return Err("No YAML documents to convert".to_string());
+    }
+    let mut out = Vec::new();
+    for (i, (_, doc)) in docs.iter().enumerate() {
+        let mut manifest: Value = serde_yaml::from_str(doc)
+            .map_err(|e| format!("document {}: YAML parse error: {}", i + 1, e))?;
+        if manifest["kind"] != DATAFLOW_KIND {
+            out.push(doc.clone());
+            continue;
+        }
+        let from = manifest["apiVersion"].as_str().unwrap_or("").to_string();
+        if from == target {
+            out.push(format!("# Already {}; not changed\n{}", target, doc));
+            continue;
+        }
+        let mut notes = Vec::new();
+        let mut version = from.clone();
+        while version != target {
+            let Some((_, to, convert)) = STEPS.iter().find(|(f, _, _)| *f == version) else {
+                return Err(format!(
+                    "document {}: no conversion from apiVersion '{}' to {}; known: {}",
+                    i + 1,
+                    version,
+                    target,
+                    known.join(", ")
+                ));
+            };
+            if let Some(spec) = manifest.get_mut("spec").and_then(Value::as_object_mut) {
+                convert(spec, &mut notes);
+            }
+            version = to.to_string();
+        }
+        manifest["apiVersion"] = Value::String(target.to_string());
+        if doc.lines().any(|l| l.trim_start().starts_with('#')) {
+            notes.push(
+                "Needs attention: comments of the original document are not preserved.".to_string(),
+            );
+        }
+        let converted = to_canonical_yaml(&manifest)?;
+        if target == DATAFLOW_API_VERSION {
+            if let Err(errors) = validate_dataflow_manifest(&converted) {
+                notes.extend(
+                    errors
+                        .into_iter()
+                        .map(|e| format!("Needs attention: validation error: {}", e)),
+                );
+            }
+        }
+        let mut text = format!("# Converted from {} to {}\n", from, target);
+        for n in &notes {
+            text.push_str(&format!("# - {}\n", n));
+        }
+        text.push_str(&converted);
+        out.push(text);
+    }
+    Ok(out.join("---\n"))
+}
+
+/// Removes the value at a dot-separated path.
+fn take_path(map: &mut JsonMap<String, Value>, path: &str) -> Option<Value> {
+    match path.split_once('.') {
+        Some((head, rest)) => take_path(map.get_mut(head)?.as_object_mut()?, rest),
+        None => map.remove(path),
+    }
+}
+
+/// Sets the value at a dot-separated path, creating mappings on the way; false when a value is already there.
+fn put_path(map: &mut JsonMap<String, Value>, path: &str, value: Value) -> bool {
+    match path.split_once('.') {
+        Some((head, rest)) => match map
+            .entry(head)
+            .or_insert_with(|| Value::Object(JsonMap::new()))
+            .as_object_mut()
+        {
+            Some(inner) => put_path(inner, rest, value),
+            None => false,
+        },
+        None if map.contains_key(path) => false,
+        None => {
+            map.insert(path.to_string(), value);
+            true
+        }
+    }
+}
+
+fn v1alpha1_to_v1(spec: &mut JsonMap<String, Value>, notes: &mut Vec<String>) {
+    for (old, new) in V1ALPHA1_SPEC_MOVES {
+        if let Some(value) = take_path(spec, old) {
+            if put_path(spec, new, value.clone()) {
+                notes.push(format!("spec.{} moved to spec.{}", old, new));
+            } else {
+                put_path(spec, old, value);
+                notes.push(format!(
+                    "Needs attention: spec.{} was not moved because spec.{} is already set",
+                    old, new
+                ));
+            }
+        }
+    }
+    let mut spec_value = Value::Object(std::mem::take(spec));
+    convert_endpoints(&mut spec_value, "spec", notes);
+    if let Value::Object(converted) = spec_value {
+        *spec = converted;
+    }
+}
+
+/// Applies the v1alpha1 connector changes to every source / sink (spec.sink, branches, DLQ, router routes).
+fn convert_endpoints(v: &mut Value, path: &str, notes: &mut Vec<String>) {
+    match v {
+        Value::Object(map) => {
+            let connector = map.get("type").and_then(Value::as_str).map(str::to_string);
+            if let Some(connector) = connector
+                .filter(|c| SOURCE_TYPES.contains(&c.as_str()) || SINK_TYPES.contains(&c.as_str()))
+            {
+                if let Some(block) = map.get_mut(&connector).and_then(Value::as_object_mut) {
+                    convert_block(&connector, block, &format!("{}.{}", path, connector), notes);
+                }
+            }
+            for (k, child) in map.iter_mut() {
+                convert_endpoints(child, &format!("{}.{}", path, k), notes);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter_mut().enumerate() {
+                convert_endpoints(child, &format!("{}[{}]", path, i), notes);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn convert_block(
+    connector: &str,
+    block: &mut JsonMap<String, Value>,
+    path: &str,
+    notes: &mut Vec<String>,
+) {
+    for (_, old, new) in V1ALPHA1_FIELD_RENAMES
+        .iter()
+        .filter(|(c, _, _)| *c == connector)
+    {
+        if let Some(value) = block.remove(*old) {
+            if block.contains_key(*new) {
+                notes.push(format!(
+                    "Needs attention: {}.{} was dropped because {} is already set",
+                    path, old, new
+                ));
+            } else {
+                block.insert(new.to_string(), value);
+                notes.push(format!("{}.{} renamed to {}", path, old, new));
+            }
+        }
+    }
+    // v1alpha1 kafka read a list of topics; v1 reads one topic or a topicPattern.
+    if connector == "kafka" {
+        if let Some(Value::Array(topics)) = block.remove("topics") {
+            let names: Vec<&str> = topics.iter().filter_map(Value::as_str).collect();
+            match names.as_slice() {
+                [one] => {
+                    block.insert("topic".to_string(), Value::String(one.to_string()));
+                    notes.push(format!("{}.topics with one topic became topic", path));
+                }
+                _ => {
+                    let pattern = names
+                        .iter()
+                        .map(|n| regex::escape(n))
+                        .collect::<Vec<_>>()
+                        .join("|");
+                    block.insert("topicPattern".to_string(), Value::String(pattern.clone()));
+                    notes.push(format!(
+                        "Needs attention: {}.topics ({}) became topicPattern '{}'; topics created later that match it are read too",
+                        path,
+                        names.join(", "),
+                        pattern
+                    ));
+                }
+            }
+        }
+    }
+    if connector == "postgresql" {
+        if let Some(raw) = block.remove("rawMode") {
+            if raw == true && !block.contains_key("payloadMode") {
+                block.insert(
+                    "payloadMode".to_string(),
+                    Value::String("jsonb".to_string()),
+                );
+            }
+            notes.push(format!("{}.rawMode replaced by payloadMode", path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_v1alpha1_to_v1() {
+        let yaml = r#"# orders pipeline
+apiVersion: dataflow.dataflow.io/v1alpha1
+kind: DataFlow
+metadata:
+  name: orders
+spec:
+  replicas: 2
+  source:
+    type: kafka
+    kafka: { brokers: ["k:9092"], topics: [orders], groupId: orders-flow }
+  sink:
+    type: postgresql
+    postgresql: { connectionURL: "postgres://pg/shop", table: orders, rawMode: true }
+  deadLetterQueue:
+    type: kafka
+    kafka: { brokers: ["k:9092"], topic: orders-dlq }
+---
+apiVersion: v1
+kind: Secret
+metadata: { name: pg }
+"#;
+        let out = convert_dataflow_apiversion(yaml, None).unwrap();
+        let docs: Vec<&str> = out.split("---\n").collect();
+        assert_eq!(docs.len(), 2);
+        assert!(docs[0].starts_with(
+            "# Converted from dataflow.dataflow.io/v1alpha1 to dataflow.dataflow.io/v1\n"
+        ));
+        assert!(docs[0].contains("# - spec.replicas moved to spec.parallelism\n"));
+        assert!(docs[0].contains("# - spec.source.kafka.groupId renamed to consumerGroup\n"));
+        assert!(docs[0].contains(
+            "# - Needs attention: comments of the original document are not preserved.\n"
+        ));
+        assert!(!docs[0].contains("validation error"));
+        let manifest: Value = serde_yaml::from_str(docs[0]).unwrap();
+        assert_eq!(manifest["spec"]["parallelism"], 2);
+        assert_eq!(manifest["spec"]["source"]["kafka"]["topic"], "orders");
+        assert_eq!(
+            manifest["spec"]["sink"]["postgresql"]["payloadMode"],
+            "jsonb"
+        );
+        assert_eq!(
+            manifest["spec"]["errorHandling"]["dlq"]["kafka"]["topic"],
+            "orders-dlq"
+        );
+        assert!(docs[1].contains("kind: Secret"));
+    }
+
+    #[test]
+    fn test_convert_reports_manual_work_and_unknown_versions() {
+        let yaml = "apiVersion: dataflow.dataflow.io/v1alpha1\nkind: DataFlow\nmetadata: {name: a}\nspec:\n  source: {type: kafka, kafka: {brokers: [\"k:9092\"], topics: [a.orders, b.orders]}}\n  sink: {type: kafka, kafka: {brokers: [\"k:9092\"], topic: out}}\n";
+        let out = convert_dataflow_apiversion(yaml, None).unwrap();
+        assert!(out.contains(r"became topicPattern 'a\.orders|b\.orders'"));
+        assert!(
+            convert_dataflow_apiversion(yaml, Some("dataflow.dataflow.io/v2"))
+                .unwrap_err()
+                .starts_with("Unknown target apiVersion")
+        );
+        let unknown = yaml.replace("v1alpha1", "v0");
+        assert!(convert_dataflow_apiversion(&unknown, None)
+            .unwrap_err()
+            .contains("no conversion from apiVersion 'dataflow.dataflow.io/v0'"));
+    }
+}
@@ -0,0 +1,310 @@
+// Structural validation against the DataFlow CRD installed in the cluster: the openAPIV3Schema of the served
+// version a manifest's apiVersion names, so validation follows the operator version actually deployed.
+
+use crate::tools::kube::kubectl_json;
+use crate::tools::manifest::{split_documents, Diagnostic, DocumentReport, Severity};
+use crate::types::DATAFLOW_CRD_NAME;
+use serde_json::Value;
+
+/// Reads the DataFlow CRD from the cluster (kubectl, current or given context).
+pub async fn fetch_crd(context: Option<&str>) -> Result<Value, String> {
+    kubectl_json(
+        "Live CRD schema validation",
+        &["get", "crd", DATAFLOW_CRD_NAME],
+        context,
+    )
+    .await
+}
+
+/// openAPIV3Schema of the served CRD version api_version (group/version) refers to.
+pub fn crd_version_schema<'a>(crd: &'a Value, api_version: &str) -> Result<&'a Value, String> {
+    let (group, version) = api_version
+        .split_once('/')
+        .ok_or_else(|| format!("apiVersion '{}' must be group/version", api_version))?;
+    let crd_group = crd["spec"]["group"].as_str().unwrap_or("");
+    if group != crd_group {
+        return Err(format!(
+            "apiVersion group '{}' does not match the installed CRD group '{}'",
+            group, crd_group
+        ));
+    }
+    let versions = crd["spec"]["versions"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let served: Vec<&str> = versions
+        .iter()
+        .filter(|v| v["served"].as_bool() != Some(false))
+        .filter_map(|v| v["name"].as_str())
+        .collect();
+    versions
+        .iter()
+        .find(|v| v["name"] == version && v["served"].as_bool() != Some(false))
+        .map(|v| &v["schema"]["openAPIV3Schema"])
+        .filter(|s| s.is_object())
+        .ok_or_else(|| {
+            format!(
+                "apiVersion {} is not served by the installed CRD (served: {})",
+                api_version,
+                served.join(", ")
+            )
+        })
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn type_matches(t: &str, v: &Value) -> bool {
+    match t {
+        "object" => v.is_object(),
+        "array" => v.is_array(),
+        "string" => v.is_string(),
+        "integer" => v.is_i64() || v.is_u64(),
+        "number" => v.is_number(),
+        "boolean" => v.is_boolean(),
+        _ => true,
+    }
+}
+
+/// Checks v against an OpenAPI v3 (structural) schema: types, required, enum, ranges, lengths, patterns and
+/// fields the schema does not declare (rejected by strict field validation on apply).
+pub fn check_schema(schema: &Value, v: &Value, path: &str, errors: &mut Vec<String>) {
+    let at = if path.is_empty() { "manifest" } else { path };
+    if schema["x-kubernetes-int-or-string"] == true {
+        if !(v.is_string() || v.is_i64() || v.is_u64()) {
+            errors.push(format!("{} must be an integer or a string", at));
+        }
+        return;
+    }
+    if let Some(t) = schema["type"].as_str() {
+        let nullable = v.is_null() && schema["nullable"] == true;
+        if !(type_matches(t, v) || nullable) {
+            errors.push(format!("{} must be of type {}", at, t));
+            return;
+        }
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        if !values.contains(v) {
+            let names: Vec<String> = values
+                .iter()
+                .map(|e| {
+                    e.as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| e.to_string())
+                })
+                .collect();
+            errors.push(format!("{} must be one of: {}", at, names.join(", ")));
+        }
+    }
+    match v {
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if schema["minLength"].as_u64().is_some_and(|min| len < min) {
+                errors.push(format!(
+                    "{} must be at least {} characters",
+                    at, schema["minLength"]
+                ));
+            }
+            if schema["maxLength"].as_u64().is_some_and(|max| len > max) {
+                errors.push(format!(
+                    "{} must be at most {} characters",
+                    at, schema["maxLength"]
+                ));
+            }
+            if let Some(pattern) = schema["pattern"].as_str() {
+                if regex::Regex::new(pattern).is_ok_and(|re| !re.is_match(s)) {
+                    errors.push(format!("{} '{}' does not match pattern {}", at, s, pattern));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let (min, max) = (schema["minimum"].as_f64(), schema["maximum"].as_f64());
+            let x = n.as_f64().unwrap_or_default();
+            if min.is_some_and(|m| x < m) || max.is_some_and(|m| x > m) {
+                let bound = |b: &Value| {
+                    if b.is_null() {
+                        "unbounded".to_string()
+                    } else {
+                        b.to_string()
+                    }
+                };
+                errors.push(format!(
+                    "{} must be between {} and {}",
+                    at,
+                    bound(&schema["minimum"]),
+                    bound(&schema["maximum"])
+                ));
+            }
+        }
+        Value::Array(items) => {
+            if schema["minItems"]
+                .as_u64()
+                .is_some_and(|min| (items.len() as u64) < min)
+            {
+                errors.push(format!(
+                    "{} must have at least {} items",
+                    at, schema["minItems"]
+                ));
+            }
+            if schema["items"].is_object() {
+                for (i, item) in items.iter().enumerate() {
+                    check_schema(&schema["items"], item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Value::Object(map) => {
+            for field in schema["required"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if !map.contains_key(field) {
+                    errors.push(format!("{} is required", child(path, field)));
+                }
+            }
+            let properties = schema["properties"].as_object();
+            let preserve = schema["x-kubernetes-preserve-unknown-fields"] == true;
+            for (key, value) in map {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(s) => check_schema(s, value, &child(path, key), errors),
+                    None if schema["additionalProperties"].is_object() => check_schema(
+                        &schema["additionalProperties"],
+                        value,
+                        &child(path, key),
+                        errors,
+                    ),
+                    // Only mappings that declare their fields reject others (metadata has no properties).
+                    None if properties.is_some()
+                        && !preserve
+                        && schema["additionalProperties"] != true =>
+                    {
+                        errors.push(format!(
+                            "{} is not a field of the installed CRD schema",
+                            child(path, key)
+                        ))
+                    }
+                    None => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Adds the findings of the installed CRD's schema to each document report (matched by the document index of
+/// validate_dataflow_documents); findings the built-in validator already reported are skipped.
+pub fn add_crd_findings(config_yaml: &str, crd: &Value, reports: &mut [DocumentReport]) {
+    let docs = split_documents(config_yaml);
+    for report in reports.iter_mut() {
+        let Some((offset, doc)) = docs.get(report.index - 1) else {
+            continue;
+        };
+        let Ok(manifest) = serde_yaml::from_str::<Value>(doc) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        match crd_version_schema(crd, manifest["apiVersion"].as_str().unwrap_or("")) {
+            Ok(schema) => check_schema(schema, &manifest, "", &mut errors),
+            Err(e) => errors.push(format!("apiVersion: {}", e)),
+        }
+        for e in errors {
+            if report.diagnostics.iter().any(|d| d.message == e) {
+                continue;
+            }
+            let mut d = Diagnostic::located(Severity::Error, format!("{} (installed CRD)", e), doc);
+            d.line += offset;
+            report.diagnostics.push(d);
+        }
+        report.diagnostics.sort_by_key(|d| d.severity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::manifest::validate_dataflow_documents;
+    use serde_json::json;
+
+    fn crd() -> Value {
+        json!({
+            "spec": {
+                "group": "dataflow.dataflow.io",
+                "versions": [{ "name": "v1", "served": true, "storage": true, "schema": { "openAPIV3Schema": {
+                    "type": "object",
+                    "properties": {
+                        "apiVersion": { "type": "string" },
+                        "kind": { "type": "string" },
+                        "metadata": { "type": "object" },
+                        "spec": {
+                            "type": "object",
+                            "required": ["source", "sink"],
+                            "properties": {
+                                "source": { "type": "object", "x-kubernetes-preserve-unknown-fields": true },
+                                "sink": { "type": "object", "x-kubernetes-preserve-unknown-fields": true },
+                                "parallelism": { "type": "integer", "minimum": 1, "maximum": 16 }
+                            }
+                        }
+                    }
+                } } }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_check_schema_reports_operator_constraints() {
+        let manifest = json!({ "apiVersion": "dataflow.dataflow.io/v1", "kind": "DataFlow", "metadata": { "name": "a" },
+            "spec": { "source": { "type": "kafka" }, "sink": { "type": "kafka" }, "parallelism": 32, "replicas": 2 } });
+        let mut errors = Vec::new();
+        check_schema(
+            crd_version_schema(&crd(), "dataflow.dataflow.io/v1").unwrap(),
+            &manifest,
+            "",
+            &mut errors,
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "spec.parallelism must be between 1 and 16".to_string(),
+                "spec.replicas is not a field of the installed CRD schema".to_string(),
+            ]
+        );
+        let err = crd_version_schema(&crd(), "dataflow.dataflow.io/v2").unwrap_err();
+        assert_eq!(
+            err,
+            "apiVersion dataflow.dataflow.io/v2 is not served by the installed CRD (served: v1)"
+        );
+    }
+
+    #[test]
+    fn test_add_crd_findings_locates_per_document() {
+        let doc = "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: a\nspec:\n  source: {type: kafka, kafka: {brokers: [\"k:9092\"], topic: in}}\n  sink: {type: kafka, kafka: {brokers: [\"k:9092\"], topic: out}}\n  parallelism: 20\n";
+        let secret = "apiVersion: v1\nkind: Secret\nmetadata:\n  name: a-credentials\nstringData:\n  password: x\n";
+        let config = format!(
+            "{}---\n{}---\n{}",
+            doc.replace("parallelism: 20", "parallelism: 2"),
+            secret,
+            doc
+        );
+        let mut reports = validate_dataflow_documents(&config, false);
+        add_crd_findings(&config, &crd(), &mut reports);
+        reports.reverse();
+        let first: Vec<&Diagnostic> = reports[0]
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .collect();
+        assert_eq!(first.len(), 1);
+        assert_eq!(
+            first[0].message,
+            "spec.parallelism must be between 1 and 16 (installed CRD)"
+        );
+        assert_eq!((first[0].line, first[0].column), (24, 3));
+        assert!(!reports[1].has_errors());
+    }
+}
@@ -0,0 +1,412 @@
+// Plaintext credential checks: inline passwords, tokens and keys in a DataFlow spec, and passwords embedded in
+// connection URLs, with the secretKeyRef that should replace them.
+
+use crate::tools::manifest::split_documents;
+use crate::tools::output::to_canonical_yaml;
+use reqwest::Url;
+use serde_json::{json, Value};
+
+/// Field names (compared case-insensitively) whose string value is a secret.
+const SECRET_FIELDS: [&str; 12] = [
+    "password",
+    "token",
+    "apiKey",
+    "apiSecret",
+    "accessKey",
+    "accessKeyId",
+    "secretKey",
+    "secretAccessKey",
+    "sessionToken",
+    "clientSecret",
+    "authorization",
+    "bearerToken",
+];
+
+/// Fields holding a URL that may carry user:password@ or a password query parameter.
+const URL_FIELDS: [&str; 4] = ["connectionString", "serverURL", "url", "endpoint"];
+
+pub(crate) fn is_secret_field(key: &str) -> bool {
+    SECRET_FIELDS.iter().any(|f| f.eq_ignore_ascii_case(key))
+}
+
+/// ${VAR} placeholders are filled in at deploy time and are not credentials.
+fn is_placeholder(value: &str) -> bool {
+    let v = value.trim();
+    v.is_empty() || (v.starts_with("${") && v.ends_with('}'))
+}
+
+/// True when a URL carries a password in its user info or query (password=, pwd=, sslpassword=).
+fn url_has_password(value: &str) -> bool {
+    let Ok(url) = Url::parse(value.trim()) else {
+        return false;
+    };
+    url.password().is_some_and(|p| !is_placeholder(p))
+        || url.query_pairs().any(|(k, v)| {
+            ["password", "pwd", "sslpassword"].contains(&k.to_lowercase().as_str())
+                && !is_placeholder(&v)
+        })
+}
+
+/// value with the password in its user info or query replaced by ***; None when it is not a URL with a password.
+pub(crate) fn mask_url_password(value: &str) -> Option<String> {
+    if !url_has_password(value) {
+        return None;
+    }
+    let mut url = Url::parse(value.trim()).ok()?;
+    if url.password().is_some() {
+        url.set_password(Some("***")).ok()?;
+    }
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            match ["password", "pwd", "sslpassword"].contains(&k.to_lowercase().as_str()) {
+                true => (k.to_string(), "***".to_string()),
+                false => (k.to_string(), v.to_string()),
+            }
+        })
+        .collect();
+    if !pairs.is_empty() {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    Some(url.to_string())
+}
+
+/// A plaintext credential: manifest path and the secretKeyRef suggestion that replaces it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaintextCredential {
+    pub path: String,
+    pub suggestion: String,
+}
+
+impl PlaintextCredential {
+    /// Warning text starting with the manifest path (located in validation output).
+    pub fn message(&self) -> String {
+        format!(
+            "{} is a plaintext credential and would be committed with the manifest; use {}",
+            self.path, self.suggestion
+        )
+    }
+}
+
+/// Plaintext credentials under spec; secret names the Secret the suggestions reference.
+pub fn plaintext_credentials(spec: &Value, secret: &str) -> Vec<PlaintextCredential> {
+    let mut found = Vec::new();
+    walk(spec, "spec", secret, &mut found, &mut Vec::new());
+    found
+}
+
+/// secretKeyRef values must name a Secret and a key: {secretKeyRef: {name, key}}.
+pub fn check_secret_refs(spec: &Value, errors: &mut Vec<String>) {
+    walk(spec, "spec", "", &mut Vec::new(), errors);
+}
+
+fn walk(
+    v: &Value,
+    path: &str,
+    secret: &str,
+    found: &mut Vec<PlaintextCredential>,
+    errors: &mut Vec<String>,
+) {
+    match v {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let p = format!("{}.{}", path, key);
+                let credential = is_secret_field(key) || URL_FIELDS.contains(&key.as_str());
+                match value {
+                    Value::String(s) if is_secret_field(key) && !is_placeholder(s) => found.push(PlaintextCredential {
+                        path: p,
+                        suggestion: format!("{}: {{secretKeyRef: {{name: {}, key: {}}}}}", key, secret, key),
+                    }),
+                    Value::String(s) if URL_FIELDS.contains(&key.as_str()) && url_has_password(s) => {
+                        found.push(PlaintextCredential {
+                            path: p,
+                            suggestion: format!(
+                                "{}: {{secretKeyRef: {{name: {}, key: {}}}}} with the full URL stored in the Secret",
+                                key, secret, key
+                            ),
+                        })
+                    }
+                    Value::Object(inner) if credential && inner.contains_key("secretKeyRef") => {
+                        let reference = &inner["secretKeyRef"];
+                        if inner.len() > 1 {
+                            errors.push(format!("{} must be a string or {{secretKeyRef: {{name, key}}}}", p));
+                        }
+                        for field in ["name", "key"] {
+                            if reference.get(field).and_then(Value::as_str).map(str::is_empty).unwrap_or(true) {
+                                errors.push(format!("{}.secretKeyRef.{} is required", p, field));
+                            }
+                        }
+                    }
+                    _ => walk(value, &p, secret, found, errors),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                walk(item, &format!("{}[{}]", path, i), secret, found, errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A credential moved out of a spec into a Secret key: where it was, the key and the value the Secret must hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovedCredential {
+    pub path: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// Replaces the plaintext credentials under spec with {secretKeyRef: {name: secret, key}}. Keys are the field
+/// names; a field name already holding another value is prefixed with its spec section (sink-connectionString).
+pub fn move_credentials_to_secret(spec: &mut Value, secret: &str) -> Vec<MovedCredential> {
+    let mut moved = Vec::new();
+    move_walk(spec, "spec", secret, &mut moved);
+    moved
+}
+
+fn move_walk(v: &mut Value, path: &str, secret: &str, moved: &mut Vec<MovedCredential>) {
+    match v {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let p = format!("{}.{}", path, key);
+                let plaintext = match value {
+                    Value::String(s) => {
+                        (is_secret_field(key) && !is_placeholder(s))
+                            || (URL_FIELDS.contains(&key.as_str()) && url_has_password(s))
+                    }
+                    _ => false,
+                };
+                if !plaintext {
+                    move_walk(value, &p, secret, moved);
+                    continue;
+                }
+                let text = value.as_str().unwrap_or("").to_string();
+                let secret_key = secret_key(key, &p, &text, moved);
+                *value = json!({ "secretKeyRef": { "name": secret, "key": secret_key } });
+                moved.push(MovedCredential {
+                    path: p,
+                    key: secret_key,
+                    value: text,
+                });
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                move_walk(item, &format!("{}[{}]", path, i), secret, moved);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Secret key for a credential: the field name, reused for the same value and made unique for another one.
+fn secret_key(field: &str, path: &str, value: &str, moved: &[MovedCredential]) -> String {
+    let section: String = path
+        .trim_start_matches("spec.")
+        .split('.')
+        .next()
+        .unwrap_or("")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let section = section.trim_matches('-');
+    let field: String = field
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    (0..)
+        .map(|n| match n {
+            0 => field.clone(),
+            1 => format!("{}-{}", section, field),
+            n => format!("{}-{}-{}", section, field, n),
+        })
+        .find(|key| moved.iter().all(|m| m.key != *key || m.value == value))
+        .unwrap_or_default()
+}
+
+/// Replaces the plaintext credentials of every DataFlow document in a generated or migrated YAML with
+/// secretKeyRef references to <name>-credentials, and lists the Secrets to create in each document's header
+/// (replacing the plaintext credential warnings there). Values are not printed unless secret_manifest is set: then
+/// each such document is followed by a Secret document holding them in stringData.
+pub fn use_secrets(yaml: &str, secret_manifest: bool) -> Result<String, String> {
+    let mut docs = Vec::new();
+    for (_, doc) in split_documents(yaml) {
+        let body_start = doc
+            .lines()
+            .take_while(|l| l.trim().is_empty() || l.trim_start().starts_with('#'))
+            .count();
+        let header: Vec<&str> = doc.lines().take(body_start).collect();
+        let mut manifest: Value =
+            serde_yaml::from_str(&doc).map_err(|e| format!("YAML parse error: {}", e))?;
+        if manifest["kind"] != crate::types::DATAFLOW_KIND {
+            docs.push(doc);
+            continue;
+        }
+        let name = manifest["metadata"]["name"].as_str().map(str::to_string);
+        let secret = secret_name(name.as_deref());
+        let warnings: Vec<String> = plaintext_credentials(&manifest["spec"], &secret)
+            .iter()
+            .map(PlaintextCredential::message)
+            .collect();
+        let namespace = manifest["metadata"]["namespace"]
+            .as_str()
+            .map(str::to_string);
+        let moved = move_credentials_to_secret(&mut manifest["spec"], &secret);
+        if moved.is_empty() {
+            docs.push(doc);
+            continue;
+        }
+        let mut out: String = header
+            .iter()
+            .filter(|l| !warnings.iter().any(|w| l.contains(w.as_str())))
+            .map(|l| format!("{}\n", l))
+            .collect();
+        let mut unique: Vec<&MovedCredential> = Vec::new();
+        for m in &moved {
+            if !unique.iter().any(|u| u.key == m.key) {
+                unique.push(m);
+            }
+        }
+        if secret_manifest {
+            out.push_str(&format!(
+                "# Credentials were moved to Secret {} in the next document; keep it out of version control or encrypt it (e.g. Sealed Secrets, SOPS)\n",
+                secret
+            ));
+        } else {
+            let literals: Vec<String> = unique
+                .iter()
+                .map(|m| format!("--from-literal={}=<{}>", m.key, m.path))
+                .collect();
+            let ns = namespace
+                .as_deref()
+                .map(|ns| format!(" -n {}", ns))
+                .unwrap_or_default();
+            out.push_str(&format!("# Credentials were replaced with secretKeyRef references; create Secret {} before applying:\n", secret));
+            out.push_str(&format!(
+                "#   kubectl create secret generic {}{} {}\n",
+                secret,
+                ns,
+                literals.join(" ")
+            ));
+        }
+        out.push_str(&to_canonical_yaml(&manifest)?);
+        docs.push(out);
+        if secret_manifest {
+            let mut metadata = json!({ "name": secret });
+            if let Some(ns) = namespace {
+                metadata["namespace"] = json!(ns);
+            }
+            let data: serde_json::Map<String, Value> = unique
+                .iter()
+                .map(|m| (m.key.clone(), json!(m.value)))
+                .collect();
+            let companion = json!({ "apiVersion": "v1", "kind": "Secret", "metadata": metadata, "type": "Opaque", "stringData": data });
+            let flow = name.as_deref().unwrap_or("dataflow");
+            let header = format!("# Credentials of DataFlow {}\n", flow);
+            docs.push(header + &serde_yaml::to_string(&companion).map_err(|e| e.to_string())?);
+        }
+    }
+    Ok(docs.join("---\n"))
+}
+
+/// Secret name suggested for a flow's credentials.
+pub fn secret_name(flow: Option<&str>) -> String {
+    format!(
+        "{}-credentials",
+        flow.filter(|n| !n.is_empty()).unwrap_or("dataflow")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plaintext_credentials() {
+        let spec = json!({
+            "source": { "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "in",
+                "sasl": { "mechanism": "PLAIN", "username": "app", "password": "hunter2" } } },
+            "sink": { "type": "postgresql", "postgresql": { "connectionString": "postgres://app:s3cret@pg/shop", "table": "t" } },
+            "transformations": [
+                { "type": "enrich", "enrich": { "backend": "http", "http": { "url": "https://api/lookup", "headers": { "Authorization": "Bearer abc" } } } },
+                { "type": "mask", "mask": { "fields": ["$.password"] } }
+            ],
+            "errorHandling": { "dlq": { "type": "elasticsearch", "elasticsearch": { "password": "${ES_PASSWORD}" } } }
+        });
+        let found = plaintext_credentials(&spec, &secret_name(Some("orders")));
+        let paths: Vec<&str> = found.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "spec.sink.postgresql.connectionString",
+                "spec.source.kafka.sasl.password",
+                "spec.transformations[0].enrich.http.headers.Authorization",
+            ]
+        );
+        assert_eq!(
+            found[1].message(),
+            "spec.source.kafka.sasl.password is a plaintext credential and would be committed with the manifest; use password: {secretKeyRef: {name: orders-credentials, key: password}}"
+        );
+        assert!(plaintext_credentials(&json!({ "sink": { "postgresql": { "connectionString": "postgres://app@pg/shop?sslmode=require" } } }), "s").is_empty());
+    }
+
+    #[test]
+    fn test_use_secrets() {
+        let yaml = "# Generated DataFlow manifest\n# Warning: spec.sink.postgresql.connectionString is a plaintext credential and would be committed with the manifest; use connectionString: {secretKeyRef: {name: orders-credentials, key: connectionString}} with the full URL stored in the Secret\napiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\nspec:\n  source:\n    type: postgresql\n    postgresql:\n      connectionString: postgres://app:a@src/shop\n      table: orders\n  sink:\n    type: postgresql\n    postgresql:\n      connectionString: postgres://app:b@dst/shop\n      table: orders\n      password: ${PG_PASSWORD}\n";
+        let out = use_secrets(yaml, false).unwrap();
+        assert!(!out.contains("# Warning:") && !out.contains("app:a@"));
+        assert!(out.contains("#   kubectl create secret generic orders-credentials --from-literal=connectionString=<spec.sink.postgresql.connectionString> --from-literal=source-connectionString=<spec.source.postgresql.connectionString>\n"));
+        let doc: Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(
+            doc["spec"]["source"]["postgresql"]["connectionString"],
+            json!({ "secretKeyRef": { "name": "orders-credentials", "key": "source-connectionString" } })
+        );
+        assert_eq!(
+            doc["spec"]["sink"]["postgresql"]["password"],
+            "${PG_PASSWORD}"
+        );
+        let clean = "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: x\n";
+        assert_eq!(use_secrets(clean, true).unwrap(), clean);
+        let out = use_secrets(
+            &yaml.replace("  name: orders\n", "  name: orders\n  namespace: shop\n"),
+            true,
+        )
+        .unwrap();
+        assert!(!out.contains("kubectl"));
+        let docs = split_documents(&out);
+        assert_eq!(docs.len(), 2);
+        let secret: Value = serde_yaml::from_str(&docs[1].1).unwrap();
+        assert_eq!(
+            secret["metadata"],
+            json!({ "name": "orders-credentials", "namespace": "shop" })
+        );
+        assert_eq!(
+            secret["stringData"],
+            json!({ "connectionString": "postgres://app:b@dst/shop", "source-connectionString": "postgres://app:a@src/shop" })
+        );
+    }
+
+    #[test]
+    fn test_check_secret_refs() {
+        let spec = json!({
+            "source": { "kafka": { "sasl": { "password": { "secretKeyRef": { "name": "kafka", "key": "password" } } } } },
+            "sink": { "postgresql": { "connectionString": { "secretKeyRef": { "name": "pg" } } } }
+        });
+        let mut errors = Vec::new();
+        check_secret_refs(&spec, &mut errors);
+        assert_eq!(
+            errors,
+            vec!["spec.sink.postgresql.connectionString.secretKeyRef.key is required".to_string()]
+        );
+        assert!(plaintext_credentials(&spec, "s").is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+// Content digest and optional HMAC signature annotations, so applied resources can be traced to the generated artifact.
+
+use crate::tools::output::to_canonical_yaml;
+use hmac::{Hmac, Mac};
+use serde_json::{Map as JsonMap, Value};
+use sha2::{Digest, Sha256};
+
+pub const DIGEST_ANNOTATION: &str = "dataflow.io/content-digest";
+pub const SIGNATURE_ANNOTATION: &str = "dataflow.io/signature";
+
+/// Metadata set by the API server; excluded so a resource read back from the cluster still verifies.
+pub(crate) const SERVER_METADATA: [&str; 6] = [
+    "resourceVersion",
+    "uid",
+    "creationTimestamp",
+    "generation",
+    "managedFields",
+    "selfLink",
+];
+
+/// Annotations written by kubectl or the API server, not by the flow's author; excluded for the same reason.
+pub(crate) const SERVER_ANNOTATIONS: [&str; 2] = [
+    "kubectl.kubernetes.io/last-applied-configuration",
+    "deployment.kubernetes.io/revision",
+];
+
+fn parse(yaml: &str) -> Result<Value, String> {
+    let v: Value = serde_yaml::from_str(yaml).map_err(|e| format!("YAML parse error: {}", e))?;
+    if !v.is_object() {
+        return Err("manifest must be a YAML mapping".to_string());
+    }
+    Ok(v)
+}
+
+/// Canonical JSON of the manifest without status, server metadata, server annotations and the digest/signature
+/// annotations. serde_json maps are sorted, so key order and YAML formatting do not change the digest.
+fn canonical(manifest: &Value) -> String {
+    let mut v = manifest.clone();
+    if let Some(obj) = v.as_object_mut() {
+        obj.remove("status");
+        if let Some(Value::Object(metadata)) = obj.get_mut("metadata") {
+            for key in SERVER_METADATA {
+                metadata.remove(key);
+            }
+            let mut drop_annotations = false;
+            if let Some(Value::Object(annotations)) = metadata.get_mut("annotations") {
+                annotations.remove(DIGEST_ANNOTATION);
+                annotations.remove(SIGNATURE_ANNOTATION);
+                for a in SERVER_ANNOTATIONS {
+                    annotations.remove(a);
+                }
+                drop_annotations = annotations.is_empty();
+            }
+            if drop_annotations {
+                metadata.remove("annotations");
+            }
+        }
+    }
+    v.to_string()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn digest(manifest: &Value) -> String {
+    format!(
+        "sha256:{}",
+        hex(&Sha256::digest(canonical(manifest).as_bytes()))
+    )
+}
+
+/// Lowercase or uppercase hex to bytes; None for odd lengths and non-hex characters.
+fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn mac(digest: &str, key: &str) -> Result<Hmac<Sha256>, String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).map_err(|e| e.to_string())?;
+    mac.update(digest.as_bytes());
+    Ok(mac)
+}
+
+fn signature(digest: &str, key: &str) -> Result<String, String> {
+    Ok(format!(
+        "hmac-sha256:{}",
+        hex(&mac(digest, key)?.finalize().into_bytes())
+    ))
+}
+
+/// Checks an hmac-sha256:<hex> signature of digest in constant time (Mac::verify_slice).
+fn signature_matches(digest: &str, key: &str, signature: &str) -> Result<bool, String> {
+    let Some(bytes) = signature.strip_prefix("hmac-sha256:").and_then(unhex) else {
+        return Ok(false);
+    };
+    Ok(mac(digest, key)?.verify_slice(&bytes).is_ok())
+}
+
+/// Embeds the content digest (and an HMAC-SHA256 signature of it when key is given) as annotations.
+/// Returns the re-serialized manifest; YAML comments are not preserved.
+pub fn sign_dataflow_manifest(config_yaml: &str, key: Option<&str>) -> Result<String, String> {
+    let mut manifest = parse(config_yaml)?;
+    let digest = digest(&manifest);
+    let signature = match key.filter(|k| !k.is_empty()) {
+        Some(k) => Some(signature(&digest, k)?),
+        None => None,
+    };
+    let obj = manifest
+        .as_object_mut()
+        .ok_or("manifest must be a YAML mapping")?;
+    let metadata = obj
+        .entry("metadata".to_string())
+        .or_insert_with(|| Value::Object(JsonMap::new()));
+    let metadata = metadata
+        .as_object_mut()
+        .ok_or("metadata must be a mapping")?;
+    let annotations = metadata
+        .entry("annotations".to_string())
+        .or_insert_with(|| Value::Object(JsonMap::new()));
+    let annotations = annotations
+        .as_object_mut()
+        .ok_or("metadata.annotations must be a mapping")?;
+    annotations.insert(DIGEST_ANNOTATION.to_string(), Value::String(digest));
+    annotations.remove(SIGNATURE_ANNOTATION);
+    if let Some(s) = signature {
+        annotations.insert(SIGNATURE_ANNOTATION.to_string(), Value::String(s));
+    }
+    to_canonical_yaml(&manifest)
+}
+
+/// Recomputes the digest and compares it (and the signature, when key is given) with the annotations.
+/// Ok holds a short confirmation; Err lists every mismatch.
+pub fn verify_dataflow_manifest(
+    config_yaml: &str,
+    key: Option<&str>,
+) -> Result<String, Vec<String>> {
+    let manifest = parse(config_yaml).map_err(|e| vec![e])?;
+    let annotation = |name: &str| {
+        manifest["metadata"]["annotations"][name]
+            .as_str()
+            .map(str::to_string)
+    };
+    let expected = digest(&manifest);
+    let mut problems = Vec::new();
+    match annotation(DIGEST_ANNOTATION) {
+        Some(d) if d == expected => {}
+        Some(d) => problems.push(format!(
+            "{} is {} but the content hashes to {}; the manifest was modified after signing",
+            DIGEST_ANNOTATION, d, expected
+        )),
+        None => problems.push(format!("{} annotation is missing", DIGEST_ANNOTATION)),
+    }
+    let signed = match (
+        key.filter(|k| !k.is_empty()),
+        annotation(SIGNATURE_ANNOTATION),
+    ) {
+        (Some(k), Some(s)) => {
+            if !signature_matches(&expected, k, &s).map_err(|e| vec![e])? {
+                problems.push(format!(
+                    "{} does not match the given key",
+                    SIGNATURE_ANNOTATION
+                ));
+            }
+            true
+        }
+        (Some(_), None) => {
+            problems.push(format!("{} annotation is missing", SIGNATURE_ANNOTATION));
+            false
+        }
+        (None, _) => false,
+    };
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+    Ok(if signed {
+        format!("Digest {} and signature verified", expected)
+    } else {
+        format!("Digest {} verified", expected)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: orders
+spec:
+  source:
+    type: kafka
+    kafka: {brokers: ["k:9092"], topic: orders}
+  sink:
+    type: kafka
+    kafka: {brokers: ["k:9092"], topic: out}
+"#;
+
+    #[test]
+    fn test_sign_and_verify_dataflow_manifest() {
+        let signed = sign_dataflow_manifest(MANIFEST, Some("s3cret")).unwrap();
+        assert!(signed.contains("dataflow.io/content-digest: sha256:"));
+        assert!(signed.starts_with(
+            "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\n"
+        ));
+        assert!(signed.contains("spec:\n  source:\n    type: kafka\n    kafka:\n"));
+        assert!(verify_dataflow_manifest(&signed, Some("s3cret"))
+            .unwrap()
+            .contains("signature verified"));
+
+        // Server-side metadata does not break verification; content changes and wrong keys do.
+        let applied = signed.replace(
+            "  name: orders\n",
+            "  name: orders\n  resourceVersion: \"42\"\n",
+        );
+        assert!(verify_dataflow_manifest(&applied, None).is_ok());
+        let kubectl_applied = signed.replace(
+            "  annotations:\n",
+            "  annotations:\n    kubectl.kubernetes.io/last-applied-configuration: '{\"kind\":\"DataFlow\"}'\n",
+        );
+        assert!(kubectl_applied.contains("last-applied-configuration"));
+        assert!(verify_dataflow_manifest(&kubectl_applied, Some("s3cret")).is_ok());
+        let tampered = signed.replace("topic: out", "topic: other");
+        assert!(verify_dataflow_manifest(&tampered, None).unwrap_err()[0]
+            .contains("modified after signing"));
+        assert!(
+            verify_dataflow_manifest(&signed, Some("wrong")).unwrap_err()[0]
+                .contains("does not match")
+        );
+        let digest = digest(&parse(MANIFEST).unwrap());
+        let expected = signature(&digest, "s3cret").unwrap();
+        assert!(signature_matches(
+            &digest,
+            "s3cret",
+            &expected
+                .to_uppercase()
+                .replace("HMAC-SHA256:", "hmac-sha256:")
+        )
+        .unwrap());
+        assert!(!signature_matches(&digest, "s3cret", &expected[..expected.len() - 1]).unwrap());
+        assert!(!signature_matches(&digest, "s3cret", "hmac-sha256:zz").unwrap());
+    }
+
+    #[test]
+    fn test_digest_ignores_formatting() {
+        let reformatted = MANIFEST.replace(
+            "kafka: {brokers: [\"k:9092\"], topic: out}",
+            "kafka:\n      topic: out\n      brokers:\n        - k:9092",
+        );
+        assert_eq!(
+            digest(&parse(MANIFEST).unwrap()),
+            digest(&parse(&reformatted).unwrap())
+        );
+    }
+}
@@ -0,0 +1,693 @@
+// Documentation bundle: JSON Schema of the DataFlow manifest per apiVersion, connector field tables and
+// transformation examples, generated from the catalogs and value lists the validator uses.
+
+use crate::tools::reference::{default_connectors, default_transformations};
+use crate::types::{
+    BATCH_TRANSFORMATION_TYPES, CURSOR_STORAGES, DATAFLOW_API_VERSION, DATAFLOW_KIND,
+    ENRICH_BACKENDS, ES_DOCUMENT_ID_STRATEGIES, ES_NULL_VALUE_BEHAVIORS, KAFKA_FORMATS,
+    KAFKA_OFFSET_RESETS, MAX_PARALLELISM, ON_ERROR_POLICIES, PAYLOAD_COMPRESSIONS, PAYLOAD_MODES,
+    POLL_MODES, S3_FORMATS, S3_PARTITIONERS, SCHEMA_REGISTRY_AUTH_TYPES, SCRIPT_LANGUAGES,
+    SINK_TYPES, SOURCE_TYPES, SUBJECT_NAME_STRATEGIES, SYSLOG_PARSERS, SYSLOG_PROTOCOLS,
+    TRINO_CATALOG_TYPES, TRINO_INSERT_STRATEGIES,
+};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// apiVersions the bundle documents (the ones the validator accepts).
+pub const DOCUMENTED_API_VERSIONS: [&str; 1] = [DATAFLOW_API_VERSION];
+
+/// Bundle format version, bumped on incompatible layout changes.
+const DOCS_BUNDLE_VERSION: u32 = 1;
+
+/// Allowed values of connector fields: (connector, field, values).
+const FIELD_ENUMS: [(&str, &str, &[&str]); 16] = [
+    ("kafka", "format", &KAFKA_FORMATS),
+    ("kafka", "offsetReset", &KAFKA_OFFSET_RESETS),
+    ("syslog", "protocol", &SYSLOG_PROTOCOLS),
+    ("syslog", "parser", &SYSLOG_PARSERS),
+    ("postgresql", "mode", &POLL_MODES),
+    ("postgresql", "cursorStorage", &CURSOR_STORAGES),
+    ("trino", "mode", &POLL_MODES),
+    ("trino", "cursorStorage", &CURSOR_STORAGES),
+    ("clickhouse", "mode", &POLL_MODES),
+    ("clickhouse", "cursorStorage", &CURSOR_STORAGES),
+    ("postgresql", "payloadMode", &PAYLOAD_MODES),
+    ("postgresql", "compression", &PAYLOAD_COMPRESSIONS),
+    (
+        "elasticsearch",
+        "nullValueBehavior",
+        &ES_NULL_VALUE_BEHAVIORS,
+    ),
+    (
+        "elasticsearch",
+        "documentIdStrategy",
+        &ES_DOCUMENT_ID_STRATEGIES,
+    ),
+    ("trino", "insertStrategy", &TRINO_INSERT_STRATEGIES),
+    ("trino", "catalogType", &TRINO_CATALOG_TYPES),
+];
+
+/// DNS-1123 subdomain (metadata.name) and label (metadata.namespace), as checked by the validator.
+const DNS1123_SUBDOMAIN_PATTERN: &str =
+    r"^[a-z0-9]([-a-z0-9]*[a-z0-9])?(\.[a-z0-9]([-a-z0-9]*[a-z0-9])?)*$";
+const DNS1123_LABEL_PATTERN: &str = r"^[a-z0-9]([-a-z0-9]*[a-z0-9])?$";
+
+/// S3 has its own format list; the kafka entry above covers kafka only.
+const S3_FIELD_ENUMS: [(&str, &[&str]); 2] =
+    [("format", &S3_FORMATS), ("partitioner", &S3_PARTITIONERS)];
+
+/// JSON type of a connector field; fields not listed take any value.
+pub(crate) fn field_type(field: &str) -> Option<&'static str> {
+    Some(match field {
+        "brokers"
+        | "addresses"
+        | "mergeKeys"
+        | "partitionFields"
+        | "partitionBy"
+        | "batchTransformations" => "array",
+        "batchSize" | "batchFlushIntervalSeconds" | "flushSize" => "integer",
+        "autoCreateTable" | "upsertMode" | "rawMode" => "boolean",
+        "tls" | "sasl" | "keycloak" | "replication" | "schemaRegistry" | "cloudevents"
+        | "columnMapping" => "object",
+        "topic" | "topicPattern" | "table" | "connectionString" | "serverURL" | "catalog"
+        | "schema" | "query" | "pollInterval" | "incrementalColumn" | "consumerGroup"
+        | "address" | "index" | "bucket" | "region" | "endpoint" | "prefix" | "conflictKey"
+        | "payloadColumn" | "fileFormat" | "pathFormat" | "username" | "password"
+        | "messageType" => "string",
+        _ => return None,
+    })
+}
+
+fn field_values(connector: &str, field: &str) -> Option<&'static [&'static str]> {
+    if connector == "s3" {
+        return S3_FIELD_ENUMS
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, v)| *v);
+    }
+    FIELD_ENUMS
+        .iter()
+        .find(|(c, f, _)| *c == connector && *f == field)
+        .map(|(_, _, v)| *v)
+}
+
+fn str_list(v: &Value) -> Vec<String> {
+    v.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Field table of a catalog entry: one row per field with required, type and allowed values.
+fn field_rows(connector: &str, entry: &Value) -> Vec<Value> {
+    let required = str_list(&entry["required_fields"]);
+    let optional = str_list(&entry["optional_fields"]);
+    required
+        .iter()
+        .map(|f| (f, true))
+        .chain(optional.iter().map(|f| (f, false)))
+        .map(|(field, is_required)| {
+            let mut row = json!({ "field": field, "required": is_required });
+            if let Some(t) = field_type(field) {
+                row["type"] = json!(t);
+            }
+            if let Some(values) = field_values(connector, field) {
+                row["values"] = json!(values);
+            }
+            row
+        })
+        .collect()
+}
+
+/// JSON Schema of one connector block (e.g. spec.source.kafka) from its field table.
+fn block_schema(connector: &str, entry: &Value) -> Value {
+    let mut properties = JsonMap::new();
+    let mut required = Vec::new();
+    for row in field_rows(connector, entry) {
+        let field = row["field"].as_str().unwrap_or("").to_string();
+        let mut prop = JsonMap::new();
+        if let Some(t) = row.get("type") {
+            prop.insert("type".to_string(), t.clone());
+        }
+        if let Some(values) = row.get("values") {
+            prop.insert("enum".to_string(), values.clone());
+        }
+        if row["required"] == true {
+            required.push(field.clone());
+        }
+        // Credentials may reference a Secret instead of an inline value.
+        if ["password", "connectionString", "serverURL"].contains(&field.as_str()) {
+            prop.remove("type");
+            prop.insert(
+                "oneOf".to_string(),
+                json!([{ "type": "string" }, { "$ref": "#/$defs/secretKeyRef" }]),
+            );
+        }
+        if field == "batchTransformations" {
+            prop.insert(
+                "items".to_string(),
+                json!({ "$ref": "#/$defs/batchTransformation" }),
+            );
+        }
+        if field == "schemaRegistry" {
+            prop = JsonMap::from_iter([("$ref".to_string(), json!("#/$defs/schemaRegistry"))]);
+        }
+        properties.insert(field, Value::Object(prop));
+    }
+    let mut schema =
+        json!({ "type": "object", "description": entry["description"], "properties": properties });
+    // topicPattern subscribes instead of topic: one of the two is required.
+    if required.iter().any(|f| f == "topic") && schema["properties"].get("topicPattern").is_some() {
+        required.retain(|f| f != "topic");
+        schema["oneOf"] = json!([{ "required": ["topic"] }, { "required": ["topicPattern"] }]);
+    }
+    schema["required"] = json!(required);
+    schema
+}
+
+/// Source or sink schema: type enum plus one block per type, required when type selects it.
+fn endpoint_schema(
+    kind: &str,
+    types: &[&str],
+    defs: &mut JsonMap<String, Value>,
+    catalog: &JsonMap<String, Value>,
+) -> Value {
+    let mut properties = JsonMap::new();
+    properties.insert(
+        "type".to_string(),
+        json!({ "type": "string", "enum": types }),
+    );
+    let mut conditions = Vec::new();
+    for t in types {
+        let def = format!("{}.{}", kind, t);
+        let entry = catalog.get(*t).cloned().unwrap_or_else(|| json!({}));
+        defs.insert(def.clone(), block_schema(t, &entry));
+        properties.insert(t.to_string(), json!({ "$ref": format!("#/$defs/{}", def) }));
+        conditions.push(json!({
+            "if": { "properties": { "type": { "const": t } }, "required": ["type"] },
+            "then": { "required": [t] }
+        }));
+    }
+    json!({ "type": "object", "required": ["type"], "properties": properties, "allOf": conditions })
+}
+
+/// Config block of a transformation type (e.g. the filter object of type: filter), mirroring the validator's checks.
+fn transformation_block_schema(kind: &str) -> Value {
+    let string = json!({ "type": "string" });
+    let paths = json!({ "type": "array", "items": { "type": "string" }, "minItems": 1 });
+    match kind {
+        "filter" => {
+            json!({ "type": "object", "required": ["condition"], "properties": { "condition": string } })
+        }
+        "mask" => json!({
+            "type": "object",
+            "required": ["fields"],
+            "properties": { "fields": paths, "maskChar": string, "keepLength": { "type": "boolean" } }
+        }),
+        "select" | "remove" => {
+            json!({ "type": "object", "required": ["fields"], "properties": { "fields": paths } })
+        }
+        "flatten" | "unwrap" => {
+            json!({ "type": "object", "required": ["field"], "properties": { "field": string } })
+        }
+        "unwrapCloudEvent" => {
+            json!({ "type": "object", "properties": { "attributesField": string } })
+        }
+        "timestamp" => {
+            json!({ "type": "object", "properties": { "fieldName": string, "format": string } })
+        }
+        "snakeCase" | "camelCase" => {
+            json!({ "type": "object", "properties": { "deep": { "type": "boolean" } } })
+        }
+        "router" => json!({
+            "type": "object",
+            "required": ["routes"],
+            "properties": {
+                "routes": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "condition": string, "sink": { "$ref": "#/$defs/sink" }, "branch": string },
+                        "oneOf": [{ "required": ["sink"] }, { "required": ["branch"] }]
+                    }
+                }
+            }
+        }),
+        "script" => json!({
+            "type": "object",
+            "required": ["language"],
+            "properties": {
+                "language": { "type": "string", "enum": SCRIPT_LANGUAGES },
+                "code": { "type": "string", "minLength": 1 },
+                "configMapRef": { "type": "object", "required": ["name", "key"], "properties": { "name": string, "key": string } },
+                "limits": { "type": "object", "properties": { "cpu": string, "timeout": string } }
+            },
+            "oneOf": [{ "required": ["code"] }, { "required": ["configMapRef"] }]
+        }),
+        "enrich" => json!({
+            "type": "object",
+            "required": ["backend", "targetField"],
+            "properties": {
+                "backend": { "type": "string", "enum": ENRICH_BACKENDS },
+                "http": { "type": "object", "required": ["url"], "properties": { "url": { "type": "string", "pattern": "^https?://" }, "method": string } },
+                "grpc": { "type": "object", "required": ["target", "method"], "properties": { "target": string, "method": string } },
+                "targetField": { "type": "string", "minLength": 1 },
+                "timeout": string,
+                "retry": { "type": "object", "properties": { "maxAttempts": { "type": "integer", "minimum": 0, "maximum": 10 }, "backoff": string } },
+                "circuitBreaker": {
+                    "type": "object",
+                    "properties": {
+                        "failureThreshold": { "type": "integer", "minimum": 1, "maximum": 1000 },
+                        "openDuration": string,
+                        "halfOpenRequests": { "type": "integer", "minimum": 1, "maximum": 100 }
+                    }
+                }
+            },
+            "allOf": [
+                { "if": { "properties": { "backend": { "const": "http" } } }, "then": { "required": ["http"] } },
+                { "if": { "properties": { "backend": { "const": "grpc" } } }, "then": { "required": ["grpc"] } }
+            ]
+        }),
+        "sort" => json!({
+            "type": "object",
+            "required": ["field"],
+            "properties": { "field": { "type": "string", "minLength": 1 }, "order": { "type": "string", "enum": ["asc", "desc"] } }
+        }),
+        "dedupe" => json!({
+            "type": "object",
+            "required": ["key"],
+            "properties": { "key": paths, "keep": { "type": "string", "enum": ["last", "first"] } }
+        }),
+        "chunk" => json!({
+            "type": "object",
+            "required": ["size"],
+            "properties": { "size": { "type": "integer", "minimum": 1, "maximum": 1_000_000 } }
+        }),
+        _ => json!({ "type": "object" }),
+    }
+}
+
+/// Transformation schema for types: type enum, onError and one block per type, required when type selects it.
+fn transformation_schema(types: &[&str], defs: &mut JsonMap<String, Value>) -> Value {
+    let mut properties = JsonMap::new();
+    properties.insert(
+        "type".to_string(),
+        json!({ "type": "string", "enum": types }),
+    );
+    properties.insert(
+        "onError".to_string(),
+        json!({ "type": "string", "enum": ON_ERROR_POLICIES }),
+    );
+    let mut conditions = Vec::new();
+    for t in types {
+        let def = format!("transformation.{}", t);
+        defs.insert(def.clone(), transformation_block_schema(t));
+        properties.insert(t.to_string(), json!({ "$ref": format!("#/$defs/{}", def) }));
+        conditions.push(json!({
+            "if": { "properties": { "type": { "const": t } }, "required": ["type"] },
+            "then": { "required": [t] }
+        }));
+    }
+    json!({ "type": "object", "required": ["type"], "properties": properties, "allOf": conditions })
+}
+
+/// JSON Schema (draft 2020-12) of a DataFlow manifest for api_version.
+pub fn manifest_json_schema(api_version: &str) -> Result<Value, String> {
+    if !DOCUMENTED_API_VERSIONS.contains(&api_version) {
+        return Err(format!(
+            "Unknown apiVersion '{}'; documented: {}",
+            api_version,
+            DOCUMENTED_API_VERSIONS.join(", ")
+        ));
+    }
+    let connectors = default_connectors();
+    let transformations = default_transformations();
+    let empty = JsonMap::new();
+    let mut defs = JsonMap::new();
+    let source = endpoint_schema(
+        "source",
+        &SOURCE_TYPES,
+        &mut defs,
+        connectors["sources"].as_object().unwrap_or(&empty),
+    );
+    let sink = endpoint_schema(
+        "sink",
+        &SINK_TYPES,
+        &mut defs,
+        connectors["sinks"].as_object().unwrap_or(&empty),
+    );
+    defs.insert("source".to_string(), source);
+    defs.insert("sink".to_string(), sink);
+    let stream: Vec<&str> = transformations
+        .keys()
+        .map(String::as_str)
+        .filter(|t| !BATCH_TRANSFORMATION_TYPES.contains(t))
+        .collect();
+    let transformation = transformation_schema(&stream, &mut defs);
+    defs.insert("transformation".to_string(), transformation);
+    let batch = transformation_schema(&BATCH_TRANSFORMATION_TYPES, &mut defs);
+    defs.insert("batchTransformation".to_string(), batch);
+    defs.insert(
+        "secretKeyRef".to_string(),
+        json!({
+            "type": "object",
+            "required": ["secretKeyRef"],
+            "additionalProperties": false,
+            "properties": {
+                "secretKeyRef": {
+                    "type": "object",
+                    "required": ["name", "key"],
+                    "properties": { "name": { "type": "string", "minLength": 1 }, "key": { "type": "string", "minLength": 1 } }
+                }
+            }
+        }),
+    );
+    let credential = json!({ "oneOf": [{ "type": "string" }, { "$ref": "#/$defs/secretKeyRef" }] });
+    defs.insert(
+        "schemaRegistry".to_string(),
+        json!({
+            "type": "object",
+            "required": ["url"],
+            "additionalProperties": false,
+            "properties": {
+                "url": { "type": "string", "pattern": "^https?://" },
+                "subjectNameStrategy": { "enum": SUBJECT_NAME_STRATEGIES },
+                "auth": {
+                    "type": "object",
+                    "required": ["type"],
+                    "properties": {
+                        "type": { "enum": SCHEMA_REGISTRY_AUTH_TYPES },
+                        "username": credential,
+                        "password": credential,
+                        "token": credential
+                    }
+                }
+            }
+        }),
+    );
+    let quantity = json!({ "oneOf": [{ "type": "string", "pattern": r"^[0-9.]+([numkMGTPE]|[KMGTPE]i|[eE][0-9]+)?$" }, { "type": "number" }] });
+    defs.insert(
+        "resourceList".to_string(),
+        json!({ "type": "object", "properties": { "cpu": quantity, "memory": quantity, "ephemeral-storage": quantity } }),
+    );
+    let transformation_list =
+        json!({ "type": "array", "items": { "$ref": "#/$defs/transformation" } });
+    Ok(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": format!("{} ({})", DATAFLOW_KIND, api_version),
+        "type": "object",
+        "required": ["apiVersion", "kind", "metadata", "spec"],
+        "properties": {
+            "apiVersion": { "const": api_version },
+            "kind": { "const": DATAFLOW_KIND },
+            "metadata": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": { "type": "string", "maxLength": 63, "pattern": DNS1123_SUBDOMAIN_PATTERN },
+                    "namespace": { "type": "string", "maxLength": 63, "pattern": DNS1123_LABEL_PATTERN },
+                    "labels": { "type": "object", "additionalProperties": { "type": "string" } },
+                    "annotations": { "type": "object", "additionalProperties": { "type": "string" } }
+                }
+            },
+            "spec": {
+                "type": "object",
+                "required": ["source", "sink"],
+                "properties": {
+                    "source": { "$ref": "#/$defs/source" },
+                    "sink": { "$ref": "#/$defs/sink" },
+                    "transformations": transformation_list,
+                    "branches": {
+                        "type": "object",
+                        "additionalProperties": {
+                            "type": "object",
+                            "required": ["sink"],
+                            "properties": { "transformations": transformation_list, "sink": { "$ref": "#/$defs/sink" } }
+                        }
+                    },
+                    "errorHandling": { "type": "object", "required": ["dlq"], "properties": { "dlq": { "$ref": "#/$defs/sink" } } },
+                    "parallelism": { "type": "integer", "minimum": 1, "maximum": MAX_PARALLELISM },
+                    "resources": {
+                        "type": "object",
+                        "properties": { "requests": { "$ref": "#/$defs/resourceList" }, "limits": { "$ref": "#/$defs/resourceList" } }
+                    }
+                }
+            }
+        },
+        "$defs": defs
+    }))
+}
+
+/// Replaces "#/$defs/..." references with the definitions they name so part of the schema stands alone; a
+/// definition referenced inside itself becomes a plain object.
+fn inline_refs(value: &Value, defs: &JsonMap<String, Value>, stack: &mut Vec<String>) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(name) = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix("#/$defs/"))
+            {
+                if stack.iter().any(|s| s == name) {
+                    return json!({ "type": "object" });
+                }
+                stack.push(name.to_string());
+                let inlined = inline_refs(defs.get(name).unwrap_or(&json!({})), defs, stack);
+                stack.pop();
+                return inlined;
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), inline_refs(v, defs, stack)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| inline_refs(v, defs, stack)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// Self-contained schema of a structured generate_dataflow_manifest parameter: source_config / sink_config as any
+/// connector block of that side (fields, types and enums; required fields are left to validation, since the
+/// description may supply them), transformations as an array of transformations, transformation as one.
+pub fn generate_param_schema(param: &str) -> Value {
+    let schema = manifest_json_schema(DATAFLOW_API_VERSION).unwrap_or_default();
+    let empty = JsonMap::new();
+    let defs = schema["$defs"].as_object().unwrap_or(&empty);
+    let (side, types): (&str, &[&str]) = match param {
+        "source_config" => ("source", &SOURCE_TYPES),
+        "sink_config" => ("sink", &SINK_TYPES),
+        _ => {
+            let item = inline_refs(
+                &json!({ "$ref": "#/$defs/transformation" }),
+                defs,
+                &mut Vec::new(),
+            );
+            if param == "transformation" {
+                return item;
+            }
+            return json!({
+                "type": "array",
+                "description": "Transformations in order, e.g. [{\"type\": \"mask\", \"mask\": {\"fields\": [\"$.email\"]}}]",
+                "items": item
+            });
+        }
+    };
+    let blocks: Vec<Value> = types
+        .iter()
+        .map(|t| {
+            let mut block = inline_refs(
+                &json!({ "$ref": format!("#/$defs/{}.{}", side, t) }),
+                defs,
+                &mut Vec::new(),
+            );
+            if let Some(map) = block.as_object_mut() {
+                map.remove("required");
+                map.remove("oneOf");
+                map.insert("title".to_string(), json!(t));
+            }
+            block
+        })
+        .collect();
+    let example = match side {
+        "source" => r#"{"brokers": ["localhost:9092"], "topic": "orders"}"#,
+        _ => r#"{"connectionString": "postgres://user@host:5432/db", "table": "orders"}"#,
+    };
+    json!({
+        "type": "object",
+        "description": format!("Fields of spec.{}.<{}_type>, e.g. {}", side, side, example),
+        "anyOf": blocks
+    })
+}
+
+/// Connector catalog as field tables: {sources: {kafka: {description, fields: [...]}}, sinks: {...}}.
+fn connector_tables() -> Value {
+    let connectors = default_connectors();
+    let mut out = JsonMap::new();
+    for direction in ["sources", "sinks"] {
+        let mut section = JsonMap::new();
+        for (name, entry) in connectors[direction].as_object().into_iter().flatten() {
+            section.insert(
+                name.clone(),
+                json!({ "description": entry["description"], "fields": field_rows(name, entry) }),
+            );
+        }
+        out.insert(direction.to_string(), Value::Object(section));
+    }
+    Value::Object(out)
+}
+
+/// The whole documentation bundle as one JSON artifact: schemas per apiVersion, connector field tables and
+/// transformation descriptions with examples, in the server locale.
+pub fn docs_bundle() -> Result<String, String> {
+    let mut schemas = JsonMap::new();
+    for version in DOCUMENTED_API_VERSIONS {
+        schemas.insert(version.to_string(), manifest_json_schema(version)?);
+    }
+    let bundle = json!({
+        "bundleVersion": DOCS_BUNDLE_VERSION,
+        "generator": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+        "locale": crate::config::current().locale(),
+        "apiVersions": DOCUMENTED_API_VERSIONS,
+        "schemas": schemas,
+        "connectors": connector_tables(),
+        "transformations": default_transformations()
+    });
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_json_schema_from_catalog() {
+        let schema = manifest_json_schema(DATAFLOW_API_VERSION).unwrap();
+        let kafka = &schema["$defs"]["source.kafka"];
+        assert_eq!(kafka["required"], json!(["brokers"]));
+        assert_eq!(kafka["oneOf"][1]["required"], json!(["topicPattern"]));
+        assert_eq!(
+            kafka["properties"]["offsetReset"]["enum"],
+            json!(KAFKA_OFFSET_RESETS)
+        );
+        assert_eq!(
+            schema["$defs"]["sink.s3"]["properties"]["format"]["enum"],
+            json!(S3_FORMATS)
+        );
+        assert_eq!(
+            schema["$defs"]["sink.s3"]["properties"]["schemaRegistry"]["$ref"],
+            "#/$defs/schemaRegistry"
+        );
+        assert_eq!(
+            schema["$defs"]["schemaRegistry"]["properties"]["subjectNameStrategy"]["enum"],
+            json!(SUBJECT_NAME_STRATEGIES)
+        );
+        assert_eq!(
+            schema["$defs"]["sink"]["properties"]["type"]["enum"],
+            json!(SINK_TYPES)
+        );
+        let types = schema["$defs"]["transformation"]["properties"]["type"]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(types.contains(&json!("filter")) && types.contains(&json!("router")));
+        assert!(!types.contains(&json!("sort")));
+        assert!(manifest_json_schema("dataflow.dataflow.io/v2").is_err());
+    }
+
+    #[test]
+    fn test_manifest_json_schema_transformation_blocks() {
+        let schema = manifest_json_schema(DATAFLOW_API_VERSION).unwrap();
+        let defs = &schema["$defs"];
+        assert_eq!(
+            defs["transformation.filter"]["required"],
+            json!(["condition"])
+        );
+        assert_eq!(
+            defs["transformation.script"]["properties"]["language"]["enum"],
+            json!(SCRIPT_LANGUAGES)
+        );
+        assert_eq!(
+            defs["transformation.script"]["oneOf"][1]["required"],
+            json!(["configMapRef"])
+        );
+        assert_eq!(
+            defs["transformation.router"]["properties"]["routes"]["items"]["properties"]["sink"]
+                ["$ref"],
+            "#/$defs/sink"
+        );
+        assert_eq!(
+            defs["batchTransformation"]["properties"]["type"]["enum"],
+            json!(BATCH_TRANSFORMATION_TYPES)
+        );
+        assert_eq!(
+            defs["sink.postgresql"]["properties"]["batchTransformations"]["items"]["$ref"],
+            "#/$defs/batchTransformation"
+        );
+        assert_eq!(
+            schema["properties"]["metadata"]["properties"]["name"]["maxLength"],
+            63
+        );
+    }
+
+    #[test]
+    fn test_generate_param_schema_is_self_contained() {
+        let source = generate_param_schema("source_config");
+        let kafka = source["anyOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b["title"] == "kafka")
+            .unwrap();
+        assert_eq!(
+            kafka["properties"]["offsetReset"]["enum"],
+            json!(KAFKA_OFFSET_RESETS)
+        );
+        assert!(kafka.get("required").is_none());
+        let sink = generate_param_schema("sink_config");
+        let postgresql = sink["anyOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b["title"] == "postgresql")
+            .unwrap();
+        assert_eq!(
+            postgresql["properties"]["connectionString"]["oneOf"][1]["required"],
+            json!(["secretKeyRef"])
+        );
+        let transformations = generate_param_schema("transformations");
+        assert_eq!(transformations["type"], "array");
+        let router = &transformations["items"]["properties"]["router"];
+        assert_eq!(
+            router["properties"]["routes"]["items"]["properties"]["sink"]["properties"]["type"]
+                ["enum"],
+            json!(SINK_TYPES)
+        );
+        assert!(
+            !transformations.to_string().contains("$ref") && !sink.to_string().contains("$ref")
+        );
+    }
+
+    #[test]
+    fn test_docs_bundle_layout() {
+        let bundle: Value = serde_json::from_str(&docs_bundle().unwrap()).unwrap();
+        assert_eq!(bundle["apiVersions"], json!([DATAFLOW_API_VERSION]));
+        assert!(bundle["schemas"][DATAFLOW_API_VERSION]["$defs"].is_object());
+        let rows = bundle["connectors"]["sinks"]["postgresql"]["fields"]
+            .as_array()
+            .unwrap();
+        assert_eq!(
+            rows[0],
+            json!({ "field": "connectionString", "required": true, "type": "string" })
+        );
+        assert!(rows
+            .iter()
+            .any(|r| r["field"] == "payloadMode" && r["values"] == json!(PAYLOAD_MODES)));
+        assert_eq!(
+            bundle["transformations"]["filter"]["example"]["type"],
+            "filter"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+// Export a live DataFlow from the cluster as clean YAML: the reverse path for adopting manually created flows into GitOps.
+
+use crate::tools::digest::{SERVER_ANNOTATIONS, SERVER_METADATA};
+use crate::tools::kube::kubectl_json;
+use crate::tools::output::to_canonical_yaml;
+use crate::types::DATAFLOW_CRD_NAME;
+use serde_json::Value;
+
+/// Values the operator fills in when a field is omitted; dropped so the export only keeps what was chosen.
+const DEFAULTED_FIELDS: [(&str, &str); 6] = [
+    ("/spec/parallelism", "1"),
+    ("/spec/source/kafka/format", "\"json\""),
+    ("/spec/sink/kafka/format", "\"json\""),
+    ("/spec/sink/s3/format", "\"json\""),
+    ("/spec/sink/s3/partitioner", "\"default\""),
+    ("/spec/sink/trino/insertStrategy", "\"insert\""),
+];
+
+/// Removes empty mappings and lists left behind after stripping (e.g. metadata.annotations: {}).
+fn prune_empty(v: &mut Value) {
+    match v {
+        Value::Object(map) => {
+            for child in map.values_mut() {
+                prune_empty(child);
+            }
+            map.retain(|_, c| {
+                !matches!(c, Value::Object(m) if m.is_empty())
+                    && !matches!(c, Value::Array(a) if a.is_empty())
+            });
+        }
+        Value::Array(items) => items.iter_mut().for_each(prune_empty),
+        _ => {}
+    }
+}
+
+fn remove_pointer(v: &mut Value, pointer: &str) {
+    let (parent, key) = pointer.rsplit_once('/').unwrap_or(("", pointer));
+    if let Some(Value::Object(map)) = v.pointer_mut(parent) {
+        map.remove(key);
+    }
+}
+
+/// Strips status, server-set metadata, kubectl annotations and operator defaults from a live resource.
+pub fn clean_deployed(mut resource: Value) -> Value {
+    if let Some(obj) = resource.as_object_mut() {
+        obj.remove("status");
+    }
+    if let Some(Value::Object(metadata)) = resource.get_mut("metadata") {
+        for key in SERVER_METADATA
+            .iter()
+            .chain(["ownerReferences", "finalizers", "deletionTimestamp"].iter())
+        {
+            metadata.remove(*key);
+        }
+        if let Some(Value::Object(annotations)) = metadata.get_mut("annotations") {
+            for a in SERVER_ANNOTATIONS {
+                annotations.remove(a);
+            }
+        }
+    }
+    for (pointer, default) in DEFAULTED_FIELDS {
+        let default: Value = serde_json::from_str(default).unwrap_or(Value::Null);
+        if resource.pointer(pointer) == Some(&default) {
+            remove_pointer(&mut resource, pointer);
+        }
+    }
+    prune_empty(&mut resource);
+    resource
+}
+
+fn namespace_or_default(namespace: Option<&str>) -> &str {
+    namespace.filter(|n| !n.is_empty()).unwrap_or("default")
+}
+
+/// Workspace key of an exported DataFlow: <namespace>/<name>, so same-named flows of two namespaces do not collide.
+pub fn workspace_key(name: &str, namespace: Option<&str>) -> String {
+    format!("{}/{}", namespace_or_default(namespace), name)
+}
+
+/// Fetches DataFlow name in namespace via kubectl and returns it as clean YAML (keys in canonical order).
+pub async fn export_deployed_dataflow(
+    name: &str,
+    namespace: Option<&str>,
+    context: Option<&str>,
+) -> Result<String, String> {
+    let namespace = namespace_or_default(namespace);
+    let live = kubectl_json(
+        "DataFlow export",
+        &["get", DATAFLOW_CRD_NAME, name, "-n", namespace],
+        context,
+    )
+    .await?;
+    let cleaned = clean_deployed(live);
+    if cleaned.get("spec").is_none() {
+        return Err(format!("DataFlow {}/{} has no spec", namespace, name));
+    }
+    to_canonical_yaml(&cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_workspace_key() {
+        assert_eq!(
+            workspace_key("orders", Some("streaming")),
+            "streaming/orders"
+        );
+        assert_eq!(workspace_key("orders", Some("")), "default/orders");
+        assert_eq!(workspace_key("orders", None), "default/orders");
+    }
+
+    #[test]
+    fn test_clean_deployed_strips_server_fields_and_defaults() {
+        let live = json!({
+            "apiVersion": "dataflow.dataflow.io/v1",
+            "kind": "DataFlow",
+            "metadata": {
+                "name": "orders",
+                "namespace": "streaming",
+                "uid": "1b2c",
+                "resourceVersion": "981",
+                "generation": 3,
+                "creationTimestamp": "2026-01-01T00:00:00Z",
+                "managedFields": [{ "manager": "kubectl" }],
+                "annotations": { "kubectl.kubernetes.io/last-applied-configuration": "{...}" },
+                "labels": { "team": "shop" }
+            },
+            "spec": {
+                "parallelism": 1,
+                "source": { "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "orders", "format": "json" } },
+                "sink": { "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "out", "format": "avro" } }
+            },
+            "status": { "phase": "Running" }
+        });
+        let cleaned = clean_deployed(live);
+        assert_eq!(
+            cleaned,
+            json!({
+                "apiVersion": "dataflow.dataflow.io/v1",
+                "kind": "DataFlow",
+                "metadata": { "name": "orders", "namespace": "streaming", "labels": { "team": "shop" } },
+                "spec": {
+                    "source": { "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "orders" } },
+                    "sink": { "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "out", "format": "avro" } }
+                }
+            })
+        );
+    }
+}
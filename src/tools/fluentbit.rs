@@ -0,0 +1,685 @@
+// Fluent Bit pipeline migration: classic [INPUT]/[FILTER]/[OUTPUT] or YAML configs; every kafka or
+// elasticsearch output becomes one DataFlow fed by the input its Match selects, with modify /
+// record_modifier / grep / nest filters mapped to DataFlow transformations.
+
+use crate::tools::migration::{block, placeholder_kafka, render_migrated, sanitize_name};
+use crate::tools::provenance::{annotate, or_filled, GenerationNote};
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_KIND, KAFKA_OFFSET_RESETS};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// One [INPUT]/[FILTER]/[OUTPUT] section: plugin name and its properties in order (keys may repeat).
+#[derive(Debug, Clone, Default)]
+struct Section {
+    name: String,
+    props: Vec<(String, String)>,
+}
+
+impl Section {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.props
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.props
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn on(&self, key: &str) -> bool {
+        self.get(key)
+            .is_some_and(|v| matches!(v.to_lowercase().as_str(), "on" | "true" | "yes" | "1"))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Pipeline {
+    inputs: Vec<Section>,
+    filters: Vec<Section>,
+    outputs: Vec<Section>,
+}
+
+/// Classic format: [SECTION] headers, "Key Value" lines, @SET variables; @INCLUDE is reported, not followed.
+fn parse_classic(text: &str, notes: &mut Vec<String>) -> Pipeline {
+    let mut pipeline = Pipeline::default();
+    let mut vars: Vec<(String, String)> = Vec::new();
+    let mut current: Option<(String, Section)> = None;
+    let flush = |current: Option<(String, Section)>, pipeline: &mut Pipeline| {
+        if let Some((kind, section)) = current {
+            match kind.as_str() {
+                "INPUT" => pipeline.inputs.push(section),
+                "FILTER" => pipeline.filters.push(section),
+                "OUTPUT" => pipeline.outputs.push(section),
+                _ => {}
+            }
+        }
+    };
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(kind) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            flush(current.take(), &mut pipeline);
+            current = Some((kind.trim().to_uppercase(), Section::default()));
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("@SET ") {
+            if let Some((k, v)) = rest.split_once('=') {
+                vars.push((format!("${{{}}}", k.trim()), v.trim().to_string()));
+            }
+            continue;
+        }
+        if let Some(file) = line.strip_prefix("@INCLUDE ") {
+            notes.push(format!(
+                "@INCLUDE {} was not followed; migrate the included sections separately.",
+                file.trim()
+            ));
+            continue;
+        }
+        let (key, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let mut value = value.trim().to_string();
+        for (var, replacement) in &vars {
+            value = value.replace(var, replacement);
+        }
+        if let Some((_, section)) = current.as_mut() {
+            if key.eq_ignore_ascii_case("name") {
+                section.name = value.to_lowercase();
+            } else {
+                section.props.push((key.to_string(), value));
+            }
+        }
+    }
+    flush(current.take(), &mut pipeline);
+    pipeline
+}
+
+/// YAML format: pipeline.inputs / filters / outputs lists; list values expand into repeated keys.
+fn parse_yaml(v: &Value) -> Pipeline {
+    let sections = |key: &str| -> Vec<Section> {
+        v["pipeline"][key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+            .map(|m| {
+                let mut section = Section::default();
+                for (k, val) in m {
+                    let values: Vec<String> = match val {
+                        Value::Array(a) => a.iter().map(scalar).collect(),
+                        other => vec![scalar(other)],
+                    };
+                    if k.eq_ignore_ascii_case("name") {
+                        section.name = values.concat().to_lowercase();
+                    } else {
+                        section
+                            .props
+                            .extend(values.into_iter().map(|x| (k.clone(), x)));
+                    }
+                }
+                section
+            })
+            .collect()
+    };
+    Pipeline {
+        inputs: sections("inputs"),
+        filters: sections("filters"),
+        outputs: sections("outputs"),
+    }
+}
+
+fn scalar(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Fluent Bit tag matching: '*' matches any run of characters.
+fn tag_matches(pattern: &str, tag: &str) -> bool {
+    let re = format!(
+        "^{}$",
+        pattern
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".*")
+    );
+    regex::Regex::new(&re).is_ok_and(|r| r.is_match(tag))
+}
+
+/// Whether a filter or output selects the tag (Match glob or Match_Regex).
+fn selects(section: &Section, tag: &str) -> bool {
+    if let Some(re) = section.get("Match_Regex") {
+        return regex::Regex::new(re).is_ok_and(|r| r.is_match(tag));
+    }
+    section.get("Match").is_some_and(|m| tag_matches(m, tag))
+}
+
+/// Tag of each input: Tag, or "<plugin>.<n>" like Fluent Bit's default.
+fn input_tags(inputs: &[Section]) -> Vec<String> {
+    let mut seen: Vec<&str> = Vec::new();
+    inputs
+        .iter()
+        .map(|i| {
+            let n = seen.iter().filter(|s| **s == i.name).count();
+            seen.push(&i.name);
+            i.get("Tag")
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}.{}", i.name, n))
+        })
+        .collect()
+}
+
+/// A plain record key as a JSONPath; record accessors ($kubernetes['pod']) are not translated.
+fn key_path(key: &str) -> Option<String> {
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'));
+    valid.then(|| format!("$.{}", key))
+}
+
+fn brokers(section: &Section, path: &str, filled: &mut Vec<GenerationNote>) -> Vec<String> {
+    let list: Vec<String> = section
+        .get("Brokers")
+        .unwrap_or("")
+        .split(',')
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty())
+        .collect();
+    if list.is_empty() {
+        filled.push(GenerationNote::new(
+            path,
+            Value::from(vec!["localhost:9092"]),
+            "Brokers not set",
+        ));
+        return vec!["localhost:9092".to_string()];
+    }
+    list
+}
+
+fn map_input(
+    input: &Section,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> JsonMap<String, Value> {
+    match input.name.as_str() {
+        "kafka" => {
+            let mut kafka = JsonMap::new();
+            kafka.insert(
+                "brokers".to_string(),
+                json!(brokers(input, "spec.source.kafka.brokers", filled)),
+            );
+            let topics: Vec<String> = input
+                .get("Topics")
+                .unwrap_or("")
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+            if topics.len() > 1 {
+                let pattern = topics
+                    .iter()
+                    .map(|t| regex::escape(t))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                kafka.insert("topicPattern".to_string(), Value::String(pattern));
+            } else {
+                let topic = or_filled(
+                    topics.into_iter().next(),
+                    "input-topic",
+                    "spec.source.kafka.topic",
+                    "Topics not set",
+                    filled,
+                );
+                kafka.insert("topic".to_string(), Value::String(topic));
+            }
+            if let Some(group) = input.get("group_id") {
+                kafka.insert(
+                    "consumerGroup".to_string(),
+                    Value::String(group.to_string()),
+                );
+            }
+            if let Some(reset) = input
+                .get("rdkafka.auto.offset.reset")
+                .filter(|r| KAFKA_OFFSET_RESETS.contains(r))
+            {
+                kafka.insert("offsetReset".to_string(), Value::String(reset.to_string()));
+            }
+            kafka.insert(
+                "format".to_string(),
+                Value::String(
+                    if input.get("Format") == Some("json") {
+                        "json"
+                    } else {
+                        "string"
+                    }
+                    .to_string(),
+                ),
+            );
+            block("kafka", kafka)
+        }
+        "syslog" => {
+            let mut syslog = JsonMap::new();
+            let address = format!(
+                "{}:{}",
+                input.get("Listen").unwrap_or("0.0.0.0"),
+                input.get("Port").unwrap_or("5140")
+            );
+            syslog.insert("address".to_string(), Value::String(address));
+            let mode = input.get("Mode").unwrap_or("unix_udp").to_lowercase();
+            if mode.starts_with("unix") {
+                notes.push(format!("syslog Mode {} (unix socket) is not supported; listening on the network instead.", mode));
+            }
+            syslog.insert(
+                "protocol".to_string(),
+                Value::String(if mode.ends_with("tcp") { "tcp" } else { "udp" }.to_string()),
+            );
+            match input.get("Parser") {
+                Some("syslog-rfc5424") => {
+                    syslog.insert("parser".to_string(), Value::String("rfc5424".to_string()));
+                }
+                Some("syslog-rfc3164" | "syslog-rfc3164-local") => {
+                    syslog.insert("parser".to_string(), Value::String("rfc3164".to_string()));
+                }
+                _ => {}
+            }
+            block("syslog", syslog)
+        }
+        other => {
+            notes.push(format!("Input '{}' is not supported; the kafka source is a placeholder (ship the data to Kafka first).", other));
+            placeholder_kafka(
+                "spec.source",
+                "input-topic",
+                &format!("unsupported Fluent Bit input {}", other),
+                filled,
+            )
+        }
+    }
+}
+
+/// kafka or es/elasticsearch/opensearch output; None for other plugins.
+fn map_output(
+    output: &Section,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> Option<JsonMap<String, Value>> {
+    match output.name.as_str() {
+        "kafka" => {
+            let mut kafka = JsonMap::new();
+            kafka.insert(
+                "brokers".to_string(),
+                json!(brokers(output, "spec.sink.kafka.brokers", filled)),
+            );
+            let topics: Vec<&str> = output
+                .get("Topics")
+                .unwrap_or("")
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .collect();
+            if topics.len() > 1 || output.get("Topic_Key").is_some() {
+                notes.push(format!(
+                    "Output kafka selects topics per record (Topics {} / Topic_Key); DataFlow writes to one topic (use a router for the others).",
+                    topics.join(",")
+                ));
+            }
+            let topic = or_filled(
+                topics.first().map(|t| t.to_string()),
+                "output-topic",
+                "spec.sink.kafka.topic",
+                "Topics not set",
+                filled,
+            );
+            kafka.insert("topic".to_string(), Value::String(topic));
+            let format = match output.get("Format").unwrap_or("json") {
+                "json" => "json",
+                "avro" => "avro",
+                _ => "string",
+            };
+            kafka.insert("format".to_string(), Value::String(format.to_string()));
+            Some(block("kafka", kafka))
+        }
+        "es" | "elasticsearch" | "opensearch" => {
+            let mut es = JsonMap::new();
+            let scheme = if output.on("tls") { "https" } else { "http" };
+            let address = format!(
+                "{}://{}:{}",
+                scheme,
+                output.get("Host").unwrap_or("127.0.0.1"),
+                output.get("Port").unwrap_or("9200")
+            );
+            es.insert("addresses".to_string(), json!([address]));
+            let index = or_filled(
+                output.get("Index").map(str::to_string),
+                "fluent-bit",
+                "spec.sink.elasticsearch.index",
+                "Index not set (Fluent Bit defaults to fluent-bit)",
+                filled,
+            );
+            es.insert("index".to_string(), Value::String(index));
+            if output.on("Logstash_Format") {
+                notes.push("Logstash_Format On writes a daily index (logstash-YYYY.MM.DD); DataFlow writes to the fixed index, use an ILM rollover alias instead.".to_string());
+            }
+            if output.get("HTTP_User").is_some() {
+                notes.push("HTTP_User/HTTP_Passwd were not copied; set username and password from a Secret.".to_string());
+            }
+            if output.get("Cloud_ID").is_some() {
+                notes.push(
+                    "Cloud_ID was not migrated; set addresses to the Elastic Cloud endpoint."
+                        .to_string(),
+                );
+            }
+            Some(block("elasticsearch", es))
+        }
+        _ => None,
+    }
+}
+
+/// Anchored literal regexes (^error$) as a string value; anything with regex syntax returns None.
+fn literal_regex(re: &str) -> Option<&str> {
+    let lit = re.strip_prefix('^')?.strip_suffix('$')?;
+    (!lit.contains([
+        '.', '*', '+', '?', '(', ')', '[', ']', '{', '}', '|', '\\', '\'',
+    ]))
+    .then_some(lit)
+}
+
+/// Transformations for one filter, or None (with a note) when it cannot be mapped.
+fn map_filter(filter: &Section, label: &str, notes: &mut Vec<String>) -> Vec<Value> {
+    let mut out = Vec::new();
+    let mut removed = Vec::new();
+    let mut unmapped = Vec::new();
+    match filter.name.as_str() {
+        "modify" => {
+            if filter.get("Condition").is_some() {
+                notes.push(format!("{}: Condition rules were not migrated; the filter is not converted (use a router with branches).", label));
+                return out;
+            }
+            for (key, value) in &filter.props {
+                match key.to_lowercase().as_str() {
+                    "match" | "match_regex" | "alias" => {}
+                    "remove" => match key_path(value) {
+                        Some(p) => removed.push(p),
+                        None => unmapped.push(format!("Remove {}", value)),
+                    },
+                    "rename" | "hard_rename" => notes.push(format!(
+                        "{}: {} {} has no DataFlow equivalent (there is no rename transformation); rename the field downstream or with a script.",
+                        label, key, value
+                    )),
+                    _ => unmapped.push(format!("{} {}", key, value)),
+                }
+            }
+        }
+        "record_modifier" => {
+            let selected: Vec<String> = filter
+                .all("Allowlist_key")
+                .chain(filter.all("Whitelist_key"))
+                .filter_map(key_path)
+                .collect();
+            if !selected.is_empty() {
+                out.push(json!({ "type": "select", "select": { "fields": selected } }));
+            }
+            for key in filter.all("Remove_key") {
+                match key_path(key) {
+                    Some(p) => removed.push(p),
+                    None => unmapped.push(format!("Remove_key {}", key)),
+                }
+            }
+            for record in filter.all("Record") {
+                unmapped.push(format!("Record {}", record));
+            }
+        }
+        "grep" => {
+            for (key, negate) in [("Regex", false), ("Exclude", true)] {
+                for rule in filter.all(key) {
+                    let condition = rule
+                        .split_once(char::is_whitespace)
+                        .and_then(|(field, re)| {
+                            Some(format!(
+                                "{} {} '{}'",
+                                key_path(field)?,
+                                if negate { "!=" } else { "==" },
+                                literal_regex(re.trim())?
+                            ))
+                        });
+                    match condition {
+                        Some(c) => {
+                            out.push(json!({ "type": "filter", "filter": { "condition": c } }))
+                        }
+                        None => unmapped.push(format!("{} {}", key, rule)),
+                    }
+                }
+            }
+        }
+        "nest" => match (
+            filter.get("Operation"),
+            filter.get("Nested_under").and_then(key_path),
+        ) {
+            (Some("lift"), Some(field)) => {
+                out.push(json!({ "type": "unwrap", "unwrap": { "field": field } }))
+            }
+            _ => unmapped.push("nest (only Operation lift maps, to unwrap)".to_string()),
+        },
+        "parser" => notes.push(format!(
+            "{} was not migrated: set format on the kafka source to parse records.",
+            label
+        )),
+        "lua" => notes.push(format!(
+            "{} was not migrated: put the Lua code in a ConfigMap and use a script transformation.",
+            label
+        )),
+        _ => notes.push(format!(
+            "{} has no DataFlow equivalent; migrate its logic manually.",
+            label
+        )),
+    }
+    if !removed.is_empty() {
+        out.push(json!({ "type": "remove", "remove": { "fields": removed } }));
+    }
+    if !unmapped.is_empty() {
+        notes.push(format!("{}: not migrated: {}.", label, unmapped.join("; ")));
+    }
+    out
+}
+
+/// Migrates a Fluent Bit configuration (classic or YAML) to DataFlow manifests, one per kafka or
+/// elasticsearch output, joined as a multi-document YAML with migration notes as comment headers.
+pub fn migrate_fluentbit_to_dataflow(config_text: &str) -> Result<String, String> {
+    let mut global_notes = Vec::new();
+    let pipeline = match serde_yaml::from_str::<Value>(config_text) {
+        Ok(v) if v["pipeline"].is_object() => parse_yaml(&v),
+        _ => parse_classic(config_text, &mut global_notes),
+    };
+    if pipeline.outputs.is_empty() {
+        return Err(
+            "Expected a Fluent Bit config with [OUTPUT] sections (or pipeline.outputs)".to_string(),
+        );
+    }
+    let tags = input_tags(&pipeline.inputs);
+    let mut docs = Vec::new();
+    let mut skipped = Vec::new();
+    for (n, output) in pipeline.outputs.iter().enumerate() {
+        let mut notes = global_notes.clone();
+        let mut filled = Vec::new();
+        let Some(sink) = map_output(output, &mut filled, &mut notes) else {
+            skipped.push(output.name.clone());
+            continue;
+        };
+        let matched: Vec<usize> = (0..tags.len())
+            .filter(|&i| selects(output, &tags[i]))
+            .collect();
+        if matched.len() > 1 {
+            notes.push(format!(
+                "Output {} matches {} inputs ({}); only '{}' was mapped. Create one DataFlow per input.",
+                output.name,
+                matched.len(),
+                matched.iter().map(|&i| tags[i].as_str()).collect::<Vec<_>>().join(", "),
+                tags[matched[0]]
+            ));
+        }
+        let (source, transformations) = match matched.first() {
+            Some(&i) => {
+                let source = map_input(&pipeline.inputs[i], &mut filled, &mut notes);
+                let mut transformations = Vec::new();
+                for (k, filter) in pipeline
+                    .filters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, f)| selects(f, &tags[i]))
+                {
+                    let label = format!("filter {} ({})", k + 1, filter.name);
+                    transformations.extend(map_filter(filter, &label, &mut notes));
+                }
+                (source, transformations)
+            }
+            None => {
+                notes.push(format!(
+                    "Output {} matches no input; the kafka source is a placeholder.",
+                    output.name
+                ));
+                (
+                    placeholder_kafka(
+                        "spec.source",
+                        "input-topic",
+                        "no input matches the output",
+                        &mut filled,
+                    ),
+                    Vec::new(),
+                )
+            }
+        };
+
+        let name = sanitize_name(output.get("Alias").unwrap_or(&format!(
+            "fluent-bit-{}-{}",
+            output.name,
+            n + 1
+        )));
+        let mut metadata = JsonMap::new();
+        metadata.insert("name".to_string(), Value::String(name));
+        annotate(&mut metadata, &filled);
+        let mut spec = JsonMap::new();
+        spec.insert("source".to_string(), Value::Object(source));
+        spec.insert("sink".to_string(), Value::Object(sink));
+        if !transformations.is_empty() {
+            spec.insert("transformations".to_string(), Value::Array(transformations));
+        }
+        let mut manifest = JsonMap::new();
+        manifest.insert(
+            "apiVersion".to_string(),
+            Value::String(DATAFLOW_API_VERSION.to_string()),
+        );
+        manifest.insert("kind".to_string(), Value::String(DATAFLOW_KIND.to_string()));
+        manifest.insert("metadata".to_string(), Value::Object(metadata));
+        manifest.insert("spec".to_string(), Value::Object(spec));
+        docs.push(render_migrated("Fluent Bit config", &manifest, notes)?);
+    }
+    if docs.is_empty() {
+        return Err(format!(
+            "No kafka or elasticsearch outputs to migrate (found: {})",
+            skipped.join(", ")
+        ));
+    }
+    let mut out = docs.join("---\n");
+    if !skipped.is_empty() {
+        out.insert_str(
+            0,
+            &format!(
+                "# Outputs not migrated (only kafka and elasticsearch are): {}\n",
+                skipped.join(", ")
+            ),
+        );
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_fluentbit_classic() {
+        let config = r#"
+@SET brokers=k1:9092,k2:9092
+[INPUT]
+    Name    kafka
+    Tag     app.logs
+    Brokers ${brokers}
+    Topics  app-logs
+    Format  json
+    group_id fluent-bit
+
+[FILTER]
+    Name   grep
+    Match  app.*
+    Exclude level ^debug$
+
+[FILTER]
+    Name   modify
+    Match  app.*
+    Remove password
+    Remove token
+    Rename msg message
+
+[FILTER]
+    Name   record_modifier
+    Match  other.*
+    Remove_key host
+
+[OUTPUT]
+    Name   es
+    Match  app.*
+    Host   es.internal
+    Port   9200
+    Index  app-logs
+    tls    On
+
+[OUTPUT]
+    Name   stdout
+    Match  *
+"#;
+        let out = migrate_fluentbit_to_dataflow(config).unwrap();
+        assert!(
+            out.starts_with("# Outputs not migrated (only kafka and elasticsearch are): stdout\n")
+        );
+        let doc: serde_yaml::Value = serde_yaml::from_str(out.split_once('\n').unwrap().1).unwrap();
+        assert_eq!(doc["metadata"]["name"], "fluent-bit-es-1");
+        assert_eq!(doc["spec"]["source"]["kafka"]["brokers"][1], "k2:9092");
+        assert_eq!(
+            doc["spec"]["source"]["kafka"]["consumerGroup"],
+            "fluent-bit"
+        );
+        let t = &doc["spec"]["transformations"];
+        assert_eq!(t[0]["filter"]["condition"], "$.level != 'debug'");
+        assert_eq!(
+            t[1]["remove"]["fields"],
+            serde_yaml::from_str::<serde_yaml::Value>("['$.password', '$.token']").unwrap()
+        );
+        assert!(t[2].is_null());
+        assert_eq!(
+            doc["spec"]["sink"]["elasticsearch"]["addresses"][0],
+            "https://es.internal:9200"
+        );
+        assert!(out.contains("Rename msg message has no DataFlow equivalent"));
+        assert!(!out.contains("Validation error"));
+    }
+
+    #[test]
+    fn test_migrate_fluentbit_yaml_and_tags() {
+        let yaml = "pipeline:\n  inputs:\n    - {name: syslog, mode: udp, port: 514, parser: syslog-rfc5424}\n  filters:\n    - {name: record_modifier, match: '*', allowlist_key: [host, message]}\n  outputs:\n    - {name: kafka, match: 'syslog.*', brokers: 'k:9092', topics: logs, alias: to-kafka}\n";
+        let out = migrate_fluentbit_to_dataflow(yaml).unwrap();
+        let doc: serde_yaml::Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(doc["metadata"]["name"], "to-kafka");
+        assert_eq!(doc["spec"]["source"]["syslog"]["address"], "0.0.0.0:514");
+        assert_eq!(doc["spec"]["source"]["syslog"]["parser"], "rfc5424");
+        assert_eq!(
+            doc["spec"]["transformations"][0]["select"]["fields"][1],
+            "$.message"
+        );
+        assert!(tag_matches("kube.*", "kube.var.log") && !tag_matches("kube.*", "app.log"));
+        assert!(migrate_fluentbit_to_dataflow("[OUTPUT]\n    Name stdout\n").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+// Garbage-collection report: DataFlows in a manifest directory or the session workspace vs. DataFlows in the cluster.
+
+use crate::tools::kube::kubectl_json;
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_CRD_NAME, DATAFLOW_KIND};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Namespace assumed for manifests without metadata.namespace.
+const DEFAULT_NAMESPACE: &str = "default";
+
+/// Reads every .yaml / .yml file under dir (recursively) as (path, content). Symlinked directories are skipped so
+/// a link cycle cannot make the walk loop.
+pub fn read_manifest_dir(dir: &str) -> Result<Vec<(String, String)>, String> {
+    let mut out = Vec::new();
+    let mut stack = vec![Path::new(dir).to_path_buf()];
+    while let Some(path) = stack.pop() {
+        let entries = std::fs::read_dir(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let file_type = entry.file_type().map_err(|e| e.to_string())?;
+            let p = entry.path();
+            if file_type.is_dir() {
+                stack.push(p);
+            } else if file_type.is_symlink() && p.is_dir() {
+                continue;
+            } else if matches!(p.extension().and_then(|e| e.to_str()), Some("yaml" | "yml")) {
+                let content =
+                    std::fs::read_to_string(&p).map_err(|e| format!("{}: {}", p.display(), e))?;
+                out.push((p.display().to_string(), content));
+            }
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// "namespace/name" of every DataFlow in the sources, mapped to where it was found.
+fn local_dataflows(
+    sources: &[(String, String)],
+    namespace: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut out = BTreeMap::new();
+    for (origin, content) in sources {
+        for doc in serde_yaml::Deserializer::from_str(content) {
+            let v = Value::deserialize(doc)
+                .map_err(|e| format!("{}: YAML parse error: {}", origin, e))?;
+            if v["kind"].as_str() != Some(DATAFLOW_KIND) {
+                continue;
+            }
+            if let Some(name) = v["metadata"]["name"].as_str() {
+                let ns = v["metadata"]["namespace"].as_str().unwrap_or(namespace);
+                out.insert(format!("{}/{}", ns, name), origin.clone());
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Compares local DataFlows with a DataFlow list from the cluster (kubectl get -o json). confirmed lists
+/// "namespace/name" orphans to generate deletion manifests for (use with kubectl delete -f).
+pub fn gc_report(
+    sources: &[(String, String)],
+    deployed: &Value,
+    namespace: Option<&str>,
+    confirmed: &[String],
+) -> Result<String, String> {
+    let namespace = namespace
+        .filter(|n| !n.is_empty())
+        .unwrap_or(DEFAULT_NAMESPACE);
+    let local = local_dataflows(sources, namespace)?;
+    let in_cluster: BTreeSet<String> = deployed["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let name = item["metadata"]["name"].as_str()?;
+            let ns = item["metadata"]["namespace"]
+                .as_str()
+                .unwrap_or(DEFAULT_NAMESPACE);
+            Some(format!("{}/{}", ns, name))
+        })
+        .collect();
+
+    let deployed_only: Vec<&String> = in_cluster
+        .iter()
+        .filter(|k| !local.contains_key(*k))
+        .collect();
+    let local_only: Vec<Value> = local
+        .iter()
+        .filter(|(k, _)| !in_cluster.contains(*k))
+        .map(|(k, origin)| json!({ "resource": k, "source": origin }))
+        .collect();
+    let mut report = json!({
+        "deployed_not_in_git": deployed_only,
+        "in_git_not_deployed": local_only,
+        "in_sync": local.keys().filter(|k| in_cluster.contains(*k)).count()
+    });
+
+    let mut deletions = Vec::new();
+    let mut rejected = Vec::new();
+    for c in confirmed {
+        let Some((ns, name)) = c.split_once('/').filter(|_| deployed_only.contains(&c)) else {
+            rejected.push(format!(
+                "{}: not a deployed-but-not-in-git orphan, no deletion generated",
+                c
+            ));
+            continue;
+        };
+        let stub = json!({
+            "apiVersion": DATAFLOW_API_VERSION,
+            "kind": DATAFLOW_KIND,
+            "metadata": { "name": name, "namespace": ns }
+        });
+        deletions.push(serde_yaml::to_string(&stub).map_err(|e| e.to_string())?);
+    }
+    if !deletions.is_empty() {
+        report["deletion_manifests"] = Value::String(deletions.join("---\n"));
+    }
+    if !rejected.is_empty() {
+        report["warnings"] = json!(rejected);
+    }
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+/// Lists DataFlows in the cluster (one namespace, or all when namespace is empty) and builds the report.
+pub async fn gc_report_from_cluster(
+    sources: &[(String, String)],
+    namespace: Option<&str>,
+    context: Option<&str>,
+    confirmed: &[String],
+) -> Result<String, String> {
+    let ns = namespace.filter(|n| !n.is_empty());
+    let mut args = vec!["get", DATAFLOW_CRD_NAME];
+    match ns {
+        Some(n) => args.extend(["-n", n]),
+        None => args.push("--all-namespaces"),
+    }
+    let deployed = kubectl_json("Orphan report", &args, context).await?;
+    gc_report(sources, &deployed, ns, confirmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gc_report_finds_orphans_both_ways() {
+        let sources = vec![(
+            "flows/orders.yaml".to_string(),
+            "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata: {name: orders, namespace: streaming}\n---\napiVersion: v1\nkind: Secret\nmetadata: {name: pg}\n---\napiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata: {name: audit, namespace: streaming}\n".to_string(),
+        )];
+        let deployed = json!({ "items": [
+            { "metadata": { "name": "orders", "namespace": "streaming" } },
+            { "metadata": { "name": "manual-test", "namespace": "streaming" } }
+        ] });
+        let confirmed = vec![
+            "streaming/manual-test".to_string(),
+            "streaming/orders".to_string(),
+        ];
+        let report: Value =
+            serde_json::from_str(&gc_report(&sources, &deployed, None, &confirmed).unwrap())
+                .unwrap();
+        assert_eq!(
+            report["deployed_not_in_git"],
+            json!(["streaming/manual-test"])
+        );
+        assert_eq!(
+            report["in_git_not_deployed"][0]["resource"],
+            "streaming/audit"
+        );
+        assert_eq!(
+            report["in_git_not_deployed"][0]["source"],
+            "flows/orders.yaml"
+        );
+        assert_eq!(report["in_sync"], 1);
+        let deletions = report["deletion_manifests"].as_str().unwrap();
+        assert!(deletions.contains("name: manual-test") && !deletions.contains("name: orders"));
+        assert!(report["warnings"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("streaming/orders: not a deployed-but-not-in-git orphan"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_manifest_dir_skips_symlink_cycles() {
+        let dir = std::env::temp_dir().join(format!("dataflow-mcp-gc-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("flows")).unwrap();
+        std::fs::write(dir.join("flows/orders.yaml"), "kind: DataFlow\n").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("flows/loop")).unwrap();
+        let files = read_manifest_dir(&dir.display().to_string()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].0.ends_with("flows/orders.yaml"));
+    }
+}
@@ -0,0 +1,113 @@
+// GitOps companions of generated manifests: an ArgoCD Application that syncs the DataFlow from a Git repository.
+
+use crate::tools::manifest::split_documents;
+use crate::types::DATAFLOW_KIND;
+use serde_json::{json, Value};
+
+/// ArgoCD Application apiVersion and the namespace ArgoCD watches by default.
+pub const ARGOCD_API_VERSION: &str = "argoproj.io/v1alpha1";
+const ARGOCD_NAMESPACE: &str = "argocd";
+
+/// Where the DataFlow manifest lives in Git and where ArgoCD deploys it; unset fields take defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ArgoCdTarget {
+    pub repo_url: String,
+    pub path: Option<String>,
+    pub revision: Option<String>,
+    pub namespace: Option<String>,
+}
+
+/// ArgoCD Application for a flow: source repo_url at revision (default HEAD) and path (default dataflows/<flow>),
+/// destination namespace (default the flow's namespace or default), automated sync with prune and self-heal.
+pub fn argocd_application(
+    flow: &str,
+    flow_namespace: Option<&str>,
+    target: &ArgoCdTarget,
+) -> Value {
+    let path = target
+        .path
+        .clone()
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| format!("dataflows/{}", flow));
+    let namespace = target
+        .namespace
+        .as_deref()
+        .or(flow_namespace)
+        .filter(|n| !n.is_empty())
+        .unwrap_or("default");
+    json!({
+        "apiVersion": ARGOCD_API_VERSION,
+        "kind": "Application",
+        "metadata": { "name": flow, "namespace": ARGOCD_NAMESPACE },
+        "spec": {
+            "project": "default",
+            "source": {
+                "repoURL": target.repo_url,
+                "targetRevision": target.revision.as_deref().filter(|r| !r.is_empty()).unwrap_or("HEAD"),
+                "path": path
+            },
+            "destination": { "server": "https://kubernetes.default.svc", "namespace": namespace },
+            "syncPolicy": { "automated": { "prune": true, "selfHeal": true }, "syncOptions": ["CreateNamespace=true"] }
+        }
+    })
+}
+
+/// Appends an ArgoCD Application document for the first DataFlow of yaml.
+pub fn with_argocd_application(yaml: &str, target: &ArgoCdTarget) -> Result<String, String> {
+    if !target.repo_url.starts_with("https://")
+        && !target.repo_url.starts_with("git@")
+        && !target.repo_url.starts_with("ssh://")
+    {
+        return Err(format!(
+            "argocd_repo_url '{}' must be an https://, ssh:// or git@ Git URL",
+            target.repo_url
+        ));
+    }
+    let flow = split_documents(yaml)
+        .iter()
+        .filter_map(|(_, doc)| serde_yaml::from_str::<Value>(doc).ok())
+        .find(|v| v["kind"] == DATAFLOW_KIND)
+        .ok_or("no DataFlow manifest to point the ArgoCD Application at")?;
+    let name = flow["metadata"]["name"].as_str().unwrap_or("dataflow");
+    let application = argocd_application(name, flow["metadata"]["namespace"].as_str(), target);
+    let doc = serde_yaml::to_string(&application).map_err(|e| e.to_string())?;
+    let path = application["spec"]["source"]["path"].as_str().unwrap_or("");
+    Ok(format!(
+        "{}---\n# ArgoCD Application: commit the DataFlow manifest to {} in {}\n{}",
+        yaml, path, target.repo_url, doc
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_argocd_application() {
+        let yaml = "# Generated DataFlow manifest\napiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\n  namespace: shop\n";
+        let target = ArgoCdTarget {
+            repo_url: "https://git.example.com/flows.git".to_string(),
+            ..Default::default()
+        };
+        let out = with_argocd_application(yaml, &target).unwrap();
+        let docs = split_documents(&out);
+        assert_eq!(docs.len(), 2);
+        let app: Value = serde_yaml::from_str(&docs[1].1).unwrap();
+        assert_eq!(
+            app["spec"]["source"],
+            json!({ "repoURL": "https://git.example.com/flows.git", "targetRevision": "HEAD", "path": "dataflows/orders" })
+        );
+        assert_eq!(app["spec"]["destination"]["namespace"], "shop");
+        assert_eq!(
+            app["metadata"],
+            json!({ "name": "orders", "namespace": "argocd" })
+        );
+        let bad = ArgoCdTarget {
+            repo_url: "flows".to_string(),
+            ..Default::default()
+        };
+        assert!(with_argocd_application(yaml, &bad)
+            .unwrap_err()
+            .contains("must be an https://"));
+    }
+}
@@ -0,0 +1,461 @@
+// Inference of source, sink and transformations from the free-text description of generate_dataflow_manifest
+// ("from kafka topic orders to postgres table orders, mask emails"), with a record of what was inferred.
+
+use crate::tools::reference::default_connectors;
+use serde_json::{json, Map as JsonMap, Value};
+
+/// Words naming a connector: (word, connector type).
+const CONNECTOR_WORDS: [(&str, &str); 12] = [
+    ("kafka", "kafka"),
+    ("redpanda", "kafka"),
+    ("postgresql", "postgresql"),
+    ("postgres", "postgresql"),
+    ("pg", "postgresql"),
+    ("clickhouse", "clickhouse"),
+    ("trino", "trino"),
+    ("syslog", "syslog"),
+    ("elasticsearch", "elasticsearch"),
+    ("elastic", "elasticsearch"),
+    ("opensearch", "elasticsearch"),
+    ("s3", "s3"),
+];
+
+/// Words naming what a connector reads or writes; a bare name after the connector is its default object.
+const OBJECT_WORDS: [&str; 5] = ["topic", "table", "index", "bucket", "port"];
+
+/// Clause verbs and the transformation they describe.
+const VERBS: [(&str, &str); 13] = [
+    ("mask", "mask"),
+    ("hide", "mask"),
+    ("redact", "mask"),
+    ("remove", "remove"),
+    ("drop", "remove"),
+    ("strip", "remove"),
+    ("select", "select"),
+    ("keep", "select"),
+    ("filter", "filter"),
+    ("where", "filter"),
+    ("flatten", "flatten"),
+    ("unwrap", "unwrap"),
+    ("add", "timestamp"),
+];
+
+/// Words skipped in field lists ("mask all the email fields").
+const FILLER_WORDS: [&str; 14] = [
+    "the", "a", "an", "all", "any", "only", "field", "fields", "column", "columns", "value",
+    "values", "of", "and",
+];
+
+/// What a description says about the flow.
+#[derive(Debug, Clone, Default)]
+pub struct Inference {
+    pub source_type: Option<String>,
+    pub sink_type: Option<String>,
+    pub source_config: JsonMap<String, Value>,
+    pub sink_config: JsonMap<String, Value>,
+    pub transformations: Vec<Value>,
+    /// Clauses of the description nothing was inferred from.
+    pub unparsed: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Side {
+    Source,
+    Sink,
+}
+
+/// Parses simple phrases: "from <connector> [topic|table|index|bucket] <name> [at <brokers or URL>]",
+/// "to|into <connector> ...", and comma-separated transformation clauses ("mask emails and phones",
+/// "remove password", "keep only id, name", "filter where level = error", "flatten items", "add timestamp",
+/// "convert to snake case").
+pub fn infer_from_description(description: &str) -> Inference {
+    let mut inference = Inference::default();
+    let mut last_list: Option<usize> = None;
+    for clause in clauses(description) {
+        let words: Vec<&str> = clause.split_whitespace().collect();
+        let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+        let mut understood = false;
+        let mut i = 0;
+        while i < words.len() {
+            let side = match lower[i].as_str() {
+                "from" => Side::Source,
+                "to" | "into" => Side::Sink,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            let mut j = i + 1;
+            while j < words.len() && ["the", "a", "an"].contains(&lower[j].as_str()) {
+                j += 1;
+            }
+            let Some((_, connector)) = lower
+                .get(j)
+                .and_then(|w| CONNECTOR_WORDS.iter().find(|(word, _)| word == w))
+            else {
+                i += 1;
+                continue;
+            };
+            understood = true;
+            i = connector_phrase(&mut inference, side, connector, &words, &lower, j + 1);
+        }
+        if !understood {
+            understood = transformation_clause(&mut inference, &words, &lower, &mut last_list);
+        } else {
+            last_list = None;
+        }
+        if !understood && !clause.trim().is_empty() {
+            inference.unparsed.push(clause.trim().to_string());
+        }
+    }
+    inference
+}
+
+/// Splits on commas, semicolons, "then" and "and" before a verb ("... orders and mask emails").
+/// Commas inside a word ("k1:9092,k2:9092", "email,phone") do not split.
+fn clauses(description: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let normalized = format!("{} ", description)
+        .replace(", ", "; ")
+        .replace(",\n", ";\n");
+    for part in normalized.split([';', '\n']) {
+        let mut current: Vec<&str> = Vec::new();
+        let words: Vec<&str> = part.split_whitespace().collect();
+        for (k, w) in words.iter().enumerate() {
+            let next_is_verb = words
+                .get(k + 1)
+                .is_some_and(|n| verb(&n.to_lowercase()).is_some() || is_case_phrase(n));
+            let split =
+                w.eq_ignore_ascii_case("then") || (w.eq_ignore_ascii_case("and") && next_is_verb);
+            if split {
+                if !current.is_empty() {
+                    out.push(current.join(" "));
+                }
+                current.clear();
+            } else {
+                current.push(w.trim_end_matches('.'));
+            }
+        }
+        if !current.is_empty() {
+            out.push(current.join(" "));
+        }
+    }
+    out
+}
+
+fn verb(word: &str) -> Option<&'static str> {
+    VERBS.iter().find(|(v, _)| *v == word).map(|(_, t)| *t)
+}
+
+fn is_case_phrase(word: &str) -> bool {
+    let w = word.to_lowercase();
+    w == "convert" || w.starts_with("snake") || w.starts_with("camel")
+}
+
+/// Catalog fields of a connector on one side (required and optional).
+fn connector_fields(side: Side, connector: &str) -> Vec<String> {
+    let catalog = default_connectors();
+    let entry = &catalog[if side == Side::Source {
+        "sources"
+    } else {
+        "sinks"
+    }][connector];
+    ["required_fields", "optional_fields"]
+        .iter()
+        .flat_map(|k| entry[*k].as_array().cloned().unwrap_or_default())
+        .filter_map(|f| f.as_str().map(str::to_string))
+        .collect()
+}
+
+fn default_object(connector: &str) -> &'static str {
+    match connector {
+        "kafka" => "topic",
+        "elasticsearch" => "index",
+        "s3" => "bucket",
+        "syslog" => "port",
+        _ => "table",
+    }
+}
+
+/// Reads "[object] <name>" and "at|on <address>" pairs after a connector word; returns the index after the phrase.
+fn connector_phrase(
+    inference: &mut Inference,
+    side: Side,
+    connector: &str,
+    words: &[&str],
+    lower: &[String],
+    mut i: usize,
+) -> usize {
+    let (type_slot, config) = match side {
+        Side::Source => (&mut inference.source_type, &mut inference.source_config),
+        Side::Sink => (&mut inference.sink_type, &mut inference.sink_config),
+    };
+    // The first phrase per side wins ("from kafka ... to postgres ... to s3" keeps postgres).
+    if type_slot.as_deref().is_some_and(|t| t != connector) {
+        return skip_phrase(words, lower, i);
+    }
+    *type_slot = Some(connector.to_string());
+    let fields = connector_fields(side, connector);
+    let mut set = |field: &str, value: Value| {
+        if fields.iter().any(|f| f == field) && !config.contains_key(field) {
+            config.insert(field.to_string(), value);
+        }
+    };
+    while i < words.len() {
+        let w = lower[i].as_str();
+        if ["from", "to", "into"].contains(&w) || verb(w).is_some() {
+            break;
+        }
+        let (object, value) = if OBJECT_WORDS.contains(&w) {
+            (w, words.get(i + 1).copied())
+        } else if (w == "at" || w == "on") && i + 1 < words.len() {
+            ("address", words.get(i + 1).copied())
+        } else if i == words.len() - 1
+            || ["at", "on", "to", "into", "from"].contains(&lower[i + 1].as_str())
+        {
+            (default_object(connector), Some(words[i]))
+        } else {
+            break;
+        };
+        let Some(value) = value else { break };
+        match (object, connector) {
+            ("address", _) if value.contains("://") => {
+                let field = if connector == "trino" {
+                    "serverURL"
+                } else {
+                    "connectionString"
+                };
+                set(field, json!(value));
+            }
+            ("address", "kafka") => {
+                let brokers: Vec<&str> = value.split(',').filter(|b| !b.is_empty()).collect();
+                set("brokers", json!(brokers));
+            }
+            ("port", "syslog") => set("address", json!(format!("0.0.0.0:{}", value))),
+            ("table", "trino") => {
+                // catalog.schema.table
+                let parts: Vec<&str> = value.split('.').collect();
+                if let [catalog, schema, table] = parts.as_slice() {
+                    set("catalog", json!(catalog));
+                    set("schema", json!(schema));
+                    set("table", json!(table));
+                } else {
+                    set("table", json!(value));
+                }
+            }
+            ("topic", "kafka") | ("table", _) | ("index", _) | ("bucket", _) => {
+                set(object, json!(value))
+            }
+            _ => {}
+        }
+        i += if OBJECT_WORDS.contains(&w) || w == "at" || w == "on" {
+            2
+        } else {
+            1
+        };
+    }
+    i
+}
+
+/// Index after a connector phrase that is not used.
+fn skip_phrase(words: &[&str], lower: &[String], mut i: usize) -> usize {
+    while i < words.len()
+        && !["from", "to", "into"].contains(&lower[i].as_str())
+        && verb(&lower[i]).is_none()
+    {
+        i += 1;
+    }
+    i
+}
+
+/// "emails" -> "email", "categories" -> "category"; words ending in -ss, -us or -is are kept.
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies").filter(|s| s.len() > 1) {
+        return format!("{}y", stem);
+    }
+    if word.len() > 3
+        && word.ends_with('s')
+        && !["ss", "us", "is"].iter().any(|e| word.ends_with(e))
+    {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
+
+/// JSONPath fields of a list, one per word ("emails and phones" -> $.email, $.phone).
+fn field_list(words: &[&str]) -> Vec<String> {
+    words
+        .iter()
+        .flat_map(|w| w.split(','))
+        .map(|w| {
+            w.trim_matches(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '$'))
+        })
+        .filter(|w| !w.is_empty() && !FILLER_WORDS.contains(&w.to_lowercase().as_str()))
+        .map(|w| match w.strip_prefix("$.") {
+            Some(_) => w.to_string(),
+            None => format!("$.{}", singular(w)),
+        })
+        .collect()
+}
+
+/// filter condition from "<field> <op> <value>" (=, ==, !=, is, is not, equals, >, >=, <, <=).
+fn condition(words: &[&str]) -> Option<String> {
+    let words: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|w| !["where", "when", "if", "only"].contains(&w.to_lowercase().as_str()))
+        .collect();
+    let (field, rest) = words.split_first()?;
+    let (op, value) = match rest {
+        [w, "not", value] if w.eq_ignore_ascii_case("is") => ("!=", *value),
+        [op, value] => {
+            let op = match op.to_lowercase().as_str() {
+                "=" | "==" | "is" | "equals" => "==",
+                "!=" => "!=",
+                o @ (">" | ">=" | "<" | "<=") => return Some(render_condition(field, o, value)),
+                _ => return None,
+            };
+            (op, *value)
+        }
+        _ => return None,
+    };
+    Some(render_condition(field, op, value))
+}
+
+fn render_condition(field: &str, op: &str, value: &str) -> String {
+    let value = value.trim_matches(|c| c == '\'' || c == '"');
+    let literal = if value.parse::<f64>().is_ok() || value == "true" || value == "false" {
+        value.to_string()
+    } else {
+        format!("'{}'", value)
+    };
+    format!("$.{} {} {}", field.trim_start_matches("$."), op, literal)
+}
+
+/// Adds the transformation a clause describes; a clause without a verb continues the previous field list
+/// ("mask email, phone"). Returns whether the clause was understood.
+fn transformation_clause(
+    inference: &mut Inference,
+    words: &[&str],
+    lower: &[String],
+    last_list: &mut Option<usize>,
+) -> bool {
+    let Some(first) = lower.first() else {
+        return false;
+    };
+    let joined = lower.join(" ");
+    let (kind, config) = if joined.contains("snake") {
+        ("snakeCase", json!({ "deep": true }))
+    } else if joined.contains("camel") {
+        ("camelCase", json!({ "deep": true }))
+    } else {
+        match verb(first) {
+            Some(kind @ ("mask" | "remove" | "select")) => {
+                let fields = field_list(&words[1..]);
+                if fields.is_empty() {
+                    return false;
+                }
+                (kind, json!({ "fields": fields }))
+            }
+            Some("filter") => match condition(&words[1..]) {
+                Some(c) => ("filter", json!({ "condition": c })),
+                None => return false,
+            },
+            Some(kind @ ("flatten" | "unwrap")) => match field_list(&words[1..]).first() {
+                Some(field) => (kind, json!({ "field": field })),
+                None => return false,
+            },
+            Some("timestamp") if joined.contains("timestamp") => {
+                let name = field_list(&words[1..])
+                    .into_iter()
+                    .map(|f| f[2..].to_string())
+                    .find(|f| f != "timestamp");
+                (
+                    "timestamp",
+                    json!({ "fieldName": name.unwrap_or_else(|| "created_at".to_string()) }),
+                )
+            }
+            _ => {
+                // "mask email, phone": the words after a comma continue the previous list.
+                let Some(index) = *last_list else {
+                    return false;
+                };
+                let fields = field_list(words);
+                let pointer = format!(
+                    "/{}/fields",
+                    inference.transformations[index]["type"]
+                        .as_str()
+                        .unwrap_or("")
+                );
+                let Some(list) = inference.transformations[index]
+                    .pointer_mut(&pointer)
+                    .and_then(Value::as_array_mut)
+                else {
+                    return false;
+                };
+                list.extend(fields.iter().map(|f| json!(f)));
+                return !fields.is_empty();
+            }
+        }
+    };
+    let index = inference.transformations.len();
+    *last_list = config.get("fields").map(|_| index);
+    inference
+        .transformations
+        .push(json!({ "type": kind, kind: config }));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_source_sink_and_transformations() {
+        let i = infer_from_description("From Kafka topic orders at k1:9092,k2:9092 to postgres table orders, mask emails and phones, remove password");
+        assert_eq!(i.source_type.as_deref(), Some("kafka"));
+        assert_eq!(i.sink_type.as_deref(), Some("postgresql"));
+        assert_eq!(i.source_config["topic"], "orders");
+        assert_eq!(i.source_config["brokers"], json!(["k1:9092", "k2:9092"]));
+        assert_eq!(i.sink_config["table"], "orders");
+        assert_eq!(
+            i.transformations,
+            vec![
+                json!({ "type": "mask", "mask": { "fields": ["$.email", "$.phone"] } }),
+                json!({ "type": "remove", "remove": { "fields": ["$.password"] } }),
+            ]
+        );
+        assert!(i.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_infer_filters_lists_and_unparsed_clauses() {
+        let i = infer_from_description(
+            "from syslog port 514 into trino table hive.logs.events; keep only host, message; filter where severity <= 3 then convert to snake case, make it fast",
+        );
+        assert_eq!(i.source_config["address"], "0.0.0.0:514");
+        assert_eq!(i.sink_type.as_deref(), Some("trino"));
+        assert_eq!(
+            (
+                i.sink_config["catalog"].as_str(),
+                i.sink_config["table"].as_str()
+            ),
+            (Some("hive"), Some("events"))
+        );
+        assert_eq!(i.source_type.as_deref(), Some("syslog"));
+        assert_eq!(
+            i.transformations[0],
+            json!({ "type": "select", "select": { "fields": ["$.host", "$.message"] } })
+        );
+        assert_eq!(
+            i.transformations[1],
+            json!({ "type": "filter", "filter": { "condition": "$.severity <= 3" } })
+        );
+        assert_eq!(i.transformations[2]["type"], "snakeCase");
+        assert_eq!(i.unparsed, vec!["make it fast"]);
+        assert_eq!(
+            condition(&["status", "is", "not", "deleted"]).as_deref(),
+            Some("$.status != 'deleted'")
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Tool inputs given by file path or URL instead of inline text, restricted to the configured allow-lists.
+
+use crate::config::Config;
+use reqwest::Url;
+use std::path::{Path, PathBuf};
+
+/// Whether path lies under one of the allowed directories (both resolved, so ".." and symlinks cannot escape).
+fn path_allowed(config: &Config, path: &Path) -> bool {
+    config
+        .allowed_paths
+        .iter()
+        .filter_map(|root| std::fs::canonicalize(root).ok())
+        .any(|root| path.starts_with(root))
+}
+
+/// Whether url matches an allowed prefix: same scheme, host and port, and a path at or below the prefix path.
+fn url_allowed(config: &Config, url: &Url) -> bool {
+    config
+        .allowed_urls
+        .iter()
+        .filter_map(|a| Url::parse(a).ok())
+        .any(|allowed| {
+            let prefix = allowed.path().trim_end_matches('/');
+            allowed.scheme() == url.scheme()
+                && allowed.host_str() == url.host_str()
+                && allowed.port_or_known_default() == url.port_or_known_default()
+                && (url.path() == prefix || url.path().starts_with(&format!("{}/", prefix)))
+        })
+}
+
+/// Resolves a file or directory path and checks that it lies under an allowed directory.
+pub fn allowed_path(config: &Config, path: &str) -> Result<PathBuf, String> {
+    if config.allowed_paths.is_empty() {
+        return Err("path inputs are disabled; allow directories with DATAFLOW_MCP_ALLOWED_PATHS or --allow-path".to_string());
+    }
+    let resolved = std::fs::canonicalize(path).map_err(|e| format!("{}: {}", path, e))?;
+    if !path_allowed(config, &resolved) {
+        return Err(format!(
+            "{} is outside the allowed directories ({})",
+            path,
+            config.allowed_paths.join(", ")
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Reads a file under an allowed directory.
+pub fn read_path(config: &Config, path: &str) -> Result<String, String> {
+    let resolved = allowed_path(config, path)?;
+    std::fs::read_to_string(&resolved).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Fetches a URL matching an allowed prefix. Redirects are followed only while every hop matches one too.
+pub async fn fetch_url(config: &Config, url: &str) -> Result<String, String> {
+    config.ensure_online("Reading input from a URL")?;
+    if config.allowed_urls.is_empty() {
+        return Err("url inputs are disabled; allow URL prefixes with DATAFLOW_MCP_ALLOWED_URLS or --allow-url".to_string());
+    }
+    let parsed = Url::parse(url).map_err(|e| format!("{}: invalid URL: {}", url, e))?;
+    if !url_allowed(config, &parsed) {
+        return Err(format!(
+            "{} does not match an allowed URL prefix ({})",
+            url,
+            config.allowed_urls.join(", ")
+        ));
+    }
+    let hops = config.clone();
+    let redirects = reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() < 10 && url_allowed(&hops, attempt.url()) {
+            attempt.follow()
+        } else {
+            attempt.stop()
+        }
+    });
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .redirect(redirects)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let resp = client
+        .get(parsed)
+        .send()
+        .await
+        .map_err(|e| format!("GET {} failed: {}", url, e))?;
+    if resp.status().is_redirection() {
+        let location = resp
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|l| l.to_str().ok())
+            .unwrap_or("");
+        return Err(format!(
+            "GET {} redirected to {}, which does not match an allowed URL prefix",
+            url, location
+        ));
+    }
+    if !resp.status().is_success() {
+        return Err(format!("GET {} returned {}", url, resp.status()));
+    }
+    resp.text().await.map_err(|e| format!("GET {}: {}", url, e))
+}
+
+/// The tool input: inline text (field), or the content of path or url. Exactly one must be given.
+pub async fn resolve_input(
+    field: &str,
+    inline: &str,
+    path: Option<&str>,
+    url: Option<&str>,
+) -> Result<String, String> {
+    let path = path.filter(|p| !p.is_empty());
+    let url = url.filter(|u| !u.is_empty());
+    let given = usize::from(!inline.trim().is_empty())
+        + usize::from(path.is_some())
+        + usize::from(url.is_some());
+    if given > 1 {
+        return Err(format!("{}, path and url are mutually exclusive", field));
+    }
+    let config = crate::config::current();
+    match (path, url) {
+        (Some(p), _) => read_path(config, p),
+        (_, Some(u)) => fetch_url(config, u).await,
+        _ if given == 0 => Err(format!("{} is required (or give path or url)", field)),
+        _ => Ok(inline.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_path_respects_allow_list() {
+        let dir = std::env::temp_dir().join(format!("dataflow-mcp-input-{}", std::process::id()));
+        let allowed = dir.join("manifests");
+        std::fs::create_dir_all(&allowed).unwrap();
+        std::fs::write(allowed.join("flow.yaml"), "kind: DataFlow\n").unwrap();
+        std::fs::write(dir.join("secret.yaml"), "x").unwrap();
+        let config = Config {
+            allowed_paths: vec![allowed.display().to_string()],
+            ..Config::default()
+        };
+        let inside = allowed.join("flow.yaml").display().to_string();
+        assert_eq!(read_path(&config, &inside).unwrap(), "kind: DataFlow\n");
+        let escape = allowed.join("../secret.yaml").display().to_string();
+        assert!(read_path(&config, &escape)
+            .unwrap_err()
+            .contains("outside the allowed directories"));
+        assert!(read_path(&Config::default(), &inside)
+            .unwrap_err()
+            .starts_with("path inputs are disabled"));
+        assert_eq!(
+            allowed_path(&config, &allowed.display().to_string()).unwrap(),
+            std::fs::canonicalize(&allowed).unwrap()
+        );
+        assert!(allowed_path(&config, &dir.display().to_string())
+            .unwrap_err()
+            .contains("outside the allowed directories"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_url_allowed_prefix() {
+        let config = Config {
+            allowed_urls: vec!["https://git.example.com/raw/".to_string()],
+            ..Config::default()
+        };
+        let ok = |u: &str| url_allowed(&config, &Url::parse(u).unwrap());
+        assert!(ok("https://git.example.com/raw/team/flow.yaml"));
+        assert!(!ok("https://git.example.com/rawx/flow.yaml"));
+        assert!(!ok("https://git.example.com.evil.io/raw/flow.yaml"));
+        assert!(!ok("http://git.example.com/raw/flow.yaml"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_rejects_redirect_outside_allow_list() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut sock, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let n = sock.read(&mut buf).await.unwrap();
+                let req = String::from_utf8_lossy(&buf[..n]).to_string();
+                let resp = if req.starts_with("GET /raw/moved.yaml") {
+                    "HTTP/1.1 302 Found\r\nlocation: /raw/flow.yaml\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string()
+                } else if req.starts_with("GET /raw/") {
+                    "HTTP/1.1 200 OK\r\ncontent-length: 14\r\nconnection: close\r\n\r\nkind: DataFlow".to_string()
+                } else {
+                    "HTTP/1.1 302 Found\r\nlocation: http://169.254.169.254/latest/meta-data\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string()
+                };
+                sock.write_all(resp.as_bytes()).await.unwrap();
+            }
+        });
+        let config = Config {
+            allowed_urls: vec![
+                format!("http://{}/raw/", addr),
+                format!("http://{}/open/", addr),
+            ],
+            ..Config::default()
+        };
+        assert_eq!(
+            fetch_url(&config, &format!("http://{}/raw/moved.yaml", addr))
+                .await
+                .unwrap(),
+            "kind: DataFlow"
+        );
+        let err = fetch_url(&config, &format!("http://{}/open/flow.yaml", addr))
+            .await
+            .unwrap_err();
+        assert!(err.ends_with("redirected to http://169.254.169.254/latest/meta-data, which does not match an allowed URL prefix"));
+    }
+}
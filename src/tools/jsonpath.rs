@@ -0,0 +1,275 @@
+// JSONPath syntax checks for field references in manifests ($.a.b, $.items[0], $..id, $['x'], [?(@.a > 1)]).
+
+/// Checks that expr is a syntactically valid JSONPath starting at the root ($).
+pub fn check_jsonpath(expr: &str) -> Result<(), String> {
+    let chars: Vec<char> = expr.chars().collect();
+    if chars.first() != Some(&'$') {
+        return Err("must start with '$'".to_string());
+    }
+    let mut i = 1;
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                if chars.get(i) == Some(&'.') {
+                    i += 1;
+                }
+                match chars.get(i) {
+                    Some('*') => i += 1,
+                    Some('[') => {}
+                    Some(c) if is_name_char(*c) => {
+                        while i < chars.len() && is_name_char(chars[i]) {
+                            i += 1;
+                        }
+                    }
+                    Some(c) => return Err(format!("unexpected '{}' at position {}", c, i)),
+                    None => return Err("ends with '.'".to_string()),
+                }
+            }
+            '[' => {
+                let close = find_bracket_end(&chars, i)?;
+                check_bracket(&chars[i + 1..close].iter().collect::<String>(), i)?;
+                i = close + 1;
+            }
+            c => return Err(format!("unexpected '{}' at position {}", c, i)),
+        }
+    }
+    Ok(())
+}
+
+/// Checks a filter / router condition: JSONPath operands compared with ==, !=, <, <=, >, >= against
+/// quoted strings, numbers, true/false/null or other paths, combined with &&, ||, ! and parentheses.
+/// A bare path is a truthiness test.
+pub fn check_condition(expr: &str) -> Result<(), String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut i = 0;
+    let mut depth = 0usize;
+    // Whether the next token must be an operand (path, literal, '(' or '!').
+    let mut want_operand = true;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        if want_operand {
+            match c {
+                '(' => {
+                    depth += 1;
+                    i += 1;
+                }
+                '!' if two != "!=" => i += 1,
+                '$' => {
+                    let start = i;
+                    while i < chars.len()
+                        && !chars[i].is_whitespace()
+                        && !"=!<>&|()".contains(chars[i])
+                    {
+                        if chars[i] == '[' {
+                            i = find_bracket_end(&chars, i)?;
+                        }
+                        i += 1;
+                    }
+                    let path: String = chars[start..i].iter().collect();
+                    check_jsonpath(&path).map_err(|e| format!("'{}' {}", path, e))?;
+                    want_operand = false;
+                }
+                '\'' | '"' => {
+                    let end = chars[i + 1..]
+                        .iter()
+                        .position(|&q| q == c)
+                        .ok_or(format!("unclosed string at position {}", i))?;
+                    i += end + 2;
+                    want_operand = false;
+                }
+                _ => {
+                    let start = i;
+                    while i < chars.len()
+                        && (chars[i].is_alphanumeric() || ".-+_".contains(chars[i]))
+                    {
+                        i += 1;
+                    }
+                    let word: String = chars[start..i].iter().collect();
+                    if word.is_empty() {
+                        return Err(format!("unexpected '{}' at position {}", c, start));
+                    }
+                    if word.parse::<f64>().is_err()
+                        && !matches!(word.as_str(), "true" | "false" | "null")
+                    {
+                        return Err(format!(
+                            "unexpected '{}' at position {} (quote string literals)",
+                            word, start
+                        ));
+                    }
+                    want_operand = false;
+                }
+            }
+        } else {
+            match two.as_str() {
+                "==" | "!=" | "<=" | ">=" | "&&" | "||" => i += 2,
+                _ if c == '<' || c == '>' => i += 1,
+                _ if c == ')' => {
+                    if depth == 0 {
+                        return Err(format!("unbalanced ')' at position {}", i));
+                    }
+                    depth -= 1;
+                    i += 1;
+                    continue;
+                }
+                _ => {
+                    return Err(format!(
+                        "expected an operator at position {}, found '{}'",
+                        i, c
+                    ))
+                }
+            }
+            want_operand = true;
+        }
+    }
+    if want_operand {
+        return Err("incomplete expression".to_string());
+    }
+    if depth > 0 {
+        return Err("unclosed '('".to_string());
+    }
+    Ok(())
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Index of the ']' closing the '[' at open, honouring quotes and nested brackets in filters.
+fn find_bracket_end(chars: &[char], open: usize) -> Result<usize, String> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (j, &c) in chars.iter().enumerate().skip(open) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(j);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(format!("unclosed '[' at position {}", open))
+}
+
+/// Bracket contents: '*', a quoted name, an index, a slice, an index union or a filter ?(...).
+fn check_bracket(inner: &str, pos: usize) -> Result<(), String> {
+    let inner = inner.trim();
+    if inner == "*" {
+        return Ok(());
+    }
+    if inner.len() >= 2
+        && (inner.starts_with('\'') && inner.ends_with('\'')
+            || inner.starts_with('"') && inner.ends_with('"'))
+    {
+        return Ok(());
+    }
+    if let Some(filter) = inner.strip_prefix('?') {
+        let filter = filter.trim();
+        if filter.len() < 2 || !filter.starts_with('(') || !filter.ends_with(')') {
+            return Err(format!("filter at position {} must be ?(...)", pos));
+        }
+        if filter[1..filter.len() - 1].trim().is_empty() {
+            return Err(format!("empty filter at position {}", pos));
+        }
+        return Ok(());
+    }
+    let is_int = |s: &str| {
+        let s = s.trim();
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    };
+    if inner.contains(':') {
+        let parts: Vec<&str> = inner.split(':').collect();
+        if parts.len() <= 3 && parts.iter().all(|p| p.trim().is_empty() || is_int(p)) {
+            return Ok(());
+        }
+        return Err(format!("invalid slice '[{}]' at position {}", inner, pos));
+    }
+    if !inner.is_empty() && inner.split(',').all(is_int) {
+        return Ok(());
+    }
+    Err(format!(
+        "invalid selector '[{}]' at position {}",
+        inner, pos
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_jsonpath_valid() {
+        for expr in [
+            "$",
+            "$.id",
+            "$.user.first_name",
+            "$.items[0]",
+            "$.items[*].sku",
+            "$..id",
+            "$['odd key']",
+            "$.items[-1:]",
+            "$.items[0,2]",
+            "$.items[?(@.price > 10)]",
+        ] {
+            assert!(check_jsonpath(expr).is_ok(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_check_jsonpath_invalid() {
+        assert!(check_jsonpath("id")
+            .unwrap_err()
+            .contains("must start with '$'"));
+        assert!(check_jsonpath("$.items[")
+            .unwrap_err()
+            .contains("unclosed '['"));
+        assert!(check_jsonpath("$.a.").is_err());
+        assert!(check_jsonpath("$.items[abc]")
+            .unwrap_err()
+            .contains("invalid selector"));
+        assert!(check_jsonpath("$.items[?@.a]")
+            .unwrap_err()
+            .contains("must be ?(...)"));
+    }
+
+    #[test]
+    fn test_check_condition() {
+        for expr in [
+            "$.level != 'debug'",
+            "$.active",
+            "!($.a == 1 || $.b >= 2.5) && $.c == null",
+            "$.items[?(@.qty > 1)] != null",
+            "$['odd key'] == \"x y\"",
+            "$.total < $.limit",
+        ] {
+            assert!(check_condition(expr).is_ok(), "{}", expr);
+        }
+        assert!(check_condition("$.items[ == 1")
+            .unwrap_err()
+            .contains("unclosed '['"));
+        assert!(check_condition("$.level == debug")
+            .unwrap_err()
+            .contains("quote string literals"));
+        assert!(check_condition("$.a ==")
+            .unwrap_err()
+            .contains("incomplete"));
+        assert!(check_condition("($.a == 1")
+            .unwrap_err()
+            .contains("unclosed '('"));
+        assert!(check_condition("$.a 1")
+            .unwrap_err()
+            .contains("expected an operator"));
+    }
+}
@@ -0,0 +1,374 @@
+// Kafka Streams migration assistant: parses a Topology#describe() dump, finds source/sink topics and the
+// operators between them, and emits skeleton DataFlow manifests with notes on what needs manual work.
+
+use crate::tools::migration::{render_migrated, sanitize_name};
+use crate::tools::provenance::{annotate, or_filled, GenerationNote};
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_KIND};
+use regex::Regex;
+use serde_json::{json, Map as JsonMap, Value};
+use std::collections::BTreeMap;
+
+/// Operators (name without KSTREAM-/KTABLE- prefix and counter) that keep state or need a store.
+const STATEFUL_OPERATORS: &[&str] = &[
+    "AGGREGATE",
+    "REDUCE",
+    "COUNT",
+    "JOIN",
+    "JOINTHIS",
+    "JOINOTHER",
+    "OUTERTHIS",
+    "OUTEROTHER",
+    "OUTERSHARED",
+    "LEFTTHIS",
+    "LEFTOTHER",
+    "MERGE-JOIN",
+    "WINDOWED",
+    "TOTABLE",
+    "SUPPRESS",
+    "REPARTITION",
+    "SOURCE",
+    "SELECT",
+    "FK-JOIN",
+    "TOSTREAM",
+];
+
+/// Stateless operators whose code cannot be read from the topology: hint for the manual rewrite.
+const OPERATOR_HINTS: &[(&str, &str)] = &[
+    ("MAPVALUES", "rewrite the value mapper with select/remove/flatten or a script transformation"),
+    ("MAP", "rewrite the mapper with select/remove/flatten or a script transformation; key changes are not supported"),
+    ("FLATMAPVALUES", "use unwrap/flatten if the mapper splits an array field, otherwise a script transformation"),
+    ("FLATMAP", "use unwrap/flatten if the mapper splits an array field, otherwise a script transformation"),
+    ("TRANSFORMVALUES", "rewrite the transformer with a script transformation"),
+    ("TRANSFORM", "rewrite the transformer with a script transformation"),
+    ("PROCESSVALUES", "rewrite the processor with a script transformation"),
+    ("PROCESSOR", "rewrite the processor with a script transformation"),
+    ("KEY-SELECT", "re-keying is not supported; records keep their key"),
+    ("PEEK", "side effects are dropped"),
+    ("FOREACH", "side effects are dropped"),
+    ("PRINTER", "print() is dropped"),
+    ("MERGE", "each merged input gets its own DataFlow"),
+];
+
+#[derive(Debug, Default)]
+struct Node {
+    kind: String,
+    sub_topology: String,
+    /// Source topics, or the topic pattern for pattern subscriptions.
+    topics: Vec<String>,
+    pattern: Option<String>,
+    /// Sink topic; None for a TopicNameExtractor.
+    sink_topic: Option<String>,
+    stores: Vec<String>,
+    next: Vec<String>,
+}
+
+/// Operator name without the KSTREAM- / KTABLE- prefix and the -0000000003 counter.
+fn operator(name: &str) -> Option<&str> {
+    let rest = name
+        .strip_prefix("KSTREAM-")
+        .or_else(|| name.strip_prefix("KTABLE-"))?;
+    Some(
+        rest.trim_end_matches(|c: char| c.is_ascii_digit())
+            .trim_end_matches('-'),
+    )
+}
+
+fn list(s: &str) -> Vec<String> {
+    s.trim_matches(|c| c == '[' || c == ']')
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn parse(description: &str) -> Result<(BTreeMap<String, Node>, Vec<String>), String> {
+    let sub_re = Regex::new(r"^\s*Sub-topology:\s*(\S+)(.*)$").map_err(|e| e.to_string())?;
+    let node_re = Regex::new(r"^\s*(Source|Processor|Sink):\s*(\S+)\s*\((.*)\)\s*$")
+        .map_err(|e| e.to_string())?;
+    let mut nodes: BTreeMap<String, Node> = BTreeMap::new();
+    let mut notes = Vec::new();
+    let mut sub = String::new();
+    let mut current: Option<String> = None;
+    for line in description.lines() {
+        if let Some(c) = sub_re.captures(line) {
+            sub = c[1].to_string();
+            if c[2].contains("global store") {
+                notes.push(format!("Sub-topology {} maintains a global store (GlobalKTable); it cannot be migrated automatically.", sub));
+            }
+            current = None;
+        } else if let Some(c) = node_re.captures(line) {
+            let mut node = Node {
+                kind: c[1].to_string(),
+                sub_topology: sub.clone(),
+                ..Default::default()
+            };
+            let details = &c[3];
+            if let Some(t) = details.strip_prefix("topics: ") {
+                node.topics = list(t);
+            } else if let Some(p) = details.strip_prefix("topic pattern: ") {
+                node.pattern = Some(p.trim().to_string());
+            } else if let Some(t) = details.strip_prefix("topic: ") {
+                node.sink_topic = Some(t.trim().to_string());
+            } else if let Some(s) = details.strip_prefix("stores: ") {
+                node.stores = list(s);
+            }
+            current = Some(c[2].to_string());
+            nodes.insert(c[2].to_string(), node);
+        } else if let Some(targets) = line.trim().strip_prefix("-->") {
+            if let Some(node) = current.as_ref().and_then(|n| nodes.get_mut(n)) {
+                node.next = list(targets).into_iter().filter(|t| t != "none").collect();
+            }
+        }
+    }
+    if !nodes.values().any(|n| n.kind == "Source") {
+        return Err(
+            "Expected a Kafka Streams Topology#describe() output with at least one Source node"
+                .to_string(),
+        );
+    }
+    Ok((nodes, notes))
+}
+
+/// All source-to-sink paths as node name lists.
+fn paths(nodes: &BTreeMap<String, Node>) -> Vec<Vec<String>> {
+    fn walk(nodes: &BTreeMap<String, Node>, path: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+        let last = &nodes[path.last().expect("non-empty path")];
+        if last.kind == "Sink" {
+            out.push(path.clone());
+            return;
+        }
+        for next in &last.next {
+            if nodes.contains_key(next) && !path.contains(next) {
+                path.push(next.clone());
+                walk(nodes, path, out);
+                path.pop();
+            }
+        }
+    }
+    let mut out = Vec::new();
+    for (name, node) in nodes {
+        if node.kind == "Source" {
+            walk(nodes, &mut vec![name.clone()], &mut out);
+        }
+    }
+    out
+}
+
+fn is_internal(topic: &str) -> bool {
+    topic.ends_with("-repartition") || topic.ends_with("-changelog")
+}
+
+/// Generates skeleton DataFlow manifests from a Kafka Streams Topology#describe() dump, one per source-to-sink
+/// path. Filters and branches become filter transformations with placeholder conditions; stateful operators
+/// (aggregations, joins, windows, stores) are listed in notes, as they cannot be migrated automatically.
+pub fn migrate_kafka_streams_topology(
+    description: &str,
+    bootstrap_servers: Option<&str>,
+) -> Result<String, String> {
+    let (nodes, global_notes) = parse(description)?;
+    let paths = paths(&nodes);
+    if paths.is_empty() {
+        return Err("No path from a Source to a Sink node found in the topology".to_string());
+    }
+    let brokers: Option<Vec<String>> = bootstrap_servers.map(|s| {
+        s.split(',')
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty())
+            .collect()
+    });
+
+    let mut docs = Vec::new();
+    for path in paths {
+        let mut notes = global_notes.clone();
+        let mut filled = Vec::new();
+        let source = &nodes[&path[0]];
+        let sink_name = path.last().expect("non-empty path");
+        let sink = &nodes[sink_name];
+
+        let brokers_for = |side: &str, filled: &mut Vec<GenerationNote>| match &brokers {
+            Some(b) if !b.is_empty() => json!(b),
+            _ => {
+                let default = json!(["localhost:9092"]);
+                filled.push(GenerationNote::new(
+                    &format!("spec.{}.kafka.brokers", side),
+                    default.clone(),
+                    "bootstrap servers are not part of the topology",
+                ));
+                default
+            }
+        };
+        let mut source_kafka = JsonMap::new();
+        source_kafka.insert("brokers".to_string(), brokers_for("source", &mut filled));
+        let source_label = match (&source.pattern, source.topics.as_slice()) {
+            (Some(p), _) => {
+                source_kafka.insert("topicPattern".to_string(), Value::String(p.clone()));
+                p.clone()
+            }
+            (None, [topic]) => {
+                source_kafka.insert("topic".to_string(), Value::String(topic.clone()));
+                topic.clone()
+            }
+            (None, topics) => {
+                let pattern = topics
+                    .iter()
+                    .map(|t| regex::escape(t))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                source_kafka.insert("topicPattern".to_string(), Value::String(pattern));
+                topics.join("-")
+            }
+        };
+        source_kafka.insert("format".to_string(), Value::String("json".to_string()));
+        let mut sink_kafka = JsonMap::new();
+        sink_kafka.insert("brokers".to_string(), brokers_for("sink", &mut filled));
+        let sink_topic = or_filled(
+            sink.sink_topic.clone(),
+            "TODO-output-topic",
+            "spec.sink.kafka.topic",
+            "the sink uses a TopicNameExtractor",
+            &mut filled,
+        );
+        sink_kafka.insert("topic".to_string(), Value::String(sink_topic.clone()));
+        sink_kafka.insert("format".to_string(), Value::String("json".to_string()));
+        notes.push(
+            "Serdes are not part of the topology; format json was assumed for source and sink."
+                .to_string(),
+        );
+        for topic in source
+            .topics
+            .iter()
+            .chain(std::iter::once(&sink_topic))
+            .filter(|t| is_internal(t))
+        {
+            notes.push(format!("{} is an internal Kafka Streams topic: the stateful step on the other side of it is not part of this DataFlow.", topic));
+        }
+
+        let mut transformations = Vec::new();
+        for name in &path[1..path.len() - 1] {
+            let node = &nodes[name];
+            let op = operator(name);
+            if let Some("FILTER" | "BRANCHCHILD") = op {
+                let i = transformations.len();
+                let condition = or_filled(
+                    None,
+                    "$.TODO",
+                    &format!("spec.transformations[{}].filter.condition", i),
+                    &format!("translate the predicate of {}", name),
+                    &mut filled,
+                );
+                transformations
+                    .push(json!({ "type": "filter", "filter": { "condition": condition } }));
+            } else if op == Some("BRANCH") {
+                continue;
+            } else if !node.stores.is_empty()
+                || op.map(|o| STATEFUL_OPERATORS.contains(&o)).unwrap_or(false)
+            {
+                let stores = if node.stores.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (stores: {})", node.stores.join(", "))
+                };
+                notes.push(format!("{}{} is stateful and cannot be migrated automatically; DataFlow transformations are stateless.", name, stores));
+            } else if let Some((_, hint)) =
+                op.and_then(|o| OPERATOR_HINTS.iter().find(|(k, _)| *k == o))
+            {
+                notes.push(format!("{}: {}.", name, hint));
+            } else {
+                notes.push(format!("{} is a custom or unknown operator; review its code and add the equivalent transformation.", name));
+            }
+        }
+
+        let mut metadata = JsonMap::new();
+        let name = sanitize_name(&format!("{}-to-{}", source_label, sink_topic));
+        metadata.insert(
+            "name".to_string(),
+            Value::String(if name.is_empty() {
+                format!("subtopology-{}", source.sub_topology)
+            } else {
+                name
+            }),
+        );
+        annotate(&mut metadata, &filled);
+        let mut spec = JsonMap::new();
+        spec.insert(
+            "source".to_string(),
+            json!({ "type": "kafka", "kafka": source_kafka }),
+        );
+        spec.insert(
+            "sink".to_string(),
+            json!({ "type": "kafka", "kafka": sink_kafka }),
+        );
+        if !transformations.is_empty() {
+            spec.insert("transformations".to_string(), Value::Array(transformations));
+        }
+        let mut manifest = JsonMap::new();
+        manifest.insert(
+            "apiVersion".to_string(),
+            Value::String(DATAFLOW_API_VERSION.to_string()),
+        );
+        manifest.insert("kind".to_string(), Value::String(DATAFLOW_KIND.to_string()));
+        manifest.insert("metadata".to_string(), Value::Object(metadata));
+        manifest.insert("spec".to_string(), Value::Object(spec));
+        docs.push(render_migrated(
+            &format!("Kafka Streams sub-topology {}", source.sub_topology),
+            &manifest,
+            notes,
+        )?);
+    }
+    Ok(docs.join("---\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOPOLOGY: &str = "Topologies:
+   Sub-topology: 0
+    Source: KSTREAM-SOURCE-0000000000 (topics: [orders])
+      --> KSTREAM-FILTER-0000000001
+    Processor: KSTREAM-FILTER-0000000001 (stores: [])
+      --> KSTREAM-MAPVALUES-0000000002
+      <-- KSTREAM-SOURCE-0000000000
+    Processor: KSTREAM-MAPVALUES-0000000002 (stores: [])
+      --> KSTREAM-SINK-0000000003, KSTREAM-AGGREGATE-0000000004
+      <-- KSTREAM-FILTER-0000000001
+    Sink: KSTREAM-SINK-0000000003 (topic: orders-clean)
+      <-- KSTREAM-MAPVALUES-0000000002
+    Processor: KSTREAM-AGGREGATE-0000000004 (stores: [totals-store])
+      --> KTABLE-TOSTREAM-0000000005
+      <-- KSTREAM-MAPVALUES-0000000002
+    Processor: KTABLE-TOSTREAM-0000000005 (stores: [])
+      --> KSTREAM-SINK-0000000006
+      <-- KSTREAM-AGGREGATE-0000000004
+    Sink: KSTREAM-SINK-0000000006 (topic: order-totals)
+      <-- KTABLE-TOSTREAM-0000000005
+";
+
+    #[test]
+    fn test_migrate_kafka_streams_topology_paths() {
+        let out = migrate_kafka_streams_topology(TOPOLOGY, Some("k1:9092,k2:9092")).unwrap();
+        let docs: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(&out)
+            .map(|d| serde::Deserialize::deserialize(d).unwrap())
+            .collect();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0]["metadata"]["name"], "orders-to-orders-clean");
+        assert_eq!(docs[0]["spec"]["source"]["kafka"]["brokers"][1], "k2:9092");
+        assert_eq!(
+            docs[0]["spec"]["transformations"][0]["filter"]["condition"],
+            "$.TODO"
+        );
+        assert_eq!(docs[1]["spec"]["sink"]["kafka"]["topic"], "order-totals");
+        assert!(out.contains("KSTREAM-MAPVALUES-0000000002: rewrite the value mapper"));
+        assert!(out.contains("KSTREAM-AGGREGATE-0000000004 (stores: totals-store) is stateful"));
+        assert!(!out.contains("Validation error"));
+    }
+
+    #[test]
+    fn test_migrate_kafka_streams_topology_rejects_non_topology() {
+        assert!(migrate_kafka_streams_topology("hello", None).is_err());
+        let pattern = "Sub-topology: 0\n Source: in (topic pattern: logs-.*)\n  --> out\n Sink: out (extractor class: com.acme.Router)\n  <-- in\n";
+        let out = migrate_kafka_streams_topology(pattern, None).unwrap();
+        assert!(out.contains("topicPattern: logs-.*"));
+        assert!(out.contains("TODO-output-topic"));
+    }
+}
@@ -0,0 +1,67 @@
+// kubectl access for tools that read the cluster (uses the caller's kubeconfig and current or given context).
+
+use serde_json::Value;
+
+/// Runs `kubectl <args> -o json` and parses the output; what names the feature for the offline-mode error.
+pub async fn kubectl_json(
+    what: &str,
+    args: &[&str],
+    context: Option<&str>,
+) -> Result<Value, String> {
+    crate::config::current().ensure_online(what)?;
+    let mut cmd = tokio::process::Command::new("kubectl");
+    if let Some(ctx) = context.filter(|c| !c.is_empty()) {
+        cmd.arg("--context").arg(ctx);
+    }
+    cmd.args(args).arg("-o").arg("json");
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("{}: failed to run kubectl: {}", what, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{}: kubectl {} failed: {}",
+            what,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("{}: invalid kubectl JSON output: {}", what, e))
+}
+
+/// Pipes one manifest into `kubectl apply -f -` and returns kubectl's summary line (e.g. "dataflow.../orders created").
+pub async fn kubectl_apply(
+    what: &str,
+    manifest_yaml: &str,
+    context: Option<&str>,
+) -> Result<String, String> {
+    use tokio::io::AsyncWriteExt;
+
+    crate::config::current().ensure_online(what)?;
+    let mut cmd = tokio::process::Command::new("kubectl");
+    if let Some(ctx) = context.filter(|c| !c.is_empty()) {
+        cmd.arg("--context").arg(ctx);
+    }
+    cmd.args(["apply", "-f", "-"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("{}: failed to run kubectl: {}", what, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(manifest_yaml.as_bytes())
+            .await
+            .map_err(|e| format!("{}: failed to write to kubectl: {}", what, e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("{}: kubectl did not finish: {}", what, e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
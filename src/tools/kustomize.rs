@@ -0,0 +1,246 @@
+// Kustomize layout for DataFlow manifests: a base with the manifests as given and one overlay per environment
+// that patches namespace, name suffix, Kafka brokers and parallelism.
+
+use crate::tools::manifest::split_documents;
+use crate::tools::output::to_canonical_yaml;
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_KIND};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// Settings of one environment overlay; unset fields keep the base values.
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    pub name: String,
+    pub namespace: Option<String>,
+    pub brokers: Vec<String>,
+    pub name_suffix: Option<String>,
+    pub parallelism: Option<u32>,
+}
+
+const KUSTOMIZE_API_VERSION: &str = "kustomize.config.k8s.io/v1beta1";
+
+/// JSON pointer of a dotted manifest path segment list ("~" and "/" escaped).
+fn pointer(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Pointers of every kafka brokers list under spec: source, sink, DLQ, branch sinks and router route sinks.
+fn broker_pointers(v: &Value, path: &mut Vec<String>, found: &mut Vec<String>) {
+    match v {
+        Value::Object(map) => {
+            for (key, value) in map {
+                path.push(key.clone());
+                if key == "brokers" && path.len() >= 2 && path[path.len() - 2] == "kafka" {
+                    found.push(pointer(path));
+                } else {
+                    broker_pointers(value, path, found);
+                }
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                path.push(i.to_string());
+                broker_pointers(item, path, found);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Builds base/ and overlays/<env>/ for the DataFlow documents of yaml and returns them as JSON
+/// {"files": {path: content}}. Each overlay sets its namespace and nameSuffix and patches every DataFlow with
+/// JSON 6902 operations: brokers of all kafka blocks and spec.parallelism. Other documents are left out.
+pub fn generate_kustomize_overlays(
+    yaml: &str,
+    environments: &[Environment],
+) -> Result<String, String> {
+    if environments.is_empty() {
+        return Err("environments must list at least one environment, e.g. [{name: dev, namespace: dataflow-dev}]".to_string());
+    }
+    let mut flows = Vec::new();
+    for (_, doc) in split_documents(yaml) {
+        let manifest: Value =
+            serde_yaml::from_str(&doc).map_err(|e| format!("YAML parse error: {}", e))?;
+        if manifest["kind"] != DATAFLOW_KIND {
+            continue;
+        }
+        let name = manifest["metadata"]["name"]
+            .as_str()
+            .ok_or("every DataFlow needs metadata.name to be patched by name")?;
+        let mut brokers = Vec::new();
+        broker_pointers(
+            &manifest["spec"],
+            &mut vec!["spec".to_string()],
+            &mut brokers,
+        );
+        flows.push((name.to_string(), manifest, brokers));
+    }
+    if flows.is_empty() {
+        return Err("no DataFlow manifest found".to_string());
+    }
+    let (group, version) = DATAFLOW_API_VERSION
+        .split_once('/')
+        .unwrap_or(("", DATAFLOW_API_VERSION));
+
+    let mut files = JsonMap::new();
+    let base_docs: Vec<String> = flows
+        .iter()
+        .map(|(_, m, _)| to_canonical_yaml(m))
+        .collect::<Result<_, _>>()?;
+    files.insert(
+        "base/dataflow.yaml".to_string(),
+        json!(base_docs.join("---\n")),
+    );
+    let base = json!({ "apiVersion": KUSTOMIZE_API_VERSION, "kind": "Kustomization", "resources": ["dataflow.yaml"] });
+    files.insert(
+        "base/kustomization.yaml".to_string(),
+        json!(to_canonical_yaml(&base)?),
+    );
+
+    for env in environments {
+        let env_name = env.name.trim();
+        if env_name.is_empty()
+            || !env_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("environment name '{}' must be a non-empty directory name (letters, digits, - and _)", env.name));
+        }
+        let mut kustomization = json!({ "apiVersion": KUSTOMIZE_API_VERSION, "kind": "Kustomization", "resources": ["../../base"] });
+        if let Some(ns) = env.namespace.as_deref().filter(|n| !n.is_empty()) {
+            kustomization["namespace"] = json!(ns);
+        }
+        if let Some(suffix) = env.name_suffix.as_deref().filter(|s| !s.is_empty()) {
+            kustomization["nameSuffix"] = json!(suffix);
+        }
+        let mut patches = Vec::new();
+        for (name, _, brokers) in &flows {
+            let mut ops = Vec::new();
+            if !env.brokers.is_empty() {
+                ops.extend(
+                    brokers
+                        .iter()
+                        .map(|p| json!({ "op": "replace", "path": p, "value": env.brokers })),
+                );
+            }
+            if let Some(p) = env.parallelism {
+                ops.push(json!({ "op": "add", "path": "/spec/parallelism", "value": p }));
+            }
+            if ops.is_empty() {
+                continue;
+            }
+            patches.push(json!({
+                "target": { "group": group, "version": version, "kind": DATAFLOW_KIND, "name": name },
+                "patch": serde_yaml::to_string(&ops).map_err(|e| e.to_string())?
+            }));
+        }
+        if !patches.is_empty() {
+            kustomization["patches"] = Value::Array(patches);
+        }
+        files.insert(
+            format!("overlays/{}/kustomization.yaml", env_name),
+            json!(to_canonical_yaml(&kustomization)?),
+        );
+    }
+    serde_json::to_string_pretty(&json!({ "files": files })).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\nspec:\n  source:\n    type: kafka\n    kafka: {brokers: [\"localhost:9092\"], topic: orders}\n  sink:\n    type: postgresql\n    postgresql: {connectionString: \"postgres://pg/shop\", table: orders}\n  errorHandling:\n    dlq:\n      type: kafka\n      kafka: {brokers: [\"localhost:9092\"], topic: orders-dlq}\n";
+
+    #[test]
+    fn test_generate_kustomize_overlays() {
+        let envs = [
+            Environment {
+                name: "dev".to_string(),
+                namespace: Some("dataflow-dev".to_string()),
+                ..Default::default()
+            },
+            Environment {
+                name: "prod".to_string(),
+                namespace: Some("dataflow".to_string()),
+                brokers: vec!["k1.prod:9092".to_string(), "k2.prod:9092".to_string()],
+                name_suffix: Some("-prod".to_string()),
+                parallelism: Some(4),
+            },
+        ];
+        let out: Value =
+            serde_json::from_str(&generate_kustomize_overlays(MANIFEST, &envs).unwrap()).unwrap();
+        let files = out["files"].as_object().unwrap();
+        let paths: Vec<&str> = files.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "base/dataflow.yaml",
+                "base/kustomization.yaml",
+                "overlays/dev/kustomization.yaml",
+                "overlays/prod/kustomization.yaml"
+            ]
+        );
+        assert!(files["base/dataflow.yaml"]
+            .as_str()
+            .unwrap()
+            .contains("spec:\n  source:\n    type: kafka\n    kafka:\n"));
+        let dev: Value =
+            serde_yaml::from_str(files["overlays/dev/kustomization.yaml"].as_str().unwrap())
+                .unwrap();
+        assert_eq!(
+            dev,
+            json!({ "apiVersion": KUSTOMIZE_API_VERSION, "kind": "Kustomization", "resources": ["../../base"], "namespace": "dataflow-dev" })
+        );
+        let prod: Value =
+            serde_yaml::from_str(files["overlays/prod/kustomization.yaml"].as_str().unwrap())
+                .unwrap();
+        assert_eq!(prod["nameSuffix"], "-prod");
+        let patch = &prod["patches"][0];
+        assert_eq!(
+            patch["target"],
+            json!({ "group": "dataflow.dataflow.io", "version": "v1", "kind": "DataFlow", "name": "orders" })
+        );
+        let ops: Value = serde_yaml::from_str(patch["patch"].as_str().unwrap()).unwrap();
+        let op_paths: Vec<&str> = ops
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|o| o["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            op_paths,
+            vec![
+                "/spec/errorHandling/dlq/kafka/brokers",
+                "/spec/source/kafka/brokers",
+                "/spec/parallelism"
+            ]
+        );
+        assert_eq!(ops[0]["value"], json!(["k1.prod:9092", "k2.prod:9092"]));
+    }
+
+    #[test]
+    fn test_generate_kustomize_overlays_errors() {
+        assert!(generate_kustomize_overlays(MANIFEST, &[])
+            .unwrap_err()
+            .starts_with("environments must list"));
+        let bad = [Environment {
+            name: "../prod".to_string(),
+            ..Default::default()
+        }];
+        assert!(generate_kustomize_overlays(MANIFEST, &bad)
+            .unwrap_err()
+            .contains("must be a non-empty directory name"));
+        let dev = [Environment {
+            name: "dev".to_string(),
+            ..Default::default()
+        }];
+        assert_eq!(
+            generate_kustomize_overlays("apiVersion: v1\nkind: Secret\n", &dev).unwrap_err(),
+            "no DataFlow manifest found"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+// Lint DataFlow manifests: non-fatal suggestions on top of validate_dataflow_manifest.
+
+use crate::types::ParsedDataFlow;
+use serde_json::Value;
+
+/// Scripts with at most this many code lines are considered trivial.
+const TRIVIAL_SCRIPT_LINES: usize = 3;
+
+/// Lints a DataFlow YAML manifest and returns warnings (empty if nothing to report).
+/// Only YAML parse failures are errors; structural problems are reported by validate_dataflow_manifest.
+pub fn lint_dataflow_manifest(config_yaml: &str) -> Result<Vec<String>, String> {
+    let parsed: ParsedDataFlow =
+        serde_yaml::from_str(config_yaml).map_err(|e| format!("YAML parse error: {}", e))?;
+    let mut warnings = Vec::new();
+    let spec = match &parsed.spec {
+        Some(s) => s,
+        None => return Ok(warnings),
+    };
+    if let Some(transformations) = &spec.transformations {
+        lint_transformations(transformations, "spec.transformations", &mut warnings);
+    }
+    for (name, branch) in spec.branches.iter().flatten() {
+        if let Some(transformations) = &branch.transformations {
+            lint_transformations(
+                transformations,
+                &format!("spec.branches.{}.transformations", name),
+                &mut warnings,
+            );
+        }
+    }
+    Ok(warnings)
+}
+
+fn lint_transformations(transformations: &[Value], path: &str, warnings: &mut Vec<String>) {
+    for (i, t) in transformations.iter().enumerate() {
+        if t.get("type").and_then(Value::as_str) != Some("script") {
+            continue;
+        }
+        let code = match t
+            .get("script")
+            .and_then(|s| s.get("code"))
+            .and_then(Value::as_str)
+        {
+            Some(c) => c,
+            None => continue,
+        };
+        if let Some(w) = trivial_script_warning(code) {
+            warnings.push(format!("{}[{}].script: {}", path, i, w));
+        }
+    }
+}
+
+/// Flags short inline scripts and names the declarative transformation that likely replaces them.
+fn trivial_script_warning(code: &str) -> Option<String> {
+    let lines: Vec<&str> = code
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("--") && !l.starts_with("//"))
+        .collect();
+    if lines.len() > TRIVIAL_SCRIPT_LINES {
+        return None;
+    }
+    let body = lines.join(" ").to_lowercase();
+    let suggestion = if body.contains("return nil")
+        || body.contains("return null")
+        || body.contains("return false")
+    {
+        "filter"
+    } else if body.contains("= nil") || body.contains("delete ") {
+        "remove"
+    } else if body.contains("lower(") || body.contains("upper(") || body.contains("tolowercase") {
+        "snakeCase/camelCase"
+    } else {
+        "filter, select, remove or mask"
+    };
+    Some(format!(
+        "script is trivial ({} line(s)); consider the declarative {} transformation instead, which needs no sandbox",
+        lines.len(),
+        suggestion
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_trivial_script_suggests_filter() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: script
+spec:
+  source:
+    type: kafka
+    kafka: {}
+  sink:
+    type: kafka
+    kafka: {}
+  transformations:
+    - type: script
+      script:
+        language: lua
+        code: |
+          if msg.level == "debug" then return nil end
+          return msg
+"#;
+        let warnings = lint_dataflow_manifest(yaml).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("spec.transformations[0].script"));
+        assert!(warnings[0].contains("declarative filter"));
+    }
+
+    #[test]
+    fn test_lint_non_trivial_script_has_no_warnings() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: script
+spec:
+  source:
+    type: kafka
+    kafka: {}
+  sink:
+    type: kafka
+    kafka: {}
+  transformations:
+    - type: script
+      script:
+        language: javascript
+        code: |
+          const parts = msg.path.split("/");
+          msg.tenant = parts[1];
+          msg.resource = parts[2];
+          msg.depth = parts.length;
+          return msg;
+"#;
+        assert!(lint_dataflow_manifest(yaml).unwrap().is_empty());
+    }
+}
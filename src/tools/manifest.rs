@@ -1,21 +1,109 @@
 // Generate and validate DataFlow manifests.
 
-use crate::types::{ParsedDataFlow, DATAFLOW_API_VERSION, DATAFLOW_KIND, SINK_TYPES, SOURCE_TYPES};
-use serde_json::{Map as JsonMap, Value};
+use crate::tools::avro::check_avro_schema;
+use crate::tools::compatibility::validate_format_compatibility;
+use crate::tools::connection::{check_connection, normalize_spec};
+use crate::tools::credentials::{check_secret_refs, plaintext_credentials, secret_name};
+use crate::tools::docs::manifest_json_schema;
+use crate::tools::inference::infer_from_description;
+use crate::tools::jsonpath::{check_condition, check_jsonpath};
+use crate::tools::migration::sanitize_name;
+use crate::tools::naming::{
+    apply_name_strategy, apply_naming_conventions, check_dns1123_label, check_dns1123_subdomain,
+    check_label_value, check_qualified_key, conventions as naming_conventions, parse_name_strategy,
+};
+use crate::tools::output::to_canonical_yaml;
+use crate::tools::provenance::{annotate, or_filled, GenerationNote};
+use crate::tools::resources::{resources_block, validate_resources, DEFAULT_RESOURCES};
+use crate::tools::sarif::finding_location;
+use crate::tools::schema_registry::validate_schema_registry;
+use crate::types::{
+    ParsedBranch, ParsedDataFlow, ParsedMetadata, ParsedSink, ParsedSource, ParsedSpec,
+    BATCH_TRANSFORMATION_TYPES, CLOUDEVENTS_MODES, CURSOR_STORAGES, DATAFLOW_API_VERSION,
+    DATAFLOW_KIND, DLQ_TYPES, ENRICH_BACKENDS, ES_DOCUMENT_ID_STRATEGIES, ES_NULL_VALUE_BEHAVIORS,
+    FIELD_DEPENDENCIES, KAFKA_FORMATS, KAFKA_OFFSET_RESETS, MAX_PARALLELISM, ON_ERROR_POLICIES,
+    PAYLOAD_COMPRESSIONS, PAYLOAD_MODES, PG_REPLICATION_PLUGINS, POLLING_SOURCE_TYPES, POLL_MODES,
+    RELATIONAL_SINK_TYPES, S3_FORMATS, S3_PARTITIONERS, SCRIPT_LANGUAGES, SINK_TYPES, SOURCE_TYPES,
+    SYSLOG_PARSERS, SYSLOG_PROTOCOLS, TRINO_CATALOG_TYPES, TRINO_HIVE_FILE_FORMATS,
+    TRINO_ICEBERG_FILE_FORMATS, TRINO_INSERT_STRATEGIES,
+};
+use serde::Serialize;
+use serde_json::{json, Map as JsonMap, Value};
+use std::collections::BTreeMap;
 
 /// Generates a DataFlow YAML manifest from the given parameters.
-/// source_config and sink_config are optional JSON objects (as strings); if provided they are merged under source[source_type] and sink[sink_type].
-/// transformations is optional JSON array string.
+/// source_config and sink_config are optional JSON objects placed under source[source_type] and sink[sink_type];
+/// transformations is an optional JSON array. JSON-encoded strings of either are accepted too.
+/// What is not given (types, config fields, transformations) is inferred from description; given values win.
+/// parallelism and resources (spec.resources) are validated; without resources DEFAULT_RESOURCES are filled in.
+/// dlq (kafka or postgresql) adds spec.errorHandling.dlq (see dlq_sink) and sends failures of transformations
+/// without an onError policy there.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_dataflow_manifest(
     description: Option<&str>,
-    source_type: &str,
-    sink_type: &str,
-    source_config: Option<&str>,
-    sink_config: Option<&str>,
-    transformations: Option<&str>,
+    source_type: Option<&str>,
+    sink_type: Option<&str>,
+    source_config: Option<&Value>,
+    sink_config: Option<&Value>,
+    transformations: Option<&Value>,
     name: Option<&str>,
     namespace: Option<&str>,
+    name_strategy: Option<&str>,
+    parallelism: Option<u32>,
+    resources: Option<&Value>,
+    dlq: Option<&str>,
 ) -> Result<String, String> {
+    let name_strategy = parse_name_strategy(name_strategy)?;
+    let dlq = dlq.map(str::trim).filter(|d| !d.is_empty());
+    if let Some(d) = dlq.filter(|d| !DLQ_TYPES.contains(d)) {
+        return Err(format!(
+            "dlq '{}' is not supported; use one of: {}",
+            d,
+            DLQ_TYPES.join(", ")
+        ));
+    }
+    let mut errors = Vec::new();
+    check_int_range(
+        parallelism.map(|p| json!(p)).as_ref(),
+        "parallelism",
+        1,
+        MAX_PARALLELISM,
+        &mut errors,
+    );
+    if let Some(r) = resources {
+        validate_resources(r, "resources", &mut errors);
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+    let inference = infer_from_description(description.unwrap_or(""));
+    let mut inferred: Vec<String> = Vec::new();
+    let source_type = match source_type.filter(|t| !t.is_empty()) {
+        Some(t) => t,
+        None => {
+            let t = inference.source_type.as_deref().ok_or_else(|| {
+                format!(
+                    "source_type is required (or name the source in description, e.g. \"from kafka topic orders\"); one of: {}",
+                    SOURCE_TYPES.join(", ")
+                )
+            })?;
+            inferred.push(format!("spec.source.type = {}", t));
+            t
+        }
+    };
+    let sink_type = match sink_type.filter(|t| !t.is_empty()) {
+        Some(t) => t,
+        None => {
+            let t = inference.sink_type.as_deref().ok_or_else(|| {
+                format!(
+                    "sink_type is required (or name the sink in description, e.g. \"to postgres table orders\"); one of: {}",
+                    SINK_TYPES.join(", ")
+                )
+            })?;
+            inferred.push(format!("spec.sink.type = {}", t));
+            t
+        }
+    };
     if !SOURCE_TYPES.contains(&source_type) {
         return Err(format!(
             "source_type must be one of: {}",
@@ -29,77 +117,792 @@ pub fn generate_dataflow_manifest(
         ));
     }
 
+    let mut filled: Vec<GenerationNote> = Vec::new();
     let mut metadata: JsonMap<String, Value> = JsonMap::new();
-    metadata.insert(
-        "name".to_string(),
-        Value::String(name.unwrap_or("dataflow-example").to_string()),
-    );
     if let Some(ns) = namespace {
         metadata.insert("namespace".to_string(), Value::String(ns.to_string()));
     }
 
     let mut source: JsonMap<String, Value> = JsonMap::new();
     source.insert("type".to_string(), Value::String(source_type.to_string()));
-    let source_config_obj: JsonMap<String, Value> = if let Some(sc) = source_config {
-        serde_json::from_str(sc).map_err(|e| format!("source_config invalid JSON: {}", e))?
+    let inferred_source =
+        (inference.source_type.as_deref() == Some(source_type)).then_some(&inference.source_config);
+    let mut source_config_obj: JsonMap<String, Value> = if let Some(sc) = source_config {
+        json_param(sc, "source_config")?
+    } else if inferred_source.is_some_and(|c| !c.is_empty()) {
+        JsonMap::new()
     } else {
+        filled.push(GenerationNote::new(
+            &format!("spec.source.{}", source_type),
+            Value::Object(JsonMap::new()),
+            "source_config not provided; required fields must be filled in",
+        ));
         JsonMap::new()
     };
+    merge_inferred(
+        &mut source_config_obj,
+        inferred_source,
+        &format!("spec.source.{}", source_type),
+        &mut inferred,
+    );
     source.insert(source_type.to_string(), Value::Object(source_config_obj));
 
     let mut sink: JsonMap<String, Value> = JsonMap::new();
     sink.insert("type".to_string(), Value::String(sink_type.to_string()));
-    let sink_config_obj: JsonMap<String, Value> = if let Some(sc) = sink_config {
-        serde_json::from_str(sc).unwrap_or_else(|_| JsonMap::new())
+    let sink_path = format!("spec.sink.{}", sink_type);
+    let inferred_sink =
+        (inference.sink_type.as_deref() == Some(sink_type)).then_some(&inference.sink_config);
+    let mut sink_config_obj: JsonMap<String, Value> = if let Some(sc) = sink_config {
+        json_param(sc, "sink_config").unwrap_or_else(|_| {
+            filled.push(GenerationNote::new(
+                &sink_path,
+                Value::Object(JsonMap::new()),
+                "sink_config is not a JSON object and was ignored",
+            ));
+            JsonMap::new()
+        })
+    } else if inferred_sink.is_some_and(|c| !c.is_empty()) {
+        JsonMap::new()
     } else {
+        filled.push(GenerationNote::new(
+            &sink_path,
+            Value::Object(JsonMap::new()),
+            "sink_config not provided; required fields must be filled in",
+        ));
         JsonMap::new()
     };
+    merge_inferred(
+        &mut sink_config_obj,
+        inferred_sink,
+        &sink_path,
+        &mut inferred,
+    );
     sink.insert(sink_type.to_string(), Value::Object(sink_config_obj));
+    let derived = default_flow_name(
+        source_type,
+        &source[source_type],
+        sink_type,
+        &sink[sink_type],
+    );
+    metadata.insert(
+        "name".to_string(),
+        Value::String(or_filled(
+            name.filter(|n| !n.is_empty()).map(str::to_string),
+            &derived,
+            "metadata.name",
+            "name not provided; derived from the source and sink",
+            &mut filled,
+        )),
+    );
 
     let mut spec: JsonMap<String, Value> = JsonMap::new();
     spec.insert("source".to_string(), Value::Object(source));
     spec.insert("sink".to_string(), Value::Object(sink));
     if let Some(tr) = transformations {
-        let arr: Value = serde_json::from_str(tr).map_err(|e| format!("transformations invalid JSON: {}", e))?;
-        if let Value::Array(a) = arr {
-            if !a.is_empty() {
-                spec.insert("transformations".to_string(), Value::Array(a));
-            }
+        let a: Vec<Value> = json_param(tr, "transformations")?;
+        if !a.is_empty() {
+            spec.insert("transformations".to_string(), Value::Array(a));
+        }
+    } else if !inference.transformations.is_empty() {
+        for (i, t) in inference.transformations.iter().enumerate() {
+            let kind = t["type"].as_str().unwrap_or("");
+            inferred.push(format!(
+                "spec.transformations[{}] = {} {}",
+                i, kind, t[kind]
+            ));
         }
+        spec.insert(
+            "transformations".to_string(),
+            Value::Array(inference.transformations.clone()),
+        );
+    }
+
+    if let Some(p) = parallelism {
+        spec.insert("parallelism".to_string(), json!(p));
+    }
+    let resources = resources.cloned().unwrap_or_else(|| {
+        let defaults: Vec<(&str, &str, Option<&str>)> = DEFAULT_RESOURCES
+            .iter()
+            .map(|(s, n, q)| (*s, *n, Some(*q)))
+            .collect();
+        let block = resources_block(&defaults).unwrap_or_default();
+        filled.push(GenerationNote::new(
+            "spec.resources",
+            block.clone(),
+            "resources not provided; defaults, tune them to the flow's load",
+        ));
+        block
+    });
+    spec.insert("resources".to_string(), resources);
+
+    let mut spec_value = Value::Object(spec);
+    for n in normalize_spec(&mut spec_value) {
+        filled.push(GenerationNote::new(
+            &n.path,
+            Value::String(n.to),
+            "normalized from a JDBC URL",
+        ));
+    }
+    let spec = match spec_value {
+        Value::Object(spec) => spec,
+        _ => JsonMap::new(),
+    };
+
+    if let Some(Value::String(base)) = metadata.get("name") {
+        let seed = Value::Object(spec.clone()).to_string();
+        let name = apply_name_strategy(base, name_strategy, &seed, 1);
+        metadata.insert("name".to_string(), Value::String(name));
     }
 
     let mut top: JsonMap<String, Value> = JsonMap::new();
-    top.insert("apiVersion".to_string(), Value::String(DATAFLOW_API_VERSION.to_string()));
+    top.insert(
+        "apiVersion".to_string(),
+        Value::String(DATAFLOW_API_VERSION.to_string()),
+    );
     top.insert("kind".to_string(), Value::String(DATAFLOW_KIND.to_string()));
     top.insert("metadata".to_string(), Value::Object(metadata));
     top.insert("spec".to_string(), Value::Object(spec));
+    let mut top = Value::Object(top);
 
-    let yaml = serde_yaml::to_string(&top).map_err(|e| e.to_string())?;
-    let mut out = format!("# Generated DataFlow manifest\n");
+    let (mut renamed, mut naming_warnings) =
+        apply_naming_conventions(naming_conventions(), &mut top);
+    // The DLQ topic / table follows the final metadata.name, so it is added after the name strategy and the
+    // conventions, which then run again for the DLQ itself (already conforming names are left alone).
+    if let Some(kind) = dlq {
+        let flow = top["metadata"]["name"]
+            .as_str()
+            .unwrap_or("dataflow")
+            .to_string();
+        let spec = top
+            .get_mut("spec")
+            .and_then(Value::as_object_mut)
+            .ok_or("spec is missing")?;
+        let dlq_sink = dlq_sink(kind, &flow, &spec["source"], &spec["sink"], &mut filled);
+        spec.insert("errorHandling".to_string(), json!({ "dlq": dlq_sink }));
+        for (i, t) in spec
+            .get_mut("transformations")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            if let Some(t) = t.as_object_mut().filter(|t| !t.contains_key("onError")) {
+                t.insert("onError".to_string(), json!("dlq"));
+                filled.push(GenerationNote::new(
+                    &format!("spec.transformations[{}].onError", i),
+                    json!("dlq"),
+                    "dlq requested; failed messages go to the DLQ",
+                ));
+            }
+        }
+        let (dlq_renamed, warnings) = apply_naming_conventions(naming_conventions(), &mut top);
+        renamed.extend(dlq_renamed);
+        naming_warnings = warnings;
+    }
+    for (path, value) in renamed {
+        filled.push(GenerationNote::new(
+            &path,
+            Value::String(value),
+            "renamed to follow the naming conventions",
+        ));
+    }
+    if let Some(metadata) = top.get_mut("metadata").and_then(Value::as_object_mut) {
+        annotate(metadata, &filled);
+    }
+
+    let credentials =
+        plaintext_credentials(&top["spec"], &secret_name(top["metadata"]["name"].as_str()));
+    let yaml = to_canonical_yaml(&top)?;
+    let mut out = String::from("# Generated DataFlow manifest\n");
     if let Some(d) = description {
         out.push_str(&format!("# Description: {}\n", d));
     }
+    for i in &inferred {
+        out.push_str(&format!("# Inferred from description: {}\n", i));
+    }
+    if !inferred.is_empty() {
+        for clause in &inference.unparsed {
+            out.push_str(&format!("# Not understood in description: '{}'\n", clause));
+        }
+    }
+    for w in &naming_warnings {
+        out.push_str(&format!("# Warning: {}\n", w));
+    }
+    for c in &credentials {
+        out.push_str(&format!("# Warning: {}\n", c.message()));
+    }
     out.push_str(&yaml);
     Ok(out)
 }
 
+/// metadata.name derived from the flow, e.g. kafka-orders-to-pg-orders: short connector names with the topic,
+/// table (without schema), index or bucket of each side, at most 63 characters.
+pub(crate) fn default_flow_name(
+    source_type: &str,
+    source: &Value,
+    sink_type: &str,
+    sink: &Value,
+) -> String {
+    let side = |kind: &str, block: &Value| {
+        let short = match kind {
+            "postgresql" => "pg",
+            "clickhouse" => "ch",
+            "elasticsearch" => "es",
+            other => other,
+        };
+        let object = block["topic"]
+            .as_str()
+            .or_else(|| {
+                block["table"]
+                    .as_str()
+                    .map(|t| t.rsplit('.').next().unwrap_or(t))
+            })
+            .or_else(|| block["index"].as_str())
+            .or_else(|| block["bucket"].as_str());
+        match object {
+            Some(o) if !o.is_empty() => format!("{}-{}", short, o),
+            _ => short.to_string(),
+        }
+    };
+    let name = sanitize_name(&format!(
+        "{}-to-{}",
+        side(source_type, source),
+        side(sink_type, sink)
+    ));
+    let name: String = name.chars().filter(char::is_ascii).take(63).collect();
+    name.trim_end_matches('-').to_string()
+}
+
+/// DLQ sink of a generated flow. kafka: topic <flow>-dlq on the brokers of the kafka source or sink; postgresql:
+/// table <sink table>_dlq (or <flow>_dlq) over the connection of the postgresql sink or source. Values that cannot
+/// be taken from the flow are placeholders recorded in filled.
+fn dlq_sink(
+    kind: &str,
+    flow: &str,
+    source: &Value,
+    sink: &Value,
+    filled: &mut Vec<GenerationNote>,
+) -> Value {
+    let path = format!("spec.errorHandling.dlq.{}", kind);
+    let from_flow = |field: &str| {
+        [source, sink]
+            .iter()
+            .find(|s| s["type"] == kind)
+            .and_then(|s| s[kind].get(field))
+            .cloned()
+    };
+    let block = match kind {
+        "kafka" => {
+            let brokers = from_flow("brokers").unwrap_or_else(|| {
+                let placeholder = json!(["kafka:9092"]);
+                filled.push(GenerationNote::new(
+                    &format!("{}.brokers", path),
+                    placeholder.clone(),
+                    "no kafka brokers in the flow; placeholder",
+                ));
+                placeholder
+            });
+            let topic = format!("{}-dlq", flow);
+            filled.push(GenerationNote::new(
+                &format!("{}.topic", path),
+                json!(topic),
+                "DLQ topic derived from the flow name",
+            ));
+            json!({ "brokers": brokers, "topic": topic })
+        }
+        _ => {
+            let connection = from_flow("connectionString").unwrap_or_else(|| {
+                let placeholder = json!("postgres://dataflow@postgres:5432/dataflow");
+                filled.push(GenerationNote::new(
+                    &format!("{}.connectionString", path),
+                    placeholder.clone(),
+                    "no postgresql connection in the flow; placeholder",
+                ));
+                placeholder
+            });
+            let table = match sink["postgresql"]["table"]
+                .as_str()
+                .filter(|_| sink["type"] == "postgresql")
+            {
+                Some(t) => format!("{}_dlq", t),
+                None => format!("{}_dlq", flow.replace('-', "_")),
+            };
+            filled.push(GenerationNote::new(
+                &format!("{}.table", path),
+                json!(table),
+                "DLQ table derived from the sink table or flow name",
+            ));
+            json!({ "connectionString": connection, "table": table, "payloadMode": "jsonb" })
+        }
+    };
+    json!({ "type": kind, kind: block })
+}
+
+/// A structured parameter: the JSON value itself, or a JSON-encoded string of it (older clients).
+fn json_param<T: serde::de::DeserializeOwned>(value: &Value, param: &str) -> Result<T, String> {
+    let parsed = match value {
+        Value::String(s) => serde_json::from_str(s),
+        v => serde_json::from_value(v.clone()),
+    };
+    parsed.map_err(|e| format!("{} invalid JSON: {}", param, e))
+}
+
+/// Adds the inferred config fields the given config does not set, noting each as "path.field = value".
+fn merge_inferred(
+    config: &mut JsonMap<String, Value>,
+    inferred: Option<&JsonMap<String, Value>>,
+    path: &str,
+    notes: &mut Vec<String>,
+) {
+    for (field, value) in inferred.into_iter().flatten() {
+        if !config.contains_key(field) {
+            let shown = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            notes.push(format!("{}.{} = {}", path, field, shown));
+            config.insert(field.clone(), value.clone());
+        }
+    }
+}
+
+/// Splits a multi-document YAML on `---` separator lines into (lines before the document, document text);
+/// documents holding only comments or whitespace are skipped.
+pub(crate) fn split_documents(yaml: &str) -> Vec<(usize, String)> {
+    let mut docs = vec![(0, String::new())];
+    for (n, line) in yaml.lines().enumerate() {
+        let t = line.trim_end();
+        if t == "---" || t.starts_with("--- ") || t == "..." {
+            docs.push((n + 1, String::new()));
+            continue;
+        }
+        let doc = &mut docs.last_mut().expect("at least one document").1;
+        doc.push_str(line);
+        doc.push('\n');
+    }
+    docs.retain(|(_, d)| {
+        d.lines()
+            .any(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+    });
+    docs
+}
+
+/// Severity of a validation diagnostic: only errors make a manifest invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+/// One finding with the manifest path it refers to and its 1-based position in the YAML.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, message: String) -> Self {
+        Diagnostic {
+            severity,
+            path: None,
+            message,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Diagnostic located in yaml by the manifest path its message starts with.
+    pub fn located(severity: Severity, message: String, yaml: &str) -> Self {
+        let (path, line, column) = finding_location(&message, yaml);
+        let path = path.map(str::to_string);
+        Diagnostic {
+            severity,
+            path,
+            message,
+            line,
+            column,
+        }
+    }
+}
+
+/// Diagnostics of one document of a (multi-document) YAML; index is 1-based.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocumentReport {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DocumentReport {
+    /// "document N (metadata.name)".
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(n) => format!("document {} ({})", self.index, n),
+            None => format!("document {}", self.index),
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    #[cfg(test)]
+    pub fn errors(&self) -> Vec<String> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.message.clone())
+            .collect()
+    }
+}
+
+/// One "- severity: message" group per report with diagnostics; with several reports each group is headed by
+/// the document label.
+pub fn render_reports(reports: &[DocumentReport]) -> Vec<String> {
+    let multi = reports.len() > 1;
+    reports
+        .iter()
+        .filter(|r| !r.diagnostics.is_empty())
+        .map(|r| {
+            let lines: Vec<String> = r
+                .diagnostics
+                .iter()
+                .map(|d| format!("- {}: {}", d.severity.as_str(), d.message))
+                .collect();
+            if multi {
+                format!("{}:\n{}", r.label(), lines.join("\n"))
+            } else {
+                lines.join("\n")
+            }
+        })
+        .collect()
+}
+
+/// Kubernetes documents emitted next to a DataFlow (apiVersion, kind): the Secret of secret_manifest, the
+/// ArgoCD Application of argocd_repo_url and the NetworkPolicy of network_policy.
+pub const COMPANION_KINDS: &[(&str, &str)] = &[
+    ("v1", "Secret"),
+    ("argoproj.io/v1alpha1", "Application"),
+    ("networking.k8s.io/v1", "NetworkPolicy"),
+];
+
+/// Validates every document of a (multi-document) YAML and returns one report per document; strict also
+/// reports fields outside the manifest schema (see strict_unknown_fields). Companion documents (COMPANION_KINDS)
+/// are skipped.
+pub fn validate_dataflow_documents(config_yaml: &str, strict: bool) -> Vec<DocumentReport> {
+    let docs = split_documents(config_yaml);
+    if docs.is_empty() {
+        let empty = Diagnostic::new(Severity::Error, "document is empty".to_string());
+        return vec![DocumentReport {
+            index: 1,
+            name: None,
+            diagnostics: vec![empty],
+        }];
+    }
+    docs.iter()
+        .enumerate()
+        .filter(|(_, (_, doc))| {
+            let v = serde_yaml::from_str::<Value>(doc).unwrap_or_default();
+            !COMPANION_KINDS
+                .iter()
+                .any(|(api, kind)| v["apiVersion"] == *api && v["kind"] == *kind)
+        })
+        .map(|(i, (offset, doc))| {
+            let name = serde_yaml::from_str::<Value>(doc)
+                .ok()
+                .and_then(|v| v["metadata"]["name"].as_str().map(str::to_string));
+            let mut diagnostics = diagnose_dataflow_manifest(doc);
+            if strict {
+                let unknown = strict_unknown_fields(doc)
+                    .into_iter()
+                    .map(|e| Diagnostic::located(Severity::Error, e, doc));
+                diagnostics.extend(unknown);
+                diagnostics.sort_by_key(|d| d.severity);
+            }
+            for d in &mut diagnostics {
+                d.line += offset;
+            }
+            DocumentReport {
+                index: i + 1,
+                name,
+                diagnostics,
+            }
+        })
+        .collect()
+}
+
+/// Validation errors of a manifest followed by non-fatal warnings and info notes, ordered by severity.
+pub fn diagnose_dataflow_manifest(config_yaml: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = validate_dataflow_manifest(config_yaml)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| Diagnostic::located(Severity::Error, e, config_yaml))
+        .collect();
+    if let Ok(parsed) = serde_yaml::from_str::<ParsedDataFlow>(config_yaml) {
+        if let Some(spec) = &parsed.spec {
+            let mut notes = Vec::new();
+            advisories(spec, &mut notes);
+            diagnostics.extend(
+                notes
+                    .into_iter()
+                    .map(|d| Diagnostic::located(d.severity, d.message, config_yaml)),
+            );
+        }
+    }
+    if let Ok(raw) = serde_yaml::from_str::<Value>(config_yaml) {
+        let secret = secret_name(raw["metadata"]["name"].as_str());
+        for c in plaintext_credentials(&raw["spec"], &secret) {
+            diagnostics.push(Diagnostic::located(
+                Severity::Warning,
+                c.message(),
+                config_yaml,
+            ));
+        }
+    }
+    diagnostics.sort_by_key(|d| d.severity);
+    diagnostics
+}
+
+/// Fields the manifest schema does not declare: top-level and spec fields, errorHandling, branches, and the
+/// transformation config blocks (connector blocks are always checked against the catalog). Kubernetes metadata
+/// and status are not checked.
+pub fn strict_unknown_fields(config_yaml: &str) -> Vec<String> {
+    let (Ok(manifest), Ok(schema)) = (
+        serde_yaml::from_str::<Value>(config_yaml),
+        manifest_json_schema(DATAFLOW_API_VERSION),
+    ) else {
+        return Vec::new();
+    };
+    let mut errors = Vec::new();
+    if let Some(map) = manifest.as_object() {
+        let known: Vec<String> = schema["properties"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(k, _)| k.clone())
+            .chain(["status".to_string()])
+            .collect();
+        for key in map.keys().filter(|k| !known.contains(k)) {
+            errors.push(unknown_field_message(key, key, &known));
+        }
+    }
+    check_known_fields(
+        &manifest["spec"],
+        &schema["properties"]["spec"],
+        &schema["$defs"],
+        "spec",
+        &mut errors,
+    );
+    errors
+}
+
+fn unknown_field_message(path: &str, field: &str, known: &[String]) -> String {
+    match closest_name(field, known) {
+        Some(s) => format!("{} is not a known field (did you mean {}?)", path, s),
+        None => format!(
+            "{} is not a known field; known fields: {}",
+            path,
+            known.join(", ")
+        ),
+    }
+}
+
+fn check_known_fields(
+    v: &Value,
+    schema: &Value,
+    defs: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let mut schema = schema;
+    if let Some(def) = schema["$ref"]
+        .as_str()
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+    {
+        // Connector blocks are checked against the catalog by the regular validation.
+        if def.starts_with("source.") || def.starts_with("sink.") {
+            return;
+        }
+        schema = &defs[def];
+    }
+    // Credentials: a string or a secretKeyRef object.
+    if let Some(options) = schema["oneOf"]
+        .as_array()
+        .filter(|_| schema["properties"].is_null())
+    {
+        if let Some(object) = options
+            .iter()
+            .find(|o| o["$ref"].is_string())
+            .filter(|_| v.is_object())
+        {
+            check_known_fields(v, object, defs, path, errors);
+        }
+        return;
+    }
+    match v {
+        Value::Object(map) => {
+            let properties = schema["properties"].as_object();
+            let known: Vec<String> = properties
+                .into_iter()
+                .flatten()
+                .map(|(k, _)| k.clone())
+                .collect();
+            for (key, child) in map {
+                let child_path = format!("{}.{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(s) => check_known_fields(child, s, defs, &child_path, errors),
+                    None if schema["additionalProperties"].is_object() => check_known_fields(
+                        child,
+                        &schema["additionalProperties"],
+                        defs,
+                        &child_path,
+                        errors,
+                    ),
+                    None if properties.is_some() => {
+                        errors.push(unknown_field_message(&child_path, key, &known))
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) if schema["items"].is_object() => {
+            for (i, item) in items.iter().enumerate() {
+                check_known_fields(
+                    item,
+                    &schema["items"],
+                    defs,
+                    &format!("{}[{}]", path, i),
+                    errors,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Warnings and info notes: settings left to operator defaults and deprecated fields.
+fn advisories(spec: &ParsedSpec, out: &mut Vec<Diagnostic>) {
+    if let Some(source) = &spec.source {
+        if let (Some("kafka"), Some(kafka)) = (source.type_.as_deref(), &source.kafka) {
+            if kafka.get("consumerGroup").is_none() {
+                out.push(Diagnostic::new(
+                    Severity::Warning,
+                    "spec.source.kafka.consumerGroup is not set; the operator derives one from the DataFlow name, so renaming the flow resets its offsets".to_string(),
+                ));
+            }
+        }
+        let polling = source
+            .type_
+            .as_deref()
+            .filter(|t| POLLING_SOURCE_TYPES.contains(t));
+        let block = match polling {
+            Some("postgresql") => &source.postgresql,
+            Some("trino") => &source.trino,
+            Some("clickhouse") => &source.clickhouse,
+            _ => &None,
+        };
+        if let (Some(t), Some(block)) = (polling, block) {
+            if block.get("pollInterval").is_none() && block.get("replication").is_none() {
+                out.push(Diagnostic::new(
+                    Severity::Info,
+                    format!(
+                        "spec.source.{}.pollInterval is not set; the operator default is used",
+                        t
+                    ),
+                ));
+            }
+        }
+    }
+    let mut sinks: Vec<(String, &ParsedSink)> = Vec::new();
+    if let Some(s) = &spec.sink {
+        sinks.push(("spec.sink".to_string(), s));
+    }
+    for (name, branch) in spec.branches.iter().flatten() {
+        if let Some(s) = &branch.sink {
+            sinks.push((format!("spec.branches.{}.sink", name), s));
+        }
+    }
+    if let Some(s) = spec.error_handling.as_ref().and_then(|eh| eh.dlq.as_ref()) {
+        sinks.push(("spec.errorHandling.dlq".to_string(), s));
+    }
+    for (path, sink) in sinks {
+        let sink_type = sink.type_.as_deref().unwrap_or("");
+        let block = match sink_type {
+            "postgresql" => &sink.postgresql,
+            "trino" => &sink.trino,
+            "clickhouse" => &sink.clickhouse,
+            _ => continue,
+        };
+        let Some(block) = block else { continue };
+        if block.get("batchSize").is_none() {
+            out.push(Diagnostic::new(
+                Severity::Info,
+                format!(
+                    "{}.{}.batchSize is not set; the operator default is used",
+                    path, sink_type
+                ),
+            ));
+        }
+        if sink_type == "postgresql"
+            && block.get("rawMode").is_some()
+            && block.get("payloadMode").is_none()
+        {
+            out.push(Diagnostic::new(
+                Severity::Warning,
+                format!(
+                    "{}.postgresql.rawMode is deprecated; use payloadMode: jsonb",
+                    path
+                ),
+            ));
+        }
+    }
+}
+
 /// Validates a DataFlow YAML manifest: parsing, apiVersion/kind, spec.source/spec.sink, and basic required fields per type.
+/// Router routes and named branches (spec.branches) are checked so every route reaches a valid sink;
+/// transformations with onError: dlq require spec.errorHandling.dlq.
 pub fn validate_dataflow_manifest(config_yaml: &str) -> Result<(), Vec<String>> {
-    let parsed: ParsedDataFlow = serde_yaml::from_str(config_yaml).map_err(|e| {
-        vec![format!("YAML parse error: {}", e)]
-    })?;
+    let parsed: ParsedDataFlow =
+        serde_yaml::from_str(config_yaml).map_err(|e| vec![format!("YAML parse error: {}", e)])?;
 
     let mut errors = Vec::new();
 
-    if parsed.api_version.as_deref() != Some(DATAFLOW_API_VERSION) {
-        errors.push(format!(
-            "apiVersion must be '{}'",
-            DATAFLOW_API_VERSION
-        ));
+    match parsed.api_version.as_deref() {
+        Some(DATAFLOW_API_VERSION) => {}
+        Some(old) if crate::tools::conversion::can_convert(old) => errors.push(format!(
+            "apiVersion must be '{}' ({} can be upgraded with convert_dataflow_apiversion)",
+            DATAFLOW_API_VERSION, old
+        )),
+        _ => errors.push(format!("apiVersion must be '{}'", DATAFLOW_API_VERSION)),
     }
     if parsed.kind.as_deref() != Some(DATAFLOW_KIND) {
         errors.push(format!("kind must be '{}'", DATAFLOW_KIND));
     }
+    match &parsed.metadata {
+        Some(metadata) => validate_metadata(metadata, &mut errors),
+        None => errors.push("metadata.name is required".to_string()),
+    }
     let spec = match &parsed.spec {
         Some(s) => s,
         None => {
@@ -122,215 +925,3319 @@ pub fn validate_dataflow_manifest(config_yaml: &str) -> Result<(), Vec<String>>
         }
     };
 
-    let source_type = source.type_.as_deref().unwrap_or("");
-    if !SOURCE_TYPES.contains(&source_type) {
+    validate_source(source, "spec.source", &mut errors);
+    validate_sink(sink, "spec.sink", &mut errors);
+    if let Ok(raw) = serde_yaml::from_str::<Value>(config_yaml) {
+        check_secret_refs(&raw["spec"], &mut errors);
+        validate_format_compatibility(&raw["spec"], &mut errors);
+    }
+    check_int_range(
+        spec.parallelism.as_ref(),
+        "spec.parallelism",
+        1,
+        MAX_PARALLELISM,
+        &mut errors,
+    );
+    if let Some(resources) = &spec.resources {
+        validate_resources(resources, "spec.resources", &mut errors);
+    }
+    if spec.replicas.is_some() {
         errors.push(format!(
-            "spec.source.type must be one of: {}",
-            SOURCE_TYPES.join(", ")
+            "spec.replicas is not a DataFlow v1 field; set the number of pods with spec.parallelism (an integer between 1 and {})",
+            MAX_PARALLELISM
         ));
+    }
+
+    let branches = spec.branches.clone().unwrap_or_default();
+    let dlq = spec.error_handling.as_ref().and_then(|eh| eh.dlq.as_ref());
+    if let Some(d) = dlq {
+        validate_sink(d, "spec.errorHandling.dlq", &mut errors);
+        if let Some((kind, object)) = spec.sink.as_ref().and_then(|s| same_destination(d, s)) {
+            errors.push(format!(
+                "spec.errorHandling.dlq writes to the same {} {} as spec.sink; send failed messages to a separate topic or table",
+                kind, object
+            ));
+        }
+    } else if spec.error_handling.is_some() {
+        errors
+            .push("spec.errorHandling.dlq is required when spec.errorHandling is set".to_string());
+    }
+    let ctx = ChainContext {
+        branches: &branches,
+        has_dlq: dlq.is_some(),
+    };
+    if let Some(transformations) = &spec.transformations {
+        validate_transformations(transformations, "spec.transformations", &ctx, &mut errors);
+    }
+    for (name, branch) in &branches {
+        let path = format!("spec.branches.{}", name);
+        match &branch.sink {
+            Some(s) => validate_sink(s, &format!("{}.sink", path), &mut errors),
+            None => errors.push(format!("{}.sink is required", path)),
+        }
+        if let Some(transformations) = &branch.transformations {
+            validate_transformations(
+                transformations,
+                &format!("{}.transformations", path),
+                &ctx,
+                &mut errors,
+            );
+        }
+    }
+    validate_loops(spec, &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
     } else {
-        match source_type {
-            "kafka" => {
-                if source.kafka.is_none() {
-                    errors.push("spec.source.kafka is required when source.type is kafka".to_string());
-                }
+        Err(errors)
+    }
+}
+
+/// Kafka sinks (spec.sink, branch sinks, router route sinks, the DLQ) writing to the topic the kafka source
+/// reads on the same brokers: every written message would be consumed again, forever.
+fn validate_loops(spec: &ParsedSpec, errors: &mut Vec<String>) {
+    let source = match spec
+        .source
+        .as_ref()
+        .filter(|s| s.type_.as_deref() == Some("kafka"))
+        .and_then(|s| s.kafka.as_ref())
+    {
+        Some(k) => k,
+        None => return,
+    };
+    // (path of the sink's kafka.topic, its kafka block)
+    let mut sinks: Vec<(String, Value)> = Vec::new();
+    let mut add_sink = |path: String, type_: Option<&str>, kafka: Option<&Value>| {
+        if let (Some("kafka"), Some(kafka)) = (type_, kafka) {
+            sinks.push((format!("{}.kafka.topic", path), kafka.clone()));
+        }
+    };
+    let parsed_sinks = spec
+        .sink
+        .iter()
+        .map(|s| ("spec.sink".to_string(), s))
+        .chain(
+            spec.error_handling
+                .iter()
+                .filter_map(|eh| eh.dlq.as_ref())
+                .map(|s| ("spec.errorHandling.dlq".to_string(), s)),
+        )
+        .chain(spec.branches.iter().flatten().filter_map(|(name, b)| {
+            b.sink
+                .as_ref()
+                .map(|s| (format!("spec.branches.{}.sink", name), s))
+        }));
+    for (path, sink) in parsed_sinks {
+        add_sink(path, sink.type_.as_deref(), sink.kafka.as_ref());
+    }
+    let mut chains = vec![(
+        "spec.transformations".to_string(),
+        spec.transformations.as_deref().unwrap_or_default(),
+    )];
+    for (name, branch) in spec.branches.iter().flatten() {
+        chains.push((
+            format!("spec.branches.{}.transformations", name),
+            branch.transformations.as_deref().unwrap_or_default(),
+        ));
+    }
+    for (path, chain) in &chains {
+        for (i, t) in chain
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t["type"] == "router")
+        {
+            for (j, route) in t["router"]["routes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                let sink = &route["sink"];
+                add_sink(
+                    format!("{}[{}].router.routes[{}].sink", path, i, j),
+                    sink["type"].as_str(),
+                    sink.get("kafka"),
+                );
             }
-            "postgresql" => {
-                if source.postgresql.is_none() {
-                    errors.push("spec.source.postgresql is required when source.type is postgresql".to_string());
-                }
+        }
+    }
+    let brokers = |k: &Value| -> Vec<String> {
+        k["brokers"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|b| b.trim().to_lowercase())
+            .collect()
+    };
+    let source_brokers = brokers(source);
+    for (path, kafka) in sinks {
+        let Some(topic) = kafka["topic"].as_str() else {
+            continue;
+        };
+        // Unknown brokers on either side: assume the same cluster.
+        let sink_brokers = brokers(&kafka);
+        if !source_brokers.is_empty()
+            && !sink_brokers.is_empty()
+            && !sink_brokers.iter().any(|b| source_brokers.contains(b))
+        {
+            continue;
+        }
+        let what = if path.contains(".router.") {
+            "routes messages back into"
+        } else {
+            "writes to"
+        };
+        if source["topic"].as_str() == Some(topic) {
+            errors.push(format!(
+                "{} '{}' {} the source topic on the same brokers: every message written is consumed again (infinite loop); use a different topic",
+                path, topic, what
+            ));
+        } else if let Some(pattern) = source["topicPattern"].as_str() {
+            if regex::Regex::new(&format!("^(?:{})$", pattern)).is_ok_and(|re| re.is_match(topic)) {
+                errors.push(format!(
+                    "{} '{}' matches the source topicPattern '{}' on the same brokers: every message written is consumed again (infinite loop); use a topic outside the pattern",
+                    path, topic, pattern
+                ));
             }
-            "trino" => {
-                if source.trino.is_none() {
-                    errors.push("spec.source.trino is required when source.type is trino".to_string());
-                }
+        }
+    }
+}
+
+/// Kubernetes object metadata: name (DNS-1123 subdomain, at most 63 characters as the operator uses it as a
+/// label value), namespace (DNS-1123 label), label keys/values and annotation keys.
+fn validate_metadata(metadata: &ParsedMetadata, errors: &mut Vec<String>) {
+    match metadata.name.as_deref() {
+        Some(name) => {
+            if let Err(e) = check_dns1123_subdomain(name) {
+                errors.push(format!(
+                    "metadata.name '{}' is not a valid Kubernetes name: {}",
+                    name, e
+                ));
+            } else if name.len() > 63 {
+                errors.push(format!("metadata.name '{}' must be at most 63 characters (it is used as a label value)", name));
             }
-            "clickhouse" => {
-                if source.clickhouse.is_none() {
-                    errors.push("spec.source.clickhouse is required when source.type is clickhouse".to_string());
+        }
+        None => errors.push("metadata.name is required".to_string()),
+    }
+    if let Some(ns) = metadata.namespace.as_deref() {
+        if let Err(e) = check_dns1123_label(ns) {
+            errors.push(format!(
+                "metadata.namespace '{}' is not a valid namespace: {}",
+                ns, e
+            ));
+        }
+    }
+    for (key, value) in metadata.labels.iter().flatten() {
+        if let Err(e) = check_qualified_key(key) {
+            errors.push(format!("metadata.labels: key '{}' is invalid: {}", key, e));
+        }
+        match value.as_str() {
+            Some(v) => {
+                if let Err(e) = check_label_value(v) {
+                    errors.push(format!("metadata.labels.{}: value '{}' {}", key, v, e));
                 }
             }
-            _ => {}
+            None => errors.push(format!(
+                "metadata.labels.{} must be a string (quote numbers and booleans)",
+                key
+            )),
+        }
+    }
+    for (key, value) in metadata.annotations.iter().flatten() {
+        if let Err(e) = check_qualified_key(key) {
+            errors.push(format!(
+                "metadata.annotations: key '{}' is invalid: {}",
+                key, e
+            ));
+        }
+        if !value.is_string() {
+            errors.push(format!(
+                "metadata.annotations.{} must be a string (quote numbers and booleans)",
+                key
+            ));
         }
     }
+    let annotations_size: usize = metadata
+        .annotations
+        .iter()
+        .flatten()
+        .map(|(k, v)| k.len() + v.as_str().map(str::len).unwrap_or(0))
+        .sum();
+    if annotations_size > 256 * 1024 {
+        errors.push(format!(
+            "metadata.annotations total {} bytes, more than the 256 KiB Kubernetes allows",
+            annotations_size
+        ));
+    }
+}
 
-    let sink_type = sink.type_.as_deref().unwrap_or("");
-    if !SINK_TYPES.contains(&sink_type) {
+/// Checks source type, the presence of its type-specific block and type-specific field values.
+fn validate_source(source: &ParsedSource, path: &str, errors: &mut Vec<String>) {
+    let source_type = source.type_.as_deref().unwrap_or("");
+    if !SOURCE_TYPES.contains(&source_type) {
         errors.push(format!(
-            "spec.sink.type must be one of: {}",
-            SINK_TYPES.join(", ")
+            "{}.type must be one of: {}",
+            path,
+            SOURCE_TYPES.join(", ")
         ));
-    } else {
-        match sink_type {
-            "kafka" => {
-                if sink.kafka.is_none() {
-                    errors.push("spec.sink.kafka is required when sink.type is kafka".to_string());
-                }
-            }
-            "postgresql" => {
-                if sink.postgresql.is_none() {
-                    errors.push("spec.sink.postgresql is required when sink.type is postgresql".to_string());
-                }
-            }
-            "trino" => {
-                if sink.trino.is_none() {
-                    errors.push("spec.sink.trino is required when sink.type is trino".to_string());
-                }
-            }
-            "clickhouse" => {
-                if sink.clickhouse.is_none() {
-                    errors.push("spec.sink.clickhouse is required when sink.type is clickhouse".to_string());
+        return;
+    }
+    let block = match source_type {
+        "kafka" => &source.kafka,
+        "postgresql" => &source.postgresql,
+        "trino" => &source.trino,
+        "clickhouse" => &source.clickhouse,
+        "syslog" => &source.syslog,
+        _ => return,
+    };
+    let block = match block {
+        Some(b) => b,
+        None => {
+            errors.push(format!(
+                "{}.{} is required when source.type is {}",
+                path, source_type, source_type
+            ));
+            return;
+        }
+    };
+    validate_catalog_fields(
+        block,
+        "sources",
+        source_type,
+        &format!("{}.{}", path, source_type),
+        errors,
+    );
+    validate_field_dependencies(
+        block,
+        source_type,
+        &format!("{}.{}", path, source_type),
+        errors,
+    );
+    validate_schema_registry(block, &format!("{}.{}", path, source_type), errors);
+    validate_connection(
+        block,
+        source_type,
+        &format!("{}.{}", path, source_type),
+        errors,
+    );
+    validate_intervals(block, &format!("{}.{}", path, source_type), errors);
+    match source_type {
+        "syslog" => validate_syslog(block, &format!("{}.syslog", path), errors),
+        "postgresql" => {
+            validate_postgres_replication(block, &format!("{}.postgresql", path), errors)
+        }
+        "kafka" => {
+            validate_kafka_format(block, &format!("{}.kafka", path), errors);
+            validate_cloudevents(block, &format!("{}.kafka", path), false, errors);
+            validate_topic_pattern(block, &format!("{}.kafka", path), errors);
+            if let Some(reset) = block.get("offsetReset") {
+                if !reset
+                    .as_str()
+                    .map(|r| KAFKA_OFFSET_RESETS.contains(&r))
+                    .unwrap_or(false)
+                {
+                    errors.push(format!(
+                        "{}.kafka.offsetReset must be one of: {}",
+                        path,
+                        KAFKA_OFFSET_RESETS.join(", ")
+                    ));
                 }
             }
-            _ => {}
         }
+        _ => {}
+    }
+    if POLLING_SOURCE_TYPES.contains(&source_type) {
+        validate_poll_cursor(block, &format!("{}.{}", path, source_type), errors);
     }
+}
 
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(errors)
+/// Checks a connector block against the connector catalog (required_fields / optional_fields in reference.rs):
+/// every required field must be present and every field name must be known.
+fn validate_catalog_fields(
+    block: &Value,
+    kind: &str,
+    connector: &str,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let catalog = crate::tools::reference::default_connectors();
+    let entry = match catalog.get(kind).and_then(|c| c.get(connector)) {
+        Some(e) => e,
+        None => return,
+    };
+    let names = |key: &str| -> Vec<String> {
+        entry[key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    let required = names("required_fields");
+    let known: Vec<String> = required
+        .iter()
+        .cloned()
+        .chain(names("optional_fields"))
+        .collect();
+    let map = match block.as_object() {
+        Some(m) => m,
+        None => {
+            errors.push(format!("{} must be an object", path));
+            return;
+        }
+    };
+    for field in &required {
+        // A kafka source subscribes either to topic or to topicPattern.
+        let replaced = field == "topic" && kind == "sources" && map.contains_key("topicPattern");
+        if !map.contains_key(field) && !replaced {
+            errors.push(format!("{}.{} is required", path, field));
+        }
+    }
+    for field in map.keys().filter(|k| !known.contains(k)) {
+        match closest_name(field, &known) {
+            Some(s) => errors.push(format!(
+                "{}.{} is not a known {} field (did you mean {}?)",
+                path, field, connector, s
+            )),
+            None => errors.push(format!(
+                "{}.{} is not a known {} field; known fields: {}",
+                path,
+                field,
+                connector,
+                known.join(", ")
+            )),
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// connectionString / serverURL must be a URL with a scheme the connector supports (JDBC URLs are rejected
+/// with their normalized form).
+fn validate_connection(block: &Value, connector: &str, path: &str, errors: &mut Vec<String>) {
+    for field in ["connectionString", "serverURL"] {
+        if let Some(value) = block.get(field).and_then(Value::as_str) {
+            if let Err(e) = check_connection(connector, value) {
+                errors.push(format!("{}.{}: {}", path, field, e));
+            }
+        }
+    }
+}
 
-    #[test]
-    fn test_generate_dataflow_manifest_kafka_postgresql() {
-        let yaml = generate_dataflow_manifest(
-            Some("Kafka to PostgreSQL"),
-            "kafka",
-            "postgresql",
-            Some(r#"{"brokers":["localhost:9092"],"topic":"input-topic"}"#),
-            Some(r#"{"connectionString":"postgres://u:p@h/db","table":"t"}"#),
+/// Connector duration fields (Go-style strings) and their accepted range in milliseconds.
+const CONNECTOR_DURATIONS: [(&str, u64, u64); 1] = [("pollInterval", 1_000, 86_400_000)];
+/// Connector interval fields given as whole seconds, and their accepted range.
+const CONNECTOR_SECONDS: [(&str, i64, i64); 1] = [("batchFlushIntervalSeconds", 1, 3_600)];
+
+/// pollInterval and flush intervals of a connector block: format and sane ranges.
+fn validate_intervals(block: &Value, path: &str, errors: &mut Vec<String>) {
+    for (field, min_ms, max_ms) in CONNECTOR_DURATIONS {
+        check_duration_range(
+            block.get(field),
+            &format!("{}.{}", path, field),
+            min_ms,
+            max_ms,
+            errors,
+        );
+    }
+    for (field, min, max) in CONNECTOR_SECONDS {
+        let f_path = format!("{}.{}", path, field);
+        match block
+            .get(field)
+            .and_then(Value::as_str)
+            .map(parse_duration_ms)
+        {
+            Some(Some(ms)) => errors.push(format!(
+                "{} is a number of seconds, not a duration string; use {}",
+                f_path,
+                (ms / 1_000).max(1)
+            )),
+            _ => check_int_range(block.get(field), &f_path, min, max, errors),
+        }
+    }
+}
+
+/// Closest known name for a misspelled field: same name ignoring case and -/_, or edit distance of at most 2.
+fn closest_name<'a>(field: &str, known: &'a [String]) -> Option<&'a str> {
+    let normalize = |s: &str| s.to_lowercase().replace(['_', '-'], "");
+    let wanted = normalize(field);
+    known
+        .iter()
+        .map(|k| (k, edit_distance(&wanted, &normalize(k))))
+        .filter(|(_, d)| *d <= 2)
+        .min_by_key(|(_, d)| *d)
+        .map(|(k, _)| k.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = (prev + usize::from(ca != *cb)).min(row[j] + 1).min(cur + 1);
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Incremental polling: each poll reads rows with incrementalColumn greater than the stored cursor.
+fn validate_poll_cursor(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let mode = match block.get("mode") {
+        Some(m) => m.as_str().unwrap_or(""),
+        None => "bulk",
+    };
+    if !POLL_MODES.contains(&mode) {
+        errors.push(format!(
+            "{}.mode must be one of: {}",
+            path,
+            POLL_MODES.join(", ")
+        ));
+        return;
+    }
+    if mode == "bulk" {
+        for field in ["incrementalColumn", "initialValue", "cursorStorage"] {
+            if block.get(field).is_some() {
+                errors.push(format!("{}.{} requires mode: incremental", path, field));
+            }
+        }
+        return;
+    }
+    if block.get("replication").is_some() {
+        errors.push(format!(
+            "{}: mode incremental cannot be used with replication",
+            path
+        ));
+    }
+    match block.get("incrementalColumn").and_then(Value::as_str) {
+        Some(c) if is_sql_identifier(c) => {}
+        Some(c) => errors.push(format!(
+            "{}.incrementalColumn '{}' is not a valid column name",
+            path, c
+        )),
+        None => errors.push(format!(
+            "{}.incrementalColumn is required when mode is incremental",
+            path
+        )),
+    }
+    if let Some(v) = block.get("initialValue") {
+        if !(v.is_string() || v.is_number()) {
+            errors.push(format!(
+                "{}.initialValue must be a string or a number",
+                path
+            ));
+        }
+    }
+    if let Some(storage) = block.get("cursorStorage") {
+        if !storage
+            .as_str()
+            .map(|s| CURSOR_STORAGES.contains(&s))
+            .unwrap_or(false)
+        {
+            errors.push(format!(
+                "{}.cursorStorage must be one of: {}",
+                path,
+                CURSOR_STORAGES.join(", ")
+            ));
+        }
+    }
+}
+
+/// topicPattern subscribes a kafka source to every topic matching a regex; it replaces topic.
+fn validate_topic_pattern(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let pattern = match block.get("topicPattern") {
+        Some(p) => p,
+        None => return,
+    };
+    if block.get("topic").is_some() {
+        errors.push(format!(
+            "{}: topic and topicPattern are mutually exclusive",
+            path
+        ));
+    }
+    match pattern.as_str() {
+        Some(p) if !p.is_empty() => {
+            if let Err(e) = regex::Regex::new(p) {
+                errors.push(format!("{}.topicPattern is not a valid regex: {}", path, e));
+            }
+        }
+        _ => errors.push(format!(
+            "{}.topicPattern must be a non-empty regex string",
+            path
+        )),
+    }
+}
+
+/// CloudEvents envelope on a kafka block: mode, extension attribute mapping, and for sinks the required type/source.
+fn validate_cloudevents(block: &Value, path: &str, is_sink: bool, errors: &mut Vec<String>) {
+    let ce = match block.get("cloudevents") {
+        Some(ce) => ce,
+        None => return,
+    };
+    let path = format!("{}.cloudevents", path);
+    if !ce
+        .get("mode")
+        .and_then(Value::as_str)
+        .map(|m| CLOUDEVENTS_MODES.contains(&m))
+        .unwrap_or(false)
+    {
+        errors.push(format!(
+            "{}.mode must be one of: {}",
+            path,
+            CLOUDEVENTS_MODES.join(", ")
+        ));
+    }
+    if let Some(ext) = ce.get("extensions") {
+        match ext.as_object() {
+            Some(map) => {
+                for (name, field) in map {
+                    let valid_name = !name.is_empty()
+                        && name.len() <= 20
+                        && name
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+                    if !valid_name {
+                        errors.push(format!(
+                            "{}.extensions: attribute name '{}' must be 1-20 lowercase letters or digits",
+                            path, name
+                        ));
+                    }
+                    if !field.is_string() {
+                        errors.push(format!(
+                            "{}.extensions.{} must be a field path string",
+                            path, name
+                        ));
+                    }
+                }
+            }
+            None => errors.push(format!(
+                "{}.extensions must be a map of attribute name to field path",
+                path
+            )),
+        }
+    }
+    if is_sink {
+        for attr in ["type", "source"] {
+            if ce
+                .get(attr)
+                .and_then(Value::as_str)
+                .map(|v| v.is_empty())
+                .unwrap_or(true)
+            {
+                errors.push(format!(
+                    "{}.{} is required when writing CloudEvents",
+                    path, attr
+                ));
+            }
+        }
+    }
+}
+
+/// Checks the kafka block format and, when present, the inline avroSchema content.
+fn validate_kafka_format(block: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(format) = block.get("format") {
+        if !format
+            .as_str()
+            .map(|f| KAFKA_FORMATS.contains(&f))
+            .unwrap_or(false)
+        {
+            errors.push(format!(
+                "{}.format must be one of: {}",
+                path,
+                KAFKA_FORMATS.join(", ")
+            ));
+        }
+    }
+    if let Some(schema) = block.get("avroSchema") {
+        for problem in check_avro_schema(schema) {
+            errors.push(format!("{}.avroSchema: {}", path, problem));
+        }
+    }
+    // Schema requirements of each format are in FIELD_DEPENDENCIES.
+    if block.get("format").and_then(Value::as_str) == Some("jsonschema") {
+        match block.get("jsonSchema") {
+            Some(Value::String(s))
+                if serde_json::from_str::<Value>(s)
+                    .map(|v| !v.is_object())
+                    .unwrap_or(true) =>
+            {
+                errors.push(format!("{}.jsonSchema must be a JSON object", path));
+            }
+            Some(v) if !v.is_object() && !v.is_string() => {
+                errors.push(format!("{}.jsonSchema must be a JSON object", path));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Checks the FIELD_DEPENDENCIES of a connector block: fields that only work together with others.
+fn validate_field_dependencies(
+    block: &Value,
+    connector: &str,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let get = |field: &str| field.split('.').try_fold(block, |v, key| v.get(key));
+    let is_set = |field: &str| get(field).is_some_and(|v| !v.is_null() && v.as_str() != Some(""));
+    for dep in FIELD_DEPENDENCIES
+        .iter()
+        .filter(|d| d.connector == connector)
+    {
+        let triggered = match (get(dep.field), dep.equals) {
+            (Some(v), Some(expected)) => {
+                v.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| v.to_string())
+                    == expected
+            }
+            (Some(v), None) => !v.is_null(),
+            (None, _) => false,
+        };
+        if !triggered {
+            continue;
+        }
+        if dep.any {
+            if !dep.requires.iter().any(|r| is_set(r)) {
+                errors.push(format!(
+                    "{}: {} requires {}",
+                    path,
+                    dep.when,
+                    dep.requires.join(" or ")
+                ));
+            }
+        } else {
+            for r in dep.requires.iter().filter(|r| !is_set(r)) {
+                errors.push(format!("{}.{} is required with {}", path, r, dep.when));
+            }
+        }
+    }
+}
+
+fn validate_syslog(block: &Value, path: &str, errors: &mut Vec<String>) {
+    if block
+        .get("address")
+        .and_then(Value::as_str)
+        .map(|a| a.is_empty())
+        .unwrap_or(true)
+    {
+        errors.push(format!(
+            "{}.address is required (e.g. \"0.0.0.0:514\")",
+            path
+        ));
+    }
+    if let Some(protocol) = block.get("protocol") {
+        if !protocol
+            .as_str()
+            .map(|p| SYSLOG_PROTOCOLS.contains(&p))
+            .unwrap_or(false)
+        {
+            errors.push(format!(
+                "{}.protocol must be one of: {}",
+                path,
+                SYSLOG_PROTOCOLS.join(", ")
+            ));
+        }
+    }
+    if let Some(parser) = block.get("parser") {
+        if !parser
+            .as_str()
+            .map(|p| SYSLOG_PARSERS.contains(&p))
+            .unwrap_or(false)
+        {
+            errors.push(format!(
+                "{}.parser must be one of: {}",
+                path,
+                SYSLOG_PARSERS.join(", ")
+            ));
+        }
+    }
+}
+
+/// Logical replication (CDC) mode of a postgresql source; streams changes from a slot instead of polling,
+/// so query and pollInterval do not apply.
+fn validate_postgres_replication(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let replication = match block.get("replication") {
+        Some(r) => r,
+        None => return,
+    };
+    let path = format!("{}.replication", path);
+    if !replication.is_object() {
+        errors.push(format!(
+            "{} must be an object with slotName and publication",
+            path
+        ));
+        return;
+    }
+    for field in ["query", "pollInterval"] {
+        if block.get(field).is_some() {
+            errors.push(format!(
+                "{}: {} cannot be used with replication (polling and CDC are mutually exclusive)",
+                path, field
+            ));
+        }
+    }
+    // Slot names are stricter than identifiers: only lower case letters, digits and underscores.
+    match replication.get("slotName").and_then(Value::as_str) {
+        Some(slot) if is_sql_identifier(slot) && !slot.chars().any(|c| c.is_ascii_uppercase()) => {}
+        Some(slot) => errors.push(format!(
+            "{}.slotName '{}' must contain only lower case letters, digits and underscores (max 63)",
+            path, slot
+        )),
+        None => errors.push(format!("{}.slotName is required", path)),
+    }
+    match replication.get("publication").and_then(Value::as_str) {
+        Some(p) if is_sql_identifier(p) => {}
+        Some(p) => errors.push(format!(
+            "{}.publication '{}' is not a valid identifier",
+            path, p
+        )),
+        None => errors.push(format!("{}.publication is required", path)),
+    }
+    if let Some(plugin) = replication.get("plugin") {
+        if !plugin
+            .as_str()
+            .map(|p| PG_REPLICATION_PLUGINS.contains(&p))
+            .unwrap_or(false)
+        {
+            errors.push(format!(
+                "{}.plugin must be one of: {}",
+                path,
+                PG_REPLICATION_PLUGINS.join(", ")
+            ));
+        }
+    }
+}
+
+/// Connector type and object (topic, table, index or bucket) when both sinks write there over the same connection.
+fn same_destination(a: &ParsedSink, b: &ParsedSink) -> Option<(String, String)> {
+    let destination = |s: &ParsedSink| {
+        let kind = s.type_.clone()?;
+        let block = match kind.as_str() {
+            "kafka" => s.kafka.as_ref(),
+            "postgresql" => s.postgresql.as_ref(),
+            "trino" => s.trino.as_ref(),
+            "clickhouse" => s.clickhouse.as_ref(),
+            "elasticsearch" => s.elasticsearch.as_ref(),
+            "s3" => s.s3.as_ref(),
+            _ => None,
+        }?;
+        let object = ["topic", "table", "index", "bucket"]
+            .iter()
+            .find_map(|f| block[*f].as_str())?
+            .to_string();
+        let connection = [
+            "brokers",
+            "connectionString",
+            "serverURL",
+            "addresses",
+            "endpoint",
+        ]
+        .iter()
+        .find_map(|f| block.get(*f))
+        .cloned();
+        Some((kind, object, connection, block["prefix"].clone()))
+    };
+    let a = destination(a)?;
+    (destination(b)? == a).then_some((a.0, a.1))
+}
+
+/// Checks sink type and the presence of its type-specific block; path is the sink location (e.g. spec.sink).
+fn validate_sink(sink: &ParsedSink, path: &str, errors: &mut Vec<String>) {
+    let sink_type = sink.type_.as_deref().unwrap_or("");
+    if !SINK_TYPES.contains(&sink_type) {
+        errors.push(format!(
+            "{}.type must be one of: {}",
+            path,
+            SINK_TYPES.join(", ")
+        ));
+        return;
+    }
+    let block = match sink_type {
+        "kafka" => &sink.kafka,
+        "postgresql" => &sink.postgresql,
+        "trino" => &sink.trino,
+        "clickhouse" => &sink.clickhouse,
+        "elasticsearch" => &sink.elasticsearch,
+        "s3" => &sink.s3,
+        _ => return,
+    };
+    let block = match block {
+        Some(b) => b,
+        None => {
+            errors.push(format!(
+                "{}.{} is required when sink.type is {}",
+                path, sink_type, sink_type
+            ));
+            return;
+        }
+    };
+    let block_path = format!("{}.{}", path, sink_type);
+    validate_catalog_fields(block, "sinks", sink_type, &block_path, errors);
+    validate_field_dependencies(block, sink_type, &block_path, errors);
+    validate_schema_registry(block, &block_path, errors);
+    validate_connection(block, sink_type, &block_path, errors);
+    validate_intervals(block, &block_path, errors);
+    if let Some(mapping) = block.get("columnMapping") {
+        if RELATIONAL_SINK_TYPES.contains(&sink_type) {
+            validate_column_mapping(mapping, block, &block_path, errors);
+        } else {
+            errors.push(format!(
+                "{}.columnMapping is only supported on sinks: {}",
+                block_path,
+                RELATIONAL_SINK_TYPES.join(", ")
+            ));
+        }
+    }
+    match sink_type {
+        "kafka" => {
+            validate_kafka_format(block, &block_path, errors);
+            validate_cloudevents(block, &block_path, true, errors);
+        }
+        "postgresql" => validate_payload_mode(block, &block_path, errors),
+        "elasticsearch" => validate_elasticsearch(block, &block_path, errors),
+        "trino" => validate_trino_write(block, &block_path, errors),
+        "s3" => validate_s3(block, &block_path, errors),
+        _ => {}
+    }
+    if let Some(batch) = block.get("batchTransformations") {
+        if RELATIONAL_SINK_TYPES.contains(&sink_type) {
+            validate_batch_transformations(batch, block, &block_path, errors);
+        } else {
+            errors.push(format!(
+                "{}.batchTransformations is only supported on sinks: {}",
+                block_path,
+                RELATIONAL_SINK_TYPES.join(", ")
+            ));
+        }
+    }
+}
+
+fn validate_elasticsearch(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let addresses_ok = block
+        .get("addresses")
+        .and_then(Value::as_array)
+        .map(|a| {
+            !a.is_empty()
+                && a.iter().all(|u| {
+                    u.as_str()
+                        .map(|u| u.starts_with("http://") || u.starts_with("https://"))
+                        .unwrap_or(false)
+                })
+        })
+        .unwrap_or(false);
+    if !addresses_ok {
+        errors.push(format!(
+            "{}.addresses must be a non-empty array of http(s) URLs",
+            path
+        ));
+    }
+    if block
+        .get("index")
+        .and_then(Value::as_str)
+        .map(|i| i.is_empty())
+        .unwrap_or(true)
+    {
+        errors.push(format!("{}.index is required", path));
+    }
+    for (field, allowed) in [
+        ("nullValueBehavior", &ES_NULL_VALUE_BEHAVIORS[..]),
+        ("documentIdStrategy", &ES_DOCUMENT_ID_STRATEGIES[..]),
+    ] {
+        if let Some(v) = block.get(field) {
+            if !v.as_str().map(|v| allowed.contains(&v)).unwrap_or(false) {
+                errors.push(format!(
+                    "{}.{} must be one of: {}",
+                    path,
+                    field,
+                    allowed.join(", ")
+                ));
+            }
+        }
+    }
+}
+
+/// Explicit message field -> column mapping: JSONPath field, SQL identifier column (unique), optional column type.
+fn validate_column_mapping(
+    mapping: &Value,
+    block: &Value,
+    block_path: &str,
+    errors: &mut Vec<String>,
+) {
+    let path = format!("{}.columnMapping", block_path);
+    let items = match mapping.as_array() {
+        Some(a) if !a.is_empty() => a,
+        _ => {
+            errors.push(format!(
+                "{} must be a non-empty array of {{field, column, type}}",
+                path
+            ));
+            return;
+        }
+    };
+    if let Some(mode @ ("jsonb" | "bytea")) = block.get("payloadMode").and_then(Value::as_str) {
+        errors.push(format!(
+            "{}: columnMapping cannot be used with payloadMode {}",
+            block_path, mode
+        ));
+    }
+    let mut seen: Vec<String> = Vec::new();
+    for (i, m) in items.iter().enumerate() {
+        let m_path = format!("{}[{}]", path, i);
+        match m.get("field").and_then(Value::as_str) {
+            Some(field) => {
+                if let Err(e) = check_jsonpath(field) {
+                    errors.push(format!(
+                        "{}.field '{}' is not a valid JSONPath: {}",
+                        m_path, field, e
+                    ));
+                }
+            }
+            None => errors.push(format!("{}.field is required", m_path)),
+        }
+        match m.get("column").and_then(Value::as_str) {
+            Some(column) => {
+                if !is_sql_identifier(column) {
+                    errors.push(format!(
+                        "{}.column '{}' must be a SQL identifier (letters, digits, _; max 63 chars)",
+                        m_path, column
+                    ));
+                } else if seen.contains(&column.to_lowercase()) {
+                    errors.push(format!(
+                        "{}.column '{}' is mapped more than once",
+                        m_path, column
+                    ));
+                } else {
+                    seen.push(column.to_lowercase());
+                }
+            }
+            None => errors.push(format!("{}.column is required", m_path)),
+        }
+        if let Some(t) = m.get("type") {
+            if t.as_str().map(|t| t.trim().is_empty()).unwrap_or(true) {
+                errors.push(format!("{}.type must be a non-empty column type", m_path));
+            }
+        }
+    }
+}
+
+fn is_sql_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && s.len() <= 63
+}
+
+/// S3 sink: objects are flushed every flushSize records under keys built by the partitioner.
+fn validate_s3(block: &Value, path: &str, errors: &mut Vec<String>) {
+    for field in ["bucket", "region"] {
+        if block
+            .get(field)
+            .and_then(Value::as_str)
+            .map(|v| v.is_empty())
+            .unwrap_or(true)
+        {
+            errors.push(format!("{}.{} is required", path, field));
+        }
+    }
+    if let Some(format) = block.get("format") {
+        if !format
+            .as_str()
+            .map(|f| S3_FORMATS.contains(&f))
+            .unwrap_or(false)
+        {
+            errors.push(format!(
+                "{}.format must be one of: {}",
+                path,
+                S3_FORMATS.join(", ")
+            ));
+        }
+    }
+    check_int_range(
+        block.get("flushSize"),
+        &format!("{}.flushSize", path),
+        1,
+        10_000_000,
+        errors,
+    );
+    let partitioner = match block.get("partitioner") {
+        Some(p) => p.as_str().unwrap_or(""),
+        None => return,
+    };
+    match partitioner {
+        "field" => {
+            let ok = block
+                .get("partitionFields")
+                .and_then(Value::as_array)
+                .map(|f| !f.is_empty())
+                .unwrap_or(false);
+            if !ok {
+                errors.push(format!(
+                    "{}.partitionFields must be a non-empty array for partitioner field",
+                    path
+                ));
+            }
+        }
+        "time" => {
+            if block.get("pathFormat").and_then(Value::as_str).is_none() {
+                errors.push(format!(
+                    "{}.pathFormat is required for partitioner time",
+                    path
+                ));
+            }
+        }
+        "default" => {}
+        _ => errors.push(format!(
+            "{}.partitioner must be one of: {}",
+            path,
+            S3_PARTITIONERS.join(", ")
+        )),
+    }
+}
+
+/// Trino sink write options. fileFormat and partitionBy only take effect when the sink creates the table
+/// (ctas or autoCreateTable) and depend on the connector behind the catalog; MERGE needs an Iceberg table.
+fn validate_trino_write(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let strategy = match block.get("insertStrategy") {
+        Some(s) => {
+            let s = s.as_str().unwrap_or("");
+            if !TRINO_INSERT_STRATEGIES.contains(&s) {
+                errors.push(format!(
+                    "{}.insertStrategy must be one of: {}",
+                    path,
+                    TRINO_INSERT_STRATEGIES.join(", ")
+                ));
+                return;
+            }
+            s
+        }
+        None => "insert",
+    };
+    let catalog_type = match block.get("catalogType") {
+        Some(c) => {
+            let c = c.as_str().unwrap_or("");
+            if !TRINO_CATALOG_TYPES.contains(&c) {
+                errors.push(format!(
+                    "{}.catalogType must be one of: {}",
+                    path,
+                    TRINO_CATALOG_TYPES.join(", ")
+                ));
+                return;
+            }
+            Some(c)
+        }
+        None => None,
+    };
+    let file_format = block.get("fileFormat");
+    let partition_by = block.get("partitionBy");
+    if (file_format.is_some() || partition_by.is_some() || strategy == "merge")
+        && catalog_type.is_none()
+    {
+        errors.push(format!(
+            "{}.catalogType (hive or iceberg) is required with fileFormat, partitionBy or insertStrategy merge",
+            path
+        ));
+        return;
+    }
+    let creates_table =
+        strategy == "ctas" || block.get("autoCreateTable").and_then(Value::as_bool) == Some(true);
+    if (file_format.is_some() || partition_by.is_some()) && !creates_table {
+        errors.push(format!(
+            "{}: fileFormat and partitionBy only apply when the table is created (insertStrategy ctas or autoCreateTable: true)",
+            path
+        ));
+    }
+    let catalog_type = catalog_type.unwrap_or("");
+    if let Some(format) = file_format {
+        let allowed: &[&str] = if catalog_type == "hive" {
+            &TRINO_HIVE_FILE_FORMATS
+        } else {
+            &TRINO_ICEBERG_FILE_FORMATS
+        };
+        let f = format.as_str().unwrap_or("").to_uppercase();
+        if !allowed.contains(&f.as_str()) {
+            errors.push(format!(
+                "{}.fileFormat must be one of ({} catalog): {}",
+                path,
+                catalog_type,
+                allowed.join(", ")
+            ));
+        }
+    }
+    if let Some(columns) = partition_by {
+        match columns.as_array() {
+            Some(cols) if !cols.is_empty() => {
+                for (i, c) in cols.iter().enumerate() {
+                    let c = c.as_str().unwrap_or("");
+                    // Iceberg accepts partition transforms such as day(ts) or bucket(id, 16); Hive only plain columns.
+                    let ok = is_sql_identifier(c)
+                        || (catalog_type == "iceberg" && is_partition_transform(c));
+                    if !ok {
+                        errors.push(format!(
+                            "{}.partitionBy[{}] '{}' is not a valid {} partition column",
+                            path, i, c, catalog_type
+                        ));
+                    }
+                }
+            }
+            _ => errors.push(format!(
+                "{}.partitionBy must be a non-empty array of columns",
+                path
+            )),
+        }
+    }
+    if strategy == "merge" {
+        if catalog_type != "iceberg" {
+            errors.push(format!(
+                "{}: insertStrategy merge requires catalogType iceberg",
+                path
+            ));
+        }
+        let keys_ok = block
+            .get("mergeKeys")
+            .and_then(Value::as_array)
+            .map(|k| {
+                !k.is_empty()
+                    && k.iter()
+                        .all(|c| c.as_str().map(is_sql_identifier).unwrap_or(false))
+            })
+            .unwrap_or(false);
+        if !keys_ok {
+            errors.push(format!(
+                "{}.mergeKeys must be a non-empty array of column names for insertStrategy merge",
+                path
+            ));
+        }
+    }
+}
+
+/// Iceberg partition transform: year/month/day/hour(col), bucket(col, n), truncate(col, n).
+fn is_partition_transform(s: &str) -> bool {
+    let (func, rest) = match s.split_once('(') {
+        Some(p) => p,
+        None => return false,
+    };
+    let args: Vec<&str> = match rest.strip_suffix(')') {
+        Some(a) => a.split(',').map(str::trim).collect(),
+        None => return false,
+    };
+    let is_n = |a: &str| !a.is_empty() && a.chars().all(|c| c.is_ascii_digit());
+    match (func.trim(), args.as_slice()) {
+        ("year" | "month" | "day" | "hour", [col]) => is_sql_identifier(col),
+        ("bucket" | "truncate", [col, n]) => is_sql_identifier(col) && is_n(n),
+        _ => false,
+    }
+}
+
+/// How a postgresql sink stores messages: columns (one column per field), jsonb or bytea (whole message in payloadColumn).
+/// Whole-message modes have no per-field columns, so they cannot be combined with upsertMode or rawMode.
+fn validate_payload_mode(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let mode = match block.get("payloadMode") {
+        Some(m) => m.as_str().unwrap_or(""),
+        None => {
+            if block.get("compression").is_some() || block.get("payloadColumn").is_some() {
+                errors.push(format!(
+                    "{}: compression and payloadColumn require payloadMode jsonb or bytea",
+                    path
+                ));
+            }
+            return;
+        }
+    };
+    if !PAYLOAD_MODES.contains(&mode) {
+        errors.push(format!(
+            "{}.payloadMode must be one of: {}",
+            path,
+            PAYLOAD_MODES.join(", ")
+        ));
+        return;
+    }
+    if block.get("rawMode").and_then(Value::as_bool) == Some(true) {
+        errors.push(format!(
+            "{}: rawMode and payloadMode are mutually exclusive; use payloadMode",
+            path
+        ));
+    }
+    if mode == "columns" {
+        if block.get("compression").is_some() || block.get("payloadColumn").is_some() {
+            errors.push(format!(
+                "{}: compression and payloadColumn are not used with payloadMode columns",
+                path
+            ));
+        }
+        return;
+    }
+    if block.get("upsertMode").and_then(Value::as_bool) == Some(true) {
+        errors.push(format!(
+            "{}: upsertMode needs per-field columns for conflictKey; it cannot be used with payloadMode {}",
+            path, mode
+        ));
+    }
+    if let Some(col) = block.get("payloadColumn") {
+        if col.as_str().map(|c| c.is_empty()).unwrap_or(true) {
+            errors.push(format!(
+                "{}.payloadColumn must be a non-empty column name",
+                path
+            ));
+        }
+    }
+    if let Some(compression) = block.get("compression") {
+        let c = compression.as_str().unwrap_or("");
+        if !PAYLOAD_COMPRESSIONS.contains(&c) {
+            errors.push(format!(
+                "{}.compression must be one of: {}",
+                path,
+                PAYLOAD_COMPRESSIONS.join(", ")
+            ));
+        } else if mode == "jsonb" && c != "none" {
+            errors.push(format!(
+                "{}.compression is only supported with payloadMode bytea",
+                path
+            ));
+        }
+    }
+}
+
+/// Batch transformations run on a whole batch right before the DB write: sort, dedupe (by key), chunk (write size).
+fn validate_batch_transformations(
+    batch: &Value,
+    block: &Value,
+    block_path: &str,
+    errors: &mut Vec<String>,
+) {
+    let path = format!("{}.batchTransformations", block_path);
+    let items = match batch.as_array() {
+        Some(a) => a,
+        None => {
+            errors.push(format!("{} must be an array", path));
+            return;
+        }
+    };
+    for (i, t) in items.iter().enumerate() {
+        let t_path = format!("{}[{}]", path, i);
+        let t_type = t.get("type").and_then(Value::as_str).unwrap_or("");
+        let cfg = t.get(t_type);
+        match t_type {
+            "sort" => {
+                match cfg.and_then(|c| c.get("field")).and_then(Value::as_str) {
+                    Some(f) if !f.is_empty() => check_field_paths(
+                        Some(&Value::from(f)),
+                        &format!("{}.sort.field", t_path),
+                        errors,
+                    ),
+                    _ => errors.push(format!("{}.sort.field is required", t_path)),
+                }
+                if let Some(order) = cfg.and_then(|c| c.get("order")) {
+                    if !matches!(order.as_str(), Some("asc") | Some("desc")) {
+                        errors.push(format!("{}.sort.order must be one of: asc, desc", t_path));
+                    }
+                }
+            }
+            "dedupe" => {
+                let key_ok = cfg
+                    .and_then(|c| c.get("key"))
+                    .and_then(Value::as_array)
+                    .map(|k| !k.is_empty() && k.iter().all(Value::is_string))
+                    .unwrap_or(false);
+                if !key_ok {
+                    errors.push(format!(
+                        "{}.dedupe.key must be a non-empty array of field paths",
+                        t_path
+                    ));
+                } else {
+                    check_field_paths(
+                        cfg.and_then(|c| c.get("key")),
+                        &format!("{}.dedupe.key", t_path),
+                        errors,
+                    );
+                }
+                if let Some(keep) = cfg.and_then(|c| c.get("keep")) {
+                    if !matches!(keep.as_str(), Some("last") | Some("first")) {
+                        errors.push(format!(
+                            "{}.dedupe.keep must be one of: last, first",
+                            t_path
+                        ));
+                    }
+                }
+            }
+            "chunk" => {
+                check_int_range(
+                    cfg.and_then(|c| c.get("size")),
+                    &format!("{}.chunk.size", t_path),
+                    1,
+                    1_000_000,
+                    errors,
+                );
+                match cfg.and_then(|c| c.get("size")) {
+                    None => errors.push(format!("{}.chunk.size is required", t_path)),
+                    Some(size) => {
+                        if let (Some(size), Some(batch_size)) = (
+                            size.as_i64(),
+                            block.get("batchSize").and_then(Value::as_i64),
+                        ) {
+                            if size > batch_size {
+                                errors.push(format!(
+                                    "{}.chunk.size ({}) must not exceed batchSize ({})",
+                                    t_path, size, batch_size
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+            _ => errors.push(format!(
+                "{}.type must be one of: {}",
+                t_path,
+                BATCH_TRANSFORMATION_TYPES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Checks a JSONPath field reference or an array of them; a missing value is left to other checks.
+fn check_field_paths(v: Option<&Value>, path: &str, errors: &mut Vec<String>) {
+    let items: Vec<(String, &Value)> = match v {
+        None => return,
+        Some(Value::Array(a)) => a
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (format!("{}[{}]", path, i), f))
+            .collect(),
+        Some(f) => vec![(path.to_string(), f)],
+    };
+    for (p, f) in items {
+        match f.as_str() {
+            Some(s) => {
+                if let Err(e) = check_jsonpath(s) {
+                    errors.push(format!("{} '{}' is not a valid JSONPath: {}", p, s, e));
+                }
+            }
+            None => errors.push(format!("{} must be a JSONPath string", p)),
+        }
+    }
+}
+
+/// Spec-level declarations that transformation chains may refer to.
+struct ChainContext<'a> {
+    branches: &'a BTreeMap<String, ParsedBranch>,
+    has_dlq: bool,
+}
+
+/// Checks a transformation chain: onError policies, and router routes targeting either an inline sink or a declared branch.
+fn validate_transformations(
+    transformations: &[Value],
+    path: &str,
+    ctx: &ChainContext,
+    errors: &mut Vec<String>,
+) {
+    for (i, t) in transformations.iter().enumerate() {
+        let t_path = format!("{}[{}]", path, i);
+        if let Some(on_error) = t.get("onError") {
+            match on_error.as_str() {
+                Some("dlq") if !ctx.has_dlq => errors.push(format!(
+                    "{}.onError is dlq but spec.errorHandling.dlq is not configured",
+                    t_path
+                )),
+                Some(p) if ON_ERROR_POLICIES.contains(&p) => {}
+                _ => errors.push(format!(
+                    "{}.onError must be one of: {}",
+                    t_path,
+                    ON_ERROR_POLICIES.join(", ")
+                )),
+            }
+        }
+        match t.get("type").and_then(Value::as_str) {
+            Some("filter") => match t
+                .get("filter")
+                .and_then(|f| f.get("condition"))
+                .and_then(Value::as_str)
+            {
+                Some(c) => {
+                    if let Err(e) = check_condition(c) {
+                        errors.push(format!(
+                            "{}.filter.condition '{}' is not a valid condition: {}",
+                            t_path, c, e
+                        ));
+                    }
+                }
+                None => errors.push(format!("{}.filter.condition is required", t_path)),
+            },
+            Some(kind @ ("mask" | "select" | "remove")) => check_field_paths(
+                t.get(kind).and_then(|c| c.get("fields")),
+                &format!("{}.{}.fields", t_path, kind),
+                errors,
+            ),
+            Some("flatten") => check_field_paths(
+                t.get("flatten").and_then(|c| c.get("field")),
+                &format!("{}.flatten.field", t_path),
+                errors,
+            ),
+            Some("router") => validate_router(t, &t_path, ctx, errors),
+            Some("script") => validate_script(t, &t_path, errors),
+            Some("enrich") => validate_enrich(t, &t_path, errors),
+            Some("unwrap") => match t
+                .get("unwrap")
+                .and_then(|u| u.get("field"))
+                .and_then(Value::as_str)
+            {
+                Some(field) => {
+                    if let Err(e) = check_jsonpath(field) {
+                        errors.push(format!(
+                            "{}.unwrap.field '{}' is not a valid JSONPath: {}",
+                            t_path, field, e
+                        ));
+                    }
+                }
+                None => errors.push(format!("{}.unwrap.field is required", t_path)),
+            },
+            Some("unwrapCloudEvent") => {
+                if let Some(f) = t
+                    .get("unwrapCloudEvent")
+                    .and_then(|u| u.get("attributesField"))
+                {
+                    if !f.is_string() {
+                        errors.push(format!(
+                            "{}.unwrapCloudEvent.attributesField must be a field path string",
+                            t_path
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_router(t: &Value, t_path: &str, ctx: &ChainContext, errors: &mut Vec<String>) {
+    let routes = t
+        .get("router")
+        .and_then(|r| r.get("routes"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for (j, route) in routes.iter().enumerate() {
+        let r_path = format!("{}.router.routes[{}]", t_path, j);
+        if let Some(c) = route.get("condition").and_then(Value::as_str) {
+            if let Err(e) = check_condition(c) {
+                errors.push(format!(
+                    "{}.condition '{}' is not a valid condition: {}",
+                    r_path, c, e
+                ));
+            }
+        }
+        match (route.get("sink"), route.get("branch")) {
+            (Some(_), Some(_)) => {
+                errors.push(format!("{}: set either sink or branch, not both", r_path));
+            }
+            (None, None) => {
+                errors.push(format!("{}: sink or branch is required", r_path));
+            }
+            (Some(sink), None) => match serde_json::from_value::<ParsedSink>(sink.clone()) {
+                Ok(parsed) => validate_sink(&parsed, &format!("{}.sink", r_path), errors),
+                Err(e) => errors.push(format!("{}.sink is invalid: {}", r_path, e)),
+            },
+            (None, Some(branch)) => {
+                let name = branch.as_str().unwrap_or("");
+                if !ctx.branches.contains_key(name) {
+                    errors.push(format!(
+                        "{}.branch '{}' is not declared in spec.branches",
+                        r_path, name
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Script transformation: a supported language, exactly one of inline code / configMapRef, string-valued limits.
+fn validate_script(t: &Value, t_path: &str, errors: &mut Vec<String>) {
+    let path = format!("{}.script", t_path);
+    let script = match t.get("script") {
+        Some(s) if s.is_object() => s,
+        _ => {
+            errors.push(format!("{} is required when type is script", path));
+            return;
+        }
+    };
+    let language = script.get("language").and_then(Value::as_str).unwrap_or("");
+    if !SCRIPT_LANGUAGES.contains(&language) {
+        errors.push(format!(
+            "{}.language must be one of: {}",
+            path,
+            SCRIPT_LANGUAGES.join(", ")
+        ));
+    }
+    match (script.get("code"), script.get("configMapRef")) {
+        (Some(_), Some(_)) => errors.push(format!(
+            "{}: set either code or configMapRef, not both",
+            path
+        )),
+        (None, None) => errors.push(format!("{}: code or configMapRef is required", path)),
+        (Some(code), None) => {
+            if code.as_str().map(|c| c.trim().is_empty()).unwrap_or(true) {
+                errors.push(format!("{}.code must be a non-empty string", path));
+            }
+        }
+        (None, Some(cm)) => {
+            for field in ["name", "key"] {
+                if cm
+                    .get(field)
+                    .and_then(Value::as_str)
+                    .map(|v| v.is_empty())
+                    .unwrap_or(true)
+                {
+                    errors.push(format!("{}.configMapRef.{} is required", path, field));
+                }
+            }
+        }
+    }
+    if let Some(limits) = script.get("limits") {
+        if let Some(v) = limits.get("cpu") {
+            if !v.is_string() {
+                errors.push(format!(
+                    "{}.limits.cpu must be a string (e.g. \"100m\")",
+                    path
+                ));
+            }
+        }
+        check_duration_range(
+            limits.get("timeout"),
+            &format!("{}.limits.timeout", path),
+            1,
+            60_000,
+            errors,
+        );
+    }
+}
+
+/// Enrich lookups call an external service per message, so timeout, retry and circuit breaker must be bounded.
+fn validate_enrich(t: &Value, t_path: &str, errors: &mut Vec<String>) {
+    let path = format!("{}.enrich", t_path);
+    let enrich = match t.get("enrich") {
+        Some(e) if e.is_object() => e,
+        _ => {
+            errors.push(format!("{} is required when type is enrich", path));
+            return;
+        }
+    };
+    let backend = enrich.get("backend").and_then(Value::as_str).unwrap_or("");
+    match backend {
+        "http" => {
+            let url = enrich
+                .get("http")
+                .and_then(|h| h.get("url"))
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                errors.push(format!(
+                    "{}.http.url must be an http:// or https:// URL",
+                    path
+                ));
+            }
+        }
+        "grpc" => {
+            let grpc = enrich.get("grpc");
+            for field in ["target", "method"] {
+                if grpc
+                    .and_then(|g| g.get(field))
+                    .and_then(Value::as_str)
+                    .map(|v| v.is_empty())
+                    .unwrap_or(true)
+                {
+                    errors.push(format!(
+                        "{}.grpc.{} is required when backend is grpc",
+                        path, field
+                    ));
+                }
+            }
+        }
+        _ => errors.push(format!(
+            "{}.backend must be one of: {}",
+            path,
+            ENRICH_BACKENDS.join(", ")
+        )),
+    }
+    if enrich
+        .get("targetField")
+        .and_then(Value::as_str)
+        .map(|v| v.is_empty())
+        .unwrap_or(true)
+    {
+        errors.push(format!("{}.targetField is required", path));
+    }
+    check_duration_range(
+        enrich.get("timeout"),
+        &format!("{}.timeout", path),
+        1,
+        60_000,
+        errors,
+    );
+    if let Some(retry) = enrich.get("retry") {
+        check_int_range(
+            retry.get("maxAttempts"),
+            &format!("{}.retry.maxAttempts", path),
+            0,
+            10,
+            errors,
+        );
+        check_duration_range(
+            retry.get("backoff"),
+            &format!("{}.retry.backoff", path),
+            1,
+            60_000,
+            errors,
+        );
+    }
+    if let Some(cb) = enrich.get("circuitBreaker") {
+        check_int_range(
+            cb.get("failureThreshold"),
+            &format!("{}.circuitBreaker.failureThreshold", path),
+            1,
+            1000,
+            errors,
+        );
+        check_int_range(
+            cb.get("halfOpenRequests"),
+            &format!("{}.circuitBreaker.halfOpenRequests", path),
+            1,
+            100,
+            errors,
+        );
+        check_duration_range(
+            cb.get("openDuration"),
+            &format!("{}.circuitBreaker.openDuration", path),
+            1_000,
+            3_600_000,
+            errors,
+        );
+    }
+}
+
+/// Optional integer field within [min, max].
+fn check_int_range(v: Option<&Value>, path: &str, min: i64, max: i64, errors: &mut Vec<String>) {
+    if let Some(v) = v {
+        match v.as_i64() {
+            Some(n) if (min..=max).contains(&n) => {}
+            _ => errors.push(format!(
+                "{} must be an integer between {} and {}",
+                path, min, max
+            )),
+        }
+    }
+}
+
+/// Optional duration field (Go-style, e.g. "500ms", "30s") within [min_ms, max_ms].
+fn check_duration_range(
+    v: Option<&Value>,
+    path: &str,
+    min_ms: u64,
+    max_ms: u64,
+    errors: &mut Vec<String>,
+) {
+    if let Some(v) = v {
+        match v.as_str().and_then(parse_duration_ms) {
+            Some(ms) if (min_ms..=max_ms).contains(&ms) => {}
+            Some(_) => errors.push(format!(
+                "{} must be between {}ms and {}ms",
+                path, min_ms, max_ms
+            )),
+            None => errors.push(format!(
+                "{} must be a duration such as \"500ms\", \"30s\" or \"1m30s\" (units ms, s, m, h){}",
+                path,
+                duration_hint(v)
+            )),
+        }
+    }
+}
+
+/// Hint for a common duration mistake (missing unit, days, fractions, spelled-out units).
+fn duration_hint(v: &Value) -> String {
+    let s = match v {
+        Value::Number(n) => return format!("; write it as a string with a unit, e.g. \"{}s\"", n),
+        Value::String(s) => s.trim(),
+        _ => return String::new(),
+    };
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        format!("; add a unit, e.g. \"{}s\"", s)
+    } else if s.contains('.') {
+        "; fractions are not supported, use a smaller unit (\"1500ms\" instead of \"1.5s\")"
+            .to_string()
+    } else if s.ends_with('d') && s[..s.len() - 1].chars().all(|c| c.is_ascii_digit()) {
+        "; days are not supported, use hours (\"24h\")".to_string()
+    } else if s.contains(' ') || s.chars().filter(|c| c.is_ascii_alphabetic()).count() > 2 {
+        "; write units as ms, s, m or h without spaces (\"5m\" instead of \"5 minutes\")"
+            .to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Parses a Go-style duration ("250ms", "30s", "5m", "1h30m") into milliseconds.
+pub(crate) fn parse_duration_ms(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    let mut total: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return None;
+        }
+        let n: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "ms" => 1,
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            _ => return None,
+        };
+        total = total.checked_add(n.checked_mul(factor)?)?;
+        rest = &rest[unit_len..];
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_generate_dataflow_manifest_kafka_postgresql() {
+        let yaml = generate_dataflow_manifest(
+            Some("Kafka to PostgreSQL"),
+            Some("kafka"),
+            Some("postgresql"),
+            Some(&json!({"brokers":["localhost:9092"],"topic":"input-topic"})),
+            Some(&json!({"connectionString":"postgres://u:p@h/db","table":"t"})),
+            None,
+            Some("my-flow"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(yaml.contains("apiVersion: dataflow.dataflow.io/v1"));
+        assert!(yaml.contains("kind: DataFlow"));
+        assert!(yaml.contains("name: my-flow"));
+        assert!(yaml.contains("spec:"));
+        assert!(yaml.contains("source:"));
+        assert!(yaml.contains("sink:"));
+        assert!(yaml.contains("kafka:"));
+        assert!(yaml.contains("postgresql:"));
+        assert!(yaml.contains("brokers:"));
+        assert!(yaml.contains("connectionString:"));
+    }
+
+    #[test]
+    fn test_generate_dataflow_manifest_invalid_source_type() {
+        let err = generate_dataflow_manifest(
+            None,
+            Some("invalid"),
+            Some("postgresql"),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.contains("source_type must be one of"));
+    }
+
+    #[test]
+    fn test_generate_dataflow_manifest_from_description() {
+        let yaml = generate_dataflow_manifest(
+            Some("from kafka topic orders at k1:9092, mask email, to postgres table orders, make it fast"),
+            None,
+            None,
+            None,
+            Some(&json!(r#"{"table":"orders_v2"}"#)),
+            None,
+            Some("orders"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(yaml.contains("# Inferred from description: spec.source.type = kafka\n"));
+        assert!(yaml.contains("# Inferred from description: spec.source.kafka.topic = orders\n"));
+        assert!(yaml.contains("# Inferred from description: spec.transformations[0] = mask {\"fields\":[\"$.email\"]}\n"));
+        assert!(yaml.contains("# Not understood in description: 'make it fast'\n"));
+        assert!(!yaml.contains("spec.sink.postgresql.table ="));
+        let doc: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(doc["spec"]["sink"]["postgresql"]["table"], "orders_v2");
+        assert_eq!(doc["spec"]["source"]["kafka"]["brokers"][0], "k1:9092");
+        let err = generate_dataflow_manifest(
+            Some("copy orders"),
+            None,
+            Some("s3"),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.starts_with("source_type is required (or name the source in description"));
+    }
+
+    #[test]
+    fn test_default_flow_name() {
+        let name = |st, so: Value, kt, si: Value| default_flow_name(st, &so, kt, &si);
+        assert_eq!(
+            name(
+                "kafka",
+                json!({ "topic": "orders" }),
+                "postgresql",
+                json!({ "table": "public.orders" })
+            ),
+            "kafka-orders-to-pg-orders"
+        );
+        assert_eq!(
+            name(
+                "syslog",
+                json!({ "address": "0.0.0.0:514" }),
+                "elasticsearch",
+                json!({ "index": "Logs_2024" })
+            ),
+            "syslog-to-es-logs-2024"
+        );
+        let long = "t".repeat(80);
+        assert_eq!(
+            name("kafka", json!({ "topic": long }), "s3", json!({})).len(),
+            63
+        );
+        let yaml = generate_dataflow_manifest(
+            None,
+            Some("kafka"),
+            Some("clickhouse"),
+            Some(&json!({ "topic": "shop.orders" })),
+            Some(&json!({ "table": "orders" })),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(yaml.contains("  name: kafka-shop-orders-to-ch-orders\n"));
+    }
+
+    #[test]
+    fn test_generate_dataflow_manifest_scaling() {
+        let source = json!({ "brokers": ["k:9092"], "topic": "in" });
+        let sink = json!({ "brokers": ["k:9092"], "topic": "out" });
+        let generate = |parallelism, resources: Option<&Value>| {
+            generate_dataflow_manifest(
+                None,
+                Some("kafka"),
+                Some("kafka"),
+                Some(&source),
+                Some(&sink),
+                None,
+                Some("s"),
+                None,
+                None,
+                parallelism,
+                resources,
+                None,
+            )
+        };
+        let doc: Value = serde_yaml::from_str(&generate(None, None).unwrap()).unwrap();
+        assert_eq!(
+            doc["spec"]["resources"],
+            json!({ "requests": { "cpu": "250m", "memory": "256Mi" }, "limits": { "cpu": "1", "memory": "512Mi" } })
+        );
+        assert!(
+            doc["metadata"]["annotations"]["dataflow.io/generation-notes"]
+                .as_str()
+                .unwrap()
+                .contains("spec.resources")
+        );
+        let resources = json!({ "limits": { "memory": "2Gi" } });
+        let doc: Value =
+            serde_yaml::from_str(&generate(Some(4), Some(&resources)).unwrap()).unwrap();
+        assert_eq!(
+            (
+                doc["spec"]["parallelism"].as_u64(),
+                &doc["spec"]["resources"]
+            ),
+            (Some(4), &resources)
+        );
+        let err =
+            generate(Some(0), Some(&json!({ "requests": { "memory": "512m" } }))).unwrap_err();
+        assert_eq!(err, "parallelism must be an integer between 1 and 128; resources.requests.memory '512m' means millibytes; did you mean 512Mi?");
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_valid() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: test
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["localhost:9092"]
+      topic: input
+  sink:
+    type: postgresql
+    postgresql:
+      connectionString: "postgres://localhost/db"
+      table: out
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_wrong_kind() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: WrongKind
+metadata:
+  name: test
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert!(err.iter().any(|e| e.contains("kind")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_missing_spec() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: test
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert!(!err.is_empty());
+    }
+
+    #[test]
+    fn test_generate_dataflow_manifest_clickhouse() {
+        let yaml = generate_dataflow_manifest(
+            Some("Kafka to ClickHouse"),
+            Some("kafka"),
+            Some("clickhouse"),
+            Some(&json!({"brokers":["localhost:9092"],"topic":"input-topic"})),
+            Some(&json!({"connectionString":"clickhouse://default@localhost:9000/default","table":"output_table"})),
+            None,
+            Some("kafka-to-clickhouse"),
+            None,
+            None,
+            None,
             None,
-            Some("my-flow"),
             None,
         )
         .unwrap();
         assert!(yaml.contains("apiVersion: dataflow.dataflow.io/v1"));
         assert!(yaml.contains("kind: DataFlow"));
-        assert!(yaml.contains("name: my-flow"));
-        assert!(yaml.contains("spec:"));
-        assert!(yaml.contains("source:"));
-        assert!(yaml.contains("sink:"));
-        assert!(yaml.contains("kafka:"));
-        assert!(yaml.contains("postgresql:"));
-        assert!(yaml.contains("brokers:"));
+        assert!(yaml.contains("name: kafka-to-clickhouse"));
+        assert!(yaml.contains("clickhouse:"));
         assert!(yaml.contains("connectionString:"));
+        assert!(yaml.contains("output_table"));
+    }
+
+    #[test]
+    fn test_plaintext_credentials_in_generation_and_validation() {
+        let yaml = generate_dataflow_manifest(
+            None,
+            Some("kafka"),
+            Some("postgresql"),
+            Some(&json!({"brokers":["k:9092"],"topic":"in","sasl":{"mechanism":"PLAIN","username":"app","password":"hunter2"}})),
+            Some(&json!({"connectionString":"postgres://app:s3cret@pg/shop","table":"t"})),
+            None,
+            Some("orders"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(yaml.contains(
+            "# Warning: spec.sink.postgresql.connectionString is a plaintext credential"
+        ));
+        assert!(yaml.contains("# Warning: spec.source.kafka.sasl.password is a plaintext credential and would be committed with the manifest; use password: {secretKeyRef: {name: orders-credentials, key: password}}"));
+        let warnings: Vec<Diagnostic> = diagnose_dataflow_manifest(&yaml)
+            .into_iter()
+            .filter(|d| d.message.contains("plaintext credential"))
+            .collect();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|d| d.severity == Severity::Warning));
+        let fixed = yaml.replace(
+            "password: hunter2",
+            "password: {secretKeyRef: {name: orders-credentials}}",
+        );
+        let errors = validate_dataflow_manifest(&fixed).unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["spec.source.kafka.sasl.password.secretKeyRef.key is required".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_clickhouse() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: clickhouse-test
+spec:
+  source:
+    type: clickhouse
+    clickhouse:
+      connectionString: "clickhouse://default@localhost:9000/default"
+      table: source_table
+  sink:
+    type: clickhouse
+    clickhouse:
+      connectionString: "clickhouse://default@localhost:9000/default"
+      table: sink_table
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_router_branches() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: branches
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  transformations:
+    - type: router
+      router:
+        routes:
+          - condition: "$.level == 'error'"
+            branch: errors
+  branches:
+    errors:
+      transformations:
+        - type: mask
+          mask:
+            fields: ["$.token"]
+      sink:
+        type: postgresql
+        postgresql: { connectionString: "postgres://db/app", table: events }
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_undeclared_branch() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: branches
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  transformations:
+    - type: router
+      router:
+        routes:
+          - condition: "$.level == 'error'"
+            branch: errors
+  branches:
+    audit:
+      transformations: []
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("branch 'errors' is not declared")));
+        assert!(err
+            .iter()
+            .any(|e| e == "spec.branches.audit.sink is required"));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_on_error_dlq() {
+        let base = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: on-error
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  transformations:
+    - type: filter
+      filter:
+        condition: "$.level != 'debug'"
+      onError: dlq
+"#;
+        let err = validate_dataflow_manifest(base).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("spec.errorHandling.dlq is not configured")));
+
+        let with_dlq = format!(
+            "{}  errorHandling:\n    dlq:\n      type: kafka\n      kafka:\n        brokers: [\"kafka:9092\"]\n        topic: dlq\n",
+            base
+        );
+        assert!(validate_dataflow_manifest(&with_dlq).is_ok());
+
+        let bad_policy = base.replace("onError: dlq", "onError: retry");
+        let err = validate_dataflow_manifest(&bad_policy).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("onError must be one of: skip, dlq, fail")));
+
+        let into_sink = with_dlq.replace("topic: dlq", "topic: out");
+        let err = validate_dataflow_manifest(&into_sink).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e
+                .starts_with("spec.errorHandling.dlq writes to the same kafka out as spec.sink")));
+        let empty = format!(
+            "{}  errorHandling: {{}}\n",
+            base.replace("      onError: dlq\n", "")
+        );
+        assert_eq!(
+            validate_dataflow_manifest(&empty).unwrap_err(),
+            vec!["spec.errorHandling.dlq is required when spec.errorHandling is set"]
+        );
+    }
+
+    #[test]
+    fn test_generate_dataflow_manifest_dlq() {
+        let source = json!({ "brokers": ["k:9092"], "topic": "orders", "consumerGroup": "orders" });
+        let sink = json!({ "connectionString": "postgres://pg:5432/shop", "table": "orders" });
+        let mask = json!([{ "type": "mask", "mask": { "fields": ["$.email"] } }]);
+        let generate = |dlq| {
+            let yaml = generate_dataflow_manifest(
+                None,
+                Some("kafka"),
+                Some("postgresql"),
+                Some(&source),
+                Some(&sink),
+                Some(&mask),
+                Some("orders"),
+                None,
+                None,
+                None,
+                None,
+                dlq,
+            )?;
+            Ok::<Value, String>(serde_yaml::from_str(&yaml).unwrap())
+        };
+        let doc = generate(Some("kafka")).unwrap();
+        assert_eq!(
+            doc["spec"]["errorHandling"]["dlq"],
+            json!({ "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "orders-dlq" } })
+        );
+        assert_eq!(doc["spec"]["transformations"][0]["onError"], "dlq");
+        let doc = generate(Some("postgresql")).unwrap();
+        assert_eq!(
+            doc["spec"]["errorHandling"]["dlq"]["postgresql"]["table"],
+            "orders_dlq"
+        );
+        assert!(validate_dataflow_manifest(&serde_yaml::to_string(&doc).unwrap()).is_ok());
+        assert_eq!(
+            generate(Some("s3")).unwrap_err(),
+            "dlq 's3' is not supported; use one of: kafka, postgresql"
+        );
+        let yaml = generate_dataflow_manifest(
+            None,
+            Some("kafka"),
+            Some("postgresql"),
+            Some(&source),
+            Some(&sink),
+            None,
+            Some("orders"),
+            None,
+            Some("sequence"),
+            None,
+            None,
+            Some("kafka"),
+        )
+        .unwrap();
+        let doc: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(doc["metadata"]["name"], "orders-1");
+        assert_eq!(
+            doc["spec"]["errorHandling"]["dlq"]["kafka"]["topic"],
+            "orders-1-dlq"
+        );
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_script() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: script
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  transformations:
+    - type: script
+      script:
+        language: lua
+        configMapRef:
+          name: scripts
+          key: enrich.lua
+        limits:
+          cpu: "100m"
+          timeout: "50ms"
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let bad = yaml
+            .replace("language: lua", "language: python")
+            .replace("          key: enrich.lua\n", "");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("script.language must be one of")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("script.configMapRef.key is required")));
+    }
+
+    #[test]
+    fn test_generate_and_validate_syslog_source() {
+        let yaml = generate_dataflow_manifest(
+            Some("Syslog to Kafka"),
+            Some("syslog"),
+            Some("kafka"),
+            Some(&json!({"address":"0.0.0.0:514","protocol":"udp","parser":"rfc5424"})),
+            Some(&json!({"brokers":["localhost:9092"],"topic":"logs"})),
+            None,
+            Some("syslog-to-kafka"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(yaml.contains("type: syslog"));
+        assert!(validate_dataflow_manifest(&yaml).is_ok());
+
+        let bad = yaml.replace("protocol: udp", "protocol: http");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("spec.source.syslog.protocol must be one of: udp, tcp")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_enrich_guardrails() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: enrich
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  transformations:
+    - type: enrich
+      enrich:
+        backend: http
+        http:
+          url: "https://customers.internal/lookup"
+        targetField: "$.customer"
+        timeout: "200ms"
+        retry:
+          maxAttempts: 3
+          backoff: "100ms"
+        circuitBreaker:
+          failureThreshold: 5
+          openDuration: "30s"
+          halfOpenRequests: 1
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let bad = yaml
+            .replace("timeout: \"200ms\"", "timeout: \"5 minutes\"")
+            .replace("maxAttempts: 3", "maxAttempts: 50")
+            .replace("openDuration: \"30s\"", "openDuration: \"10ms\"");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("enrich.timeout must be a duration")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("retry.maxAttempts must be an integer between 0 and 10")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("circuitBreaker.openDuration must be between 1000ms")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_intervals() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: intervals
+spec:
+  source:
+    type: postgresql
+    postgresql: { connectionString: "postgres://pg/shop", table: orders, pollInterval: "5 minutes" }
+  sink:
+    type: clickhouse
+    clickhouse: { connectionString: "http://ch:8123/db", table: orders, batchFlushIntervalSeconds: 30s }
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert_eq!(
+            err,
+            vec![
+                "spec.source.postgresql.pollInterval must be a duration such as \"500ms\", \"30s\" or \"1m30s\" (units ms, s, m, h); write units as ms, s, m or h without spaces (\"5m\" instead of \"5 minutes\")".to_string(),
+                "spec.sink.clickhouse.batchFlushIntervalSeconds is a number of seconds, not a duration string; use 30".to_string(),
+            ]
+        );
+        let hints = yaml
+            .replace("\"5 minutes\"", "\"1d\"")
+            .replace("30s", "7200");
+        let err = validate_dataflow_manifest(&hints).unwrap_err();
+        assert!(err[0].ends_with("; days are not supported, use hours (\"24h\")"));
+        assert_eq!(
+            err[1],
+            "spec.sink.clickhouse.batchFlushIntervalSeconds must be an integer between 1 and 3600"
+        );
+        assert!(
+            validate_dataflow_manifest(&yaml.replace("\"5 minutes\"", "\"100ms\"")).unwrap_err()[0]
+                .contains("pollInterval must be between 1000ms")
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("250ms"), Some(250));
+        assert_eq!(parse_duration_ms("1m30s"), Some(90_000));
+        assert_eq!(parse_duration_ms("2h"), Some(7_200_000));
+        assert_eq!(parse_duration_ms("30"), None);
+        assert_eq!(parse_duration_ms("s"), None);
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_batch_transformations() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: batch
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: postgresql
+    postgresql:
+      connectionString: "postgres://localhost/db"
+      table: orders
+      batchSize: 1000
+      batchTransformations:
+        - type: sort
+          sort:
+            field: "$.updated_at"
+            order: asc
+        - type: dedupe
+          dedupe:
+            key: ["$.id"]
+            keep: last
+        - type: chunk
+          chunk:
+            size: 500
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let bad = yaml
+            .replace("size: 500", "size: 5000")
+            .replace("keep: last", "keep: newest");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("chunk.size (5000) must not exceed batchSize (1000)")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("dedupe.keep must be one of: last, first")));
+
+        let kafka = yaml.replace(
+            "type: postgresql\n    postgresql:",
+            "type: kafka\n    kafka:",
+        );
+        let err = validate_dataflow_manifest(&kafka).unwrap_err();
+        assert!(
+            err.iter()
+                .any(|e| e
+                    .contains("spec.sink.kafka.batchTransformations is only supported on sinks"))
+        );
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_kafka_format() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: formats
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["kafka:9092"]
+      topic: events
+      format: xml
+  sink:
+    type: kafka
+    kafka:
+      brokers: ["kafka:9092"]
+      topic: out
+      format: json
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert_eq!(
+            err,
+            vec![
+                "spec.source.kafka.format must be one of: json, avro, string, protobuf, jsonschema"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_avro_schema() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: avro
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["kafka:9092"]
+      topic: events
+      format: avro
+      avroSchema: '{"type":"record","name":"Order","fields":[{"name":"id","type":"lng"}]}'
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.starts_with("spec.source.kafka.avroSchema: invalid Avro schema")));
+
+        let fixed = yaml.replace("\"lng\"", "\"long\"");
+        assert!(validate_dataflow_manifest(&fixed).is_ok());
     }
 
     #[test]
-    fn test_generate_dataflow_manifest_invalid_source_type() {
-        let err = generate_dataflow_manifest(
-            None,
-            "invalid",
-            "postgresql",
-            None,
-            None,
-            None,
-            None,
-            None,
-        )
-        .unwrap_err();
-        assert!(err.contains("source_type must be one of"));
+    fn test_validate_dataflow_manifest_protobuf_jsonschema_completeness() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: formats
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["kafka:9092"]
+      topic: events
+      format: protobuf
+      protoSchema: "syntax = \"proto3\"; message Order { int64 id = 1; }"
+  sink:
+    type: kafka
+    kafka:
+      brokers: ["kafka:9092"]
+      topic: out
+      format: jsonschema
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e == "spec.source.kafka.messageType is required with an inline protoSchema"));
+        assert!(err.iter().any(
+            |e| e == "spec.sink.kafka: format jsonschema requires schemaRegistry or jsonSchema"
+        ));
+
+        let fixed = yaml
+            .replace(
+                "      protoSchema:",
+                "      messageType: Order\n      protoSchema:",
+            )
+            .replace(
+                "      format: jsonschema\n",
+                "      format: jsonschema\n      schemaRegistry:\n        url: http://sr:8081\n",
+            );
+        assert!(validate_dataflow_manifest(&fixed).is_ok());
     }
 
     #[test]
-    fn test_validate_dataflow_manifest_valid() {
+    fn test_validate_field_dependencies() {
         let yaml = r#"
 apiVersion: dataflow.dataflow.io/v1
 kind: DataFlow
 metadata:
-  name: test
+  name: deps
+spec:
+  source:
+    type: trino
+    trino:
+      serverURL: https://trino:8443
+      catalog: hive
+      schema: sales
+      table: orders
+      keycloak:
+        clientId: dataflow
+  sink:
+    type: postgresql
+    postgresql:
+      connectionString: postgres://pg/shop
+      table: orders
+      upsertMode: true
+  errorHandling:
+    dlq:
+      type: kafka
+      kafka:
+        brokers: ["kafka:9092"]
+        topic: dlq
+        format: avro
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert_eq!(
+            err,
+            vec![
+                "spec.source.trino.keycloak.clientSecret is required with keycloak authentication"
+                    .to_string(),
+                "spec.sink.postgresql.conflictKey is required with upsertMode: true".to_string(),
+                "spec.errorHandling.dlq.kafka: format avro requires schemaRegistry or avroSchema"
+                    .to_string(),
+            ]
+        );
+        let fixed = yaml
+            .replace(
+                "clientId: dataflow",
+                "clientId: dataflow\n        clientSecret: ${KEYCLOAK_SECRET}",
+            )
+            .replace(
+                "upsertMode: true",
+                "upsertMode: true\n      conflictKey: id",
+            )
+            .replace(
+                "format: avro",
+                "format: avro\n        schemaRegistry: {url: http://sr:8081}",
+            );
+        assert!(validate_dataflow_manifest(&fixed).is_ok());
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_cloudevents() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: cloudevents
 spec:
   source:
     type: kafka
     kafka:
-      brokers: ["localhost:9092"]
-      topic: input
+      brokers: ["kafka:9092"]
+      topic: events
+      cloudevents:
+        mode: binary
+        extensions:
+          tenantid: "$.tenant"
+  sink:
+    type: kafka
+    kafka:
+      brokers: ["kafka:9092"]
+      topic: out
+      cloudevents:
+        mode: structured
+        type: com.example.order.created
+        source: /orders
+  transformations:
+    - type: unwrapCloudEvent
+      unwrapCloudEvent:
+        attributesField: "$.ce"
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let bad = yaml
+            .replace("tenantid:", "Tenant_ID:")
+            .replace("        source: /orders\n", "")
+            .replace("mode: binary", "mode: batched");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e == "spec.source.kafka.cloudevents.mode must be one of: binary, structured"));
+        assert!(err.iter().any(|e| e.contains("attribute name 'Tenant_ID'")));
+        assert!(err.iter().any(
+            |e| e == "spec.sink.kafka.cloudevents.source is required when writing CloudEvents"
+        ));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_payload_mode() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: payload
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
   sink:
     type: postgresql
     postgresql:
       connectionString: "postgres://localhost/db"
-      table: out
+      table: events
+      payloadMode: bytea
+      payloadColumn: body
+      compression: zstd
 "#;
         assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let upsert = yaml.replace(
+            "      compression: zstd\n",
+            "      compression: zstd\n      upsertMode: true\n      conflictKey: id\n",
+        );
+        let err = validate_dataflow_manifest(&upsert).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("upsertMode needs per-field columns")));
+
+        let jsonb = yaml.replace("payloadMode: bytea", "payloadMode: jsonb");
+        let err = validate_dataflow_manifest(&jsonb).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("compression is only supported with payloadMode bytea")));
     }
 
     #[test]
-    fn test_validate_dataflow_manifest_wrong_kind() {
+    fn test_validate_dataflow_manifest_column_mapping() {
         let yaml = r#"
 apiVersion: dataflow.dataflow.io/v1
-kind: WrongKind
+kind: DataFlow
 metadata:
-  name: test
+  name: mapping
 spec:
   source:
     type: kafka
-    kafka: {}
+    kafka: { brokers: ["kafka:9092"], topic: events }
   sink:
+    type: clickhouse
+    clickhouse:
+      connectionString: "clickhouse://localhost:9000/default"
+      table: events
+      columnMapping:
+        - field: "$.id"
+          column: id
+          type: UInt64
+        - field: "$.user.email"
+          column: user_email
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let bad = yaml
+            .replace("\"$.user.email\"", "\"$.user[\"")
+            .replace("column: user_email", "column: id");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("columnMapping[1].field '$.user[' is not a valid JSONPath")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("columnMapping[1].column 'id' is mapped more than once")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_elasticsearch_sink() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: to-es
+spec:
+  source:
     type: kafka
-    kafka: {}
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: elasticsearch
+    elasticsearch:
+      addresses: ["http://es:9200"]
+      index: logs
+      nullValueBehavior: delete
 "#;
-        let err = validate_dataflow_manifest(yaml).unwrap_err();
-        assert!(err.iter().any(|e| e.contains("kind")));
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+        let bad = yaml
+            .replace("nullValueBehavior: delete", "nullValueBehavior: drop")
+            .replace("\"http://es:9200\"", "\"es:9200\"");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("addresses must be a non-empty array of http(s) URLs")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("nullValueBehavior must be one of: ignore, delete, fail")));
     }
 
     #[test]
-    fn test_validate_dataflow_manifest_missing_spec() {
+    fn test_validate_dataflow_manifest_trino_insert_strategy() {
         let yaml = r#"
 apiVersion: dataflow.dataflow.io/v1
 kind: DataFlow
 metadata:
-  name: test
+  name: to-trino
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: trino
+    trino:
+      serverURL: http://trino:8080
+      catalog: lake
+      schema: events
+      table: orders
+      catalogType: iceberg
+      insertStrategy: ctas
+      fileFormat: parquet
+      partitionBy: [region, "day(created_at)"]
 "#;
-        let err = validate_dataflow_manifest(yaml).unwrap_err();
-        assert!(!err.is_empty());
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let hive = yaml.replace("catalogType: iceberg", "catalogType: hive");
+        let err = validate_dataflow_manifest(&hive).unwrap_err();
+        assert!(err.iter().any(
+            |e| e.contains("partitionBy[1] 'day(created_at)' is not a valid hive partition column")
+        ));
+
+        let merge = yaml
+            .replace("catalogType: iceberg", "catalogType: hive")
+            .replace("insertStrategy: ctas", "insertStrategy: merge");
+        let err = validate_dataflow_manifest(&merge).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("insertStrategy merge requires catalogType iceberg")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("mergeKeys must be a non-empty array")));
+        assert!(
+            err.iter()
+                .any(|e| e
+                    .contains("fileFormat and partitionBy only apply when the table is created"))
+        );
     }
 
     #[test]
-    fn test_generate_dataflow_manifest_clickhouse() {
-        let yaml = generate_dataflow_manifest(
-            Some("Kafka to ClickHouse"),
-            "kafka",
-            "clickhouse",
-            Some(r#"{"brokers":["localhost:9092"],"topic":"input-topic"}"#),
-            Some(r#"{"connectionString":"clickhouse://default@localhost:9000/default","table":"output_table"}"#),
-            None,
-            Some("kafka-to-clickhouse"),
-            None,
-        )
-        .unwrap();
-        assert!(yaml.contains("apiVersion: dataflow.dataflow.io/v1"));
-        assert!(yaml.contains("kind: DataFlow"));
-        assert!(yaml.contains("name: kafka-to-clickhouse"));
-        assert!(yaml.contains("clickhouse:"));
-        assert!(yaml.contains("connectionString:"));
-        assert!(yaml.contains("output_table"));
+    fn test_validate_dataflow_manifest_postgresql_replication() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: cdc
+spec:
+  source:
+    type: postgresql
+    postgresql:
+      connectionString: postgres://user@pg:5432/shop
+      table: public.orders
+      replication:
+        slotName: dataflow_orders
+        publication: orders_pub
+        plugin: pgoutput
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let bad = yaml
+            .replace("slotName: dataflow_orders", "slotName: Orders-Slot")
+            .replace("plugin: pgoutput", "plugin: decoderbufs")
+            .replace(
+                "      table: public.orders",
+                "      table: public.orders\n      pollInterval: 5s",
+            );
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("pollInterval cannot be used with replication")));
+        assert!(err.iter().any(|e| e.contains("slotName 'Orders-Slot'")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("plugin must be one of: pgoutput, wal2json")));
     }
 
     #[test]
-    fn test_validate_dataflow_manifest_clickhouse() {
+    fn test_validate_dataflow_manifest_incremental_polling() {
         let yaml = r#"
 apiVersion: dataflow.dataflow.io/v1
 kind: DataFlow
 metadata:
-  name: clickhouse-test
+  name: poll
 spec:
   source:
     type: clickhouse
     clickhouse:
-      connectionString: "clickhouse://default@localhost:9000/default"
-      table: source_table
+      connectionString: clickhouse://ch:9000/db
+      table: events
+      mode: incremental
+      incrementalColumn: event_id
+      initialValue: 0
+      cursorStorage: configmap
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+
+        let missing = yaml.replace("      incrementalColumn: event_id\n", "");
+        let err = validate_dataflow_manifest(&missing).unwrap_err();
+        assert_eq!(
+            err,
+            vec!["spec.source.clickhouse.incrementalColumn is required when mode is incremental"]
+        );
+
+        let bulk = yaml.replace("mode: incremental", "mode: bulk");
+        let err = validate_dataflow_manifest(&bulk).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e == "spec.source.clickhouse.cursorStorage requires mode: incremental"));
+    }
+
+    #[test]
+    fn test_generate_dataflow_manifest_hash_suffix_name() {
+        let gen = |topic: &str| {
+            generate_dataflow_manifest(
+                None,
+                Some("kafka"),
+                Some("kafka"),
+                Some(&json!({"brokers":["k:9092"],"topic":topic})),
+                Some(&json!({"brokers":["k:9092"],"topic":"out"})),
+                None,
+                Some("orders-sync"),
+                None,
+                Some("hash-suffix"),
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+        };
+        let name = |yaml: &str| {
+            let v: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+            v["metadata"]["name"].as_str().unwrap().to_string()
+        };
+        let a = name(&gen("orders"));
+        assert!(a.starts_with("orders-sync-") && a.len() == "orders-sync-".len() + 4);
+        assert_eq!(a, name(&gen("orders")));
+        assert_ne!(a, name(&gen("refunds")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_detects_topic_loops() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: loops
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092", "kafka-2:9092"], topic: events }
+  transformations:
+    - type: router
+      router:
+        routes:
+          - condition: "$.level == 'error'"
+            sink: { type: kafka, kafka: { brokers: ["kafka:9092"], topic: events } }
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert_eq!(err.len(), 2);
+        assert!(err[0].starts_with(
+            "spec.sink.kafka.topic 'events' writes to the source topic on the same brokers"
+        ));
+        assert!(err[1].starts_with("spec.transformations[0].router.routes[0].sink.kafka.topic 'events' routes messages back into the source topic"));
+        // Another cluster is not a loop.
+        let mirrored = yaml.replace(r#"["kafka:9092", "kafka-2:9092"]"#, r#"["backup:9092"]"#);
+        assert_eq!(validate_dataflow_manifest(&mirrored).unwrap_err().len(), 1);
+        let pattern = yaml.replace(
+            "topic: events }\n  sink",
+            "topicPattern: \"ev.*\" }\n  sink",
+        );
+        assert!(validate_dataflow_manifest(&pattern).unwrap_err()[0]
+            .contains("matches the source topicPattern 'ev.*'"));
+    }
+
+    #[test]
+    fn test_strict_unknown_fields() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: strict
+  uid: 1234
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  paralelism: 2
+  transformations:
+    - type: filter
+      filter: { conditon: "$.ok" }
+    - type: enrich
+      enrich: { backend: http, http: { url: "https://api/lookup", verb: GET }, targetField: "$.c" }
+"#;
+        assert_eq!(
+            strict_unknown_fields(yaml),
+            vec![
+                "spec.paralelism is not a known field (did you mean parallelism?)".to_string(),
+                "spec.transformations[0].filter.conditon is not a known field (did you mean condition?)".to_string(),
+                "spec.transformations[1].enrich.http.verb is not a known field; known fields: method, url".to_string(),
+            ]
+        );
+        let reports = validate_dataflow_documents(yaml, true);
+        assert_eq!(reports[0].errors().len(), 4);
+        assert!(!validate_dataflow_documents(yaml, false)[0]
+            .errors()
+            .iter()
+            .any(|e| e.contains("paralelism")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_topic_pattern() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: pattern
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["k:9092"]
+      topicPattern: "orders\\..*"
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+        let bad = yaml.replace(
+            r#"topicPattern: "orders\\..*""#,
+            "topicPattern: \"orders.(\"\n      topic: orders",
+        );
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e.contains("topic and topicPattern are mutually exclusive")));
+        assert!(err
+            .iter()
+            .any(|e| e.contains("topicPattern is not a valid regex")));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_offset_reset() {
+        let yaml = |reset: &str| {
+            format!(
+                "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata: {{name: r}}\nspec:\n  source: {{type: kafka, kafka: {{brokers: [\"k:9092\"], topic: t, offsetReset: {}}}}}\n  sink: {{type: kafka, kafka: {{brokers: [\"k:9092\"], topic: out}}}}\n",
+                reset
+            )
+        };
+        assert!(validate_dataflow_manifest(&yaml("earliest")).is_ok());
+        let err = validate_dataflow_manifest(&yaml("none")).unwrap_err();
+        assert!(err
+            .iter()
+            .any(|e| e == "spec.source.kafka.offsetReset must be one of: earliest, latest"));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_catalog_fields() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: fields
+spec:
+  source:
+    type: postgresql
+    postgresql:
+      connectionString: "postgres://db/app"
+      pollIntervall: 30s
   sink:
     type: clickhouse
     clickhouse:
-      connectionString: "clickhouse://default@localhost:9000/default"
-      table: sink_table
+      connectionString: "tcp://ch:9000"
+      table: events
+      batch_size: 100
+      retries: 3
+"#;
+        let err = validate_dataflow_manifest(yaml).unwrap_err();
+        assert!(err.contains(&"spec.source.postgresql.table is required".to_string()));
+        assert!(err.contains(&"spec.source.postgresql.pollIntervall is not a known postgresql field (did you mean pollInterval?)".to_string()));
+        assert!(err.contains(&"spec.sink.clickhouse.batch_size is not a known clickhouse field (did you mean batchSize?)".to_string()));
+        assert!(err.iter().any(|e| e.starts_with("spec.sink.clickhouse.retries is not a known clickhouse field; known fields: connectionString, table")));
+        assert_eq!(err.len(), 4);
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_jsonpath_expressions() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: paths
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
+  transformations:
+    - type: filter
+      filter:
+        condition: "$.items[0].qty > 1 && $.level != 'debug'"
+    - type: mask
+      mask:
+        fields: ["$.password", "$.card.number"]
+    - type: flatten
+      flatten:
+        field: "$.items"
+    - type: router
+      router:
+        routes:
+          - condition: "$.level == 'error'"
+            sink: { type: kafka, kafka: { brokers: ["kafka:9092"], topic: errors } }
+"#;
+        assert!(validate_dataflow_manifest(yaml).is_ok());
+        let bad = yaml
+            .replace("$.items[0].qty", "$.items[")
+            .replace("\"$.card.number\"", "card.number")
+            .replace("field: \"$.items\"", "field: \"$.items.\"")
+            .replace("$.level == 'error'", "$.level = 'error'");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert_eq!(err.len(), 4, "{:?}", err);
+        assert!(err[0].starts_with("spec.transformations[0].filter.condition '$.items[ > 1"));
+        assert!(err[1].contains("spec.transformations[1].mask.fields[1] 'card.number' is not a valid JSONPath: must start with '$'"));
+        assert!(err[2].starts_with(
+            "spec.transformations[2].flatten.field '$.items.' is not a valid JSONPath"
+        ));
+        assert!(err[3].starts_with("spec.transformations[3].router.routes[0].condition '$.level = 'error'' is not a valid condition"));
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_kubernetes_metadata() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: orders.sync
+  namespace: data-team
+  labels:
+    app.kubernetes.io/name: orders
+    tier: ""
+  annotations:
+    example.com/owner: "data team"
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: out }
 "#;
         assert!(validate_dataflow_manifest(yaml).is_ok());
+        let bad = yaml
+            .replace("name: orders.sync", "name: Orders_Sync")
+            .replace("namespace: data-team", "namespace: data.team")
+            .replace("tier: \"\"", "tier: 3")
+            .replace("example.com/owner", "example.com/-owner");
+        let err = validate_dataflow_manifest(&bad).unwrap_err();
+        assert_eq!(err.len(), 4, "{:?}", err);
+        assert!(err[0].starts_with("metadata.name 'Orders_Sync' is not a valid Kubernetes name"));
+        assert!(err[1].starts_with("metadata.namespace 'data.team' is not a valid namespace"));
+        assert_eq!(
+            err[2],
+            "metadata.labels.tier must be a string (quote numbers and booleans)"
+        );
+        assert!(err[3]
+            .starts_with("metadata.annotations: key 'example.com/-owner' is invalid: name part"));
+        let long = yaml.replace(
+            "name: orders.sync",
+            &format!("name: {}.{}", "a".repeat(40), "b".repeat(40)),
+        );
+        assert!(validate_dataflow_manifest(&long).unwrap_err()[0].contains("at most 63 characters"));
+    }
+
+    #[test]
+    fn test_validate_dataflow_documents_groups_errors() {
+        let doc = |name: &str, topic: &str| {
+            format!(
+                "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: {}\nspec:\n  source: {{type: kafka, kafka: {{brokers: [\"k:9092\"], topic: {}}}}}\n  sink: {{type: kafka, kafka: {{brokers: [\"k:9092\"], topic: out}}}}\n",
+                name, topic
+            )
+        };
+        let bundle = format!(
+            "# flows\n---\n{}---\n{}...\n",
+            doc("a", "in"),
+            doc("b", "in")
+        );
+        let reports = validate_dataflow_documents(&bundle, false);
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| !r.has_errors()));
+        let with_secret = format!("{}---\napiVersion: v1\nkind: Secret\nmetadata:\n  name: a-credentials\nstringData:\n  password: x\n", doc("a", "in"));
+        assert_eq!(validate_dataflow_documents(&with_secret, false).len(), 1);
+        let bad = format!(
+            "{}---\n{}---\nkind: [\n",
+            doc("a", "in"),
+            doc("b", "in").replace("kafka: {brokers", "kafka: {broker")
+        );
+        let failed: Vec<DocumentReport> = validate_dataflow_documents(&bad, false)
+            .into_iter()
+            .filter(|r| r.has_errors())
+            .collect();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].label(), "document 2 (b)");
+        assert!(failed[0]
+            .errors()
+            .contains(&"spec.source.kafka.brokers is required".to_string()));
+        // Located in the whole input: document 2 starts after line 8, its spec.source.kafka on line 6.
+        let brokers = failed[0]
+            .diagnostics
+            .iter()
+            .find(|d| d.message == "spec.source.kafka.brokers is required")
+            .unwrap();
+        assert_eq!(
+            (brokers.path.as_deref(), brokers.line, brokers.column),
+            (Some("spec.source.kafka.brokers"), 14, 3)
+        );
+        assert_eq!(failed[1].label(), "document 3");
+        assert!(failed[1].errors()[0].starts_with("YAML parse error"));
+        let groups = render_reports(&failed);
+        assert!(groups[0].starts_with("document 2 (b):\n- error: "));
+        assert!(groups[1].starts_with("document 3:\n- error: YAML parse error"));
+        assert!(!render_reports(&failed[..1])[0].starts_with("document"));
+    }
+
+    #[test]
+    fn test_diagnose_dataflow_manifest_severities() {
+        let yaml = r#"
+apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: diag
+spec:
+  source:
+    type: kafka
+    kafka: { brokers: ["kafka:9092"], topic: events }
+  sink:
+    type: postgresql
+    postgresql: { connectionString: "postgres://db/app", table: events, rawMode: true }
+"#;
+        let diagnostics = diagnose_dataflow_manifest(yaml);
+        let severities: Vec<Severity> = diagnostics.iter().map(|d| d.severity).collect();
+        assert_eq!(
+            severities,
+            vec![Severity::Warning, Severity::Warning, Severity::Info]
+        );
+        assert!(diagnostics[0]
+            .message
+            .starts_with("spec.source.kafka.consumerGroup is not set"));
+        assert_eq!(
+            diagnostics[1].message,
+            "spec.sink.postgresql.rawMode is deprecated; use payloadMode: jsonb"
+        );
+        assert!(diagnostics[2]
+            .message
+            .starts_with("spec.sink.postgresql.batchSize is not set"));
+        let broken = diagnose_dataflow_manifest(&yaml.replace("table: events, ", ""));
+        assert_eq!(broken[0].severity, Severity::Error);
+        assert_eq!(broken[0].message, "spec.sink.postgresql.table is required");
+        assert_eq!(
+            serde_json::to_value(&broken[0]).unwrap()["severity"],
+            "error"
+        );
+    }
+
+    #[test]
+    fn test_validate_dataflow_manifest_parallelism() {
+        let yaml = |p: &str| {
+            format!(
+                "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: p\nspec:\n  parallelism: {}\n  source:\n    type: kafka\n    kafka: {{brokers: [\"k:9092\"], topic: in}}\n  sink:\n    type: kafka\n    kafka: {{brokers: [\"k:9092\"], topic: out}}\n",
+                p
+            )
+        };
+        assert!(validate_dataflow_manifest(&yaml("4")).is_ok());
+        assert!(
+            validate_dataflow_manifest(&yaml("0")).unwrap_err()[0].starts_with("spec.parallelism")
+        );
+        assert!(validate_dataflow_manifest(&yaml("\"4\"")).is_err());
     }
 }
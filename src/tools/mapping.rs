@@ -0,0 +1,526 @@
+// Field mapping between a source schema and a target table: matches fields to columns by name, then derives the
+// case conversion, renames, type casts and select that turn source messages into rows of the table.
+
+use crate::tools::manifest::{split_documents, validate_dataflow_manifest};
+use crate::tools::output::to_canonical_yaml;
+use serde_json::{json, Value};
+
+/// Type of a field or column as far as the mapping is concerned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Object,
+    Timestamp,
+    Unknown,
+}
+
+/// A named field of a schema or column of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub kind: FieldType,
+}
+
+/// snake_case of a field name: customerId and CustomerID -> customer_id, order-total -> order_total.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '-' || c == ' ' || c == '.' {
+            out.push('_');
+        } else if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = prev.is_some_and(|p| {
+                p.is_lowercase() || p.is_ascii_digit() || (p.is_uppercase() && next_lower)
+            });
+            if boundary && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// camelCase of a field name: customer_id -> customerId.
+pub(crate) fn to_camel_case(name: &str) -> String {
+    let snake = to_snake_case(name);
+    let mut parts = snake.split('_').filter(|p| !p.is_empty());
+    let mut out = parts.next().unwrap_or("").to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+/// Type of a JSON Schema type, Avro type or SQL column type name.
+fn field_type(name: &str) -> FieldType {
+    let lower = name.to_lowercase();
+    let base = lower.split(['(', ' ']).next().unwrap_or("");
+    match base {
+        "string" | "text" | "varchar" | "char" | "character" | "uuid" | "enum" | "bytes"
+        | "fixed" => FieldType::String,
+        "integer" | "int" | "long" | "bigint" | "smallint" | "tinyint" | "int2" | "int4"
+        | "int8" | "serial" | "bigserial" => FieldType::Integer,
+        b if b.starts_with("int") || b.starts_with("uint") => FieldType::Integer,
+        "number" | "float" | "double" | "real" | "numeric" | "decimal" | "float32" | "float64" => {
+            FieldType::Number
+        }
+        "boolean" | "bool" => FieldType::Boolean,
+        "object" | "record" | "map" | "array" | "json" | "jsonb" => FieldType::Object,
+        b if b.starts_with("timestamp")
+            || b.starts_with("datetime")
+            || b == "date"
+            || b == "time" =>
+        {
+            FieldType::Timestamp
+        }
+        _ => FieldType::Unknown,
+    }
+}
+
+/// Type of a JSON Schema property: type (or the first non-null of a type list), format date-time/date as timestamp.
+fn json_schema_type(property: &Value) -> FieldType {
+    if matches!(property["format"].as_str(), Some("date-time" | "date")) {
+        return FieldType::Timestamp;
+    }
+    match &property["type"] {
+        Value::String(t) => field_type(t),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null")
+            .map(field_type)
+            .unwrap_or(FieldType::Unknown),
+        _ if property["properties"].is_object() => FieldType::Object,
+        _ => FieldType::Unknown,
+    }
+}
+
+/// Type of an Avro field type: a name, a union (the first non-null branch) or a complex type with a logicalType.
+fn avro_type(t: &Value) -> FieldType {
+    match t {
+        Value::String(name) => field_type(name),
+        Value::Array(branches) => branches
+            .iter()
+            .find(|b| *b != "null")
+            .map(avro_type)
+            .unwrap_or(FieldType::Unknown),
+        Value::Object(o) => match o.get("logicalType").and_then(Value::as_str) {
+            Some(l)
+                if l.starts_with("timestamp") || l.starts_with("date") || l.starts_with("time") =>
+            {
+                FieldType::Timestamp
+            }
+            Some("decimal") => FieldType::Number,
+            Some("uuid") => FieldType::String,
+            _ => o.get("type").map(avro_type).unwrap_or(FieldType::Unknown),
+        },
+        _ => FieldType::Unknown,
+    }
+}
+
+/// Top-level fields of a JSON Schema (properties) or an Avro record (fields).
+pub fn schema_fields(schema: &Value, param: &str) -> Result<Vec<Field>, String> {
+    if let Some(fields) = schema["fields"].as_array() {
+        return fields
+            .iter()
+            .map(|f| match f["name"].as_str() {
+                Some(name) => Ok(Field {
+                    name: name.to_string(),
+                    kind: avro_type(&f["type"]),
+                }),
+                None => Err(format!("{}: every Avro field needs a name", param)),
+            })
+            .collect();
+    }
+    if let Some(properties) = schema["properties"].as_object() {
+        return Ok(properties
+            .iter()
+            .map(|(name, p)| Field {
+                name: name.clone(),
+                kind: json_schema_type(p),
+            })
+            .collect());
+    }
+    Err(format!(
+        "{} must be a JSON Schema with properties or an Avro record with fields",
+        param
+    ))
+}
+
+/// Target columns: a list of names, "name TYPE" strings or {name, type} objects, or a JSON Schema / Avro record.
+pub fn target_columns(target: &Value) -> Result<Vec<Field>, String> {
+    let Some(columns) = target.as_array() else {
+        return schema_fields(target, "target");
+    };
+    columns
+        .iter()
+        .map(|c| match c {
+            Value::String(s) => {
+                let (name, kind) = s
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((s.trim(), ""));
+                Ok(Field {
+                    name: name.to_string(),
+                    kind: field_type(kind.trim()),
+                })
+            }
+            Value::Object(o) => match o.get("name").and_then(Value::as_str) {
+                Some(name) => Ok(Field {
+                    name: name.to_string(),
+                    kind: o
+                        .get("type")
+                        .and_then(Value::as_str)
+                        .map(field_type)
+                        .unwrap_or(FieldType::Unknown),
+                }),
+                None => Err("target: every column object needs a name".to_string()),
+            },
+            _ => Err(
+                "target columns must be names, \"name TYPE\" strings or {name, type} objects"
+                    .to_string(),
+            ),
+        })
+        .collect()
+}
+
+/// Lua expression casting v from one type to another; None when no cast is needed or possible.
+fn cast(v: &str, from: FieldType, to: FieldType) -> Option<String> {
+    use FieldType::*;
+    match (from, to) {
+        (String | Number, Integer) => Some(format!("math.floor(tonumber({}))", v)),
+        (String, Number) => Some(format!("tonumber({})", v)),
+        (Integer | Number | Boolean, String) => Some(format!("tostring({})", v)),
+        (String, Boolean) => Some(format!("({} == \"true\")", v)),
+        (Integer, Boolean) => Some(format!("({} ~= 0)", v)),
+        _ => None,
+    }
+}
+
+/// Lua expression for field name of msg: msg["name"] with an escaped string literal, since field and column names may
+/// be Lua keywords or contain hyphens and spaces.
+fn lua_field(name: &str) -> String {
+    let mut literal = String::new();
+    for c in name.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            c if c.is_control() => literal.push_str(&format!("\\{:03}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    format!("msg[\"{}\"]", literal)
+}
+
+fn type_name(kind: FieldType) -> String {
+    format!("{:?}", kind).to_lowercase()
+}
+
+fn compatible(from: FieldType, to: FieldType) -> bool {
+    use FieldType::*;
+    from == to
+        || from == Unknown
+        || to == Unknown
+        || matches!(
+            (from, to),
+            (Integer, Number) | (String, Timestamp) | (_, Object)
+        )
+}
+
+/// Transformations mapping source fields to target columns, with one note per decision (case conversion, rename,
+/// cast, unmatched column or dropped field). Columns match fields by exact name, then by snake_case / camelCase,
+/// then ignoring case, '_' and '-'. A snakeCase or camelCase transformation covers the renames when it changes no
+/// exactly matched field; other renames and casts go to one lua script; a select keeps only the mapped columns.
+pub fn map_fields(source: &[Field], target: &[Field]) -> (Vec<Value>, Vec<String>) {
+    let loose = |s: &str| s.to_lowercase().replace(['_', '-'], "");
+    let mut notes = Vec::new();
+    let mut matched: Vec<(&Field, &Field)> = Vec::new();
+    for column in target {
+        let found = source
+            .iter()
+            .find(|f| f.name == column.name)
+            .or_else(|| {
+                source.iter().find(|f| {
+                    to_snake_case(&f.name) == column.name || to_camel_case(&f.name) == column.name
+                })
+            })
+            .or_else(|| {
+                source
+                    .iter()
+                    .find(|f| loose(&f.name) == loose(&column.name))
+            });
+        match found {
+            Some(field) => matched.push((field, column)),
+            None => notes.push(format!(
+                "column {} has no matching source field; it is left to the table default",
+                column.name
+            )),
+        }
+    }
+
+    let mut transformations = Vec::new();
+    let renamed: Vec<&(&Field, &Field)> =
+        matched.iter().filter(|(f, c)| f.name != c.name).collect();
+    let exact: Vec<&str> = matched
+        .iter()
+        .filter(|(f, c)| f.name == c.name)
+        .map(|(f, _)| f.name.as_str())
+        .collect();
+    let convention = [
+        ("snakeCase", to_snake_case as fn(&str) -> String),
+        ("camelCase", to_camel_case),
+    ]
+    .into_iter()
+    .find(|(_, convert)| {
+        !renamed.is_empty()
+            && renamed.iter().all(|(f, c)| convert(&f.name) == c.name)
+            && exact.iter().all(|name| convert(name) == *name)
+    });
+    let mut lines = Vec::new();
+    match convention {
+        Some((kind, _)) => {
+            transformations.push(json!({ "type": kind, kind: { "deep": false } }));
+            notes.push(format!(
+                "{} renames {}",
+                kind,
+                renamed
+                    .iter()
+                    .map(|(f, c)| format!("{} -> {}", f.name, c.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        None => {
+            for (field, column) in &renamed {
+                lines.push(format!(
+                    "{} = {}",
+                    lua_field(&column.name),
+                    lua_field(&field.name)
+                ));
+                lines.push(format!("{} = nil", lua_field(&field.name)));
+                notes.push(format!("renamed {} -> {}", field.name, column.name));
+            }
+        }
+    }
+    for (field, column) in &matched {
+        let target = lua_field(&column.name);
+        if let Some(expr) = cast(&target, field.kind, column.kind) {
+            lines.push(format!(
+                "if {} ~= nil then {} = {} end",
+                target, target, expr
+            ));
+            notes.push(format!(
+                "cast {} from {} to {}",
+                column.name,
+                type_name(field.kind),
+                type_name(column.kind)
+            ));
+        } else if !compatible(field.kind, column.kind) {
+            notes.push(format!(
+                "{} is {} in the source but {} in the table; convert it before the write",
+                column.name,
+                type_name(field.kind),
+                type_name(column.kind)
+            ));
+        }
+    }
+    if !lines.is_empty() {
+        lines.insert(
+            0,
+            "-- Generated field mapping: renames and casts to the target columns".to_string(),
+        );
+        lines.push("return msg".to_string());
+        transformations.push(json!({ "type": "script", "script": { "language": "lua", "code": format!("{}\n", lines.join("\n")) } }));
+    }
+
+    let dropped: Vec<&str> = source
+        .iter()
+        .filter(|f| !matched.iter().any(|(m, _)| m.name == f.name))
+        .map(|f| f.name.as_str())
+        .collect();
+    if !dropped.is_empty() && !matched.is_empty() {
+        let fields: Vec<String> = matched
+            .iter()
+            .map(|(_, c)| format!("$.{}", c.name))
+            .collect();
+        transformations.push(json!({ "type": "select", "select": { "fields": fields } }));
+        notes.push(format!(
+            "select drops source fields without a column: {}",
+            dropped.join(", ")
+        ));
+    }
+    (transformations, notes)
+}
+
+/// Appends the transformations mapping source_schema to target (see map_fields) to spec.transformations of yaml and
+/// returns the manifest with the mapping notes in its header; validation errors are listed as warnings.
+pub fn map_schema_to_table(
+    yaml: &str,
+    source_schema: &Value,
+    target: &Value,
+) -> Result<String, String> {
+    let parse = |v: &Value, param: &str| match v {
+        Value::String(s) => {
+            serde_json::from_str::<Value>(s).map_err(|e| format!("{} invalid JSON: {}", param, e))
+        }
+        v => Ok(v.clone()),
+    };
+    let source = schema_fields(&parse(source_schema, "source_schema")?, "source_schema")?;
+    let target = target_columns(&parse(target, "target")?)?;
+    if target.is_empty() {
+        return Err("target must list at least one column".to_string());
+    }
+    let docs = split_documents(yaml);
+    let [(_, doc)] = docs.as_slice() else {
+        return Err(format!(
+            "expected one DataFlow manifest, got {} documents",
+            docs.len()
+        ));
+    };
+    let mut manifest: Value =
+        serde_yaml::from_str(doc).map_err(|e| format!("YAML parse error: {}", e))?;
+    let (transformations, notes) = map_fields(&source, &target);
+    let Some(spec) = manifest.get_mut("spec").and_then(Value::as_object_mut) else {
+        return Err("spec is required".to_string());
+    };
+    let Some(list) = spec
+        .entry("transformations")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+    else {
+        return Err("spec.transformations must be a list".to_string());
+    };
+    let first = list.len();
+    list.extend(transformations.iter().cloned());
+
+    let updated = to_canonical_yaml(&manifest)?;
+    let mut out = match transformations.len() {
+        0 => "# Field mapping: no transformations needed\n".to_string(),
+        1 => format!(
+            "# Field mapping: added 1 transformation at spec.transformations[{}]\n",
+            first
+        ),
+        n => format!(
+            "# Field mapping: added {} transformations at spec.transformations[{}-{}]\n",
+            n,
+            first,
+            first + n - 1
+        ),
+    };
+    for note in &notes {
+        out.push_str(&format!("# - {}\n", note));
+    }
+    for e in validate_dataflow_manifest(&updated)
+        .err()
+        .unwrap_or_default()
+    {
+        out.push_str(&format!("# Warning: {}\n", e));
+    }
+    out.push_str(&updated);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(names: &[(&str, FieldType)]) -> Vec<Field> {
+        names
+            .iter()
+            .map(|(n, k)| Field {
+                name: n.to_string(),
+                kind: *k,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(to_snake_case("customerId"), "customer_id");
+        assert_eq!(to_snake_case("HTTPStatus"), "http_status");
+        assert_eq!(to_snake_case("order-total"), "order_total");
+        assert_eq!(to_camel_case("created_at"), "createdAt");
+    }
+
+    #[test]
+    fn test_map_fields() {
+        let avro = json!({ "type": "record", "name": "Order", "fields": [
+            { "name": "orderId", "type": "long" },
+            { "name": "customerEmail", "type": ["null", "string"] },
+            { "name": "total", "type": "string" },
+            { "name": "debug", "type": "boolean" }
+        ]});
+        let source = schema_fields(&avro, "source_schema").unwrap();
+        let target = target_columns(&json!(["order_id bigint", "customer_email text", { "name": "total", "type": "numeric(10,2)" }, "region"])).unwrap();
+        let (transformations, notes) = map_fields(&source, &target);
+        assert_eq!(
+            transformations[0],
+            json!({ "type": "snakeCase", "snakeCase": { "deep": false } })
+        );
+        assert_eq!(transformations[1]["script"]["code"], "-- Generated field mapping: renames and casts to the target columns\nif msg[\"total\"] ~= nil then msg[\"total\"] = tonumber(msg[\"total\"]) end\nreturn msg\n");
+        assert_eq!(
+            transformations[2],
+            json!({ "type": "select", "select": { "fields": ["$.order_id", "$.customer_email", "$.total"] } })
+        );
+        assert_eq!(
+            notes[0],
+            "column region has no matching source field; it is left to the table default"
+        );
+        assert!(notes.contains(&"select drops source fields without a column: debug".to_string()));
+
+        let (transformations, notes) = map_fields(
+            &fields(&[("cust-ID", FieldType::Integer)]),
+            &fields(&[("custid", FieldType::String)]),
+        );
+        assert_eq!(transformations[0]["script"]["code"], "-- Generated field mapping: renames and casts to the target columns\nmsg[\"custid\"] = msg[\"cust-ID\"]\nmsg[\"cust-ID\"] = nil\nif msg[\"custid\"] ~= nil then msg[\"custid\"] = tostring(msg[\"custid\"]) end\nreturn msg\n");
+        assert_eq!(
+            notes,
+            vec![
+                "renamed cust-ID -> custid",
+                "cast custid from integer to string"
+            ]
+        );
+        let (transformations, _) = map_fields(
+            &fields(&[("end", FieldType::String)]),
+            &fields(&[("end", FieldType::Integer)]),
+        );
+        assert!(transformations[0]["script"]["code"]
+            .as_str()
+            .unwrap()
+            .contains(
+            "if msg[\"end\"] ~= nil then msg[\"end\"] = math.floor(tonumber(msg[\"end\"])) end\n"
+        ));
+        assert_eq!(
+            lua_field("order \"id\"\\1\n"),
+            "msg[\"order \\\"id\\\"\\\\1\\010\"]"
+        );
+    }
+
+    #[test]
+    fn test_map_schema_to_table() {
+        let manifest = "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\nspec:\n  source:\n    type: kafka\n    kafka: {brokers: [\"k:9092\"], topic: orders, consumerGroup: orders}\n  sink:\n    type: postgresql\n    postgresql: {connectionString: \"postgres://pg:5432/shop\", table: orders}\n";
+        let schema = json!(
+            r#"{"type": "object", "properties": {"id": {"type": "integer"}, "createdAt": {"type": "string", "format": "date-time"}}}"#
+        );
+        let out = map_schema_to_table(manifest, &schema, &json!(["id", "created_at"])).unwrap();
+        assert!(out.starts_with("# Field mapping: added 1 transformation at spec.transformations[0]\n# - snakeCase renames createdAt -> created_at\n"));
+        assert!(!out.contains("# Warning"));
+        assert!(
+            map_schema_to_table(manifest, &json!({ "type": "string" }), &json!(["id"]))
+                .unwrap_err()
+                .contains("must be a JSON Schema with properties")
+        );
+    }
+}
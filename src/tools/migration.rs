@@ -0,0 +1,192 @@
+// Helpers shared by the migrators (Kafka Connect, NiFi, Airbyte, Benthos, Vector, Fluent Bit, Telegraf,
+// Kafka Streams): connector blocks, placeholders, expression conversion and rendering with migration notes.
+
+use crate::tools::connection::normalize_spec;
+use crate::tools::credentials::{plaintext_credentials, secret_name};
+use crate::tools::manifest::validate_dataflow_manifest;
+use crate::tools::output::to_canonical_yaml;
+use crate::tools::provenance::GenerationNote;
+use serde_json::{Map as JsonMap, Value};
+
+/// Source or sink block {type: kind, kind: inner}.
+pub(crate) fn block(kind: &str, inner: JsonMap<String, Value>) -> JsonMap<String, Value> {
+    let mut b = JsonMap::new();
+    b.insert("type".to_string(), Value::String(kind.to_string()));
+    b.insert(kind.to_string(), Value::Object(inner));
+    b
+}
+
+/// Strings of an array, or the comma-separated items of a string.
+pub(crate) fn str_list(v: &Value) -> Vec<String> {
+    match v {
+        Value::String(s) => s
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Value::Array(a) => a
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Kafka source or sink on localhost, recorded as filled at path.
+pub(crate) fn placeholder_kafka(
+    path: &str,
+    topic: &str,
+    reason: &str,
+    filled: &mut Vec<GenerationNote>,
+) -> JsonMap<String, Value> {
+    let mut kafka = JsonMap::new();
+    kafka.insert("brokers".to_string(), Value::from(vec!["localhost:9092"]));
+    kafka.insert("topic".to_string(), Value::String(topic.to_string()));
+    let block = block("kafka", kafka);
+    filled.push(GenerationNote::new(
+        path,
+        Value::Object(block.clone()),
+        reason,
+    ));
+    block
+}
+
+/// `this.a.b` / `.a.b` -> `$.a.b`.
+pub(crate) fn field_path(expr: &str) -> Option<String> {
+    let path = expr
+        .trim()
+        .strip_prefix("this.")
+        .or_else(|| expr.trim().strip_prefix('.'))?;
+    let valid = !path.is_empty()
+        && path
+            .split('.')
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_alphanumeric() || c == '_'));
+    valid.then(|| format!("$.{}", path))
+}
+
+/// A literal operand: "x" -> 'x'; numbers, true, false and null as is.
+fn literal(expr: &str) -> Option<String> {
+    let e = expr.trim();
+    if let Some(s) = e.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        return (!s.contains(['"', '\''])).then(|| format!("'{}'", s));
+    }
+    (e.parse::<f64>().is_ok() || matches!(e, "true" | "false" | "null")).then(|| e.to_string())
+}
+
+const COMPARISONS: [(&str, &str); 6] = [
+    ("==", "!="),
+    ("!=", "=="),
+    (">=", "<"),
+    ("<=", ">"),
+    (">", "<="),
+    ("<", ">="),
+];
+
+/// A bloblang (this.x == "y") or jq (.x == "y") comparison, or conjunctions/disjunctions of them, as a
+/// DataFlow JSONPath condition; negate inverts it (De Morgan for && / ||).
+pub(crate) fn condition(expr: &str, negate: bool) -> Option<String> {
+    // (operator, DataFlow operator, negated DataFlow operator); jq spells them and / or.
+    for (op, same, flipped) in [
+        (" && ", " && ", " || "),
+        (" || ", " || ", " && "),
+        (" and ", " && ", " || "),
+        (" or ", " || ", " && "),
+    ] {
+        if expr.contains(op) {
+            let parts: Option<Vec<String>> = expr.split(op).map(|p| condition(p, negate)).collect();
+            return parts.map(|p| p.join(if negate { flipped } else { same }));
+        }
+    }
+    let expr = expr.trim().trim_start_matches('(').trim_end_matches(')');
+    COMPARISONS.iter().find_map(|(op, inverse)| {
+        let (left, right) = expr.split_once(op)?;
+        let (field, value) = (field_path(left)?, literal(right)?);
+        Some(format!(
+            "{} {} {}",
+            field,
+            if negate { inverse } else { op },
+            value
+        ))
+    })
+}
+
+/// Lowercase name of alphanumerics and dashes (other characters become dashes), trimmed of dashes.
+pub(crate) fn sanitize_name(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+        .to_lowercase()
+}
+
+/// Renders a migrated manifest as YAML with the migration notes as a comment header; origin names what it was
+/// generated from. Never hands out a manifest our own validator rejects without saying so.
+pub(crate) fn render_migrated(
+    origin: &str,
+    manifest: &JsonMap<String, Value>,
+    mut notes: Vec<String>,
+) -> Result<String, String> {
+    let mut manifest = manifest.clone();
+    if let Some(spec) = manifest.get_mut("spec") {
+        for n in normalize_spec(spec) {
+            notes.push(format!(
+                "{} '{}' was normalized to '{}'.",
+                n.path, n.from, n.to
+            ));
+        }
+    }
+    if let Some(spec) = manifest.get("spec") {
+        let secret = secret_name(manifest.get("metadata").and_then(|m| m["name"].as_str()));
+        notes.extend(
+            plaintext_credentials(spec, &secret)
+                .iter()
+                .map(|c| format!("Security: {}.", c.message())),
+        );
+    }
+    let yaml = to_canonical_yaml(&manifest)?;
+    if let Err(errors) = validate_dataflow_manifest(&yaml) {
+        for e in errors {
+            notes.push(format!("Validation error, fix before applying: {}", e));
+        }
+    }
+    let mut out = format!("# DataFlow manifest generated from {}\n", origin);
+    if !notes.is_empty() {
+        out.push_str("# Migration notes:\n");
+        for n in &notes {
+            out.push_str(&format!("# - {}\n", n));
+        }
+    }
+    out.push('\n');
+    out.push_str(&yaml);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_shared_helpers() {
+        assert_eq!(
+            Value::Object(block("kafka", JsonMap::new())),
+            json!({ "type": "kafka", "kafka": {} })
+        );
+        assert_eq!(str_list(&json!("a, b,,c")), vec!["a", "b", "c"]);
+        assert_eq!(str_list(&json!(["a", 1, "b"])), vec!["a", "b"]);
+        assert!(str_list(&json!(null)).is_empty());
+        assert_eq!(sanitize_name("Orders_Sync.v2-"), "orders-sync-v2");
+        assert_eq!(
+            condition("this.a == \"x\" && .b > 1", true).as_deref(),
+            Some("$.a != 'x' || $.b <= 1")
+        );
+    }
+}
@@ -0,0 +1,762 @@
+// Audit report for Kafka Connect migrations: where every connector property ended up in the DataFlow spec.
+
+use crate::tools::changelog::flatten;
+use crate::tools::connect_worker::{apply_worker_properties, inherited_keys, WORKER_KEYS_KEY};
+use crate::tools::kafka_connect::{
+    connector_kind, get, group_connectors, map_smts, migrate_kafka_connect_to_dataflow, parse_input,
+};
+use crate::tools::provenance::GENERATION_NOTES_ANNOTATION;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map as JsonMap, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// Property -> DataFlow location rules: (connector kind or "*", key or "prefix*", target, confidence).
+/// "{kafka}" is the kafka block the connector's consumer/producer settings land in.
+const RULES: &[(&str, &str, &str, &str)] = &[
+    (
+        "*",
+        "connector.class",
+        "spec.source.type / spec.sink.type",
+        "high",
+    ),
+    ("*", "name", "metadata.name", "high"),
+    ("*", "tasks.max", "spec.parallelism", "high"),
+    ("*", "bootstrap.servers", "{kafka}.brokers", "high"),
+    ("*", "topics", "{kafka}.topic", "high"),
+    ("*", "topic", "{kafka}.topic", "high"),
+    ("*", "topics.regex", "{kafka}.topicPattern", "high"),
+    ("*", "group.id", "{kafka}.consumerGroup", "high"),
+    (
+        "*",
+        "consumer.override.group.id",
+        "{kafka}.consumerGroup",
+        "high",
+    ),
+    (
+        "*",
+        "auto.offset.reset",
+        "spec.source.kafka.offsetReset",
+        "high",
+    ),
+    (
+        "*",
+        "consumer.override.auto.offset.reset",
+        "spec.source.kafka.offsetReset",
+        "high",
+    ),
+    ("*", "value.converter", "{kafka}.format", "medium"),
+    (
+        "*",
+        "value.converter.schema.registry.url",
+        "{kafka}.schemaRegistry.url",
+        "high",
+    ),
+    (
+        "*",
+        "value.converter.schemas.enable",
+        "spec.transformations (unwrap)",
+        "medium",
+    ),
+    (
+        "*",
+        "schema.registry.url",
+        "{kafka}.schemaRegistry.url",
+        "high",
+    ),
+    (
+        "*",
+        "security.protocol",
+        "{kafka}.tls / {kafka}.sasl",
+        "medium",
+    ),
+    ("*", "ssl.*", "{kafka}.tls", "medium"),
+    ("*", "sasl.*", "{kafka}.sasl", "medium"),
+    (
+        "*",
+        "consumer.override.s*",
+        "{kafka}.tls / {kafka}.sasl",
+        "medium",
+    ),
+    (
+        "*",
+        "producer.override.s*",
+        "{kafka}.tls / {kafka}.sasl",
+        "medium",
+    ),
+    (
+        "*",
+        "errors.tolerance",
+        "spec.errorHandling.dlq / transformations[].onError",
+        "high",
+    ),
+    (
+        "*",
+        "errors.deadletterqueue.topic.name",
+        "spec.errorHandling.dlq.kafka.topic",
+        "high",
+    ),
+    (
+        "postgresql",
+        "connection.url",
+        "{side}.postgresql.connectionString",
+        "high",
+    ),
+    (
+        "postgresql",
+        "table.name.format",
+        "spec.sink.postgresql.table",
+        "high",
+    ),
+    (
+        "postgresql",
+        "table.whitelist",
+        "spec.source.postgresql.table",
+        "medium",
+    ),
+    (
+        "postgresql",
+        "table.include.list",
+        "spec.source.postgresql.table",
+        "medium",
+    ),
+    (
+        "postgresql",
+        "query",
+        "spec.source.postgresql.query",
+        "high",
+    ),
+    (
+        "postgresql",
+        "poll.interval.ms",
+        "spec.source.postgresql.pollInterval",
+        "high",
+    ),
+    (
+        "postgresql",
+        "mode",
+        "spec.source.postgresql.mode",
+        "medium",
+    ),
+    (
+        "postgresql",
+        "incrementing.column.name",
+        "spec.source.postgresql.incrementalColumn",
+        "high",
+    ),
+    (
+        "postgresql",
+        "timestamp.column.name",
+        "spec.source.postgresql.incrementalColumn",
+        "low",
+    ),
+    (
+        "postgresql",
+        "timestamp.initial",
+        "spec.source.postgresql.initialValue",
+        "medium",
+    ),
+    (
+        "debezium-postgresql",
+        "database.hostname",
+        "spec.source.postgresql.connectionString",
+        "high",
+    ),
+    (
+        "debezium-postgresql",
+        "database.port",
+        "spec.source.postgresql.connectionString",
+        "high",
+    ),
+    (
+        "debezium-postgresql",
+        "database.dbname",
+        "spec.source.postgresql.connectionString",
+        "high",
+    ),
+    (
+        "debezium-postgresql",
+        "database.user",
+        "spec.source.postgresql.connectionString",
+        "high",
+    ),
+    (
+        "debezium-postgresql",
+        "table.include.list",
+        "spec.source.postgresql.table",
+        "medium",
+    ),
+    (
+        "debezium-postgresql",
+        "slot.name",
+        "spec.source.postgresql.replication.slotName",
+        "high",
+    ),
+    (
+        "debezium-postgresql",
+        "publication.name",
+        "spec.source.postgresql.replication.publication",
+        "high",
+    ),
+    (
+        "debezium-postgresql",
+        "plugin.name",
+        "spec.source.postgresql.replication.plugin",
+        "high",
+    ),
+    (
+        "debezium-mysql",
+        "topic.prefix",
+        "spec.source.kafka.topic",
+        "medium",
+    ),
+    (
+        "debezium-mysql",
+        "database.server.name",
+        "spec.source.kafka.topic",
+        "medium",
+    ),
+    (
+        "debezium-mysql",
+        "table.include.list",
+        "spec.source.kafka.topic",
+        "medium",
+    ),
+    ("s3", "s3.bucket.name", "spec.sink.s3.bucket", "high"),
+    ("s3", "s3.region", "spec.sink.s3.region", "high"),
+    ("s3", "store.url", "spec.sink.s3.endpoint", "high"),
+    ("s3", "topics.dir", "spec.sink.s3.prefix", "medium"),
+    ("s3", "format.class", "spec.sink.s3.format", "medium"),
+    ("s3", "flush.size", "spec.sink.s3.flushSize", "high"),
+    (
+        "s3",
+        "partitioner.class",
+        "spec.sink.s3.partitioner",
+        "medium",
+    ),
+    (
+        "s3",
+        "partition.field.name",
+        "spec.sink.s3.partitionFields",
+        "high",
+    ),
+    ("s3", "path.format", "spec.sink.s3.pathFormat", "medium"),
+    (
+        "elasticsearch",
+        "connection.url",
+        "spec.sink.elasticsearch.addresses",
+        "high",
+    ),
+    (
+        "elasticsearch",
+        "connection.username",
+        "spec.sink.elasticsearch.username",
+        "high",
+    ),
+    (
+        "elasticsearch",
+        "connection.password",
+        "spec.sink.elasticsearch.password (placeholder)",
+        "low",
+    ),
+    (
+        "elasticsearch",
+        "topic.index.map",
+        "spec.sink.elasticsearch.index",
+        "low",
+    ),
+    (
+        "elasticsearch",
+        "behavior.on.null.values",
+        "spec.sink.elasticsearch.nullValueBehavior",
+        "high",
+    ),
+    (
+        "elasticsearch",
+        "key.ignore",
+        "spec.sink.elasticsearch.documentIdStrategy",
+        "high",
+    ),
+    (
+        "mirror-source",
+        "source.cluster.alias",
+        "spec.sink.kafka.topic",
+        "medium",
+    ),
+    (
+        "mirror-source",
+        "source.cluster.bootstrap.servers",
+        "spec.source.kafka.brokers",
+        "high",
+    ),
+    (
+        "mirror-source",
+        "target.cluster.bootstrap.servers",
+        "spec.sink.kafka.brokers",
+        "high",
+    ),
+    (
+        "mirror-source",
+        "replication.policy.*",
+        "spec.sink.kafka.topic",
+        "medium",
+    ),
+];
+
+/// Properties that only describe the Connect deployment and need no DataFlow counterpart.
+const INFORMATIONAL: [&str; 2] = ["key.converter", "header.converter"];
+
+#[derive(Debug, Serialize)]
+struct PropertyReport {
+    property: String,
+    value: String,
+    /// mapped, dropped or informational
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    /// high, medium, low; none for dropped properties
+    confidence: &'static str,
+    /// worker.properties for settings the connector inherits from the worker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConnectorReport {
+    name: String,
+    class: String,
+    kind: String,
+    /// 1-based index of the manifest (flow) this connector was migrated into
+    flow: usize,
+    /// metadata.name of that manifest (with the name strategy applied); absent when the migration fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flow_name: Option<String>,
+    properties: Vec<PropertyReport>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Summary {
+    properties: usize,
+    mapped: usize,
+    dropped: usize,
+    informational: usize,
+    low_confidence: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct MigrationReport {
+    summary: Summary,
+    connectors: Vec<ConnectorReport>,
+}
+
+/// Builds a JSON report for the same input and options (worker properties, name strategy) as
+/// migrate_kafka_connect_to_dataflow.
+pub fn migrate_kafka_connect_report(
+    kafka_connect_config: &str,
+    worker_properties: Option<&str>,
+    name_strategy: Option<&str>,
+) -> Result<String, String> {
+    let mut connectors = parse_input(kafka_connect_config)?;
+    if let Some(worker) = worker_properties.filter(|w| !w.trim().is_empty()) {
+        connectors = apply_worker_properties(&connectors, worker);
+    }
+    // A failed migration still gets a report (it shows what was dropped), just without flow names.
+    let flow_names: Vec<Option<String>> =
+        migrate_kafka_connect_to_dataflow(kafka_connect_config, worker_properties, name_strategy)
+            .map(|yaml| {
+                serde_yaml::Deserializer::from_str(&yaml)
+                    .map(|doc| {
+                        Value::deserialize(doc)
+                            .ok()
+                            .and_then(|v| v["metadata"]["name"].as_str().map(str::to_string))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    let mut report = MigrationReport {
+        summary: Summary::default(),
+        connectors: Vec::new(),
+    };
+    for (i, group) in group_connectors(&connectors).iter().enumerate() {
+        for conn in group {
+            let config = conn.config.clone().unwrap_or_default();
+            let class = get(&config, "connector.class").unwrap_or_default();
+            let (direction, kind) = connector_kind(&class);
+            let inherited = inherited_keys(&config);
+            let mut properties = report_properties(&config, direction, kind);
+            for p in &mut properties {
+                if inherited.contains(&p.property) {
+                    p.origin = Some("worker.properties");
+                }
+                report.summary.properties += 1;
+                match p.status {
+                    "mapped" => report.summary.mapped += 1,
+                    "dropped" => report.summary.dropped += 1,
+                    _ => report.summary.informational += 1,
+                }
+                if p.confidence == "low" {
+                    report.summary.low_confidence += 1;
+                }
+            }
+            report.connectors.push(ConnectorReport {
+                name: conn.name.clone().unwrap_or_default(),
+                class,
+                kind: format!("{}/{}", direction, kind),
+                flow: i + 1,
+                flow_name: flow_names.get(i).cloned().flatten(),
+                properties,
+            });
+        }
+    }
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+/// Field-level provenance for manifests produced by migrate_kafka_connect_to_dataflow from the same input:
+/// per flow, every manifest field mapped from connector properties, the values filled by the migrator, and
+/// fields with neither (constants such as apiVersion, or derived values).
+pub fn migration_provenance(
+    kafka_connect_config: &str,
+    worker_properties: Option<&str>,
+    manifests_yaml: &str,
+) -> Result<String, String> {
+    let mut connectors = parse_input(kafka_connect_config)?;
+    if let Some(worker) = worker_properties.filter(|w| !w.trim().is_empty()) {
+        connectors = apply_worker_properties(&connectors, worker);
+    }
+    let groups = group_connectors(&connectors);
+    let mut flows = Vec::new();
+    for (i, doc) in serde_yaml::Deserializer::from_str(manifests_yaml).enumerate() {
+        let manifest = Value::deserialize(doc)
+            .map_err(|e| format!("document {}: YAML parse error: {}", i + 1, e))?;
+        let mut leaves = BTreeMap::new();
+        flatten(&manifest, "", &mut leaves);
+        let filled: JsonMap<String, Value> = manifest["metadata"]["annotations"]
+            [GENERATION_NOTES_ANNOTATION]
+            .as_str()
+            .and_then(|s| serde_json::from_str::<Vec<Value>>(s).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|n| Some((n["path"].as_str()?.to_string(), n["reason"].clone())))
+            .collect();
+
+        // (manifest location, transformation type it is restricted to, "connector: property").
+        let mut sources: Vec<(String, Option<String>, String)> = Vec::new();
+        for conn in groups.get(i).into_iter().flatten() {
+            let config = conn.config.clone().unwrap_or_default();
+            let (direction, kind) =
+                connector_kind(&get(&config, "connector.class").unwrap_or_default());
+            let side = if direction == "sink" {
+                "spec.sink"
+            } else {
+                "spec.source"
+            };
+            let name = conn.name.clone().unwrap_or_default();
+            let inherited = inherited_keys(&config);
+            for p in report_properties(&config, direction, kind) {
+                let origin = if inherited.contains(&p.property) {
+                    "worker.properties"
+                } else {
+                    name.as_str()
+                };
+                let Some(target) = p.target else { continue };
+                let alternatives: Vec<&str> = target
+                    .split(" / ")
+                    .filter(|t| t.starts_with("spec.") || t.starts_with("metadata."))
+                    .collect();
+                // connector.class names both source.type and sink.type; the connector's own side is meant.
+                let own: Vec<&str> = alternatives
+                    .iter()
+                    .copied()
+                    .filter(|t| t.starts_with(side))
+                    .collect();
+                let chosen = if own.is_empty() || own.len() == alternatives.len() {
+                    alternatives
+                } else {
+                    own
+                };
+                for t in chosen {
+                    let (location, qualifier) = match t.split_once(" (") {
+                        Some((l, q)) => (l, Some(q.trim_end_matches(')').to_string())),
+                        None => (t, None),
+                    };
+                    sources.push((
+                        location.to_string(),
+                        qualifier,
+                        format!("{}: {}", origin, p.property),
+                    ));
+                }
+            }
+        }
+
+        let mut fields: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut unattributed = Vec::new();
+        for path in leaves
+            .keys()
+            .filter(|p| !p.contains(GENERATION_NOTES_ANNOTATION))
+        {
+            let from: Vec<String> = sources
+                .iter()
+                .filter(|(location, qualifier, _)| {
+                    let under = path == location
+                        || path.starts_with(&format!("{}.", location))
+                        || path.starts_with(&format!("{}[", location));
+                    // "spec.transformations (mask)" only covers the mask transformations.
+                    under
+                        && qualifier.as_ref().is_none_or(|q| {
+                            let item = path.find("].").map(|end| &path[..=end]).unwrap_or(path);
+                            leaves
+                                .get(&format!("{}.type", item))
+                                .is_none_or(|t| t.as_str() == Some(q.as_str()))
+                        })
+                })
+                .map(|(_, _, property)| property.clone())
+                .collect();
+            if !from.is_empty() {
+                fields.insert(path.clone(), from);
+            } else if !filled.contains_key(path) {
+                unattributed.push(path.clone());
+            }
+        }
+        flows.push(json!({
+            "flow": i + 1,
+            "name": manifest["metadata"]["name"],
+            "fields": fields,
+            "filled": filled,
+            "unattributed": unattributed
+        }));
+    }
+    serde_json::to_string_pretty(&json!({ "flows": flows })).map_err(|e| e.to_string())
+}
+
+fn report_properties(
+    config: &HashMap<String, String>,
+    direction: &str,
+    kind: &str,
+) -> Vec<PropertyReport> {
+    // Where consumer/producer settings go: the connector's own kafka block, or the kafka source feeding a sink.
+    let kafka_block = match (direction, kind) {
+        ("source", "kafka") | ("source", "debezium-mysql") => Some("spec.source.kafka"),
+        ("sink", "kafka") => Some("spec.sink.kafka"),
+        ("sink", _) => Some("spec.source.kafka"),
+        _ => None,
+    };
+    let side = if direction == "sink" {
+        "spec.sink"
+    } else {
+        "spec.source"
+    };
+    let mut keys: Vec<&String> = config.keys().filter(|k| *k != WORKER_KEYS_KEY).collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| {
+            let value = redact(key, &config[key]);
+            let k = key.to_lowercase();
+            // SMT predicates are evaluated during migration and explained in its notes, not mapped to a field.
+            if INFORMATIONAL.contains(&k.as_str()) || k.starts_with("predicates") {
+                return PropertyReport {
+                    property: key.clone(),
+                    value,
+                    status: "informational",
+                    target: None,
+                    confidence: "none",
+                    origin: None,
+                };
+            }
+            let (target, confidence) = match k.strip_prefix("transforms") {
+                Some(rest) => smt_target(config, rest),
+                None => rule_target(&k, kind, kafka_block, side),
+            };
+            PropertyReport {
+                property: key.clone(),
+                value,
+                status: if target.is_some() {
+                    "mapped"
+                } else {
+                    "dropped"
+                },
+                confidence: if target.is_some() { confidence } else { "none" },
+                target,
+                origin: None,
+            }
+        })
+        .collect()
+}
+
+fn rule_target(
+    key: &str,
+    kind: &str,
+    kafka_block: Option<&str>,
+    side: &str,
+) -> (Option<String>, &'static str) {
+    let rule = RULES.iter().find(|(k, pattern, _, _)| {
+        (*k == "*" || *k == kind)
+            && match pattern.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => key == *pattern,
+            }
+    });
+    match rule {
+        Some((_, _, target, confidence)) if target.contains("{kafka}") => match kafka_block {
+            Some(block) => (Some(target.replace("{kafka}", block)), confidence),
+            None => (None, "none"),
+        },
+        Some((_, _, target, confidence)) => (Some(target.replace("{side}", side)), confidence),
+        None => (None, "none"),
+    }
+}
+
+/// transforms / transforms.<alias>.*: mapped when map_smts turns the alias into a transformation.
+fn smt_target(config: &HashMap<String, String>, rest: &str) -> (Option<String>, &'static str) {
+    let alias = match rest.strip_prefix('.') {
+        Some(r) => r.split('.').next().unwrap_or(""),
+        None => return (Some("spec.transformations".to_string()), "medium"),
+    };
+    let prefix = format!("transforms.{}.", alias);
+    let mut single: HashMap<String, String> = config
+        .iter()
+        .filter(|(k, _)| k.to_lowercase().starts_with(&prefix))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    single.insert("transforms".to_string(), alias.to_string());
+    let (transformations, _) = map_smts(&single);
+    match transformations
+        .first()
+        .and_then(|t| t.get("type"))
+        .and_then(|t| t.as_str())
+    {
+        Some(t) => (Some(format!("spec.transformations ({})", t)), "medium"),
+        None => (None, "none"),
+    }
+}
+
+/// Hides secrets; the report is meant to be shared with platform teams.
+fn redact(key: &str, value: &str) -> String {
+    let k = key.to_lowercase();
+    if [
+        "password",
+        "secret",
+        "jaas.config",
+        "token",
+        "access.key",
+        "keystore.key",
+    ]
+    .iter()
+    .any(|s| k.contains(s))
+    {
+        "***".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_migrate_kafka_connect_report() {
+        let config = r#"{
+            "name": "orders-sink",
+            "config": {
+                "connector.class": "io.confluent.connect.jdbc.JdbcSinkConnector",
+                "connection.url": "jdbc:postgresql://pg:5432/shop",
+                "connection.password": "hunter2",
+                "topics": "orders",
+                "insert.mode": "upsert",
+                "transforms": "drop,route",
+                "transforms.drop.type": "org.apache.kafka.connect.transforms.ReplaceField$Value",
+                "transforms.drop.exclude": "internal",
+                "transforms.route.type": "org.apache.kafka.connect.transforms.RegexRouter"
+            }
+        }"#;
+        let report: Value =
+            serde_json::from_str(&migrate_kafka_connect_report(config, None, None).unwrap())
+                .unwrap();
+        let props = report["connectors"][0]["properties"].as_array().unwrap();
+        let find = |name: &str| {
+            props
+                .iter()
+                .find(|p| p["property"] == name)
+                .unwrap()
+                .clone()
+        };
+
+        assert_eq!(
+            find("connection.url")["target"],
+            "spec.sink.postgresql.connectionString"
+        );
+        assert_eq!(find("topics")["target"], "spec.source.kafka.topic");
+        assert_eq!(find("insert.mode")["status"], "dropped");
+        assert_eq!(find("connection.password")["value"], "***");
+        assert_eq!(
+            find("transforms.drop.exclude")["target"],
+            "spec.transformations (remove)"
+        );
+        assert_eq!(find("transforms.route.type")["status"], "dropped");
+        assert_eq!(report["summary"]["properties"], 9);
+        assert_eq!(report["summary"]["dropped"], 3);
+        assert_eq!(report["connectors"][0]["flow_name"], "orders-sink");
+
+        // Same options as the migration: worker settings are reported as inherited, names follow the strategy.
+        let worker = "bootstrap.servers=k1:9092\n";
+        let report: Value = serde_json::from_str(
+            &migrate_kafka_connect_report(config, Some(worker), Some("sequence")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(report["connectors"][0]["flow_name"], "orders-sink-1");
+        let props = report["connectors"][0]["properties"].as_array().unwrap();
+        let brokers = props
+            .iter()
+            .find(|p| p["property"] == "bootstrap.servers")
+            .unwrap();
+        assert_eq!(
+            (brokers["target"].as_str(), brokers["origin"].as_str()),
+            (Some("spec.source.kafka.brokers"), Some("worker.properties"))
+        );
+    }
+
+    #[test]
+    fn test_migration_provenance_maps_fields_to_properties() {
+        let config = r#"{
+            "name": "orders-sink",
+            "config": {
+                "connector.class": "io.confluent.connect.jdbc.JdbcSinkConnector",
+                "connection.url": "jdbc:postgresql://pg:5432/shop",
+                "topics": "orders",
+                "table.name.format": "orders",
+                "transforms": "mask",
+                "transforms.mask.type": "org.apache.kafka.connect.transforms.MaskField$Value",
+                "transforms.mask.fields": "card"
+            }
+        }"#;
+        let yaml =
+            crate::tools::kafka_connect::migrate_kafka_connect_to_dataflow(config, None, None)
+                .unwrap();
+        let out: Value =
+            serde_json::from_str(&migration_provenance(config, None, &yaml).unwrap()).unwrap();
+        let flow = &out["flows"][0];
+        let fields = &flow["fields"];
+        assert_eq!(
+            fields["spec.sink.postgresql.connectionString"],
+            json!(["orders-sink: connection.url"])
+        );
+        assert_eq!(
+            fields["spec.sink.type"],
+            json!(["orders-sink: connector.class"])
+        );
+        assert!(fields.get("spec.source.type").is_none());
+        assert_eq!(
+            fields["spec.source.kafka.topic"],
+            json!(["orders-sink: topics"])
+        );
+        assert!(fields["spec.transformations[0].mask.fields[0]"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("orders-sink: transforms.mask.fields")));
+        assert!(flow["filled"]["spec.source.kafka.brokers"].is_string());
+        assert!(flow["unattributed"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("apiVersion")));
+    }
+}
@@ -1,3 +1,52 @@
+pub mod airbyte;
+pub mod apply;
+pub mod avro;
+pub mod benthos;
+pub mod builder;
+pub mod bulk;
+pub mod capabilities;
+pub mod changelog;
+pub mod chunks;
+pub mod compatibility;
+pub mod confluent_cloud;
+pub mod connect_rest;
+pub mod connect_worker;
+pub mod connection;
+pub mod conversion;
+pub mod crd_schema;
+pub mod credentials;
+pub mod digest;
+pub mod docs;
+pub mod export;
+pub mod fluentbit;
+pub mod gc;
+pub mod gitops;
+pub mod inference;
+pub mod input;
+pub mod jsonpath;
 pub mod kafka_connect;
+pub mod kafka_streams;
+pub mod kube;
+pub mod kustomize;
+pub mod lint;
 pub mod manifest;
+pub mod mapping;
+pub mod migration;
+pub mod migration_report;
+pub mod monitoring;
+pub mod naming;
+pub mod network_policy;
+pub mod nifi;
+pub mod output;
+pub mod policy;
+pub mod provenance;
 pub mod reference;
+pub mod resources;
+pub mod sample_inference;
+pub mod sarif;
+pub mod schema_registry;
+pub mod session_stats;
+pub mod telegraf;
+pub mod templates;
+pub mod vector;
+pub mod workspace;
@@ -0,0 +1,232 @@
+// Prometheus Operator manifests for DataFlows: a ServiceMonitor or PodMonitor scraping the flow's metrics and a
+// PrometheusRule with example alerts for consumer lag and failed messages.
+
+use crate::tools::manifest::{parse_duration_ms, split_documents};
+use crate::tools::network_policy::FLOW_POD_LABEL;
+use crate::types::DATAFLOW_KIND;
+use serde_json::{json, Map as JsonMap, Value};
+use std::collections::BTreeMap;
+
+const MONITORING_API_VERSION: &str = "monitoring.coreos.com/v1";
+/// Metrics endpoint of flow pods: port name and path.
+const METRICS_PORT: &str = "metrics";
+const METRICS_PATH: &str = "/metrics";
+/// Flow metrics the alert rules use; both are labelled with dataflow and namespace.
+const LAG_METRIC: &str = "dataflow_consumer_lag";
+const FAILED_METRIC: &str = "dataflow_messages_failed_total";
+const DEFAULT_LAG_THRESHOLD: u64 = 10000;
+
+/// Monitoring settings; unset fields take defaults.
+#[derive(Debug, Clone, Default)]
+pub struct MonitoringOptions {
+    /// service (ServiceMonitor, default) or pod (PodMonitor).
+    pub monitor: Option<String>,
+    /// Scrape interval (default 30s).
+    pub interval: Option<String>,
+    /// Lag in messages above which the lag alert fires (default 10000).
+    pub lag_threshold: Option<u64>,
+    /// Labels added to the generated resources, e.g. the release label the Prometheus instance selects.
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Labels the flow's Service and pods carry: the DataFlow's metadata.labels, or app: <name> when it has none.
+fn flow_selector(manifest: &Value, name: &str) -> Value {
+    match manifest["metadata"]["labels"]
+        .as_object()
+        .filter(|l| !l.is_empty())
+    {
+        Some(labels) => Value::Object(labels.clone()),
+        None => json!({ FLOW_POD_LABEL: name }),
+    }
+}
+
+/// Alert rules of a flow: lag above lag_threshold for 10 minutes (kafka sources only) and failed messages for 5 minutes.
+fn alert_rules(name: &str, namespace: &str, kafka_source: bool, lag_threshold: u64) -> Vec<Value> {
+    let selector = format!("dataflow=\"{}\",namespace=\"{}\"", name, namespace);
+    let mut rules = Vec::new();
+    if kafka_source {
+        rules.push(json!({
+            "alert": "DataFlowConsumerLagHigh",
+            "expr": format!("max({}{{{}}}) > {}", LAG_METRIC, selector, lag_threshold),
+            "for": "10m",
+            "labels": { "severity": "warning" },
+            "annotations": { "summary": format!("DataFlow {}/{} is more than {} messages behind its Kafka source", namespace, name, lag_threshold) }
+        }));
+    }
+    rules.push(json!({
+        "alert": "DataFlowMessagesFailing",
+        "expr": format!("sum(rate({}{{{}}}[5m])) > 0", FAILED_METRIC, selector),
+        "for": "5m",
+        "labels": { "severity": "warning" },
+        "annotations": { "summary": format!("DataFlow {}/{} fails to process messages", namespace, name) }
+    }));
+    rules
+}
+
+/// ServiceMonitor (or PodMonitor) and PrometheusRule for every DataFlow of yaml, as multi-document YAML. The
+/// monitors select the flow by its labels (see flow_selector) in its namespace.
+pub fn generate_monitoring_manifests(
+    yaml: &str,
+    options: &MonitoringOptions,
+) -> Result<String, String> {
+    let (kind, endpoints_key) = match options.monitor.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("" | "service" | "servicemonitor") => ("ServiceMonitor", "endpoints"),
+        Some("pod" | "podmonitor") => ("PodMonitor", "podMetricsEndpoints"),
+        Some(other) => {
+            return Err(format!(
+                "monitor '{}' is not supported; use service or pod",
+                other
+            ))
+        }
+    };
+    let interval = options
+        .interval
+        .as_deref()
+        .filter(|i| !i.is_empty())
+        .unwrap_or("30s");
+    match parse_duration_ms(interval) {
+        Some(ms) if (1000..=3_600_000).contains(&ms) => {}
+        _ => {
+            return Err(format!(
+                "interval '{}' must be a duration between 1s and 1h, e.g. 30s",
+                interval
+            ))
+        }
+    }
+    let lag_threshold = options.lag_threshold.unwrap_or(DEFAULT_LAG_THRESHOLD);
+    let labels: JsonMap<String, Value> = options
+        .labels
+        .iter()
+        .map(|(k, v)| (k.clone(), json!(v)))
+        .collect();
+
+    let mut docs = Vec::new();
+    for (_, doc) in split_documents(yaml) {
+        let manifest: Value =
+            serde_yaml::from_str(&doc).map_err(|e| format!("YAML parse error: {}", e))?;
+        if manifest["kind"] != DATAFLOW_KIND {
+            continue;
+        }
+        let name = manifest["metadata"]["name"]
+            .as_str()
+            .ok_or("every DataFlow needs metadata.name to be monitored")?;
+        let namespace = manifest["metadata"]["namespace"]
+            .as_str()
+            .unwrap_or("default");
+        let metadata = |suffix: &str| {
+            let mut m = json!({ "name": format!("{}{}", name, suffix), "namespace": namespace });
+            if !labels.is_empty() {
+                m["labels"] = Value::Object(labels.clone());
+            }
+            m
+        };
+        let monitor = json!({
+            "apiVersion": MONITORING_API_VERSION,
+            "kind": kind,
+            "metadata": metadata(""),
+            "spec": {
+                "selector": { "matchLabels": flow_selector(&manifest, name) },
+                "namespaceSelector": { "matchNames": [namespace] },
+                endpoints_key: [{ "port": METRICS_PORT, "path": METRICS_PATH, "interval": interval }]
+            }
+        });
+        let kafka_source = manifest["spec"]["source"]["type"] == "kafka";
+        let rule = json!({
+            "apiVersion": MONITORING_API_VERSION,
+            "kind": "PrometheusRule",
+            "metadata": metadata("-alerts"),
+            "spec": { "groups": [{ "name": format!("dataflow-{}", name), "rules": alert_rules(name, namespace, kafka_source, lag_threshold) }] }
+        });
+        docs.push(format!(
+            "# {} scraping {} of {} every {}\n{}",
+            kind,
+            METRICS_PATH,
+            name,
+            interval,
+            serde_yaml::to_string(&monitor).map_err(|e| e.to_string())?
+        ));
+        docs.push(format!(
+            "# Example alerts for {}: adjust thresholds and severities to your SLOs\n{}",
+            name,
+            serde_yaml::to_string(&rule).map_err(|e| e.to_string())?
+        ));
+    }
+    if docs.is_empty() {
+        return Err("no DataFlow manifest found".to_string());
+    }
+    Ok(docs.join("---\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\n  namespace: shop\n  labels:\n    team: checkout\nspec:\n  source:\n    type: kafka\n    kafka: {brokers: [\"k:9092\"], topic: orders}\n  sink:\n    type: postgresql\n    postgresql: {connectionString: \"postgres://pg/shop\", table: orders}\n";
+
+    #[test]
+    fn test_generate_monitoring_manifests() {
+        let options = MonitoringOptions {
+            labels: BTreeMap::from([("release".to_string(), "prometheus".to_string())]),
+            ..Default::default()
+        };
+        let out = generate_monitoring_manifests(MANIFEST, &options).unwrap();
+        let docs: Vec<Value> = split_documents(&out)
+            .iter()
+            .map(|(_, d)| serde_yaml::from_str(d).unwrap())
+            .collect();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0]["kind"], "ServiceMonitor");
+        assert_eq!(
+            docs[0]["metadata"],
+            json!({ "name": "orders", "namespace": "shop", "labels": { "release": "prometheus" } })
+        );
+        assert_eq!(
+            docs[0]["spec"]["selector"]["matchLabels"],
+            json!({ "team": "checkout" })
+        );
+        assert_eq!(docs[0]["spec"]["endpoints"][0]["interval"], "30s");
+        let rules = docs[1]["spec"]["groups"][0]["rules"].as_array().unwrap();
+        assert_eq!(
+            rules[0]["expr"],
+            "max(dataflow_consumer_lag{dataflow=\"orders\",namespace=\"shop\"}) > 10000"
+        );
+        assert_eq!(rules[1]["alert"], "DataFlowMessagesFailing");
+    }
+
+    #[test]
+    fn test_generate_monitoring_manifests_pod_monitor() {
+        let unlabelled = MANIFEST
+            .replace("  labels:\n    team: checkout\n", "")
+            .replace("type: kafka\n    kafka", "type: trino\n    trino");
+        let options = MonitoringOptions {
+            monitor: Some("pod".to_string()),
+            interval: Some("1m".to_string()),
+            ..Default::default()
+        };
+        let out = generate_monitoring_manifests(&unlabelled, &options).unwrap();
+        let docs: Vec<Value> = split_documents(&out)
+            .iter()
+            .map(|(_, d)| serde_yaml::from_str(d).unwrap())
+            .collect();
+        assert_eq!(docs[0]["kind"], "PodMonitor");
+        assert_eq!(
+            docs[0]["spec"]["selector"]["matchLabels"],
+            json!({ "app": "orders" })
+        );
+        assert_eq!(docs[0]["spec"]["podMetricsEndpoints"][0]["interval"], "1m");
+        assert_eq!(
+            docs[1]["spec"]["groups"][0]["rules"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+        let bad = MonitoringOptions {
+            interval: Some("30".to_string()),
+            ..Default::default()
+        };
+        assert!(generate_monitoring_manifests(MANIFEST, &bad)
+            .unwrap_err()
+            .starts_with("interval '30' must be a duration"));
+    }
+}
@@ -0,0 +1,502 @@
+// Resource naming: Kubernetes name rules, naming strategies for generated and migrated manifests, and the
+// naming conventions (prefix / suffix / pattern for flow, topic and table names) loaded from a config file.
+
+use crate::tools::manifest::{split_documents, Diagnostic, DocumentReport, Severity};
+use crate::types::{DATAFLOW_KIND, RELATIONAL_SINK_TYPES};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Kubernetes label values (and thus names used as labels) are limited to 63 characters.
+const MAX_NAME_LEN: usize = 63;
+
+/// DNS-1123 subdomain (metadata.name, label key prefixes) is limited to 253 characters.
+const MAX_SUBDOMAIN_LEN: usize = 253;
+
+/// Checks a DNS-1123 label: at most 63 lower case alphanumerics or '-', starting and ending alphanumeric.
+pub fn check_dns1123_label(s: &str) -> Result<(), String> {
+    if s.is_empty() || s.len() > MAX_NAME_LEN {
+        return Err(format!("must be 1-{} characters", MAX_NAME_LEN));
+    }
+    let ok_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+    if !s.chars().all(|c| ok_char(c) || c == '-')
+        || !s.starts_with(ok_char)
+        || !s.ends_with(ok_char)
+    {
+        return Err("must consist of lower case alphanumerics or '-' and start and end with an alphanumeric".to_string());
+    }
+    Ok(())
+}
+
+/// Checks a DNS-1123 subdomain: dot-separated DNS-1123 labels, at most 253 characters.
+pub fn check_dns1123_subdomain(s: &str) -> Result<(), String> {
+    if s.is_empty() || s.len() > MAX_SUBDOMAIN_LEN {
+        return Err(format!("must be 1-{} characters", MAX_SUBDOMAIN_LEN));
+    }
+    for part in s.split('.') {
+        check_dns1123_label(part).map_err(|e| format!("part '{}' {}", part, e))?;
+    }
+    Ok(())
+}
+
+/// Checks a label or annotation key: optional DNS-1123 subdomain prefix and '/', then a name of at most
+/// 63 alphanumerics, '-', '_' or '.', starting and ending alphanumeric.
+pub fn check_qualified_key(key: &str) -> Result<(), String> {
+    let (prefix, name) = match key.rsplit_once('/') {
+        Some((p, n)) => (Some(p), n),
+        None => (None, key),
+    };
+    if let Some(p) = prefix {
+        check_dns1123_subdomain(p).map_err(|e| format!("prefix '{}' {}", p, e))?;
+    }
+    check_label_value(name).map_err(|e| format!("name part {}", e))?;
+    if name.is_empty() {
+        return Err("name part must not be empty".to_string());
+    }
+    Ok(())
+}
+
+/// Checks a label value: empty, or at most 63 alphanumerics, '-', '_' or '.', starting and ending alphanumeric.
+pub fn check_label_value(s: &str) -> Result<(), String> {
+    if s.len() > MAX_NAME_LEN {
+        return Err(format!("must be at most {} characters", MAX_NAME_LEN));
+    }
+    let ok_char = |c: char| c.is_ascii_alphanumeric();
+    if !s.is_empty()
+        && (!s.chars().all(|c| ok_char(c) || "-_.".contains(c))
+            || !s.starts_with(ok_char)
+            || !s.ends_with(ok_char))
+    {
+        return Err(
+            "must consist of alphanumerics, '-', '_' or '.' and start and end with an alphanumeric"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// How metadata.name is derived from the base name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameStrategy {
+    /// Use the base name as is.
+    #[default]
+    Exact,
+    /// Append 4 hex chars of a stable hash of the manifest spec: orders-sync-7f3a.
+    HashSuffix,
+    /// Append the 1-based position in the batch: orders-sync-1, orders-sync-2.
+    Sequence,
+}
+
+/// Parses the nameStrategy parameter: exact (default), hash-suffix or sequence.
+pub fn parse_name_strategy(s: Option<&str>) -> Result<NameStrategy, String> {
+    match s.map(str::trim) {
+        None | Some("") | Some("exact") => Ok(NameStrategy::Exact),
+        Some("hash-suffix") => Ok(NameStrategy::HashSuffix),
+        Some("sequence") => Ok(NameStrategy::Sequence),
+        Some(other) => Err(format!(
+            "nameStrategy must be one of: exact, hash-suffix, sequence (got '{}')",
+            other
+        )),
+    }
+}
+
+/// Applies the strategy to base; seed feeds the hash (e.g. the serialized spec), index is the 1-based position.
+pub fn apply_name_strategy(base: &str, strategy: NameStrategy, seed: &str, index: usize) -> String {
+    let suffix = match strategy {
+        NameStrategy::Exact => return base.to_string(),
+        NameStrategy::HashSuffix => format!("{:04x}", fnv1a(seed.as_bytes()) & 0xffff),
+        NameStrategy::Sequence => index.to_string(),
+    };
+    let max_base = MAX_NAME_LEN - suffix.len() - 1;
+    // Cut at a char boundary: base may hold non-ASCII alphanumerics.
+    let end = base
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .take_while(|end| *end <= max_base)
+        .last()
+        .unwrap_or(0);
+    let base = &base[..end];
+    format!("{}-{}", base.trim_end_matches('-'), suffix)
+}
+
+static CONVENTIONS: OnceLock<NamingConventions> = OnceLock::new();
+
+/// One naming rule: a required prefix and suffix and a regex the whole name must match.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NameRule {
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub suffix: Option<String>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+impl NameRule {
+    /// Why value breaks the rule, if it does.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        let mut reasons = Vec::new();
+        if let Some(prefix) = self.prefix.as_deref().filter(|p| !value.starts_with(p)) {
+            reasons.push(format!("must start with '{}'", prefix));
+        }
+        if let Some(suffix) = self.suffix.as_deref().filter(|s| !value.ends_with(s)) {
+            reasons.push(format!("must end with '{}'", suffix));
+        }
+        if let Some(pattern) = &self.pattern {
+            if regex::Regex::new(pattern).is_ok_and(|re| !re.is_match(value)) {
+                reasons.push(format!("must match {}", pattern));
+            }
+        }
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(reasons.join(" and "))
+        }
+    }
+
+    /// value with the missing prefix and suffix added (the pattern cannot be applied automatically).
+    pub fn apply(&self, value: &str) -> String {
+        let mut out = value.to_string();
+        if let Some(prefix) = self.prefix.as_deref().filter(|p| !out.starts_with(p)) {
+            out = format!("{}{}", prefix, out);
+        }
+        if let Some(suffix) = self.suffix.as_deref().filter(|s| !out.ends_with(s)) {
+            out.push_str(suffix);
+        }
+        out
+    }
+}
+
+/// Naming conventions file: rules for metadata.name, kafka topics and relational tables (the table name without
+/// its schema).
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamingConventions {
+    #[serde(default)]
+    pub name: Option<NameRule>,
+    #[serde(default)]
+    pub topic: Option<NameRule>,
+    #[serde(default)]
+    pub table: Option<NameRule>,
+}
+
+impl NamingConventions {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.topic.is_none() && self.table.is_none()
+    }
+
+    fn rule(&self, kind: NameKind) -> Option<&NameRule> {
+        match kind {
+            NameKind::Name => self.name.as_ref(),
+            NameKind::Topic => self.topic.as_ref(),
+            NameKind::Table => self.table.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NameKind {
+    Name,
+    Topic,
+    Table,
+}
+
+/// Parses a naming conventions file (YAML or JSON): {name, topic, table}, each {prefix, suffix, pattern}.
+pub fn parse_naming_conventions(text: &str) -> Result<NamingConventions, String> {
+    let conventions: NamingConventions =
+        serde_yaml::from_str(text).map_err(|e| format!("naming conventions: {}", e))?;
+    let rules = [
+        ("name", &conventions.name),
+        ("topic", &conventions.topic),
+        ("table", &conventions.table),
+    ];
+    for (kind, rule) in rules {
+        if let Some(pattern) = rule.as_ref().and_then(|r| r.pattern.as_deref()) {
+            regex::Regex::new(pattern).map_err(|e| {
+                format!("naming conventions: {}.pattern '{}': {}", kind, pattern, e)
+            })?;
+        }
+    }
+    Ok(conventions)
+}
+
+/// Loads the naming conventions file at startup; later calls keep the first value.
+pub fn init_conventions(path: &str) -> Result<(), String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("naming conventions file {}: {}", path, e))?;
+    let conventions = parse_naming_conventions(&text).map_err(|e| format!("{}: {}", path, e))?;
+    CONVENTIONS.get_or_init(|| conventions);
+    Ok(())
+}
+
+/// Conventions loaded at startup (empty if no naming file is configured).
+pub fn conventions() -> &'static NamingConventions {
+    CONVENTIONS.get_or_init(NamingConventions::default)
+}
+
+/// Names a convention applies to: (path, kind, value); spec.kafka.topic fields and relational table fields anywhere
+/// under spec (sources, sinks, DLQ, branches, router routes).
+fn named_fields(manifest: &Value) -> Vec<(String, NameKind, String)> {
+    fn walk(v: &Value, path: &str, out: &mut Vec<(String, NameKind, String)>) {
+        match v {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let p = format!("{}.{}", path, key);
+                    match (key.as_str(), child.get("topic"), child.get("table")) {
+                        ("kafka", Some(Value::String(topic)), _) => {
+                            out.push((format!("{}.topic", p), NameKind::Topic, topic.clone()))
+                        }
+                        (k, _, Some(Value::String(table)))
+                            if RELATIONAL_SINK_TYPES.contains(&k) =>
+                        {
+                            out.push((format!("{}.table", p), NameKind::Table, table.clone()))
+                        }
+                        _ => {}
+                    }
+                    walk(child, &p, out);
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    walk(item, &format!("{}[{}]", path, i), out);
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut out = Vec::new();
+    if let Some(name) = manifest["metadata"]["name"].as_str() {
+        out.push((
+            "metadata.name".to_string(),
+            NameKind::Name,
+            name.to_string(),
+        ));
+    }
+    walk(&manifest["spec"], "spec", &mut out);
+    out
+}
+
+/// schema.table -> ("schema.", "table"): conventions apply to the table name.
+fn split_table(value: &str) -> (&str, &str) {
+    match value.rfind('.') {
+        Some(i) => value.split_at(i + 1),
+        None => ("", value),
+    }
+}
+
+/// Names of a DataFlow manifest that break the conventions, as messages starting with the manifest path.
+pub fn check_naming_conventions(conventions: &NamingConventions, manifest: &Value) -> Vec<String> {
+    if manifest["kind"] != DATAFLOW_KIND {
+        return Vec::new();
+    }
+    let mut errors = Vec::new();
+    for (path, kind, value) in named_fields(manifest) {
+        let Some(rule) = conventions.rule(kind) else {
+            continue;
+        };
+        let name = if kind == NameKind::Table {
+            split_table(&value).1
+        } else {
+            value.as_str()
+        };
+        if let Err(reason) = rule.check(name) {
+            errors.push(format!(
+                "{} '{}' does not follow the naming convention: {}",
+                path, value, reason
+            ));
+        }
+    }
+    errors
+}
+
+/// Adds the prefix / suffix the conventions require to metadata.name and to the topics and tables the flow
+/// writes; source topics and tables name existing data and are only reported. Returns (path, new value) per rename
+/// and the remaining violations.
+pub fn apply_naming_conventions(
+    conventions: &NamingConventions,
+    manifest: &mut Value,
+) -> (Vec<(String, String)>, Vec<String>) {
+    let mut renamed = Vec::new();
+    for (path, kind, value) in named_fields(manifest) {
+        let Some(rule) = conventions.rule(kind) else {
+            continue;
+        };
+        if path.starts_with("spec.source.") {
+            continue;
+        }
+        let (schema, name) = if kind == NameKind::Table {
+            split_table(&value)
+        } else {
+            ("", value.as_str())
+        };
+        let applied = format!("{}{}", schema, rule.apply(name));
+        if applied != value {
+            if let Some(slot) = manifest.pointer_mut(&json_pointer(&path)) {
+                *slot = Value::String(applied.clone());
+                renamed.push((path, applied));
+            }
+        }
+    }
+    (renamed, check_naming_conventions(conventions, manifest))
+}
+
+/// spec.branches.a.sink[0].kafka.topic -> /spec/branches/a/sink/0/kafka/topic.
+fn json_pointer(path: &str) -> String {
+    path.replace('[', ".")
+        .replace(']', "")
+        .split('.')
+        .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Adds convention violations as errors to the reports of validate_dataflow_documents, matched by document index.
+pub fn add_convention_findings(
+    config_yaml: &str,
+    conventions: &NamingConventions,
+    reports: &mut [DocumentReport],
+) {
+    if conventions.is_empty() {
+        return;
+    }
+    let docs = split_documents(config_yaml);
+    for report in reports.iter_mut() {
+        let Some((offset, doc)) = docs.get(report.index - 1) else {
+            continue;
+        };
+        let Ok(manifest) = serde_yaml::from_str::<Value>(doc) else {
+            continue;
+        };
+        for e in check_naming_conventions(conventions, &manifest) {
+            let mut d = Diagnostic::located(Severity::Error, e, doc);
+            d.line += offset;
+            report.diagnostics.push(d);
+        }
+        report.diagnostics.sort_by_key(|d| d.severity);
+    }
+}
+
+/// 32-bit FNV-1a: stable across Rust versions and platforms, unlike std's DefaultHasher.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5u32, |h, b| {
+        (h ^ u32::from(*b)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_name_strategy() {
+        assert_eq!(
+            apply_name_strategy("orders-sync", NameStrategy::Exact, "x", 1),
+            "orders-sync"
+        );
+        assert_eq!(
+            apply_name_strategy("orders-sync", NameStrategy::Sequence, "x", 2),
+            "orders-sync-2"
+        );
+        let a = apply_name_strategy("orders-sync", NameStrategy::HashSuffix, "spec-a", 1);
+        let b = apply_name_strategy("orders-sync", NameStrategy::HashSuffix, "spec-b", 1);
+        assert_eq!(
+            a,
+            apply_name_strategy("orders-sync", NameStrategy::HashSuffix, "spec-a", 7)
+        );
+        assert_ne!(a, b);
+        assert_eq!(a.len(), "orders-sync-".len() + 4);
+        let long = apply_name_strategy(&"a".repeat(80), NameStrategy::HashSuffix, "s", 1);
+        assert_eq!(long.len(), MAX_NAME_LEN);
+        let wide = format!("a{}", "ж".repeat(40));
+        let cut = apply_name_strategy(&wide, NameStrategy::Sequence, "s", 3);
+        assert!(cut.len() <= MAX_NAME_LEN && cut.starts_with("aжж") && cut.ends_with("ж-3"));
+        assert!(parse_name_strategy(Some("random")).is_err());
+    }
+
+    #[test]
+    fn test_naming_conventions() {
+        let conventions = parse_naming_conventions(
+            "name: {prefix: payments-}\ntopic: {prefix: prod., pattern: '^[a-z.-]+$'}\ntable: {suffix: _v1}\n",
+        )
+        .unwrap();
+        let mut manifest = serde_json::json!({
+            "kind": "DataFlow",
+            "metadata": { "name": "orders" },
+            "spec": {
+                "source": { "type": "kafka", "kafka": { "topic": "Orders" } },
+                "sink": { "type": "postgresql", "postgresql": { "table": "public.orders" } },
+                "errorHandling": { "dlq": { "type": "kafka", "kafka": { "topic": "prod.orders-dlq" } } }
+            }
+        });
+        assert_eq!(
+            check_naming_conventions(&conventions, &manifest),
+            vec![
+                "metadata.name 'orders' does not follow the naming convention: must start with 'payments-'".to_string(),
+                "spec.sink.postgresql.table 'public.orders' does not follow the naming convention: must end with '_v1'".to_string(),
+                "spec.source.kafka.topic 'Orders' does not follow the naming convention: must start with 'prod.' and must match ^[a-z.-]+$".to_string(),
+            ]
+        );
+        let (renamed, remaining) = apply_naming_conventions(&conventions, &mut manifest);
+        assert_eq!(
+            renamed,
+            vec![
+                ("metadata.name".to_string(), "payments-orders".to_string()),
+                (
+                    "spec.sink.postgresql.table".to_string(),
+                    "public.orders_v1".to_string()
+                ),
+            ]
+        );
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].starts_with("spec.source.kafka.topic"));
+        assert!(parse_naming_conventions("topics: {prefix: a}")
+            .unwrap_err()
+            .contains("unknown field `topics`"));
+    }
+
+    #[test]
+    fn test_add_convention_findings_skips_companion_documents() {
+        let conventions = parse_naming_conventions("name: {prefix: payments-}\n").unwrap();
+        let flow = |name: &str, sink: &str| {
+            format!(
+                "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: {}\nspec:\n  source: {{type: kafka, kafka: {{brokers: [\"k:9092\"], topic: in}}}}\n  sink: {}\n",
+                name, sink
+            )
+        };
+        let secret = "apiVersion: v1\nkind: Secret\nmetadata:\n  name: a-credentials\nstringData:\n  password: x\n";
+        let sink = "{type: kafka, kafka: {brokers: [\"k:9092\"], topic: out}}";
+        let config = format!(
+            "{}---\n{}---\n{}",
+            flow("payments-a", sink),
+            secret,
+            flow("b", sink)
+        );
+        let mut reports = crate::tools::manifest::validate_dataflow_documents(&config, false);
+        add_convention_findings(&config, &conventions, &mut reports);
+        assert!(!reports[0].has_errors());
+        assert_eq!(
+            reports[1].errors(),
+            vec!["metadata.name 'b' does not follow the naming convention: must start with 'payments-'".to_string()]
+        );
+        assert_eq!(reports[1].diagnostics[0].line, 19);
+    }
+
+    #[test]
+    fn test_kubernetes_name_rules() {
+        assert!(check_dns1123_label("orders-sync-1").is_ok());
+        assert!(check_dns1123_label("Orders").is_err());
+        assert!(check_dns1123_label("-orders").is_err());
+        assert!(check_dns1123_label(&"a".repeat(64)).is_err());
+        assert!(check_dns1123_subdomain("orders.sync").is_ok());
+        assert!(check_dns1123_subdomain("orders..sync").is_err());
+        assert!(check_qualified_key("app.kubernetes.io/name").is_ok());
+        assert!(check_qualified_key("team").is_ok());
+        assert!(check_qualified_key("Example.com/team")
+            .unwrap_err()
+            .starts_with("prefix"));
+        assert!(check_qualified_key("example.com/").is_err());
+        assert!(check_label_value("").is_ok());
+        assert!(check_label_value("v1.2_rc").is_ok());
+        assert!(check_label_value("a b").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+// Egress NetworkPolicy for a DataFlow: the endpoints the flow connects to (Kafka brokers, database hosts, Trino,
+// Elasticsearch, S3, schema registries) become the only destinations its pods may reach.
+
+use crate::tools::manifest::split_documents;
+use crate::types::DATAFLOW_KIND;
+use reqwest::Url;
+use serde_json::{json, Value};
+use std::net::IpAddr;
+
+/// Pod label the operator sets to the DataFlow name; the policy selects the flow's pods by it.
+pub const FLOW_POD_LABEL: &str = "app";
+
+/// A destination of the flow: manifest path of the field, host and TCP port.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    pub path: String,
+    pub host: String,
+    pub port: u16,
+}
+
+/// Default port of a connection URL scheme.
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "postgres" | "postgresql" => Some(5432),
+        "clickhouse" | "tcp" => Some(9000),
+        "http" => Some(80),
+        "https" => Some(443),
+        _ => None,
+    }
+}
+
+/// Host and port of a URL or host:port address; None for placeholders, loopback and addresses without a port.
+fn endpoint(address: &str) -> Option<(String, u16)> {
+    let address = address.trim();
+    if address.is_empty() || address.contains("${") {
+        return None;
+    }
+    let (host, port) = if address.contains("://") {
+        let url = Url::parse(address).ok()?;
+        let host = url
+            .host_str()?
+            .trim_matches(|c| c == '[' || c == ']')
+            .to_string();
+        (host, url.port().or_else(|| default_port(url.scheme()))?)
+    } else {
+        let (host, port) = address.rsplit_once(':')?;
+        (
+            host.trim_matches(|c| c == '[' || c == ']').to_string(),
+            port.parse().ok()?,
+        )
+    };
+    let loopback = host == "localhost" || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback());
+    (!host.is_empty() && !loopback).then_some((host, port))
+}
+
+fn collect(v: &Value, path: &str, found: &mut Vec<Endpoint>) {
+    match v {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let child = format!("{}.{}", path, key);
+                let addresses: Vec<&str> = match (key.as_str(), value) {
+                    ("brokers" | "addresses", Value::Array(items)) => {
+                        items.iter().filter_map(Value::as_str).collect()
+                    }
+                    ("connectionString" | "serverURL" | "url" | "endpoint", Value::String(s)) => {
+                        vec![s.as_str()]
+                    }
+                    _ => {
+                        collect(value, &child, found);
+                        continue;
+                    }
+                };
+                for (host, port) in addresses.into_iter().filter_map(endpoint) {
+                    found.push(Endpoint {
+                        path: child.clone(),
+                        host,
+                        port,
+                    });
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect(item, &format!("{}[{}]", path, i), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every network destination under spec: kafka brokers, connectionString/serverURL, elasticsearch addresses,
+/// s3 endpoint and url fields (schema registry, keycloak, http enrich), ordered by field path.
+pub fn egress_targets(spec: &Value) -> Vec<Endpoint> {
+    let mut found = Vec::new();
+    collect(spec, "spec", &mut found);
+    found
+}
+
+/// Peer of an egress rule for host: an ipBlock for IP addresses, the namespace of a cluster service
+/// ([pod.]name.namespace.svc[.cluster.local]) or the flow's own namespace for a bare service name; None for external
+/// DNS names, which NetworkPolicy cannot match.
+fn peer(host: &str) -> Option<Value> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        let bits = if ip.is_ipv4() { 32 } else { 128 };
+        return Some(json!({ "ipBlock": { "cidr": format!("{}/{}", ip, bits) } }));
+    }
+    let service = host.strip_suffix(".cluster.local").unwrap_or(host);
+    match service.split('.').collect::<Vec<_>>().as_slice() {
+        [_] => Some(json!({ "podSelector": {} })),
+        [_, .., namespace, "svc"] => Some(
+            json!({ "namespaceSelector": { "matchLabels": { "kubernetes.io/metadata.name": namespace } } }),
+        ),
+        _ => None,
+    }
+}
+
+/// Appends a NetworkPolicy that limits egress of every DataFlow in yaml to its endpoints plus DNS. External DNS
+/// names are allowed by port only (NetworkPolicy matches IPs) and listed as warnings in the policy header.
+pub fn with_network_policy(yaml: &str) -> Result<String, String> {
+    let mut out = yaml.to_string();
+    let mut flows = 0;
+    for (_, doc) in split_documents(yaml) {
+        let manifest: Value =
+            serde_yaml::from_str(&doc).map_err(|e| format!("YAML parse error: {}", e))?;
+        if manifest["kind"] != DATAFLOW_KIND {
+            continue;
+        }
+        flows += 1;
+        let name = manifest["metadata"]["name"].as_str().unwrap_or("dataflow");
+        let mut rules = vec![json!({
+            "ports": [{ "protocol": "UDP", "port": 53 }, { "protocol": "TCP", "port": 53 }]
+        })];
+        let mut header = format!(
+            "# NetworkPolicy: egress of {} limited to DNS and its endpoints\n",
+            name
+        );
+        let mut seen = Vec::new();
+        for target in egress_targets(&manifest["spec"]) {
+            if seen.contains(&(target.host.clone(), target.port)) {
+                continue;
+            }
+            seen.push((target.host.clone(), target.port));
+            let ports = json!([{ "protocol": "TCP", "port": target.port }]);
+            match peer(&target.host) {
+                Some(to) => rules.push(json!({ "to": [to], "ports": ports })),
+                None => {
+                    header.push_str(&format!(
+                        "# Warning: {} ({}) is a DNS name; egress to port {} is allowed to any address, add an ipBlock to pin it\n",
+                        target.host, target.path, target.port
+                    ));
+                    rules.push(json!({ "ports": ports }));
+                }
+            }
+        }
+        let mut metadata = json!({ "name": format!("{}-egress", name) });
+        if let Some(ns) = manifest["metadata"]["namespace"].as_str() {
+            metadata["namespace"] = json!(ns);
+        }
+        let policy = json!({
+            "apiVersion": "networking.k8s.io/v1",
+            "kind": "NetworkPolicy",
+            "metadata": metadata,
+            "spec": {
+                "podSelector": { "matchLabels": { FLOW_POD_LABEL: name } },
+                "policyTypes": ["Egress"],
+                "egress": rules
+            }
+        });
+        out.push_str("---\n");
+        out.push_str(&header);
+        out.push_str(&serde_yaml::to_string(&policy).map_err(|e| e.to_string())?);
+    }
+    if flows == 0 {
+        return Err("no DataFlow manifest to derive a NetworkPolicy from".to_string());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: orders
+  namespace: shop
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["kafka-0.kafka.streaming.svc:9092", "10.0.0.7:9092"]
+      topic: orders
+  sink:
+    type: postgresql
+    postgresql:
+      connectionString: "postgres://app@pg.example.com/shop"
+      table: orders
+  errorHandling:
+    dlq:
+      type: kafka
+      kafka:
+        brokers: ["10.0.0.7:9092"]
+        topic: orders-dlq
+"#;
+
+    #[test]
+    fn test_egress_targets() {
+        let manifest: Value = serde_yaml::from_str(MANIFEST).unwrap();
+        let hosts: Vec<(String, u16)> = egress_targets(&manifest["spec"])
+            .into_iter()
+            .map(|e| (e.host, e.port))
+            .collect();
+        assert_eq!(
+            hosts,
+            vec![
+                ("10.0.0.7".to_string(), 9092),
+                ("pg.example.com".to_string(), 5432),
+                ("kafka-0.kafka.streaming.svc".to_string(), 9092),
+                ("10.0.0.7".to_string(), 9092),
+            ]
+        );
+        assert_eq!(endpoint("localhost:9092"), None);
+        assert_eq!(endpoint("${BROKERS}"), None);
+        assert_eq!(
+            endpoint("https://trino.example.com"),
+            Some(("trino.example.com".to_string(), 443))
+        );
+    }
+
+    #[test]
+    fn test_with_network_policy() {
+        let out = with_network_policy(MANIFEST).unwrap();
+        let docs = split_documents(&out);
+        assert_eq!(docs.len(), 2);
+        assert!(docs[1].1.contains(
+            "# Warning: pg.example.com (spec.sink.postgresql.connectionString) is a DNS name"
+        ));
+        let policy: Value = serde_yaml::from_str(&docs[1].1).unwrap();
+        assert_eq!(
+            policy["metadata"],
+            json!({ "name": "orders-egress", "namespace": "shop" })
+        );
+        assert_eq!(
+            policy["spec"]["podSelector"],
+            json!({ "matchLabels": { "app": "orders" } })
+        );
+        let egress = policy["spec"]["egress"].as_array().unwrap();
+        assert_eq!(egress.len(), 4);
+        assert_eq!(
+            egress[1]["to"],
+            json!([{ "ipBlock": { "cidr": "10.0.0.7/32" } }])
+        );
+        assert_eq!(
+            egress[2],
+            json!({ "ports": [{ "protocol": "TCP", "port": 5432 }] })
+        );
+        assert_eq!(
+            egress[3]["to"][0]["namespaceSelector"]["matchLabels"]["kubernetes.io/metadata.name"],
+            "streaming"
+        );
+        assert!(with_network_policy("apiVersion: v1\nkind: Secret\n").is_err());
+    }
+}
@@ -0,0 +1,169 @@
+// Output formats of generated and migrated manifests: YAML with comment headers (default) or JSON for
+// programmatic consumers and `kubectl apply -f -`.
+
+use crate::tools::manifest::split_documents;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub const OUTPUT_FORMATS: [&str; 2] = ["yaml", "json"];
+
+/// Keys that lead their mapping in generated YAML, in this order; after the type of a connector or transformation
+/// comes the block it names. Other keys follow alphabetically.
+const LEADING_KEYS: [&str; 12] = [
+    "apiVersion",
+    "kind",
+    "metadata",
+    "spec",
+    "name",
+    "namespace",
+    "labels",
+    "annotations",
+    "type",
+    "source",
+    "sink",
+    "transformations",
+];
+
+fn canonical(v: &Value) -> Result<serde_yaml::Value, String> {
+    let Value::Object(map) = v else {
+        return match v {
+            Value::Array(items) => items
+                .iter()
+                .map(canonical)
+                .collect::<Result<Vec<_>, _>>()
+                .map(serde_yaml::Value::Sequence),
+            _ => serde_yaml::to_value(v).map_err(|e| e.to_string()),
+        };
+    };
+    let named = map
+        .get("type")
+        .and_then(Value::as_str)
+        .filter(|t| map.contains_key(*t));
+    let rank = |key: &str| match LEADING_KEYS.iter().position(|k| *k == key) {
+        Some(i) => i,
+        None if Some(key) == named => LEADING_KEYS.iter().position(|k| *k == "type").unwrap_or(0),
+        None => LEADING_KEYS.len(),
+    };
+    let mut keys: Vec<&String> = map.keys().collect();
+    // Stable: the type's block stays right after type, the rest keep the map's alphabetical order.
+    keys.sort_by_key(|k| rank(k) * 2 + usize::from(Some(k.as_str()) == named));
+    let mut out = serde_yaml::Mapping::new();
+    for key in keys {
+        out.insert(
+            serde_yaml::Value::String(key.clone()),
+            canonical(&map[key.as_str()])?,
+        );
+    }
+    Ok(serde_yaml::Value::Mapping(out))
+}
+
+/// YAML of a manifest with deterministic key order (see LEADING_KEYS), so regenerating a flow with any tool gives
+/// minimal diffs in Git.
+pub fn to_canonical_yaml<T: Serialize>(value: &T) -> Result<String, String> {
+    let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    serde_yaml::to_string(&canonical(&value)?).map_err(|e| e.to_string())
+}
+
+/// Renders a generated or migrated YAML in format (default yaml). JSON is one object, or a v1 List of the
+/// documents when there are several; since JSON has no comments, the header comments come back as notes
+/// (prefixed with the document name when there are several documents).
+pub fn to_output_format(yaml: &str, format: Option<&str>) -> Result<(String, String), String> {
+    match format
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .unwrap_or("yaml")
+        .to_lowercase()
+        .as_str()
+    {
+        "yaml" | "yml" => Ok((yaml.to_string(), String::new())),
+        "json" => {
+            let docs = split_documents(yaml);
+            let mut items = Vec::new();
+            let mut notes = Vec::new();
+            for (_, doc) in &docs {
+                let item: Value =
+                    serde_yaml::from_str(doc).map_err(|e| format!("YAML parse error: {}", e))?;
+                let comments: Vec<&str> = doc
+                    .lines()
+                    .filter_map(|l| l.trim_start().strip_prefix('#'))
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .collect();
+                if !comments.is_empty() {
+                    if docs.len() > 1 {
+                        notes.push(format!(
+                            "{}:",
+                            item["metadata"]["name"].as_str().unwrap_or("document")
+                        ));
+                    }
+                    notes.extend(comments.iter().map(|c| c.to_string()));
+                }
+                items.push(item);
+            }
+            let out = match items.len() {
+                1 => items.remove(0),
+                _ => json!({ "apiVersion": "v1", "kind": "List", "items": items }),
+            };
+            Ok((
+                serde_json::to_string_pretty(&out).map_err(|e| e.to_string())?,
+                notes.join("\n"),
+            ))
+        }
+        other => Err(format!(
+            "output_format '{}' is not supported; use one of: {}",
+            other,
+            OUTPUT_FORMATS.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_output_format() {
+        let yaml = "# Generated DataFlow manifest\n# Warning: check it\napiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: a\n";
+        assert_eq!(
+            to_output_format(yaml, None).unwrap(),
+            (yaml.to_string(), String::new())
+        );
+        let (json_text, notes) = to_output_format(yaml, Some("JSON")).unwrap();
+        let parsed: Value = serde_json::from_str(&json_text).unwrap();
+        assert_eq!(parsed["metadata"]["name"], "a");
+        assert_eq!(notes, "Generated DataFlow manifest\nWarning: check it");
+        let two = format!("{}---\n{}", yaml, yaml.replace("name: a", "name: b"));
+        let (json_text, notes) = to_output_format(&two, Some("json")).unwrap();
+        let parsed: Value = serde_json::from_str(&json_text).unwrap();
+        assert_eq!(
+            (
+                parsed["kind"].as_str(),
+                parsed["items"][1]["metadata"]["name"].as_str()
+            ),
+            (Some("List"), Some("b"))
+        );
+        assert!(notes.starts_with("a:\nGenerated DataFlow manifest") && notes.contains("\nb:\n"));
+        assert!(to_output_format(yaml, Some("toml"))
+            .unwrap_err()
+            .contains("use one of: yaml, json"));
+    }
+
+    #[test]
+    fn test_to_canonical_yaml() {
+        let manifest = json!({
+            "spec": {
+                "transformations": [{ "onError": "skip", "filter": { "condition": "$.ok" }, "type": "filter" }],
+                "sink": { "postgresql": { "table": "t" }, "type": "postgresql" },
+                "source": { "kafka": { "topic": "a", "brokers": ["k:9092"] }, "type": "kafka" },
+                "parallelism": 2
+            },
+            "metadata": { "namespace": "shop", "name": "a", "annotations": { "x": "y" } },
+            "kind": "DataFlow",
+            "apiVersion": "dataflow.dataflow.io/v1"
+        });
+        assert_eq!(
+            to_canonical_yaml(&manifest).unwrap(),
+            "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: a\n  namespace: shop\n  annotations:\n    x: y\nspec:\n  source:\n    type: kafka\n    kafka:\n      brokers:\n      - k:9092\n      topic: a\n  sink:\n    type: postgresql\n    postgresql:\n      table: t\n  transformations:\n  - type: filter\n    filter:\n      condition: $.ok\n    onError: skip\n  parallelism: 2\n"
+        );
+    }
+}
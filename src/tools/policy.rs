@@ -0,0 +1,847 @@
+// Organizational policies: rules from a YAML/JSON file loaded at startup (or passed inline) with CEL-like
+// expressions over the manifest, checked per flow or per sink, source, transformation or branch.
+
+use crate::tools::manifest::{split_documents, Diagnostic, DocumentReport, Severity};
+use crate::types::DATAFLOW_KIND;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Parts of a flow a rule can iterate over with for_each.
+const SCOPES: [&str; 4] = ["sinks", "sources", "transformations", "branches"];
+
+/// Paths starting with these keys are read from the manifest; other paths from the for_each element.
+const ROOTS: [&str; 4] = ["apiVersion", "kind", "metadata", "spec"];
+
+/// Functions expressions may call: (name, number of arguments).
+const FUNCTIONS: [(&str, usize); 6] = [
+    ("has", 1),
+    ("size", 1),
+    ("matches", 2),
+    ("startsWith", 2),
+    ("endsWith", 2),
+    ("contains", 2),
+];
+
+/// Operators, longest first so "==" is not read as two tokens.
+const SYMBOLS: [&str; 15] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "(", ")", "[", "]", ",", ".",
+];
+
+static POLICIES: OnceLock<Vec<Policy>> = OnceLock::new();
+
+/// One rule as written in the policies file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    /// error (default), warning or info.
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default, alias = "forEach")]
+    for_each: Option<String>,
+    /// Condition selecting the manifests / elements the rule applies to.
+    #[serde(default)]
+    when: Option<String>,
+    require: String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// A compiled rule.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    pub name: String,
+    severity: Severity,
+    for_each: Option<String>,
+    when: Option<Expr>,
+    require: Expr,
+    message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Value),
+    List(Vec<Expr>),
+    Path(Vec<Segment>),
+    Call(String, Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(&'static str, Box<Expr>, Box<Expr>),
+}
+
+/// Parses a rules file: a list of rules or {policies: [...]}, in YAML or JSON.
+pub fn parse_policies(text: &str) -> Result<Vec<Policy>, String> {
+    let doc: Value =
+        serde_yaml::from_str(text).map_err(|e| format!("policies: YAML parse error: {}", e))?;
+    let rules = match &doc {
+        Value::Array(rules) => rules,
+        Value::Object(map) => match map.get("policies") {
+            Some(Value::Array(rules)) => rules,
+            _ => return Err("policies must be a list of rules (or {policies: [...]})".to_string()),
+        },
+        _ => return Err("policies must be a list of rules (or {policies: [...]})".to_string()),
+    };
+    let mut policies: Vec<Policy> = Vec::new();
+    for (i, rule) in rules.iter().enumerate() {
+        let spec: RuleSpec =
+            serde_json::from_value(rule.clone()).map_err(|e| format!("policies[{}]: {}", i, e))?;
+        let at = format!("policies[{}] ({})", i, spec.name);
+        if spec.name.trim().is_empty() {
+            return Err(format!("policies[{}]: name must not be empty", i));
+        }
+        if policies.iter().any(|p| p.name == spec.name) {
+            return Err(format!("{}: duplicate policy name", at));
+        }
+        let severity = match spec.severity.as_deref().unwrap_or("error") {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "info" => Severity::Info,
+            other => {
+                return Err(format!(
+                    "{}: severity '{}' must be one of: error, warning, info",
+                    at, other
+                ))
+            }
+        };
+        if let Some(scope) = spec.for_each.as_deref().filter(|s| !SCOPES.contains(s)) {
+            return Err(format!(
+                "{}: for_each '{}' must be one of: {}",
+                at,
+                scope,
+                SCOPES.join(", ")
+            ));
+        }
+        let when = match spec.when.as_deref() {
+            Some(w) => Some(parse_expression(w).map_err(|e| format!("{}: when: {}", at, e))?),
+            None => None,
+        };
+        let require =
+            parse_expression(&spec.require).map_err(|e| format!("{}: require: {}", at, e))?;
+        let message = spec
+            .message
+            .or(spec.description)
+            .unwrap_or_else(|| format!("requires {}", spec.require.trim()));
+        policies.push(Policy {
+            name: spec.name,
+            severity,
+            for_each: spec.for_each,
+            when,
+            require,
+            message,
+        });
+    }
+    Ok(policies)
+}
+
+/// Reads and compiles the rules file at path.
+pub fn load_policies(path: &str) -> Result<Vec<Policy>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("policy file {}: {}", path, e))?;
+    parse_policies(&text).map_err(|e| format!("policy file {}: {}", path, e))
+}
+
+/// Loads the rules file at startup; later calls keep the first value. Returns the number of rules.
+pub fn init(path: &str) -> Result<usize, String> {
+    let policies = load_policies(path)?;
+    Ok(POLICIES.get_or_init(|| policies).len())
+}
+
+/// Rules loaded at startup (none if no policy file is configured).
+pub fn active() -> &'static [Policy] {
+    POLICIES.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Sym(&'static str),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '\'' || c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err("unterminated string".to_string()),
+                    // Only the quote and the backslash are escaped, so regex classes like '\d' stay as written.
+                    Some('\\') if chars.get(i + 1).is_some_and(|n| *n == c || *n == '\\') => {
+                        text.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some(ch) if *ch == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(ch) => {
+                        text.push(*ch);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push(Token::Str(text));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || (chars[i] == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit)))
+            {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Num(
+                text.parse()
+                    .map_err(|_| format!("invalid number '{}'", text))?,
+            ));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let sym = SYMBOLS
+                .iter()
+                .find(|s| rest.starts_with(**s))
+                .ok_or_else(|| format!("unexpected character '{}'", c))?;
+            tokens.push(Token::Sym(sym));
+            i += sym.len();
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(t: &Token) -> String {
+    match t {
+        Token::Ident(s) => format!("'{}'", s),
+        Token::Str(s) => format!("string '{}'", s),
+        Token::Num(n) => format!("number {}", n),
+        Token::Sym(s) => format!("'{}'", s),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn eat(&mut self, sym: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Sym(s)) if *s == sym);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, sym: &str) -> Result<(), String> {
+        if self.eat(sym) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(t) => Err(format!("expected '{}', found {}", sym, describe(t))),
+            None => Err(format!("expected '{}' at the end", sym)),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat("||") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while self.eat("&&") {
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        let left = self.primary()?;
+        let op = match self.peek() {
+            Some(Token::Sym(s)) if ["==", "!=", "<", "<=", ">", ">="].contains(s) => *s,
+            Some(Token::Ident(s)) if s == "in" => "in",
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        Ok(Expr::Compare(op, Box::new(left), Box::new(self.primary()?)))
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Sym("(")) => {
+                let e = self.or()?;
+                self.expect(")")?;
+                Ok(e)
+            }
+            Some(Token::Sym("[")) => {
+                let mut items = Vec::new();
+                if !self.eat("]") {
+                    loop {
+                        items.push(self.or()?);
+                        if self.eat("]") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Ok(Expr::List(items))
+            }
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Num(n)) => Ok(Expr::Literal(serde_json::json!(n))),
+            Some(Token::Ident(id)) => match id.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if self.eat("(") => self.call(id),
+                _ => self.path(id),
+            },
+            Some(t) => Err(format!("expected a value, found {}", describe(&t))),
+            None => Err("expected a value at the end".to_string()),
+        }
+    }
+
+    fn call(&mut self, name: String) -> Result<Expr, String> {
+        let Some((_, arity)) = FUNCTIONS.iter().find(|(f, _)| *f == name) else {
+            let names: Vec<&str> = FUNCTIONS.iter().map(|(f, _)| *f).collect();
+            return Err(format!(
+                "unknown function '{}'; use one of: {}",
+                name,
+                names.join(", ")
+            ));
+        };
+        let mut args = Vec::new();
+        if !self.eat(")") {
+            loop {
+                args.push(self.or()?);
+                if self.eat(")") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+        if args.len() != *arity {
+            return Err(format!(
+                "{}() takes {} argument(s), got {}",
+                name,
+                arity,
+                args.len()
+            ));
+        }
+        if name == "has" && !matches!(args[0], Expr::Path(_)) {
+            return Err("has() takes a field path, e.g. has(kafka.tls)".to_string());
+        }
+        if let (true, Some(Expr::Literal(Value::String(pattern)))) =
+            (name == "matches", args.get(1))
+        {
+            regex::Regex::new(pattern)
+                .map_err(|e| format!("matches(): invalid regex '{}': {}", pattern, e))?;
+        }
+        Ok(Expr::Call(name, args))
+    }
+
+    fn path(&mut self, first: String) -> Result<Expr, String> {
+        let mut segments = vec![Segment::Key(first)];
+        loop {
+            if self.eat(".") {
+                match self.next() {
+                    Some(Token::Ident(key)) => segments.push(Segment::Key(key)),
+                    _ => return Err("expected a field name after '.'".to_string()),
+                }
+            } else if self.eat("[") {
+                match self.next() {
+                    Some(Token::Str(key)) => segments.push(Segment::Key(key)),
+                    Some(Token::Num(n)) if n.fract() == 0.0 && n >= 0.0 => {
+                        segments.push(Segment::Index(n as usize))
+                    }
+                    _ => return Err("expected a quoted key or an index inside [...]".to_string()),
+                }
+                self.expect("]")?;
+            } else {
+                return Ok(Expr::Path(segments));
+            }
+        }
+    }
+}
+
+fn parse_expression(s: &str) -> Result<Expr, String> {
+    let mut parser = Parser {
+        tokens: tokenize(s)?,
+        pos: 0,
+    };
+    if parser.tokens.is_empty() {
+        return Err("expression is empty".to_string());
+    }
+    let e = parser.or()?;
+    match parser.peek() {
+        Some(t) => Err(format!("unexpected {}", describe(t))),
+        None => Ok(e),
+    }
+}
+
+/// The manifest and the for_each element (the manifest itself without for_each) paths are read from.
+struct Scope<'a> {
+    root: &'a Value,
+    element: &'a Value,
+}
+
+impl<'a> Scope<'a> {
+    fn resolve(&self, segments: &[Segment]) -> Option<&'a Value> {
+        let start = match segments.first() {
+            Some(Segment::Key(k)) if ROOTS.contains(&k.as_str()) => self.root,
+            _ => self.element,
+        };
+        segments.iter().try_fold(start, |v, s| match s {
+            Segment::Key(k) => v.get(k),
+            Segment::Index(i) => v.get(*i),
+        })
+    }
+}
+
+fn text(v: &Value) -> String {
+    v.as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| v.to_string())
+}
+
+/// A missing field (null) is false; other non-boolean values are errors.
+fn truthy(v: &Value) -> Result<bool, String> {
+    match v {
+        Value::Bool(b) => Ok(*b),
+        Value::Null => Ok(false),
+        other => Err(format!("expected a boolean, got {}", other)),
+    }
+}
+
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x == y,
+        _ => a == b,
+    }
+}
+
+fn eval(e: &Expr, scope: &Scope) -> Result<Value, String> {
+    Ok(match e {
+        Expr::Literal(v) => v.clone(),
+        Expr::List(items) => Value::Array(
+            items
+                .iter()
+                .map(|i| eval(i, scope))
+                .collect::<Result<_, _>>()?,
+        ),
+        Expr::Path(segments) => scope.resolve(segments).cloned().unwrap_or(Value::Null),
+        Expr::Not(inner) => Value::Bool(!truthy(&eval(inner, scope)?)?),
+        Expr::And(l, r) => Value::Bool(truthy(&eval(l, scope)?)? && truthy(&eval(r, scope)?)?),
+        Expr::Or(l, r) => Value::Bool(truthy(&eval(l, scope)?)? || truthy(&eval(r, scope)?)?),
+        Expr::Compare(op, l, r) => {
+            let (l, r) = (eval(l, scope)?, eval(r, scope)?);
+            Value::Bool(match *op {
+                "==" => equal(&l, &r),
+                "!=" => !equal(&l, &r),
+                "in" => match &r {
+                    Value::Array(items) => items.iter().any(|i| equal(&l, i)),
+                    Value::String(s) => s.contains(&text(&l)),
+                    Value::Object(map) => map.contains_key(&text(&l)),
+                    Value::Null => false,
+                    other => {
+                        return Err(format!(
+                            "'in' needs a list, string or mapping, got {}",
+                            other
+                        ))
+                    }
+                },
+                _ => {
+                    let ordering = match (&l, &r) {
+                        (Value::Null, _) | (_, Value::Null) => return Ok(Value::Bool(false)),
+                        (Value::String(a), Value::String(b)) => a.cmp(b),
+                        _ => match (l.as_f64(), r.as_f64()) {
+                            (Some(a), Some(b)) => {
+                                a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                            }
+                            _ => return Err(format!("cannot compare {} and {}", l, r)),
+                        },
+                    };
+                    match *op {
+                        "<" => ordering.is_lt(),
+                        "<=" => ordering.is_le(),
+                        ">" => ordering.is_gt(),
+                        _ => ordering.is_ge(),
+                    }
+                }
+            })
+        }
+        Expr::Call(name, args) => {
+            if let ("has", [Expr::Path(segments)]) = (name.as_str(), args.as_slice()) {
+                return Ok(Value::Bool(
+                    scope.resolve(segments).is_some_and(|v| !v.is_null()),
+                ));
+            }
+            let values: Vec<Value> = args
+                .iter()
+                .map(|a| eval(a, scope))
+                .collect::<Result<_, _>>()?;
+            match (name.as_str(), values.as_slice()) {
+                ("size", [v]) => serde_json::json!(match v {
+                    Value::String(s) => s.chars().count(),
+                    Value::Array(items) => items.len(),
+                    Value::Object(map) => map.len(),
+                    _ => 0,
+                }),
+                (_, [Value::Null, _]) => Value::Bool(false),
+                ("matches", [s, pattern]) => {
+                    let re = regex::Regex::new(&text(pattern))
+                        .map_err(|e| format!("invalid regex '{}': {}", text(pattern), e))?;
+                    Value::Bool(re.is_match(&text(s)))
+                }
+                ("startsWith", [s, prefix]) => Value::Bool(text(s).starts_with(&text(prefix))),
+                ("endsWith", [s, suffix]) => Value::Bool(text(s).ends_with(&text(suffix))),
+                ("contains", [s, part]) => Value::Bool(text(s).contains(&text(part))),
+                _ => return Err(format!("{}() called with unsupported arguments", name)),
+            }
+        }
+    })
+}
+
+fn segments_path(segments: &[Segment]) -> String {
+    let mut out = String::new();
+    for s in segments {
+        match s {
+            Segment::Key(k) if k.contains(['.', '[', ' ']) => out.push_str(&format!("['{}']", k)),
+            Segment::Key(k) if out.is_empty() => out.push_str(k),
+            Segment::Key(k) => out.push_str(&format!(".{}", k)),
+            Segment::Index(i) => out.push_str(&format!("[{}]", i)),
+        }
+    }
+    out
+}
+
+/// First manifest path an expression reads: where a flow-level violation is reported.
+fn first_root_path(e: &Expr) -> Option<String> {
+    match e {
+        Expr::Path(segments) => match segments.first() {
+            Some(Segment::Key(k)) if ROOTS.contains(&k.as_str()) => Some(segments_path(segments)),
+            _ => None,
+        },
+        Expr::List(items) | Expr::Call(_, items) => items.iter().find_map(first_root_path),
+        Expr::Not(inner) => first_root_path(inner),
+        Expr::And(l, r) | Expr::Or(l, r) | Expr::Compare(_, l, r) => {
+            first_root_path(l).or_else(|| first_root_path(r))
+        }
+        Expr::Literal(_) => None,
+    }
+}
+
+/// Transformation chains of a spec: spec.transformations and each branch's transformations.
+fn chains(spec: &Value) -> Vec<(String, &Vec<Value>)> {
+    let mut out: Vec<(String, &Vec<Value>)> = spec["transformations"]
+        .as_array()
+        .map(|t| ("spec.transformations".to_string(), t))
+        .into_iter()
+        .collect();
+    for (name, branch) in spec["branches"].as_object().into_iter().flatten() {
+        if let Some(t) = branch["transformations"].as_array() {
+            out.push((format!("spec.branches.{}.transformations", name), t));
+        }
+    }
+    out
+}
+
+/// Elements of a for_each scope with their manifest paths.
+fn elements<'a>(manifest: &'a Value, scope: &str) -> Vec<(String, &'a Value)> {
+    let spec = &manifest["spec"];
+    let mut out = Vec::new();
+    match scope {
+        "sources" => out.push(("spec.source".to_string(), &spec["source"])),
+        "sinks" => {
+            out.push(("spec.sink".to_string(), &spec["sink"]));
+            out.push((
+                "spec.errorHandling.dlq".to_string(),
+                &spec["errorHandling"]["dlq"],
+            ));
+            for (name, branch) in spec["branches"].as_object().into_iter().flatten() {
+                out.push((format!("spec.branches.{}.sink", name), &branch["sink"]));
+            }
+            for (path, chain) in chains(spec) {
+                for (i, t) in chain
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| t["type"] == "router")
+                {
+                    for (j, route) in t["router"]["routes"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                    {
+                        out.push((
+                            format!("{}[{}].router.routes[{}].sink", path, i, j),
+                            &route["sink"],
+                        ));
+                    }
+                }
+            }
+        }
+        "transformations" => {
+            for (path, chain) in chains(spec) {
+                out.extend(
+                    chain
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| (format!("{}[{}]", path, i), t)),
+                );
+            }
+        }
+        _ => {
+            for (name, branch) in spec["branches"].as_object().into_iter().flatten() {
+                out.push((format!("spec.branches.{}", name), branch));
+            }
+        }
+    }
+    out.retain(|(_, v)| v.is_object());
+    out
+}
+
+/// Policy violations of one manifest as (severity, message starting with the manifest path); only DataFlow
+/// documents are checked.
+pub fn check_policies(policies: &[Policy], manifest: &Value) -> Vec<(Severity, String)> {
+    if manifest["kind"] != DATAFLOW_KIND {
+        return Vec::new();
+    }
+    let mut out = Vec::new();
+    for policy in policies {
+        let targets = match policy.for_each.as_deref() {
+            Some(scope) => elements(manifest, scope),
+            None => {
+                let path = first_root_path(&policy.require)
+                    .or_else(|| policy.when.as_ref().and_then(first_root_path))
+                    .unwrap_or_else(|| "metadata.name".to_string());
+                vec![(path, manifest)]
+            }
+        };
+        for (path, element) in targets {
+            let scope = Scope {
+                root: manifest,
+                element,
+            };
+            let applies = match &policy.when {
+                Some(when) => eval(when, &scope).and_then(|v| truthy(&v)),
+                None => Ok(true),
+            };
+            let holds = applies.and_then(|a| {
+                if a {
+                    eval(&policy.require, &scope).and_then(|v| truthy(&v))
+                } else {
+                    Ok(true)
+                }
+            });
+            match holds {
+                Ok(true) => {}
+                Ok(false) => out.push((
+                    policy.severity,
+                    format!(
+                        "{} violates policy {}: {}",
+                        path, policy.name, policy.message
+                    ),
+                )),
+                Err(e) => out.push((
+                    Severity::Error,
+                    format!(
+                        "{}: policy {} could not be evaluated: {}",
+                        path, policy.name, e
+                    ),
+                )),
+            }
+        }
+    }
+    out
+}
+
+/// Policy findings per document of a (multi-document) YAML, located like validation diagnostics.
+pub fn policy_reports(config_yaml: &str, policies: &[Policy]) -> Vec<DocumentReport> {
+    split_documents(config_yaml)
+        .iter()
+        .enumerate()
+        .map(|(i, (offset, doc))| {
+            let manifest = serde_yaml::from_str::<Value>(doc).unwrap_or(Value::Null);
+            let name = manifest["metadata"]["name"].as_str().map(str::to_string);
+            let diagnostics = check_policies(policies, &manifest)
+                .into_iter()
+                .map(|(severity, message)| {
+                    let mut d = Diagnostic::located(severity, message, doc);
+                    d.line += offset;
+                    d
+                })
+                .collect();
+            DocumentReport {
+                index: i + 1,
+                name,
+                diagnostics,
+            }
+        })
+        .collect()
+}
+
+/// Adds policy findings to the reports of validate_dataflow_documents, matched by document index (companion
+/// documents have no report).
+pub fn add_policy_findings(config_yaml: &str, policies: &[Policy], reports: &mut [DocumentReport]) {
+    if policies.is_empty() {
+        return;
+    }
+    let mut found = policy_reports(config_yaml, policies);
+    for report in reports.iter_mut() {
+        let Some(found) = found.get_mut(report.index - 1) else {
+            continue;
+        };
+        report.diagnostics.append(&mut found.diagnostics);
+        report.diagnostics.sort_by_key(|d| d.severity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RULES: &str = r#"
+policies:
+  - name: sinks-use-tls
+    description: all kafka sinks must use TLS
+    forEach: sinks
+    when: type == 'kafka'
+    require: has(kafka.tls)
+  - name: namespace-matches-team
+    severity: warning
+    require: metadata.namespace == metadata.labels.team
+    message: metadata.namespace must equal the team label
+  - name: bounded-parallelism
+    require: "!has(spec.parallelism) || spec.parallelism <= 8"
+"#;
+
+    #[test]
+    fn test_check_policies() {
+        let policies = parse_policies(RULES).unwrap();
+        let manifest = json!({
+            "apiVersion": "dataflow.dataflow.io/v1", "kind": "DataFlow",
+            "metadata": { "name": "orders", "namespace": "payments", "labels": { "team": "billing" } },
+            "spec": {
+                "parallelism": 4,
+                "source": { "type": "kafka", "kafka": { "brokers": ["k:9093"], "topic": "in", "tls": { "caFile": "/ca.pem" } } },
+                "sink": { "type": "kafka", "kafka": { "brokers": ["k:9092"], "topic": "out" } },
+                "errorHandling": { "dlq": { "type": "kafka", "kafka": { "topic": "dlq", "tls": {} } } },
+                "branches": { "audit": { "sink": { "type": "postgresql", "postgresql": { "table": "audit" } } } }
+            }
+        });
+        assert_eq!(
+            check_policies(&policies, &manifest),
+            vec![
+                (Severity::Error, "spec.sink violates policy sinks-use-tls: all kafka sinks must use TLS".to_string()),
+                (
+                    Severity::Warning,
+                    "metadata.namespace violates policy namespace-matches-team: metadata.namespace must equal the team label".to_string()
+                ),
+            ]
+        );
+        assert!(check_policies(&policies, &json!({ "kind": "Secret" })).is_empty());
+    }
+
+    #[test]
+    fn test_expressions() {
+        let root = json!({ "metadata": { "labels": { "app.kubernetes.io/name": "orders" } }, "spec": { "parallelism": 2 } });
+        let element = json!({ "type": "postgresql", "postgresql": { "connectionString": "postgres://pg/shop?sslmode=require" } });
+        let scope = Scope {
+            root: &root,
+            element: &element,
+        };
+        let check = |e: &str| eval(&parse_expression(e).unwrap(), &scope).unwrap();
+        assert_eq!(check("type in ['postgresql', 'clickhouse'] && matches(postgresql.connectionString, 'sslmode=(require|verify-\\w+)')"), true);
+        assert_eq!(
+            check("metadata.labels['app.kubernetes.io/name'] == 'orders'"),
+            true
+        );
+        assert_eq!(
+            check("spec.parallelism > 1 && !has(postgresql.table) && size(postgresql) == 1"),
+            true
+        );
+        assert_eq!(check("startsWith(missing.field, 'x')"), false);
+        assert_eq!(
+            parse_expression("has('x')").unwrap_err(),
+            "has() takes a field path, e.g. has(kafka.tls)"
+        );
+        assert_eq!(
+            parse_expression("type == ").unwrap_err(),
+            "expected a value at the end"
+        );
+        let bad = parse_policies("- {name: a, require: 'lower(x)'}").unwrap_err();
+        assert!(bad.starts_with("policies[0] (a): require: unknown function 'lower'"));
+        assert!(
+            parse_policies("- {name: a, forEach: topics, require: 'true'}")
+                .unwrap_err()
+                .contains("for_each 'topics' must be one of")
+        );
+    }
+
+    #[test]
+    fn test_add_policy_findings_skips_companion_documents() {
+        let policies = parse_policies(RULES).unwrap();
+        let flow = |name: &str, sink: &str| {
+            format!(
+                "apiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: {}\nspec:\n  source: {{type: kafka, kafka: {{brokers: [\"k:9092\"], topic: in}}}}\n  sink: {}\n",
+                name, sink
+            )
+        };
+        let secret = "apiVersion: v1\nkind: Secret\nmetadata:\n  name: a-credentials\nstringData:\n  password: x\n";
+        let tls =
+            "{type: kafka, kafka: {brokers: [\"k:9092\"], topic: out, tls: {caFile: /ca.pem}}}";
+        let plain = "{type: kafka, kafka: {brokers: [\"k:9092\"], topic: out}}";
+        let config = format!("{}---\n{}---\n{}", flow("a", tls), secret, flow("b", plain));
+        let mut reports = crate::tools::manifest::validate_dataflow_documents(&config, false);
+        add_policy_findings(&config, &policies, &mut reports);
+        assert_eq!(reports.len(), 2);
+        assert!(!reports[0].has_errors());
+        assert_eq!(reports[1].label(), "document 3 (b)");
+        let violation = reports[1]
+            .diagnostics
+            .iter()
+            .find(|d| d.message.contains("sinks-use-tls"))
+            .unwrap();
+        assert_eq!(violation.line, 22);
+    }
+}
@@ -0,0 +1,97 @@
+// Provenance of auto-filled values: recorded in a metadata annotation so it survives tools that strip YAML comments.
+
+use serde::Serialize;
+use serde_json::{Map as JsonMap, Value};
+
+/// Annotation holding a JSON array of GenerationNote.
+pub const GENERATION_NOTES_ANNOTATION: &str = "dataflow.io/generation-notes";
+
+/// One value filled in by the generator or migrator: where it is, what it is and why it was filled.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GenerationNote {
+    pub path: String,
+    pub value: Value,
+    pub reason: String,
+}
+
+impl GenerationNote {
+    pub fn new(path: &str, value: Value, reason: &str) -> Self {
+        GenerationNote {
+            path: path.to_string(),
+            value,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Returns value, or default (recording a note for path) when value is missing.
+pub fn or_filled(
+    value: Option<String>,
+    default: &str,
+    path: &str,
+    reason: &str,
+    filled: &mut Vec<GenerationNote>,
+) -> String {
+    value.unwrap_or_else(|| {
+        filled.push(GenerationNote::new(
+            path,
+            Value::String(default.to_string()),
+            reason,
+        ));
+        default.to_string()
+    })
+}
+
+/// Writes notes into metadata.annotations; does nothing when there are none.
+pub fn annotate(metadata: &mut JsonMap<String, Value>, notes: &[GenerationNote]) {
+    if notes.is_empty() {
+        return;
+    }
+    let json = serde_json::to_string(notes).unwrap_or_default();
+    let annotations = metadata
+        .entry("annotations".to_string())
+        .or_insert_with(|| Value::Object(JsonMap::new()));
+    if let Value::Object(a) = annotations {
+        a.insert(GENERATION_NOTES_ANNOTATION.to_string(), Value::String(json));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotate_records_filled_values() {
+        let mut filled = Vec::new();
+        let topic = or_filled(
+            None,
+            "input-topic",
+            "spec.source.kafka.topic",
+            "topics not set",
+            &mut filled,
+        );
+        let kept = or_filled(
+            Some("orders".to_string()),
+            "x",
+            "spec.sink.kafka.topic",
+            "unused",
+            &mut filled,
+        );
+        assert_eq!((topic.as_str(), kept.as_str()), ("input-topic", "orders"));
+
+        let mut metadata = JsonMap::new();
+        annotate(&mut metadata, &filled);
+        let json = metadata["annotations"][GENERATION_NOTES_ANNOTATION]
+            .as_str()
+            .unwrap();
+        let parsed: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!([{ "path": "spec.source.kafka.topic", "value": "input-topic", "reason": "topics not set" }])
+        );
+
+        let mut empty = JsonMap::new();
+        annotate(&mut empty, &[]);
+        assert!(empty.is_empty());
+    }
+}
@@ -1,60 +1,104 @@
 // Reference data for connectors and transformations (list_dataflow_connectors, list_dataflow_transformations).
+// Descriptions are stored per locale ({"en": ..., "ru": ...}) and resolved to the server locale on load.
 
+use crate::config::DEFAULT_LOCALE;
 use serde_json::{Map as JsonMap, Value};
 
 pub fn list_dataflow_connectors_json() -> String {
     let connectors: JsonMap<String, Value> = default_connectors();
-    serde_json::to_string_pretty(&connectors).unwrap_or_else(|_| default_connectors_raw().to_string())
+    serde_json::to_string_pretty(&connectors)
+        .unwrap_or_else(|_| default_connectors_raw().to_string())
 }
 
-fn default_connectors() -> JsonMap<String, Value> {
-    serde_json::from_str(default_connectors_raw()).unwrap_or_default()
+pub(crate) fn default_connectors() -> JsonMap<String, Value> {
+    localized(default_connectors_raw(), crate::config::current().locale())
+}
+
+/// Parses a raw catalog and replaces every per-locale description with the one for locale (or DEFAULT_LOCALE).
+fn localized(raw: &str, locale: &str) -> JsonMap<String, Value> {
+    let mut catalog: JsonMap<String, Value> = serde_json::from_str(raw).unwrap_or_default();
+    for entry in catalog.values_mut() {
+        localize(entry, locale);
+    }
+    catalog
+}
+
+fn localize(v: &mut Value, locale: &str) {
+    let Value::Object(map) = v else { return };
+    if let Some(Value::Object(texts)) = map.get("description") {
+        let text = texts
+            .get(locale)
+            .or_else(|| texts.get(DEFAULT_LOCALE))
+            .cloned()
+            .unwrap_or_default();
+        map.insert("description".to_string(), text);
+    }
+    for (key, child) in map.iter_mut() {
+        if key != "example" && key != "branch_example" {
+            localize(child, locale);
+        }
+    }
 }
 
 fn default_connectors_raw() -> &'static str {
     r#"{
   "sources": {
     "kafka": {
-      "description": "Read messages from Kafka topics",
+      "description": { "en": "Read messages from Kafka topics (format: json, avro, string, protobuf, jsonschema); topicPattern (regex) replaces topic to subscribe to every matching topic; offsetReset (earliest|latest) sets where a new consumer group starts", "ru": "Чтение сообщений из топиков Kafka (format: json, avro, string, protobuf, jsonschema); topicPattern (regex) заменяет topic и подписывает на все подходящие топики; offsetReset (earliest|latest) задаёт, с какой позиции начинает новая consumer group" },
       "required_fields": ["brokers", "topic"],
-      "optional_fields": ["consumerGroup", "tls", "sasl", "format", "avroSchema", "schemaRegistry"]
+      "optional_fields": ["consumerGroup", "tls", "sasl", "format", "avroSchema", "protoSchema", "messageType", "jsonSchema", "schemaRegistry", "cloudevents", "topicPattern", "offsetReset"]
     },
     "postgresql": {
-      "description": "Read from PostgreSQL tables",
+      "description": { "en": "Read from PostgreSQL tables by polling (query, pollInterval) or via logical replication (replication: slotName, publication, plugin pgoutput|wal2json); the two modes are mutually exclusive; polling mode: bulk|incremental with incrementalColumn, initialValue, cursorStorage: memory|configmap", "ru": "Чтение из таблиц PostgreSQL опросом (query, pollInterval) или через логическую репликацию (replication: slotName, publication, plugin pgoutput|wal2json); режимы взаимоисключающие; режим опроса: bulk|incremental с incrementalColumn, initialValue, cursorStorage: memory|configmap" },
       "required_fields": ["connectionString", "table"],
-      "optional_fields": ["query", "pollInterval"]
+      "optional_fields": ["query", "pollInterval", "replication", "mode", "incrementalColumn", "initialValue", "cursorStorage"]
     },
     "trino": {
-      "description": "Read from Trino tables",
+      "description": { "en": "Read from Trino tables (mode: bulk|incremental; incremental needs incrementalColumn, cursorStorage: memory|configmap)", "ru": "Чтение из таблиц Trino (mode: bulk|incremental; для incremental нужны incrementalColumn, cursorStorage: memory|configmap)" },
       "required_fields": ["serverURL", "catalog", "schema", "table"],
-      "optional_fields": ["query", "pollInterval", "keycloak"]
+      "optional_fields": ["query", "pollInterval", "keycloak", "mode", "incrementalColumn", "initialValue", "cursorStorage"]
     },
     "clickhouse": {
-      "description": "Read from ClickHouse tables",
+      "description": { "en": "Read from ClickHouse tables (mode: bulk|incremental; incremental needs incrementalColumn, cursorStorage: memory|configmap)", "ru": "Чтение из таблиц ClickHouse (mode: bulk|incremental; для incremental нужны incrementalColumn, cursorStorage: memory|configmap)" },
       "required_fields": ["connectionString", "table"],
-      "optional_fields": ["query", "pollInterval"]
+      "optional_fields": ["query", "pollInterval", "mode", "incrementalColumn", "initialValue", "cursorStorage"]
+    },
+    "syslog": {
+      "description": { "en": "Receive syslog messages over UDP or TCP (protocol: udp|tcp, parser: rfc3164|rfc5424)", "ru": "Приём syslog-сообщений по UDP или TCP (protocol: udp|tcp, parser: rfc3164|rfc5424)" },
+      "required_fields": ["address"],
+      "optional_fields": ["protocol", "parser"]
     }
   },
   "sinks": {
     "kafka": {
-      "description": "Write messages to Kafka topics",
+      "description": { "en": "Write messages to Kafka topics", "ru": "Запись сообщений в топики Kafka" },
       "required_fields": ["brokers", "topic"],
-      "optional_fields": ["tls", "sasl"]
+      "optional_fields": ["tls", "sasl", "format", "avroSchema", "protoSchema", "messageType", "jsonSchema", "schemaRegistry", "cloudevents"]
     },
     "postgresql": {
-      "description": "Write to PostgreSQL tables",
+      "description": { "en": "Write to PostgreSQL tables; payloadMode selects storage: columns (default, one column per field), jsonb or bytea (whole message in payloadColumn, bytea optionally compressed with gzip/zstd)", "ru": "Запись в таблицы PostgreSQL; payloadMode выбирает способ хранения: columns (по умолчанию, колонка на поле), jsonb или bytea (сообщение целиком в payloadColumn, bytea можно сжимать gzip/zstd)" },
       "required_fields": ["connectionString", "table"],
-      "optional_fields": ["batchSize", "batchFlushIntervalSeconds", "autoCreateTable", "upsertMode", "conflictKey", "rawMode"]
+      "optional_fields": ["batchSize", "batchFlushIntervalSeconds", "autoCreateTable", "upsertMode", "conflictKey", "rawMode", "batchTransformations", "payloadMode", "payloadColumn", "compression", "columnMapping"]
     },
     "trino": {
-      "description": "Write to Trino tables",
+      "description": { "en": "Write to Trino tables (insertStrategy: insert|ctas|merge; catalogType: hive|iceberg; fileFormat hive: ORC|PARQUET|AVRO|JSON|TEXTFILE, iceberg: PARQUET|ORC|AVRO; partitionBy accepts Iceberg transforms like day(ts); merge needs iceberg and mergeKeys)", "ru": "Запись в таблицы Trino (insertStrategy: insert|ctas|merge; catalogType: hive|iceberg; fileFormat hive: ORC|PARQUET|AVRO|JSON|TEXTFILE, iceberg: PARQUET|ORC|AVRO; partitionBy принимает Iceberg-трансформы вида day(ts); merge требует iceberg и mergeKeys)" },
       "required_fields": ["serverURL", "catalog", "schema", "table"],
-      "optional_fields": ["batchSize", "batchFlushIntervalSeconds", "autoCreateTable", "rawMode", "keycloak"]
+      "optional_fields": ["batchSize", "batchFlushIntervalSeconds", "autoCreateTable", "rawMode", "keycloak", "batchTransformations", "columnMapping", "insertStrategy", "catalogType", "fileFormat", "partitionBy", "mergeKeys"]
     },
     "clickhouse": {
-      "description": "Write to ClickHouse tables",
+      "description": { "en": "Write to ClickHouse tables", "ru": "Запись в таблицы ClickHouse" },
       "required_fields": ["connectionString", "table"],
-      "optional_fields": ["batchSize", "batchFlushIntervalSeconds", "autoCreateTable", "rawMode"]
+      "optional_fields": ["batchSize", "batchFlushIntervalSeconds", "autoCreateTable", "rawMode", "batchTransformations", "columnMapping"]
+    },
+    "s3": {
+      "description": { "en": "Write objects to S3 (format: json|avro|parquet; partitioner: default|field|time)", "ru": "Запись объектов в S3 (format: json|avro|parquet; partitioner: default|field|time)" },
+      "required_fields": ["bucket", "region"],
+      "optional_fields": ["endpoint", "prefix", "format", "flushSize", "partitioner", "partitionFields", "pathFormat", "schemaRegistry"]
+    },
+    "elasticsearch": {
+      "description": { "en": "Index documents into Elasticsearch (nullValueBehavior: ignore|delete|fail, documentIdStrategy: auto|key)", "ru": "Индексация документов в Elasticsearch (nullValueBehavior: ignore|delete|fail, documentIdStrategy: auto|key)" },
+      "required_fields": ["addresses", "index"],
+      "optional_fields": ["username", "password", "nullValueBehavior", "documentIdStrategy", "tls"]
     }
   }
 }"#
@@ -62,50 +106,117 @@ fn default_connectors_raw() -> &'static str {
 
 pub fn list_dataflow_transformations_json() -> String {
     let transformations: JsonMap<String, Value> = default_transformations();
-    serde_json::to_string_pretty(&transformations).unwrap_or_else(|_| default_transformations_raw().to_string())
+    serde_json::to_string_pretty(&transformations)
+        .unwrap_or_else(|_| default_transformations_raw().to_string())
 }
 
-fn default_transformations() -> JsonMap<String, Value> {
-    serde_json::from_str(default_transformations_raw()).unwrap_or_default()
+pub(crate) fn default_transformations() -> JsonMap<String, Value> {
+    localized(
+        default_transformations_raw(),
+        crate::config::current().locale(),
+    )
 }
 
 fn default_transformations_raw() -> &'static str {
     r#"{
   "timestamp": {
-    "description": "Add timestamp to each message",
+    "description": { "en": "Add timestamp to each message", "ru": "Добавляет метку времени в каждое сообщение" },
     "example": { "type": "timestamp", "timestamp": { "fieldName": "created_at", "format": "RFC3339" } }
   },
   "flatten": {
-    "description": "Flatten array into separate messages",
+    "description": { "en": "Flatten array into separate messages", "ru": "Разворачивает массив в отдельные сообщения" },
     "example": { "type": "flatten", "flatten": { "field": "$.items" } }
   },
   "filter": {
-    "description": "Filter messages by JSONPath condition",
+    "description": { "en": "Filter messages by JSONPath condition", "ru": "Фильтрует сообщения по условию JSONPath" },
     "example": { "type": "filter", "filter": { "condition": "$.level != 'error'" } }
   },
   "mask": {
-    "description": "Mask sensitive fields",
+    "description": { "en": "Mask sensitive fields", "ru": "Маскирует чувствительные поля" },
     "example": { "type": "mask", "mask": { "fields": ["$.password", "$.token"], "maskChar": "*", "keepLength": true } }
   },
   "router": {
-    "description": "Route messages to different sinks by condition",
-    "example": { "type": "router", "router": { "routes": [{ "condition": "$.level == 'error'", "sink": { "type": "kafka", "kafka": { "brokers": ["localhost:9092"], "topic": "errors" } } }] } }
+    "description": { "en": "Route messages to different sinks or named branches (spec.branches) by condition; each route sets either sink or branch", "ru": "Направляет сообщения в разные sink или именованные ветки (spec.branches) по условию; каждый маршрут задаёт либо sink, либо branch" },
+    "example": { "type": "router", "router": { "routes": [{ "condition": "$.level == 'error'", "sink": { "type": "kafka", "kafka": { "brokers": ["localhost:9092"], "topic": "errors" } } }] } },
+    "branch_example": {
+      "transformations": [{ "type": "router", "router": { "routes": [{ "condition": "$.level == 'error'", "branch": "errors" }] } }],
+      "branches": { "errors": { "transformations": [{ "type": "mask", "mask": { "fields": ["$.token"] } }], "sink": { "type": "kafka", "kafka": { "brokers": ["localhost:9092"], "topic": "errors" } } } }
+    }
   },
   "select": {
-    "description": "Select specific fields",
+    "description": { "en": "Select specific fields", "ru": "Оставляет только указанные поля" },
     "example": { "type": "select", "select": { "fields": ["$.id", "$.name", "$.timestamp"] } }
   },
   "remove": {
-    "description": "Remove specific fields",
+    "description": { "en": "Remove specific fields", "ru": "Удаляет указанные поля" },
     "example": { "type": "remove", "remove": { "fields": ["$.password", "$.token"] } }
   },
   "snakeCase": {
-    "description": "Convert field names to snake_case",
+    "description": { "en": "Convert field names to snake_case", "ru": "Переводит имена полей в snake_case" },
     "example": { "type": "snakeCase", "snakeCase": { "deep": true } }
   },
+  "script": {
+    "description": { "en": "Run a sandboxed script (lua, javascript, expr) from inline code or a ConfigMap, with cpu/time limits; prefer declarative transformations when possible", "ru": "Запускает скрипт в песочнице (lua, javascript, expr) из inline-кода или ConfigMap с лимитами cpu/time; по возможности используйте декларативные трансформации" },
+    "example": { "type": "script", "script": { "language": "lua", "configMapRef": { "name": "flow-scripts", "key": "enrich.lua" }, "limits": { "cpu": "100m", "timeout": "50ms" } } }
+  },
+  "enrich": {
+    "description": { "en": "Look up data from an HTTP or gRPC service per message and store it in targetField; timeout, retry and circuitBreaker guard against flaky backends", "ru": "Запрашивает данные у HTTP- или gRPC-сервиса для каждого сообщения и сохраняет их в targetField; timeout, retry и circuitBreaker защищают от нестабильных бэкендов" },
+    "example": { "type": "enrich", "enrich": { "backend": "http", "http": { "url": "https://customers.internal/lookup", "method": "GET" }, "targetField": "$.customer", "timeout": "200ms", "retry": { "maxAttempts": 3, "backoff": "100ms" }, "circuitBreaker": { "failureThreshold": 5, "openDuration": "30s", "halfOpenRequests": 1 } } }
+  },
+  "sort": {
+    "description": { "en": "Batch-scoped (sink batchTransformations on postgresql, trino, clickhouse): sort messages within a batch before the write", "ru": "Для батча (batchTransformations у sink postgresql, trino, clickhouse): сортирует сообщения внутри батча перед записью" },
+    "scope": "batch",
+    "example": { "type": "sort", "sort": { "field": "$.updated_at", "order": "asc" } }
+  },
+  "dedupe": {
+    "description": { "en": "Batch-scoped: keep one message per key within a batch (keep: last by default), so upserts write each row once", "ru": "Для батча: оставляет одно сообщение на ключ внутри батча (keep: last по умолчанию), чтобы upsert записывал каждую строку один раз" },
+    "scope": "batch",
+    "example": { "type": "dedupe", "dedupe": { "key": ["$.id"], "keep": "last" } }
+  },
+  "chunk": {
+    "description": { "en": "Batch-scoped: split the write into statements of at most size rows (must not exceed batchSize)", "ru": "Для батча: разбивает запись на запросы не более size строк (не больше batchSize)" },
+    "scope": "batch",
+    "example": { "type": "chunk", "chunk": { "size": 500 } }
+  },
+  "unwrapCloudEvent": {
+    "description": { "en": "Replace a CloudEvents envelope with its data payload; attributesField optionally keeps the CloudEvents attributes (source kafka.cloudevents sets binary or structured mode)", "ru": "Заменяет конверт CloudEvents его полезной нагрузкой data; attributesField при необходимости сохраняет атрибуты CloudEvents (source kafka.cloudevents задаёт режим binary или structured)" },
+    "example": { "type": "unwrapCloudEvent", "unwrapCloudEvent": { "attributesField": "$.ce" } }
+  },
+  "unwrap": {
+    "description": { "en": "Replace the message with the value at field (e.g. $.payload of a JsonConverter schema envelope, $.after of a Debezium change event); messages where it is null or missing are dropped", "ru": "Заменяет сообщение значением поля field (например, $.payload конверта схемы JsonConverter, $.after события изменений Debezium); сообщения, где оно null или отсутствует, отбрасываются" },
+    "example": { "type": "unwrap", "unwrap": { "field": "$.payload" } }
+  },
   "camelCase": {
-    "description": "Convert field names to CamelCase",
+    "description": { "en": "Convert field names to CamelCase", "ru": "Переводит имена полей в CamelCase" },
     "example": { "type": "camelCase", "camelCase": { "deep": true } }
   }
 }"#
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_descriptions_follow_locale() {
+        let ru = localized(default_connectors_raw(), "ru");
+        assert_eq!(
+            ru["sinks"]["clickhouse"]["description"],
+            "Запись в таблицы ClickHouse"
+        );
+        let en = localized(default_transformations_raw(), "en");
+        assert_eq!(
+            en["filter"]["description"],
+            "Filter messages by JSONPath condition"
+        );
+        // Unknown locales fall back to English; every entry has both languages.
+        let de = localized(default_transformations_raw(), "de");
+        assert_eq!(de["mask"]["description"], "Mask sensitive fields");
+        let raw: Value = serde_json::from_str(default_transformations_raw()).unwrap();
+        assert!(raw
+            .as_object()
+            .unwrap()
+            .values()
+            .all(|t| t["description"]["ru"].is_string()));
+    }
+}
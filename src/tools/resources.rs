@@ -0,0 +1,220 @@
+// spec.resources checks: Kubernetes quantities for cpu / memory requests and limits, sane ranges, and limits
+// not below requests, so malformed resource specs are caught before they reach the cluster.
+
+use serde_json::Value;
+
+/// Resource names the operator passes to the flow pods.
+const RESOURCE_NAMES: [&str; 3] = ["cpu", "memory", "ephemeral-storage"];
+
+const MI: f64 = 1024.0 * 1024.0;
+
+/// Accepted range per resource in base units (cores, bytes).
+const RESOURCE_RANGES: [(&str, f64, f64, &str); 3] = [
+    ("cpu", 0.001, 64.0, "1m and 64"),
+    ("memory", 16.0 * MI, MI * MI, "16Mi and 1Ti"),
+    ("ephemeral-storage", MI, MI * MI * 1024.0, "1Mi and 1Pi"),
+];
+
+/// Requests and limits a generated flow gets when none are given, so it is never unbounded.
+pub const DEFAULT_RESOURCES: [(&str, &str, &str); 4] = [
+    ("requests", "cpu", "250m"),
+    ("requests", "memory", "256Mi"),
+    ("limits", "cpu", "1"),
+    ("limits", "memory", "512Mi"),
+];
+
+/// spec.resources from (section, resource, quantity) entries with a value; None when all are empty.
+pub fn resources_block(entries: &[(&str, &str, Option<&str>)]) -> Option<Value> {
+    let mut block = serde_json::Map::new();
+    for (section, name, quantity) in entries {
+        if let Some(q) = quantity.map(str::trim).filter(|q| !q.is_empty()) {
+            let entry = block
+                .entry(section.to_string())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            entry[*name] = Value::String(q.to_string());
+        }
+    }
+    (!block.is_empty()).then_some(Value::Object(block))
+}
+
+/// Parses a Kubernetes quantity ("500m", "0.5", "512Mi", "1G", "1e3") into base units.
+pub fn parse_quantity(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '+' || c == '-'))
+        .unwrap_or(s.len());
+    let (number, suffix) = s.split_at(split);
+    let n: f64 = number.parse().ok()?;
+    let factor = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024.0,
+        "Mi" => 1024.0_f64.powi(2),
+        "Gi" => 1024.0_f64.powi(3),
+        "Ti" => 1024.0_f64.powi(4),
+        "Pi" => 1024.0_f64.powi(5),
+        "Ei" => 1024.0_f64.powi(6),
+        e if e.starts_with(['e', 'E']) => 10f64.powi(e[1..].parse().ok()?),
+        _ => return None,
+    };
+    Some(n * factor).filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+fn quantity(v: &Value) -> Option<f64> {
+    match v {
+        Value::String(s) => parse_quantity(s),
+        Value::Number(n) => n.as_f64().filter(|f| *f >= 0.0),
+        _ => None,
+    }
+}
+
+/// Checks spec.resources: {requests, limits} maps of cpu / memory / ephemeral-storage quantities.
+pub fn validate_resources(resources: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(map) = resources.as_object() else {
+        errors.push(format!(
+            "{} must be an object with requests and/or limits",
+            path
+        ));
+        return;
+    };
+    for key in map
+        .keys()
+        .filter(|k| !["requests", "limits"].contains(&k.as_str()))
+    {
+        errors.push(format!(
+            "{}.{} is not supported; use requests and limits",
+            path, key
+        ));
+    }
+    let mut parsed = Vec::new();
+    for section in ["requests", "limits"] {
+        let Some(entries) = map.get(section) else {
+            continue;
+        };
+        let Some(entries) = entries.as_object() else {
+            errors.push(format!(
+                "{}.{} must be an object (e.g. {{cpu: 500m, memory: 512Mi}})",
+                path, section
+            ));
+            continue;
+        };
+        for (name, value) in entries {
+            let q_path = format!("{}.{}.{}", path, section, name);
+            if !RESOURCE_NAMES.contains(&name.as_str()) {
+                errors.push(format!(
+                    "{} is not a supported resource; use one of: {}",
+                    q_path,
+                    RESOURCE_NAMES.join(", ")
+                ));
+                continue;
+            }
+            let Some(amount) = quantity(value) else {
+                errors.push(format!(
+                    "{} '{}' is not a valid Kubernetes quantity (e.g. cpu \"500m\" or \"2\", memory \"512Mi\" or \"2Gi\")",
+                    q_path,
+                    value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())
+                ));
+                continue;
+            };
+            // "512m" memory is 0.512 bytes: almost always meant as Mi.
+            if name != "cpu" && value.as_str().is_some_and(|s| s.trim().ends_with('m')) {
+                let s = value.as_str().unwrap_or("").trim();
+                errors.push(format!(
+                    "{} '{}' means millibytes; did you mean {}Mi?",
+                    q_path,
+                    s,
+                    &s[..s.len() - 1]
+                ));
+                continue;
+            }
+            if let Some((_, min, max, range)) =
+                RESOURCE_RANGES.iter().find(|(r, _, _, _)| r == name)
+            {
+                if amount < *min || amount > *max {
+                    errors.push(format!("{} must be between {}", q_path, range));
+                    continue;
+                }
+            }
+            parsed.push((section, name.as_str(), amount));
+        }
+    }
+    for (_, name, request) in parsed.iter().filter(|(s, _, _)| *s == "requests") {
+        if let Some((_, _, limit)) = parsed.iter().find(|(s, n, _)| *s == "limits" && n == name) {
+            if limit < request {
+                errors.push(format!(
+                    "{}.limits.{} must not be less than requests.{}",
+                    path, name, name
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("500m"), Some(0.5));
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("512Mi"), Some(512.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_quantity("1G"), Some(1e9));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("512MB"), None);
+        assert_eq!(parse_quantity("half"), None);
+    }
+
+    #[test]
+    fn test_resources_block() {
+        let block = resources_block(&[
+            ("requests", "cpu", Some("500m")),
+            ("limits", "memory", Some("1Gi")),
+            ("limits", "cpu", None),
+        ]);
+        assert_eq!(
+            block,
+            Some(json!({ "requests": { "cpu": "500m" }, "limits": { "memory": "1Gi" } }))
+        );
+        assert_eq!(resources_block(&[("requests", "cpu", Some(" "))]), None);
+    }
+
+    #[test]
+    fn test_validate_resources() {
+        let resources = json!({
+            "requests": { "cpu": "2", "memory": "512m", "gpu": 1 },
+            "limits": { "cpu": "500m", "memory": "1GB" }
+        });
+        let mut errors = Vec::new();
+        validate_resources(&resources, "spec.resources", &mut errors);
+        assert_eq!(
+            errors,
+            vec![
+                "spec.resources.requests.gpu is not a supported resource; use one of: cpu, memory, ephemeral-storage".to_string(),
+                "spec.resources.requests.memory '512m' means millibytes; did you mean 512Mi?".to_string(),
+                "spec.resources.limits.memory '1GB' is not a valid Kubernetes quantity (e.g. cpu \"500m\" or \"2\", memory \"512Mi\" or \"2Gi\")".to_string(),
+                "spec.resources.limits.cpu must not be less than requests.cpu".to_string(),
+            ]
+        );
+        let mut errors = Vec::new();
+        validate_resources(
+            &json!({ "requests": { "cpu": "100", "memory": "1Mi" } }),
+            "spec.resources",
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            "spec.resources.requests.cpu must be between 1m and 64"
+        );
+    }
+}
@@ -0,0 +1,457 @@
+// Inference of a transformation chain from examples: sample input messages and the output one of them should
+// become. Each step (unwrap, flatten, snakeCase/camelCase, mask, select/remove) is derived from the difference
+// that is left, and the chain is replayed on the samples to check the result.
+
+use crate::tools::mapping::{to_camel_case, to_snake_case};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// Proposed chain, one explanation line per step or remaining difference, and whether replaying the chain on the
+/// samples gives the expected output.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct InferredChain {
+    pub transformations: Vec<Value>,
+    pub explanation: Vec<String>,
+    pub matches: bool,
+}
+
+/// Paths of the leaves of an object ($.a, $.b.c); arrays and scalars are leaves.
+fn leaf_paths(v: &Value, prefix: &str, out: &mut Vec<String>) {
+    match v.as_object() {
+        Some(map) if !map.is_empty() || prefix == "$" => {
+            for (k, child) in map {
+                leaf_paths(child, &format!("{}.{}", prefix, k), out);
+            }
+        }
+        _ => out.push(prefix.to_string()),
+    }
+}
+
+/// Value at a $.a.b path.
+fn at<'a>(v: &'a Value, path: &str) -> Option<&'a Value> {
+    path.trim_start_matches('$')
+        .split('.')
+        .filter(|s| !s.is_empty())
+        .try_fold(v, |v, key| v.get(key))
+}
+
+/// Object keys at every depth, with their depth (0 for the top level).
+fn keys(v: &Value, depth: usize, out: &mut Vec<(String, usize)>) {
+    match v {
+        Value::Object(map) => {
+            for (k, child) in map {
+                out.push((k.clone(), depth));
+                keys(child, depth + 1, out);
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|i| keys(i, depth + 1, out)),
+        _ => {}
+    }
+}
+
+fn convert_keys(v: &Value, convert: fn(&str) -> String, deep: bool) -> Value {
+    match v {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, c)| {
+                    (
+                        convert(k),
+                        if deep {
+                            convert_keys(c, convert, deep)
+                        } else {
+                            c.clone()
+                        },
+                    )
+                })
+                .collect(),
+        ),
+        Value::Array(items) if deep => Value::Array(
+            items
+                .iter()
+                .map(|i| convert_keys(i, convert, deep))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn field(path: &str) -> &str {
+    path.trim_start_matches("$.")
+}
+
+/// Object holding the last segment of a path, given the segments before it.
+fn parent<'a>(v: &'a mut Value, parents: &[&str]) -> Option<&'a mut JsonMap<String, Value>> {
+    parents
+        .iter()
+        .try_fold(v, |v, k| v.get_mut(*k))
+        .and_then(Value::as_object_mut)
+}
+
+/// Applies one transformation of the kinds this module proposes (select with top-level fields); flatten may yield
+/// several messages.
+fn apply(t: &Value, msg: &Value) -> Vec<Value> {
+    let kind = t["type"].as_str().unwrap_or("");
+    let config = &t[kind];
+    match kind {
+        "unwrap" => at(msg, config["field"].as_str().unwrap_or(""))
+            .filter(|v| !v.is_null())
+            .cloned()
+            .into_iter()
+            .collect(),
+        "flatten" => {
+            let name = field(config["field"].as_str().unwrap_or(""));
+            match msg[name].as_array() {
+                Some(items) => items
+                    .iter()
+                    .map(|item| {
+                        let mut out = msg.clone();
+                        out[name] = item.clone();
+                        out
+                    })
+                    .collect(),
+                None => vec![msg.clone()],
+            }
+        }
+        "snakeCase" | "camelCase" => {
+            let convert = if kind == "snakeCase" {
+                to_snake_case
+            } else {
+                to_camel_case
+            };
+            vec![convert_keys(msg, convert, config["deep"] == true)]
+        }
+        "mask" | "select" | "remove" => {
+            let paths: Vec<&str> = config["fields"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            let mut out = if kind == "select" {
+                json!({})
+            } else {
+                msg.clone()
+            };
+            for path in paths {
+                let segments: Vec<&str> = field(path).split('.').collect();
+                let Some((last, parents)) = segments.split_last() else {
+                    continue;
+                };
+                match kind {
+                    "mask" => {
+                        if let Some(Value::String(s)) =
+                            parent(&mut out, parents).and_then(|p| p.get_mut(*last))
+                        {
+                            *s = "*".repeat(if config["keepLength"] == true {
+                                s.chars().count()
+                            } else {
+                                8
+                            });
+                        }
+                    }
+                    "remove" => {
+                        parent(&mut out, parents).and_then(|p| p.remove(*last));
+                    }
+                    _ => {
+                        if let Some(value) = msg.get(*last).filter(|_| parents.is_empty()) {
+                            out[*last] = value.clone();
+                        }
+                    }
+                }
+            }
+            vec![out]
+        }
+        _ => vec![msg.clone()],
+    }
+}
+
+fn run(chain: &[Value], samples: &[Value]) -> Vec<Value> {
+    chain.iter().fold(samples.to_vec(), |msgs, t| {
+        msgs.iter().flat_map(|m| apply(t, m)).collect()
+    })
+}
+
+/// Number of top-level keys of actual that match a key of expected after snake_case (used to find nested payloads).
+fn overlap(actual: &Value, expected: &JsonMap<String, Value>) -> usize {
+    let wanted: Vec<String> = expected.keys().map(|k| to_snake_case(k)).collect();
+    actual
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(k, _)| wanted.contains(&to_snake_case(k)))
+        .count()
+}
+
+/// Proposes a chain turning samples (the first of them) into expected — one output object, or the list of outputs
+/// when the samples are flattened into several messages. Both may also be given as JSON text.
+pub fn infer_transformations(samples: &Value, expected: &Value) -> Result<InferredChain, String> {
+    let parse = |v: &Value, param: &str| match v {
+        Value::String(s) => {
+            serde_json::from_str::<Value>(s).map_err(|e| format!("{} invalid JSON: {}", param, e))
+        }
+        v => Ok(v.clone()),
+    };
+    let (samples, expected) = (&parse(samples, "samples")?, &parse(expected, "expected")?);
+    let samples: Vec<Value> = match samples {
+        Value::Array(items) => items.clone(),
+        other => vec![other.clone()],
+    };
+    let outputs: Vec<Value> = match expected {
+        Value::Array(items) => items.clone(),
+        other => vec![other.clone()],
+    };
+    let (Some(first), Some(Value::Object(want))) = (samples.first(), outputs.first()) else {
+        return Err("samples and expected must contain at least one JSON object each".to_string());
+    };
+    if !first.is_object() {
+        return Err("samples must be JSON objects".to_string());
+    }
+
+    let target = &outputs[0];
+    let mut chain = Vec::new();
+    let mut explanation = Vec::new();
+    let mut msg = first.clone();
+    let mut step = |t: Value, why: String, msg: &mut Value| {
+        *msg = apply(&t, msg).into_iter().next().unwrap_or(Value::Null);
+        chain.push(t);
+        explanation.push(why);
+    };
+
+    let nested = msg
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, v)| v.is_object())
+        .map(|(k, v)| (k.clone(), overlap(v, want)))
+        .max_by_key(|(_, n)| *n);
+    if let Some((name, n)) = nested.filter(|(_, n)| *n > overlap(&msg, want)) {
+        let why = format!(
+            "unwrap: the expected fields are inside {} ({} of {} match there)",
+            name,
+            n,
+            want.len()
+        );
+        step(
+            json!({ "type": "unwrap", "unwrap": { "field": format!("$.{}", name) } }),
+            why,
+            &mut msg,
+        );
+    }
+
+    let arrays: Vec<String> = msg
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(k, v)| {
+            v.as_array().is_some_and(|a| a.iter().any(Value::is_object))
+                && want
+                    .iter()
+                    .any(|(w, e)| to_snake_case(w) == to_snake_case(k) && e.is_object())
+        })
+        .map(|(k, _)| k.clone())
+        .collect();
+    if let Some(name) = arrays.first() {
+        let why = format!(
+            "flatten: {} is a list in the input but one object per output message",
+            name
+        );
+        step(
+            json!({ "type": "flatten", "flatten": { "field": format!("$.{}", name) } }),
+            why,
+            &mut msg,
+        );
+    }
+
+    let mut want_keys = Vec::new();
+    keys(target, 0, &mut want_keys);
+    let mut have_keys = Vec::new();
+    keys(&msg, 0, &mut have_keys);
+    for (kind, convert) in [
+        ("snakeCase", to_snake_case as fn(&str) -> String),
+        ("camelCase", to_camel_case),
+    ] {
+        if !want_keys.iter().all(|(k, _)| convert(k) == *k) {
+            continue;
+        }
+        let renamed: Vec<&(String, usize)> = have_keys
+            .iter()
+            .filter(|(k, _)| convert(k) != *k && want_keys.iter().any(|(w, _)| *w == convert(k)))
+            .collect();
+        if renamed.is_empty() {
+            continue;
+        }
+        let deep = renamed.iter().any(|(_, depth)| *depth > 0);
+        let names: Vec<String> = renamed
+            .iter()
+            .map(|(k, _)| format!("{} -> {}", k, convert(k)))
+            .collect();
+        let why = format!(
+            "{}: the expected field names are {}; renames {}",
+            kind,
+            kind,
+            names.join(", ")
+        );
+        step(
+            json!({ "type": kind, kind: { "deep": deep } }),
+            why,
+            &mut msg,
+        );
+        break;
+    }
+
+    let mut want_paths = Vec::new();
+    leaf_paths(target, "$", &mut want_paths);
+    let masked: Vec<&String> = want_paths
+        .iter()
+        .filter(|p| {
+            let (Some(Value::String(w)), Some(Value::String(h))) = (at(target, p), at(&msg, p))
+            else {
+                return false;
+            };
+            !w.is_empty() && w.chars().all(|c| c == '*') && w != h
+        })
+        .collect();
+    if !masked.is_empty() {
+        let keep_length = masked.iter().all(|p| {
+            at(target, p).and_then(Value::as_str).map(str::len)
+                == at(&msg, p)
+                    .and_then(Value::as_str)
+                    .map(|s| s.chars().count())
+        });
+        let fields: Vec<&str> = masked.iter().map(|p| p.as_str()).collect();
+        let why = format!(
+            "mask: {} are replaced with '*' in the expected output",
+            fields.join(", ")
+        );
+        let mut mask = json!({ "fields": fields });
+        if keep_length {
+            mask["keepLength"] = json!(true);
+        }
+        step(json!({ "type": "mask", "mask": mask }), why, &mut msg);
+    }
+
+    let mut have_paths = Vec::new();
+    leaf_paths(&msg, "$", &mut have_paths);
+    let extra: Vec<&String> = have_paths
+        .iter()
+        .filter(|p| {
+            !want_paths
+                .iter()
+                .any(|w| w == *p || p.starts_with(&format!("{}.", w)))
+        })
+        .collect();
+    if !extra.is_empty() {
+        let kept: Vec<String> = want
+            .keys()
+            .filter(|k| msg.get(k.as_str()).is_some())
+            .map(|k| format!("$.{}", k))
+            .collect();
+        let top_level = extra.iter().all(|p| p.matches('.').count() == 1);
+        if top_level && extra.len() >= kept.len() && !kept.is_empty() {
+            let why = format!("select: keeps {} of the input fields", kept.join(", "));
+            step(
+                json!({ "type": "select", "select": { "fields": kept } }),
+                why,
+                &mut msg,
+            );
+        } else {
+            let fields: Vec<&str> = extra.iter().map(|p| p.as_str()).collect();
+            let why = format!(
+                "remove: {} are not in the expected output",
+                fields.join(", ")
+            );
+            step(
+                json!({ "type": "remove", "remove": { "fields": fields } }),
+                why,
+                &mut msg,
+            );
+        }
+    }
+
+    for path in &want_paths {
+        match (at(&msg, path), at(target, path)) {
+            (None, _) => explanation.push(format!(
+                "{} is not in the input; add it with an enrich, timestamp or script transformation",
+                path
+            )),
+            (Some(have), Some(want)) if have != want => explanation.push(format!(
+                "{} is {} in the input but {} in the expected output; values are changed only by a script transformation",
+                path, have, want
+            )),
+            _ => {}
+        }
+    }
+
+    let produced = run(&chain, &samples);
+    let matches = if produced.len() == outputs.len() {
+        produced == outputs
+    } else {
+        produced.first() == outputs.first()
+    };
+    if chain.is_empty() && matches {
+        explanation.push(
+            "the samples already have the expected shape; no transformation is needed".to_string(),
+        );
+    }
+    Ok(InferredChain {
+        transformations: chain,
+        explanation,
+        matches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_unwrap_case_and_select() {
+        let samples = json!([{
+            "schema": { "type": "struct" },
+            "payload": { "orderId": 7, "customerEmail": "ann@example.com", "internalNote": "x", "debugFlag": true, "traceId": "t1", "total": 12.5 }
+        }]);
+        let expected = json!({ "order_id": 7, "customer_email": "***************", "total": 12.5 });
+        let inferred = infer_transformations(&samples, &expected).unwrap();
+        let kinds: Vec<&str> = inferred
+            .transformations
+            .iter()
+            .map(|t| t["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, vec!["unwrap", "snakeCase", "mask", "select"]);
+        assert_eq!(
+            inferred.transformations[2]["mask"],
+            json!({ "fields": ["$.customer_email"], "keepLength": true })
+        );
+        assert_eq!(
+            inferred.transformations[3]["select"]["fields"],
+            json!(["$.customer_email", "$.order_id", "$.total"])
+        );
+        assert!(inferred.matches, "{:?}", inferred.explanation);
+    }
+
+    #[test]
+    fn test_infer_flatten_and_remove() {
+        let samples =
+            json!({ "id": 1, "token": "s3cret", "items": [{ "sku": "a" }, { "sku": "b" }] });
+        let expected =
+            json!([{ "id": 1, "items": { "sku": "a" } }, { "id": 1, "items": { "sku": "b" } }]);
+        let inferred = infer_transformations(&samples, &expected).unwrap();
+        assert_eq!(
+            inferred.transformations,
+            vec![
+                json!({ "type": "flatten", "flatten": { "field": "$.items" } }),
+                json!({ "type": "remove", "remove": { "fields": ["$.token"] } }),
+            ]
+        );
+        assert!(inferred.matches);
+
+        let inferred =
+            infer_transformations(&json!({ "id": 1 }), &json!({ "id": 2, "region": "eu" }))
+                .unwrap();
+        assert!(!inferred.matches);
+        assert_eq!(inferred.explanation, vec![
+            "$.id is 1 in the input but 2 in the expected output; values are changed only by a script transformation",
+            "$.region is not in the input; add it with an enrich, timestamp or script transformation",
+        ]);
+        assert!(infer_transformations(&json!([]), &json!({})).is_err());
+    }
+}
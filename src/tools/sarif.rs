@@ -0,0 +1,429 @@
+// SARIF 2.1.0 output for validation and lint findings, so code review tools can show them inline, and the
+// path / line / column lookup shared with the JSON output.
+
+use crate::tools::manifest::{Diagnostic, Severity};
+use crate::types::FINDING_OUTPUT_FORMATS;
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Artifact URI used when the caller does not say where the manifest lives.
+const DEFAULT_ARTIFACT_URI: &str = "dataflow.yaml";
+
+/// Rule id, name, description and the message fragment that selects it (first match wins; "" always matches).
+const VALIDATE_RULES: [(&str, &str, &str, &str); 7] = [
+    (
+        "DF001",
+        "yaml-parse",
+        "Manifest is not valid YAML",
+        "YAML parse error",
+    ),
+    (
+        "DF002",
+        "required-field",
+        "A required field is missing",
+        "is required",
+    ),
+    (
+        "DF003",
+        "allowed-values",
+        "Value is not one of the allowed values",
+        "must be one of",
+    ),
+    (
+        "DF004",
+        "value-range",
+        "Value is outside the allowed range",
+        "between",
+    ),
+    (
+        "DF005",
+        "conflicting-fields",
+        "Fields cannot be used together",
+        "exclusive",
+    ),
+    (
+        "DF006",
+        "field-dependency",
+        "Field requires another field or value",
+        "requires",
+    ),
+    ("DF100", "invalid-value", "Invalid manifest value", ""),
+];
+const LINT_RULES: [(&str, &str, &str, &str); 2] = [
+    (
+        "DF200",
+        "trivial-script",
+        "Inline script could be a declarative transformation",
+        "script is trivial",
+    ),
+    (
+        "DF299",
+        "lint-suggestion",
+        "Non-fatal manifest suggestion",
+        "",
+    ),
+];
+
+/// Which tool produced the findings: decides the rule set and the rules' default SARIF level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FindingKind {
+    Validate,
+    Lint,
+}
+
+impl FindingKind {
+    fn rules(self) -> &'static [(&'static str, &'static str, &'static str, &'static str)] {
+        match self {
+            FindingKind::Validate => &VALIDATE_RULES,
+            FindingKind::Lint => &LINT_RULES,
+        }
+    }
+
+    fn level(self) -> &'static str {
+        match self {
+            FindingKind::Validate => "error",
+            FindingKind::Lint => "warning",
+        }
+    }
+}
+
+/// Output format of validation and lint findings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FindingFormat {
+    Text,
+    Sarif,
+    Json,
+}
+
+/// Parses the format parameter: text (default), sarif or json.
+pub fn finding_format(format: Option<&str>) -> Result<FindingFormat, String> {
+    match format.unwrap_or("text") {
+        "text" => Ok(FindingFormat::Text),
+        "sarif" => Ok(FindingFormat::Sarif),
+        "json" => Ok(FindingFormat::Json),
+        _ => Err(format!(
+            "format must be one of: {}",
+            FINDING_OUTPUT_FORMATS.join(", ")
+        )),
+    }
+}
+
+/// Manifest path of a finding and its 1-based line and column in yaml: the located path (or its deepest
+/// existing parent), the position of a YAML parse error, or 1:1.
+pub(crate) fn finding_location<'a>(
+    message: &'a str,
+    yaml: &str,
+) -> (Option<&'a str>, usize, usize) {
+    let path = finding_path(message);
+    let (line, column) = path
+        .and_then(|p| locate_path(yaml, p))
+        .or_else(|| parse_error_location(message))
+        .unwrap_or((1, 1));
+    (path, line, column)
+}
+
+/// Manifest path a finding starts with ("spec.sink.s3.format must be ..." -> spec.sink.s3.format).
+fn finding_path(message: &str) -> Option<&str> {
+    let token = message.split([' ', ':']).next()?;
+    let root = token.split(['.', '[']).next()?;
+    matches!(root, "spec" | "metadata" | "apiVersion" | "kind").then_some(token)
+}
+
+/// Line and column from a serde_yaml error ("... at line 3 column 5").
+fn parse_error_location(message: &str) -> Option<(usize, usize)> {
+    let rest = &message[message.rfind("at line ")? + "at line ".len()..];
+    let mut parts = rest.split_whitespace();
+    let line = parts.next()?.parse().ok()?;
+    let column = match (parts.next(), parts.next()) {
+        (Some("column"), Some(c)) => c
+            .trim_end_matches(|ch: char| !ch.is_ascii_digit())
+            .parse()
+            .unwrap_or(1),
+        _ => 1,
+    };
+    Some((line, column))
+}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+fn segments(path: &str) -> Vec<Segment<'_>> {
+    let mut out = Vec::new();
+    for part in path.split('.') {
+        let (key, indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !key.is_empty() {
+            out.push(Segment::Key(key));
+        }
+        for idx in indices.split('[').filter(|s| !s.is_empty()) {
+            if let Ok(i) = idx.trim_end_matches(']').parse() {
+                out.push(Segment::Index(i));
+            }
+        }
+    }
+    out
+}
+
+/// 1-based line and column of a dotted manifest path (spec.transformations[0].script) in block-style YAML.
+/// Falls back to the deepest enclosing key that exists, e.g. the parent of a missing required field.
+pub(crate) fn locate_path(yaml: &str, path: &str) -> Option<(usize, usize)> {
+    // Each line as (indent, content); the key after "- " on an item line is tracked separately via `inline`.
+    let lines: Vec<(usize, &str)> = yaml
+        .lines()
+        .map(|l| {
+            let content = l.trim_start();
+            (l.len() - content.len(), content)
+        })
+        .collect();
+    let significant = |i: usize| !lines[i].1.is_empty() && !lines[i].1.starts_with('#');
+
+    let mut found: Option<(usize, usize)> = None;
+    // Block being searched: lines after `start` whose indent is greater than `parent`.
+    let (mut start, mut parent): (usize, Option<usize>) = (0, None);
+    // Content of the current line when the block begins on a "- " item line.
+    let mut inline: Option<(usize, &str)> = None;
+    for segment in segments(path) {
+        // Sequence items may sit at the same indent as their key ("key:\n- item").
+        let in_block = |i: usize, items: bool| {
+            !significant(i)
+                || parent.is_none_or(|p| {
+                    lines[i].0 > p || (items && lines[i].0 == p && lines[i].1.starts_with("- "))
+                })
+        };
+        match segment {
+            Segment::Key(key) => {
+                let prefix = format!("{}:", key);
+                let mut hit = None;
+                // Only direct children: the indent of the first key in the block.
+                let mut child = None;
+                if let Some((col, content)) = inline.take() {
+                    child = Some(col);
+                    if content.starts_with(&prefix) {
+                        hit = Some((start, col));
+                    }
+                }
+                let mut i = start + usize::from(found.is_some());
+                while hit.is_none() && i < lines.len() && in_block(i, false) {
+                    if significant(i) {
+                        let indent = *child.get_or_insert(lines[i].0);
+                        if lines[i].0 == indent && lines[i].1.starts_with(&prefix) {
+                            hit = Some((i, indent));
+                        }
+                    }
+                    i += 1;
+                }
+                let Some((line, col)) = hit else { break };
+                found = Some((line + 1, col + 1));
+                start = line;
+                parent = Some(col);
+            }
+            Segment::Index(n) => {
+                let mut seen = 0;
+                let mut hit = None;
+                let mut item_indent = None;
+                let mut i = start + 1;
+                while i < lines.len() && in_block(i, true) {
+                    let item = significant(i) && lines[i].1.starts_with("- ");
+                    if item && *item_indent.get_or_insert(lines[i].0) == lines[i].0 {
+                        if seen == n {
+                            hit = Some(i);
+                            break;
+                        }
+                        seen += 1;
+                    }
+                    i += 1;
+                }
+                let Some(line) = hit else { break };
+                let dash = lines[line].0;
+                let item = lines[line].1[2..].trim_start();
+                let col = dash + (lines[line].1.len() - item.len());
+                found = Some((line + 1, dash + 1));
+                start = line;
+                parent = Some(dash);
+                inline = Some((col, item));
+            }
+        }
+    }
+    found
+}
+
+/// SARIF level of a finding: error, warning or note.
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// SARIF log for the located findings of one manifest, each with the level of its severity. artifact_uri is the
+/// manifest path in the repository.
+pub fn to_sarif(kind: FindingKind, findings: &[Diagnostic], artifact_uri: Option<&str>) -> String {
+    let uri = artifact_uri
+        .filter(|u| !u.is_empty())
+        .unwrap_or(DEFAULT_ARTIFACT_URI);
+    let rules = kind.rules();
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let rule = rules
+                .iter()
+                .position(|r| finding.message.contains(r.3))
+                .unwrap_or(rules.len() - 1);
+            let mut location = json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                    "region": { "startLine": finding.line, "startColumn": finding.column }
+                }
+            });
+            if let Some(p) = &finding.path {
+                location["logicalLocations"] =
+                    json!([{ "fullyQualifiedName": p, "kind": "member" }]);
+            }
+            json!({
+                "ruleId": rules[rule].0,
+                "ruleIndex": rule,
+                "level": sarif_level(finding.severity),
+                "message": { "text": finding.message },
+                "locations": [location]
+            })
+        })
+        .collect();
+    let rule_descriptors: Vec<Value> = rules
+        .iter()
+        .map(|(id, name, description, _)| {
+            json!({
+                "id": id,
+                "name": name,
+                "shortDescription": { "text": description },
+                "defaultConfiguration": { "level": kind.level() }
+            })
+        })
+        .collect();
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "dataflow-mcp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rule_descriptors
+                }
+            },
+            "results": results
+        }]
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"apiVersion: dataflow.dataflow.io/v1
+kind: DataFlow
+metadata:
+  name: orders
+spec:
+  source:
+    type: kafka
+    kafka:
+      brokers: ["k:9092"]
+      topic: orders
+  sink:
+    type: s3
+    s3:
+      bucket: lake
+      format: csv
+  transformations:
+    - type: filter
+      filter:
+        condition: "$.ok"
+    - type: script
+      script:
+        code: return nil
+"#;
+
+    #[test]
+    fn test_locate_path() {
+        assert_eq!(locate_path(MANIFEST, "spec.sink.s3.format"), Some((15, 7)));
+        assert_eq!(
+            locate_path(MANIFEST, "spec.transformations[1].script"),
+            Some((21, 7))
+        );
+        assert_eq!(
+            locate_path(MANIFEST, "spec.transformations[1].type"),
+            Some((20, 7))
+        );
+        // Missing field: the enclosing mapping.
+        assert_eq!(locate_path(MANIFEST, "spec.sink.s3.region"), Some((13, 5)));
+    }
+
+    #[test]
+    fn test_to_sarif_validation_findings() {
+        let findings = vec![
+            Diagnostic::located(
+                Severity::Error,
+                "spec.sink.s3.format must be one of: json, avro, parquet".to_string(),
+                MANIFEST,
+            ),
+            Diagnostic::located(
+                Severity::Error,
+                "spec.sink.s3.region is required".to_string(),
+                MANIFEST,
+            ),
+            Diagnostic::located(
+                Severity::Warning,
+                "spec.source.kafka: consumerGroup is not set".to_string(),
+                MANIFEST,
+            ),
+            Diagnostic::located(
+                Severity::Info,
+                "spec.source.kafka: batchSize is left to the operator default".to_string(),
+                MANIFEST,
+            ),
+        ];
+        let sarif: Value = serde_json::from_str(&to_sarif(
+            FindingKind::Validate,
+            &findings,
+            Some("deploy/orders.yaml"),
+        ))
+        .unwrap();
+        let results = &sarif["runs"][0]["results"];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(results[0]["ruleId"], "DF003");
+        assert_eq!(results[0]["level"], "error");
+        let location = &results[0]["locations"][0];
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "deploy/orders.yaml"
+        );
+        assert_eq!(location["physicalLocation"]["region"]["startLine"], 15);
+        assert_eq!(
+            location["logicalLocations"][0]["fullyQualifiedName"],
+            "spec.sink.s3.format"
+        );
+        assert_eq!(results[1]["ruleId"], "DF002");
+        assert_eq!(
+            (
+                results[2]["level"].as_str(),
+                results[2]["locations"][0]["physicalLocation"]["region"]["startLine"].as_u64()
+            ),
+            (Some("warning"), Some(8))
+        );
+        assert_eq!(results[3]["level"], "note");
+
+        let trivial = Diagnostic::located(
+            Severity::Warning,
+            "spec.transformations[1].script: script is trivial".to_string(),
+            MANIFEST,
+        );
+        let lint = to_sarif(FindingKind::Lint, &[trivial], None);
+        let lint: Value = serde_json::from_str(&lint).unwrap();
+        assert_eq!(lint["runs"][0]["results"][0]["ruleId"], "DF200");
+        assert_eq!(lint["runs"][0]["results"][0]["level"], "warning");
+    }
+}
@@ -0,0 +1,296 @@
+// Schema Registry blocks of format-aware connectors (kafka avro/protobuf/jsonschema, s3 avro/parquet): one shape
+// — url, auth and subject naming strategy — for generation, validation and the connector catalog.
+
+use crate::tools::credentials::secret_name;
+use crate::tools::manifest::split_documents;
+use crate::tools::output::to_canonical_yaml;
+use crate::types::{
+    DATAFLOW_KIND, SCHEMA_REGISTRY_AUTH_TYPES, SCHEMA_REGISTRY_CONNECTORS, SUBJECT_NAME_STRATEGIES,
+};
+use reqwest::Url;
+use serde_json::{json, Value};
+
+const REGISTRY_FIELDS: [&str; 3] = ["url", "auth", "subjectNameStrategy"];
+
+/// Formats of connector that read their schema from a registry; None for connectors without one.
+pub fn registry_formats(connector: &str) -> Option<&'static [&'static str]> {
+    SCHEMA_REGISTRY_CONNECTORS
+        .iter()
+        .find(|(c, _)| *c == connector)
+        .map(|(_, f)| *f)
+}
+
+/// Checks block.schemaRegistry of a connector block at path: known fields, an http(s) url, the subject naming
+/// strategy and auth (basic: username and password, bearer: token; strings or secretKeyRef).
+pub fn validate_schema_registry(block: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(registry) = block.get("schemaRegistry") else {
+        return;
+    };
+    let path = format!("{}.schemaRegistry", path);
+    let Some(map) = registry.as_object() else {
+        errors.push(format!(
+            "{} must be an object with url, auth and subjectNameStrategy",
+            path
+        ));
+        return;
+    };
+    for key in map
+        .keys()
+        .filter(|k| !REGISTRY_FIELDS.contains(&k.as_str()))
+    {
+        errors.push(format!(
+            "{}.{} is not a known field; known fields: {}",
+            path,
+            key,
+            REGISTRY_FIELDS.join(", ")
+        ));
+    }
+    if let Some(url) = map
+        .get("url")
+        .and_then(Value::as_str)
+        .filter(|u| !u.contains("${"))
+    {
+        match Url::parse(url) {
+            Ok(u) if matches!(u.scheme(), "http" | "https") && u.host_str().is_some() => {}
+            _ => errors.push(format!(
+                "{}.url '{}' must be an http:// or https:// URL",
+                path, url
+            )),
+        }
+    }
+    if let Some(strategy) = map.get("subjectNameStrategy") {
+        if !strategy
+            .as_str()
+            .is_some_and(|s| SUBJECT_NAME_STRATEGIES.contains(&s))
+        {
+            errors.push(format!(
+                "{}.subjectNameStrategy must be one of: {}",
+                path,
+                SUBJECT_NAME_STRATEGIES.join(", ")
+            ));
+        }
+    }
+    let Some(auth) = map.get("auth") else { return };
+    let required: &[&str] = match auth["type"].as_str() {
+        Some("basic") => &["username", "password"],
+        Some("bearer") => &["token"],
+        _ => {
+            errors.push(format!(
+                "{}.auth.type must be one of: {}",
+                path,
+                SCHEMA_REGISTRY_AUTH_TYPES.join(", ")
+            ));
+            return;
+        }
+    };
+    for field in required {
+        match auth.get(*field) {
+            Some(Value::String(s)) if !s.is_empty() => {}
+            Some(Value::Object(o)) if o.contains_key("secretKeyRef") => {}
+            _ => errors.push(format!(
+                "{}.auth.{} is required with auth type {} (a string or a secretKeyRef)",
+                path,
+                field,
+                auth["type"].as_str().unwrap_or("")
+            )),
+        }
+    }
+}
+
+/// Schema Registry block for generated flows: url, subjectNameStrategy (default TopicNameStrategy) and, with auth,
+/// credentials as secretKeyRef references into secret (schema-registry-username / -password / -token).
+pub fn schema_registry_block(
+    url: &str,
+    auth: Option<&str>,
+    subject_name_strategy: Option<&str>,
+    secret: &str,
+) -> Result<Value, String> {
+    let strategy = subject_name_strategy
+        .filter(|s| !s.is_empty())
+        .unwrap_or(SUBJECT_NAME_STRATEGIES[0]);
+    let mut registry = json!({ "url": url, "subjectNameStrategy": strategy });
+    let reference = |key: &str| json!({ "secretKeyRef": { "name": secret, "key": key } });
+    match auth.filter(|a| !a.is_empty()) {
+        None => {}
+        Some("basic") => {
+            registry["auth"] = json!({ "type": "basic", "username": reference("schema-registry-username"), "password": reference("schema-registry-password") })
+        }
+        Some("bearer") => {
+            registry["auth"] =
+                json!({ "type": "bearer", "token": reference("schema-registry-token") })
+        }
+        Some(other) => {
+            return Err(format!(
+                "schema_registry_auth '{}' is not supported; use one of: {}",
+                other,
+                SCHEMA_REGISTRY_AUTH_TYPES.join(", ")
+            ))
+        }
+    }
+    let mut errors = Vec::new();
+    validate_schema_registry(
+        &json!({ "schemaRegistry": registry }),
+        "schema_registry",
+        &mut errors,
+    );
+    match errors.is_empty() {
+        true => Ok(registry),
+        false => Err(errors
+            .join("; ")
+            .replace("schema_registry.schemaRegistry.url", "schema_registry_url")),
+    }
+}
+
+/// Paths of the connector blocks under v that use a registry format and have no schemaRegistry yet.
+fn registry_blocks(v: &Value, path: &str, found: &mut Vec<String>) {
+    match v {
+        Value::Object(map) => {
+            let connector = map.get("type").and_then(Value::as_str).unwrap_or("");
+            if let (Some(formats), Some(block)) = (registry_formats(connector), map.get(connector))
+            {
+                let format = block["format"].as_str().unwrap_or("");
+                if formats.contains(&format) && block.get("schemaRegistry").is_none() {
+                    found.push(format!("{}.{}", path, connector));
+                }
+            }
+            for (key, child) in map {
+                registry_blocks(child, &format!("{}.{}", path, key), found);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                registry_blocks(item, &format!("{}[{}]", path, i), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn block_at<'a>(v: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.')
+        .try_fold(v, |v, segment| match segment.split_once('[') {
+            Some((key, index)) => v
+                .get_mut(key)?
+                .get_mut(index.trim_end_matches(']').parse::<usize>().ok()?),
+            None => v.get_mut(segment),
+        })
+}
+
+/// Adds a schemaRegistry block (see schema_registry_block) to every kafka / s3 block of the DataFlows in yaml whose
+/// format reads schemas from a registry; fails when there is none.
+pub fn with_schema_registry(
+    yaml: &str,
+    url: &str,
+    auth: Option<&str>,
+    subject_name_strategy: Option<&str>,
+) -> Result<String, String> {
+    let mut docs = Vec::new();
+    let mut added = 0;
+    for (_, doc) in split_documents(yaml) {
+        let mut manifest: Value =
+            serde_yaml::from_str(&doc).map_err(|e| format!("YAML parse error: {}", e))?;
+        let mut paths = Vec::new();
+        registry_blocks(&manifest["spec"], "spec", &mut paths);
+        if manifest["kind"] != DATAFLOW_KIND || paths.is_empty() {
+            docs.push(doc);
+            continue;
+        }
+        let secret = secret_name(manifest["metadata"]["name"].as_str());
+        let registry = schema_registry_block(url, auth, subject_name_strategy, &secret)?;
+        for path in &paths {
+            if let Some(block) = block_at(&mut manifest, path).and_then(Value::as_object_mut) {
+                block.insert("schemaRegistry".to_string(), registry.clone());
+            }
+        }
+        added += paths.len();
+        let body_start = doc
+            .lines()
+            .take_while(|l| l.trim().is_empty() || l.trim_start().starts_with('#'))
+            .count();
+        let mut out: String = doc
+            .lines()
+            .take(body_start)
+            .map(|l| format!("{}\n", l))
+            .collect();
+        out.push_str(&format!(
+            "# Schema Registry {} added to: {}\n",
+            url,
+            paths.join(", ")
+        ));
+        if registry.get("auth").is_some() {
+            out.push_str(&format!("# Schema Registry credentials are read from Secret {}; create it with the keys referenced under auth\n", secret));
+        }
+        out.push_str(&to_canonical_yaml(&manifest)?);
+        docs.push(out);
+    }
+    if added == 0 {
+        let formats: Vec<String> = SCHEMA_REGISTRY_CONNECTORS
+            .iter()
+            .map(|(c, f)| format!("{} {}", c, f.join("/")))
+            .collect();
+        return Err(format!(
+            "schema_registry_url is set but no connector uses a registry format ({}); set format in source_config or sink_config",
+            formats.join(", ")
+        ));
+    }
+    Ok(docs.join("---\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_schema_registry() {
+        let check = |registry: Value| {
+            let mut errors = Vec::new();
+            validate_schema_registry(
+                &json!({ "schemaRegistry": registry }),
+                "spec.sink.kafka",
+                &mut errors,
+            );
+            errors
+        };
+        assert!(check(
+            json!({ "url": "http://sr:8081", "subjectNameStrategy": "RecordNameStrategy" })
+        )
+        .is_empty());
+        assert_eq!(
+            check(json!({ "url": "sr:8081", "subjectNameStrategy": "topic", "auth": { "type": "basic", "username": "u" }, "cache": 1 })),
+            vec![
+                "spec.sink.kafka.schemaRegistry.cache is not a known field; known fields: url, auth, subjectNameStrategy",
+                "spec.sink.kafka.schemaRegistry.url 'sr:8081' must be an http:// or https:// URL",
+                "spec.sink.kafka.schemaRegistry.subjectNameStrategy must be one of: TopicNameStrategy, RecordNameStrategy, TopicRecordNameStrategy",
+                "spec.sink.kafka.schemaRegistry.auth.password is required with auth type basic (a string or a secretKeyRef)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_schema_registry() {
+        let yaml = "# Generated DataFlow manifest\napiVersion: dataflow.dataflow.io/v1\nkind: DataFlow\nmetadata:\n  name: orders\nspec:\n  source:\n    type: kafka\n    kafka: {brokers: [\"k:9092\"], topic: orders, format: avro}\n  sink:\n    type: s3\n    s3: {bucket: lake, region: eu-west-1, format: json}\n";
+        let out = with_schema_registry(yaml, "https://sr:8081", Some("bearer"), None).unwrap();
+        assert!(out.starts_with("# Generated DataFlow manifest\n# Schema Registry https://sr:8081 added to: spec.source.kafka\n"));
+        let doc: Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(
+            doc["spec"]["source"]["kafka"]["schemaRegistry"],
+            json!({
+                "url": "https://sr:8081",
+                "subjectNameStrategy": "TopicNameStrategy",
+                "auth": { "type": "bearer", "token": { "secretKeyRef": { "name": "orders-credentials", "key": "schema-registry-token" } } }
+            })
+        );
+        assert!(doc["spec"]["sink"]["s3"].get("schemaRegistry").is_none());
+        assert!(with_schema_registry(
+            &yaml.replace("format: avro", "format: json"),
+            "https://sr:8081",
+            None,
+            None
+        )
+        .unwrap_err()
+        .contains("no connector uses a registry format"));
+        assert!(with_schema_registry(yaml, "sr", None, None)
+            .unwrap_err()
+            .starts_with("schema_registry_url 'sr' must be"));
+    }
+}
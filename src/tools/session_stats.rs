@@ -0,0 +1,250 @@
+// Per-session tool usage: call and error counts per tool and the last error, for get_session_stats.
+
+use crate::tools::credentials::{is_secret_field, mask_url_password};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Longest error text / argument summary kept per tool.
+const MAX_RECORDED_CHARS: usize = 500;
+
+/// Tool parameters holding credentials besides the secret-named fields: the Connect REST auth and the signing key.
+const SECRET_ARGUMENTS: [&str; 2] = ["auth", "key"];
+/// Parts of a key that mark it as a credential, e.g. connection.password, sasl.jaas.config, ssl.keystore.password.
+const SECRET_KEY_PARTS: [&str; 5] = ["password", "secret", "jaas", "token", "credential"];
+const REDACTED: &str = "***";
+
+fn is_secret_key(key: &str) -> bool {
+    let lower = key.to_lowercase();
+    is_secret_field(key) || SECRET_KEY_PARTS.iter().any(|p| lower.contains(p))
+}
+
+/// s with the values of secret key=value lines (.properties text such as kafka_connect_config or
+/// worker_properties) and URL passwords in the other values replaced by ***; None when nothing changed.
+fn scrub_properties(s: &str) -> Option<String> {
+    let mut changed = false;
+    let lines: Vec<String> = s
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, _)) if is_secret_key(key.trim()) => {
+                changed = true;
+                format!("{}={}", key, REDACTED)
+            }
+            Some((key, value)) => match mask_url_password(value) {
+                Some(masked) => {
+                    changed = true;
+                    format!("{}={}", key, masked)
+                }
+                None => line.to_string(),
+            },
+            None => line.to_string(),
+        })
+        .collect();
+    changed.then(|| lines.join("\n"))
+}
+
+/// Copy of v with secret-named fields and URL passwords replaced by ***. Strings holding JSON or YAML documents
+/// (source_config, config) are redacted inside too, .properties text line by line (see scrub_properties).
+fn redact(v: &Value) -> Value {
+    match v {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| match v {
+                    Value::String(_) if is_secret_key(k) => (k.clone(), json!(REDACTED)),
+                    v => (k.clone(), redact(v)),
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        Value::String(s) => {
+            if let Some(masked) = mask_url_password(s) {
+                return json!(masked);
+            }
+            let trimmed = s.trim_start();
+            if trimmed.starts_with('{') || trimmed.starts_with('[') {
+                if let Ok(parsed) = serde_json::from_str::<Value>(s) {
+                    return json!(redact(&parsed).to_string());
+                }
+            }
+            if s.contains('\n') {
+                let docs: Result<Vec<Value>, _> = serde_yaml::Deserializer::from_str(s)
+                    .map(Value::deserialize)
+                    .collect();
+                if let Some(docs) = docs
+                    .ok()
+                    .filter(|d| !d.is_empty() && d.iter().all(Value::is_object))
+                {
+                    let redacted: Vec<String> = docs
+                        .iter()
+                        .map(|d| serde_yaml::to_string(&redact(d)).unwrap_or_default())
+                        .collect();
+                    return json!(redacted.join("---\n"));
+                }
+            }
+            scrub_properties(s)
+                .map(Value::String)
+                .unwrap_or_else(|| v.clone())
+        }
+        _ => v.clone(),
+    }
+}
+
+/// Tool arguments as recorded: JSON with the credentials redacted (see redact).
+fn redact_arguments(arguments: &Value) -> String {
+    let mut redacted = redact(arguments);
+    if let Some(map) = redacted.as_object_mut() {
+        for key in SECRET_ARGUMENTS {
+            if map.get(key).is_some_and(|v| !v.is_null()) {
+                map.insert(key.to_string(), json!(REDACTED));
+            }
+        }
+    }
+    redacted.to_string()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ToolStats {
+    pub calls: u64,
+    pub errors: u64,
+    /// Whether the most recent call failed.
+    pub last_call_failed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Arguments of the call that produced last_error (JSON, truncated).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_arguments: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct SessionStats {
+    tools: BTreeMap<String, ToolStats>,
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() <= MAX_RECORDED_CHARS {
+        return s.to_string();
+    }
+    let cut: String = s.chars().take(MAX_RECORDED_CHARS).collect();
+    format!("{}…", cut)
+}
+
+impl SessionStats {
+    /// Records a call of tool; error is the error text when the call failed. Credentials in the arguments are
+    /// redacted before they are kept.
+    pub fn record(&mut self, tool: &str, arguments: Option<&Value>, error: Option<&str>) {
+        let stats = self.tools.entry(tool.to_string()).or_default();
+        stats.calls += 1;
+        stats.last_call_failed = error.is_some();
+        if let Some(e) = error {
+            stats.errors += 1;
+            stats.last_error = Some(truncate(e));
+            stats.last_error_arguments = arguments.map(|a| truncate(&redact_arguments(a)));
+        }
+    }
+
+    /// Report as JSON: totals, per-tool stats and the tools whose latest call failed.
+    pub fn report(&self) -> String {
+        let failing: Vec<&String> = self
+            .tools
+            .iter()
+            .filter(|(_, s)| s.last_call_failed)
+            .map(|(k, _)| k)
+            .collect();
+        let report = json!({
+            "total_calls": self.tools.values().map(|s| s.calls).sum::<u64>(),
+            "total_errors": self.tools.values().map(|s| s.errors).sum::<u64>(),
+            "failing": failing,
+            "tools": self.tools
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_stats_counts_and_last_error() {
+        let mut stats = SessionStats::default();
+        stats.record(
+            "validate_dataflow_manifest",
+            Some(&json!({ "config": "x" })),
+            Some("YAML parse error"),
+        );
+        stats.record("validate_dataflow_manifest", None, None);
+        stats.record(
+            "migrate_nifi_to_dataflow",
+            Some(&json!({})),
+            Some(&"e".repeat(600)),
+        );
+        let report: Value = serde_json::from_str(&stats.report()).unwrap();
+        assert_eq!(report["total_calls"], 3);
+        assert_eq!(report["total_errors"], 2);
+        assert_eq!(report["failing"], json!(["migrate_nifi_to_dataflow"]));
+        let validate = &report["tools"]["validate_dataflow_manifest"];
+        assert_eq!(validate["calls"], 2);
+        assert_eq!(validate["last_call_failed"], false);
+        assert_eq!(validate["last_error"], "YAML parse error");
+        assert_eq!(validate["last_error_arguments"], "{\"config\":\"x\"}");
+        let nifi = report["tools"]["migrate_nifi_to_dataflow"]["last_error"]
+            .as_str()
+            .unwrap();
+        assert_eq!(nifi.chars().count(), MAX_RECORDED_CHARS + 1);
+    }
+
+    #[test]
+    fn test_session_stats_redacts_credentials() {
+        let mut stats = SessionStats::default();
+        let arguments = json!({
+            "source_config": { "brokers": ["k:9092"], "sasl": { "username": "app", "password": "hunter2" } },
+            "sink_config": "{\"connectionString\": \"postgres://app:hunter3@pg:5432/db\"}",
+            "config": "kind: DataFlow\nspec:\n  sink:\n    trino:\n      token: hunter4\n",
+            "auth": "admin:hunter5",
+            "key": "hunter6",
+            "name": "orders"
+        });
+        stats.record(
+            "generate_dataflow_manifest",
+            Some(&arguments),
+            Some("failed"),
+        );
+        let report: Value = serde_json::from_str(&stats.report()).unwrap();
+        let recorded = report["tools"]["generate_dataflow_manifest"]["last_error_arguments"]
+            .as_str()
+            .unwrap();
+        assert!(!recorded.contains("hunter"), "{}", recorded);
+        assert!(recorded.contains("app:***@pg:5432") && recorded.contains("\"name\":\"orders\""));
+    }
+
+    #[test]
+    fn test_session_stats_redacts_kafka_connect_properties() {
+        let mut stats = SessionStats::default();
+        let arguments = json!({
+            "kafka_connect_config": "name=jdbc-sink\nconnector.class=io.confluent.connect.jdbc.JdbcSinkConnector\nconnection.url=jdbc:postgresql://pg/db?password=hunter1\nconnection.password=hunter2\n",
+            "worker_properties": "bootstrap.servers=k:9092\nsasl.jaas.config=org.apache.kafka.common.security.plain.PlainLoginModule required username=\"u\" password=\"hunter3\";\nssl.keystore.password = hunter4",
+            "source_config": { "ssl.truststore.password": "hunter5", "database.user": "app" }
+        });
+        stats.record(
+            "migrate_kafka_connect_to_dataflow",
+            Some(&arguments),
+            Some("unsupported connector"),
+        );
+        let report: Value = serde_json::from_str(&stats.report()).unwrap();
+        let recorded = report["tools"]["migrate_kafka_connect_to_dataflow"]["last_error_arguments"]
+            .as_str()
+            .unwrap();
+        assert!(!recorded.contains("hunter"), "{}", recorded);
+        assert!(
+            recorded.contains("connection.password=***")
+                && recorded.contains("bootstrap.servers=k:9092"),
+            "{}",
+            recorded
+        );
+        assert!(
+            recorded.contains("\"database.user\":\"app\""),
+            "{}",
+            recorded
+        );
+    }
+}
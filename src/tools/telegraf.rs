@@ -0,0 +1,482 @@
+// Telegraf configuration migration: telegraf.conf inputs (kafka_consumer, postgresql, http) and outputs
+// (kafka, postgresql, http) mapped to DataFlow manifests, one per input/output pair.
+
+use crate::tools::migration::{block, placeholder_kafka, render_migrated, sanitize_name, str_list};
+use crate::tools::provenance::{annotate, or_filled, GenerationNote};
+use crate::types::{DATAFLOW_API_VERSION, DATAFLOW_KIND};
+use serde_json::{json, Map as JsonMap, Value};
+
+/// Plugins of a section ("inputs" / "outputs"): (plugin name, config) for every [[section.plugin]] table.
+fn plugins<'a>(config: &'a Value, section: &str) -> Vec<(&'a str, &'a Value)> {
+    let mut out = Vec::new();
+    for (name, tables) in config[section].as_object().into_iter().flatten() {
+        match tables {
+            Value::Array(list) => out.extend(list.iter().map(|t| (name.as_str(), t))),
+            table => out.push((name.as_str(), table)),
+        }
+    }
+    out
+}
+
+/// Telegraf's data_format as a kafka format: json stays json, influx / graphite / others are plain strings.
+fn kafka_format(cfg: &Value, notes: &mut Vec<String>, label: &str) -> &'static str {
+    match cfg["data_format"].as_str().unwrap_or("influx") {
+        "json" | "json_v2" => "json",
+        other => {
+            notes.push(format!(
+                "{}: data_format {} is read as plain strings; switch Telegraf to data_format json to use DataFlow transformations.",
+                label, other
+            ));
+            "string"
+        }
+    }
+}
+
+/// PostgreSQL address in libpq key=value form or as a URL -> postgres:// URL without the password.
+fn postgres_url(address: &str, notes: &mut Vec<String>, label: &str) -> String {
+    if address.starts_with("postgres://") || address.starts_with("postgresql://") {
+        return address.to_string();
+    }
+    let kv: Vec<(&str, &str)> = address
+        .split_whitespace()
+        .filter_map(|p| p.split_once('='))
+        .collect();
+    let get = |k: &str| kv.iter().find(|(key, _)| *key == k).map(|(_, v)| *v);
+    if get("password").is_some() {
+        notes.push(format!(
+            "{}: the password was not copied; add it to connectionString from a Secret.",
+            label
+        ));
+    }
+    let user = get("user").map(|u| format!("{}@", u)).unwrap_or_default();
+    let mut url = format!(
+        "postgres://{}{}:{}/{}",
+        user,
+        get("host").unwrap_or("localhost"),
+        get("port").unwrap_or("5432"),
+        get("dbname").unwrap_or("postgres")
+    );
+    if let Some(mode) = get("sslmode") {
+        url.push_str(&format!("?sslmode={}", mode));
+    }
+    url
+}
+
+fn map_input(
+    name: &str,
+    cfg: &Value,
+    interval: Option<&str>,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> JsonMap<String, Value> {
+    let label = format!("inputs.{}", name);
+    match name {
+        "kafka_consumer" => {
+            let mut kafka = JsonMap::new();
+            let brokers = str_list(&cfg["brokers"]);
+            let brokers = if brokers.is_empty() {
+                filled.push(GenerationNote::new(
+                    "spec.source.kafka.brokers",
+                    json!(["localhost:9092"]),
+                    "brokers not set",
+                ));
+                vec!["localhost:9092".to_string()]
+            } else {
+                brokers
+            };
+            kafka.insert("brokers".to_string(), json!(brokers));
+            let topics = str_list(&cfg["topics"]);
+            if let Some(pattern) = cfg["topic_regexps"]
+                .as_array()
+                .and_then(|a| a.first())
+                .and_then(Value::as_str)
+            {
+                kafka.insert(
+                    "topicPattern".to_string(),
+                    Value::String(pattern.to_string()),
+                );
+            } else if topics.len() > 1 {
+                let pattern = topics
+                    .iter()
+                    .map(|t| regex::escape(t))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                kafka.insert("topicPattern".to_string(), Value::String(pattern));
+            } else {
+                let topic = or_filled(
+                    topics.into_iter().next(),
+                    "telegraf",
+                    "spec.source.kafka.topic",
+                    "topics not set (Telegraf defaults to telegraf)",
+                    filled,
+                );
+                kafka.insert("topic".to_string(), Value::String(topic));
+            }
+            if let Some(group) = cfg["consumer_group"].as_str() {
+                kafka.insert(
+                    "consumerGroup".to_string(),
+                    Value::String(group.to_string()),
+                );
+            }
+            let reset = if cfg["offset"].as_str() == Some("newest") {
+                "latest"
+            } else {
+                "earliest"
+            };
+            kafka.insert("offsetReset".to_string(), Value::String(reset.to_string()));
+            kafka.insert(
+                "format".to_string(),
+                Value::String(kafka_format(cfg, notes, &label).to_string()),
+            );
+            if cfg["sasl_username"].is_string() || cfg["tls_ca"].is_string() {
+                notes.push(format!("{}: sasl/tls settings were not migrated; set kafka tls/sasl and take credentials from a Secret.", label));
+            }
+            block("kafka", kafka)
+        }
+        "postgresql" | "postgresql_extensible" => {
+            let mut pg = JsonMap::new();
+            let conn = match cfg["address"].as_str() {
+                Some(a) => postgres_url(a, notes, &label),
+                None => {
+                    let default = "postgres://postgres@localhost:5432/postgres";
+                    filled.push(GenerationNote::new(
+                        "spec.source.postgresql.connectionString",
+                        json!(default),
+                        "address not set",
+                    ));
+                    default.to_string()
+                }
+            };
+            pg.insert("connectionString".to_string(), Value::String(conn));
+            // postgresql collects pg_stat_database; postgresql_extensible runs its own queries.
+            let queries: Vec<&Value> = cfg["query"]
+                .as_array()
+                .map(|q| q.iter().collect())
+                .unwrap_or_default();
+            let (table, query) = match queries.first() {
+                Some(q) => (
+                    q["measurement"]
+                        .as_str()
+                        .unwrap_or("telegraf_query")
+                        .to_string(),
+                    q["sqlquery"].as_str().map(str::to_string),
+                ),
+                None => (
+                    "pg_stat_database".to_string(),
+                    Some("SELECT * FROM pg_stat_database".to_string()),
+                ),
+            };
+            if queries.len() > 1 {
+                notes.push(format!(
+                    "{} runs {} queries; only the first was mapped. Create one DataFlow per query.",
+                    label,
+                    queries.len()
+                ));
+            }
+            pg.insert("table".to_string(), Value::String(table));
+            if let Some(q) = query {
+                pg.insert("query".to_string(), Value::String(q));
+            }
+            pg.insert("mode".to_string(), Value::String("bulk".to_string()));
+            if let Some(i) = cfg["interval"].as_str().or(interval) {
+                pg.insert("pollInterval".to_string(), Value::String(i.to_string()));
+            }
+            block("postgresql", pg)
+        }
+        other => {
+            let hint = if other == "http" {
+                " (DataFlow has no HTTP polling source; publish the responses to Kafka, or call the endpoint with an enrich transformation)"
+            } else {
+                ""
+            };
+            notes.push(format!(
+                "{} is not supported{}; the kafka source is a placeholder.",
+                label, hint
+            ));
+            placeholder_kafka(
+                "spec.source",
+                "telegraf",
+                &format!("unsupported Telegraf input {}", other),
+                filled,
+            )
+        }
+    }
+}
+
+fn map_output(
+    name: &str,
+    cfg: &Value,
+    filled: &mut Vec<GenerationNote>,
+    notes: &mut Vec<String>,
+) -> JsonMap<String, Value> {
+    let label = format!("outputs.{}", name);
+    match name {
+        "kafka" => {
+            let mut kafka = JsonMap::new();
+            let brokers = str_list(&cfg["brokers"]);
+            let brokers = if brokers.is_empty() {
+                filled.push(GenerationNote::new(
+                    "spec.sink.kafka.brokers",
+                    json!(["localhost:9092"]),
+                    "brokers not set",
+                ));
+                vec!["localhost:9092".to_string()]
+            } else {
+                brokers
+            };
+            kafka.insert("brokers".to_string(), json!(brokers));
+            let topic = or_filled(
+                cfg["topic"].as_str().map(str::to_string),
+                "telegraf",
+                "spec.sink.kafka.topic",
+                "topic not set",
+                filled,
+            );
+            if cfg["topic_suffix"].is_object() {
+                notes.push(format!("{}: topic_suffix builds per-metric topics; DataFlow writes to '{}' only (use a router for the others).", label, topic));
+            }
+            kafka.insert("topic".to_string(), Value::String(topic));
+            kafka.insert(
+                "format".to_string(),
+                Value::String(kafka_format(cfg, notes, &label).to_string()),
+            );
+            block("kafka", kafka)
+        }
+        "postgresql" => {
+            let mut pg = JsonMap::new();
+            let conn = match cfg["connection"].as_str() {
+                Some(c) => postgres_url(c, notes, &label),
+                None => or_filled(
+                    None,
+                    "postgres://postgres@localhost:5432/postgres",
+                    "spec.sink.postgresql.connectionString",
+                    "connection not set",
+                    filled,
+                ),
+            };
+            pg.insert("connectionString".to_string(), Value::String(conn));
+            // Telegraf creates one table per measurement; a DataFlow writes to one table.
+            let table = or_filled(
+                None,
+                "metrics",
+                "spec.sink.postgresql.table",
+                "Telegraf writes one table per measurement",
+                filled,
+            );
+            notes.push(format!(
+                "{}: Telegraf writes one table per measurement; all metrics go to '{}' (use a router with branches per measurement).",
+                label, table
+            ));
+            pg.insert("table".to_string(), Value::String(table));
+            pg.insert("autoCreateTable".to_string(), Value::Bool(true));
+            pg.insert(
+                "payloadMode".to_string(),
+                Value::String("jsonb".to_string()),
+            );
+            pg.insert(
+                "payloadColumn".to_string(),
+                Value::String("metric".to_string()),
+            );
+            block("postgresql", pg)
+        }
+        other => {
+            let hint = if other == "http" {
+                " (DataFlow has no HTTP sink; write to Kafka and let the receiver consume the topic)"
+            } else {
+                ""
+            };
+            notes.push(format!(
+                "{} is not supported{}; the kafka sink is a placeholder.",
+                label, hint
+            ));
+            placeholder_kafka(
+                "spec.sink",
+                "telegraf-metrics",
+                &format!("unsupported Telegraf output {}", other),
+                filled,
+            )
+        }
+    }
+}
+
+/// fieldpass / fielddrop without globs as select / remove on the JSON metric's fields.
+fn field_filters(cfg: &Value, notes: &mut Vec<String>, label: &str) -> Vec<Value> {
+    let mut out = Vec::new();
+    for (key, kind) in [
+        ("fieldpass", "select"),
+        ("fieldinclude", "select"),
+        ("fielddrop", "remove"),
+        ("fieldexclude", "remove"),
+    ] {
+        let fields = str_list(&cfg[key]);
+        if fields.is_empty() {
+            continue;
+        }
+        if fields.iter().any(|f| f.contains(['*', '?', '['])) {
+            notes.push(format!(
+                "{}: {} uses globs and was not migrated.",
+                label, key
+            ));
+            continue;
+        }
+        let mut paths: Vec<String> = fields.iter().map(|f| format!("$.fields.{}", f)).collect();
+        if kind == "select" {
+            // Keep the metric envelope around the selected fields.
+            paths.splice(
+                0..0,
+                ["$.name", "$.tags", "$.timestamp"].map(str::to_string),
+            );
+        }
+        out.push(json!({ "type": kind, kind: { "fields": paths } }));
+    }
+    for key in ["namepass", "namedrop", "tagpass", "tagdrop"] {
+        if !cfg[key].is_null() {
+            notes.push(format!(
+                "{}: {} was not migrated; filter on $.name / $.tags with a filter transformation.",
+                label, key
+            ));
+        }
+    }
+    out
+}
+
+/// Migrates a telegraf.conf (TOML) to DataFlow manifests: Telegraf sends every input to every output, so
+/// each input/output pair becomes one DataFlow. Documents are joined as a multi-document YAML.
+pub fn migrate_telegraf_to_dataflow(config_toml: &str) -> Result<String, String> {
+    let config: Value =
+        toml::from_str(config_toml).map_err(|e| format!("TOML parse error: {}", e))?;
+    let inputs = plugins(&config, "inputs");
+    let outputs = plugins(&config, "outputs");
+    if inputs.is_empty() || outputs.is_empty() {
+        return Err(
+            "Expected a telegraf.conf with at least one [[inputs.*]] and one [[outputs.*]] plugin"
+                .to_string(),
+        );
+    }
+    let interval = config["agent"]["interval"].as_str();
+    let mut global_notes = Vec::new();
+    for (kind, plugin) in ["processors", "aggregators"]
+        .iter()
+        .flat_map(|s| plugins(&config, s).into_iter().map(move |(n, _)| (*s, n)))
+    {
+        global_notes.push(format!(
+            "{}.{} was not migrated: add the equivalent DataFlow transformation by hand (aggregation is not supported).",
+            kind, plugin
+        ));
+    }
+
+    let mut docs = Vec::new();
+    for (i, (input_name, input_cfg)) in inputs.iter().enumerate() {
+        for (o, (output_name, output_cfg)) in outputs.iter().enumerate() {
+            let mut notes = global_notes.clone();
+            let mut filled = Vec::new();
+            let source = map_input(input_name, input_cfg, interval, &mut filled, &mut notes);
+            let mut transformations =
+                field_filters(input_cfg, &mut notes, &format!("inputs.{}", input_name));
+            transformations.extend(field_filters(
+                output_cfg,
+                &mut notes,
+                &format!("outputs.{}", output_name),
+            ));
+            let sink = map_output(output_name, output_cfg, &mut filled, &mut notes);
+
+            let alias = |cfg: &Value, name: &str, n: usize| {
+                cfg["alias"]
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or(format!("{}{}", name, n + 1))
+            };
+            let name = sanitize_name(&format!(
+                "{}-to-{}",
+                alias(input_cfg, input_name, i),
+                alias(output_cfg, output_name, o)
+            ));
+            let mut metadata = JsonMap::new();
+            metadata.insert("name".to_string(), Value::String(name));
+            annotate(&mut metadata, &filled);
+            let mut spec = JsonMap::new();
+            spec.insert("source".to_string(), Value::Object(source));
+            spec.insert("sink".to_string(), Value::Object(sink));
+            if !transformations.is_empty() {
+                spec.insert("transformations".to_string(), Value::Array(transformations));
+            }
+            let mut manifest = JsonMap::new();
+            manifest.insert(
+                "apiVersion".to_string(),
+                Value::String(DATAFLOW_API_VERSION.to_string()),
+            );
+            manifest.insert("kind".to_string(), Value::String(DATAFLOW_KIND.to_string()));
+            manifest.insert("metadata".to_string(), Value::Object(metadata));
+            manifest.insert("spec".to_string(), Value::Object(spec));
+            docs.push(render_migrated("Telegraf config", &manifest, notes)?);
+        }
+    }
+    Ok(docs.join("---\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_telegraf_pairs_inputs_and_outputs() {
+        let config = r#"
+[agent]
+  interval = "30s"
+
+[[inputs.kafka_consumer]]
+  brokers = ["k1:9092", "k2:9092"]
+  topics = ["metrics"]
+  consumer_group = "telegraf"
+  offset = "newest"
+  data_format = "json"
+  fielddrop = ["debug"]
+
+[[inputs.postgresql_extensible]]
+  address = "host=pg user=telegraf password=secret dbname=app sslmode=disable"
+  [[inputs.postgresql_extensible.query]]
+    sqlquery = "SELECT * FROM queue_stats"
+    measurement = "queue_stats"
+
+[[processors.rename]]
+
+[[outputs.kafka]]
+  brokers = ["k1:9092"]
+  topic = "telegraf-out"
+  data_format = "json"
+"#;
+        let out = migrate_telegraf_to_dataflow(config).unwrap();
+        let docs: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(&out)
+            .map(|d| serde::Deserialize::deserialize(d).unwrap())
+            .collect();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0]["metadata"]["name"], "kafka-consumer1-to-kafka1");
+        let kafka = &docs[0]["spec"]["source"]["kafka"];
+        assert_eq!(kafka["offsetReset"], "latest");
+        assert_eq!(kafka["format"], "json");
+        assert_eq!(
+            docs[0]["spec"]["transformations"][0]["remove"]["fields"][0],
+            "$.fields.debug"
+        );
+        let pg = &docs[1]["spec"]["source"]["postgresql"];
+        assert_eq!(
+            pg["connectionString"],
+            "postgres://telegraf@pg:5432/app?sslmode=disable"
+        );
+        assert_eq!(pg["query"], "SELECT * FROM queue_stats");
+        assert_eq!(pg["pollInterval"], "30s");
+        assert!(out.contains("the password was not copied"));
+        assert!(out.contains("processors.rename was not migrated"));
+        assert!(!out.contains("Validation error"));
+    }
+
+    #[test]
+    fn test_migrate_telegraf_http_and_postgres_output() {
+        let config = "[[inputs.http]]\n  urls = [\"http://app/metrics\"]\n[[outputs.postgresql]]\n  connection = \"postgres://tg@pg:5432/metrics\"\n";
+        let out = migrate_telegraf_to_dataflow(config).unwrap();
+        let doc: serde_yaml::Value = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(doc["spec"]["sink"]["postgresql"]["payloadMode"], "jsonb");
+        assert!(out.contains("DataFlow has no HTTP polling source"));
+        assert!(migrate_telegraf_to_dataflow("[agent]\ninterval = \"10s\"\n").is_err());
+    }
+}
@@ -0,0 +1,443 @@
+// Built-in manifest templates: named profiles that turn a few inputs into a fully populated manifest with the
+// transformations and connector settings recommended for that kind of flow.
+
+use crate::tools::credentials::secret_name;
+use crate::tools::manifest::generate_dataflow_manifest;
+use serde_json::{json, Map as JsonMap, Value};
+
+/// Template input: (name, description, default; None when the input is required). Defaults may refer to other
+/// inputs as {input}.
+type Input = (&'static str, &'static str, Option<&'static str>);
+
+/// Source type, sink type, source block, sink block and transformations of a template flow.
+type Flow = (&'static str, String, Value, Value, Value);
+
+/// A named template: inputs, default flow name and the flow it builds from resolved inputs.
+pub struct Profile {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub inputs: &'static [Input],
+    flow_name: &'static str,
+    build: fn(&Inputs, &str) -> Result<Flow, String>,
+}
+
+pub const PROFILES: [Profile; 3] = [
+    Profile {
+        name: "kafka-to-warehouse",
+        description: "Kafka topic into a warehouse table: JSON from the earliest offset, snake_case keys and an ingestion timestamp, batched writes with the connection string in a Secret",
+        inputs: &[
+            ("brokers", "Kafka brokers, comma-separated", None),
+            ("topic", "Kafka topic to read", None),
+            ("warehouse", "clickhouse, postgresql or trino", Some("clickhouse")),
+            ("table", "Target table", Some("{topic}")),
+            ("consumer_group", "Kafka consumer group", Some("{topic}-to-{warehouse}")),
+            ("catalog", "Trino catalog (trino only)", Some("iceberg")),
+            ("schema", "Trino schema (trino only)", Some("analytics")),
+            ("connection", "connectionString / serverURL; a secretKeyRef to <name>-credentials when omitted", Some("")),
+        ],
+        flow_name: "{topic}-to-{warehouse}",
+        build: kafka_to_warehouse,
+    },
+    Profile {
+        name: "cdc-replication",
+        description: "PostgreSQL table replicated through logical replication (pgoutput) to a Kafka topic or an upserted PostgreSQL table, with a replication timestamp",
+        inputs: &[
+            ("table", "Source table", None),
+            ("target", "kafka or postgresql", Some("kafka")),
+            ("brokers", "Kafka brokers, comma-separated (kafka target)", Some("localhost:9092")),
+            ("topic", "Kafka topic (kafka target)", Some("cdc.{table}")),
+            ("target_table", "Target table (postgresql target)", Some("{table}")),
+            ("key", "Primary key column the target upserts on (postgresql target)", Some("id")),
+            ("slot_name", "Replication slot", Some("dataflow_{table}")),
+            ("publication", "Publication of the source table", Some("{table}_pub")),
+            ("source_connection", "Source connectionString; a secretKeyRef to <name>-credentials when omitted", Some("")),
+            ("target_connection", "Target connectionString (postgresql target); a secretKeyRef when omitted", Some("")),
+        ],
+        flow_name: "{table}-cdc",
+        build: cdc_replication,
+    },
+    Profile {
+        name: "pii-masking-pipeline",
+        description: "Kafka topic copied to a masked topic: secret fields removed, personal data masked keeping its length",
+        inputs: &[
+            ("brokers", "Kafka brokers, comma-separated", None),
+            ("input_topic", "Topic with personal data", None),
+            ("output_topic", "Masked topic", Some("{input_topic}-masked")),
+            ("pii_fields", "Fields to mask, comma-separated", Some("email,phone,ssn")),
+            ("drop_fields", "Fields to remove, comma-separated", Some("password")),
+            ("consumer_group", "Kafka consumer group", Some("{input_topic}-pii-masking")),
+        ],
+        flow_name: "{input_topic}-pii-masking",
+        build: pii_masking_pipeline,
+    },
+];
+
+/// Resolved template inputs.
+struct Inputs(JsonMap<String, Value>);
+
+impl Inputs {
+    fn get(&self, name: &str) -> String {
+        self.0
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// Comma-separated (or array) input as a list.
+    fn list(&self, name: &str) -> Vec<String> {
+        self.get(name)
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Replaces {input} references in text.
+    fn fill(&self, text: &str) -> String {
+        self.0.iter().fold(text.to_string(), |t, (k, v)| {
+            t.replace(&format!("{{{}}}", k), v.as_str().unwrap_or(""))
+        })
+    }
+}
+
+/// Profiles with their inputs, as shown when a profile is unknown.
+pub fn profiles_summary() -> String {
+    PROFILES
+        .iter()
+        .map(|p| {
+            let inputs: Vec<String> = p
+                .inputs
+                .iter()
+                .map(|(name, _, default)| match default {
+                    None => name.to_string(),
+                    Some(d) => format!("[{}={}]", name, d),
+                })
+                .collect();
+            format!("- {} ({}): {}", p.name, inputs.join(", "), p.description)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Checks inputs against the profile and fills in defaults; lists are accepted as arrays or comma-separated text.
+fn resolve_inputs(profile: &Profile, given: &JsonMap<String, Value>) -> Result<Inputs, String> {
+    if let Some(unknown) = given
+        .keys()
+        .find(|k| !profile.inputs.iter().any(|(n, _, _)| n == k))
+    {
+        let names: Vec<&str> = profile.inputs.iter().map(|(n, _, _)| *n).collect();
+        return Err(format!(
+            "unknown input '{}' for template {}; inputs: {}",
+            unknown,
+            profile.name,
+            names.join(", ")
+        ));
+    }
+    let mut values = JsonMap::new();
+    for (name, _, default) in profile.inputs {
+        let value = match given.get(*name) {
+            Some(Value::String(s)) => s.trim().to_string(),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|i| {
+                    i.as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| i.to_string())
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            Some(Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        };
+        if value.is_empty() && default.is_none() {
+            return Err(format!("template {} needs input '{}'", profile.name, name));
+        }
+        values.insert(name.to_string(), json!(value));
+    }
+    let mut inputs = Inputs(values);
+    // Defaults are filled in order, so they may refer to any input listed before them ({topic}, {warehouse}).
+    for (name, _, default) in profile.inputs {
+        if let Some(d) = default.filter(|_| inputs.get(name).is_empty()) {
+            let filled = inputs.fill(d);
+            inputs.0.insert(name.to_string(), json!(filled));
+        }
+    }
+    Ok(inputs)
+}
+
+/// Identifier form of a name for tables, slots and publications ("orders.v1-eu" -> "orders_v1_eu").
+fn identifier(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// DNS-1123 form of a name for metadata.name ("Orders.V1" -> "orders-v1").
+fn dns_name(s: &str) -> String {
+    let name: String = s
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    name.trim_matches('-')
+        .chars()
+        .take(63)
+        .collect::<String>()
+        .trim_end_matches('-')
+        .to_string()
+}
+
+/// The given connection string, or a secretKeyRef to key in the flow's credentials Secret.
+fn connection(value: String, secret: &str, key: &str) -> Value {
+    if value.is_empty() {
+        json!({ "secretKeyRef": { "name": secret, "key": key } })
+    } else {
+        json!(value)
+    }
+}
+
+fn kafka_to_warehouse(inputs: &Inputs, secret: &str) -> Result<Flow, String> {
+    let source = json!({
+        "brokers": inputs.list("brokers"),
+        "topic": inputs.get("topic"),
+        "consumerGroup": inputs.get("consumer_group"),
+        "format": "json",
+        "offsetReset": "earliest"
+    });
+    let transformations = json!([
+        { "type": "snakeCase", "snakeCase": { "deep": true } },
+        { "type": "timestamp", "timestamp": { "fieldName": "ingested_at", "format": "RFC3339" } }
+    ]);
+    let table = identifier(&inputs.get("table"));
+    let warehouse = inputs.get("warehouse");
+    let sink = match warehouse.as_str() {
+        "clickhouse" | "postgresql" => json!({
+            "connectionString": connection(inputs.get("connection"), secret, "sinkConnectionString"),
+            "table": table,
+            "batchSize": if warehouse == "clickhouse" { 10000 } else { 1000 },
+            "batchFlushIntervalSeconds": 5,
+            "autoCreateTable": true
+        }),
+        "trino" => json!({
+            "serverURL": connection(inputs.get("connection"), secret, "sinkServerURL"),
+            "catalog": inputs.get("catalog"),
+            "schema": inputs.get("schema"),
+            "table": table,
+            "catalogType": "iceberg",
+            "insertStrategy": "insert",
+            "batchSize": 10000,
+            "batchFlushIntervalSeconds": 10
+        }),
+        other => {
+            return Err(format!(
+                "warehouse '{}' is not supported; use clickhouse, postgresql or trino",
+                other
+            ))
+        }
+    };
+    Ok(("kafka", warehouse, source, sink, transformations))
+}
+
+fn cdc_replication(inputs: &Inputs, secret: &str) -> Result<Flow, String> {
+    let table = inputs.get("table");
+    let source = json!({
+        "connectionString": connection(inputs.get("source_connection"), secret, "sourceConnectionString"),
+        "table": table,
+        "replication": {
+            "slotName": identifier(&inputs.get("slot_name")),
+            "publication": identifier(&inputs.get("publication")),
+            "plugin": "pgoutput"
+        }
+    });
+    let transformations = json!([{ "type": "timestamp", "timestamp": { "fieldName": "replicated_at", "format": "RFC3339" } }]);
+    let target = inputs.get("target");
+    let sink = match target.as_str() {
+        "kafka" => {
+            json!({ "brokers": inputs.list("brokers"), "topic": inputs.get("topic"), "format": "json" })
+        }
+        "postgresql" => json!({
+            "connectionString": connection(inputs.get("target_connection"), secret, "sinkConnectionString"),
+            "table": inputs.get("target_table"),
+            "upsertMode": true,
+            "conflictKey": inputs.get("key"),
+            "batchSize": 1000,
+            "batchFlushIntervalSeconds": 5
+        }),
+        other => {
+            return Err(format!(
+                "target '{}' is not supported; use kafka or postgresql",
+                other
+            ))
+        }
+    };
+    Ok(("postgresql", target, source, sink, transformations))
+}
+
+fn pii_masking_pipeline(inputs: &Inputs, _secret: &str) -> Result<Flow, String> {
+    let brokers = inputs.list("brokers");
+    let source = json!({
+        "brokers": brokers,
+        "topic": inputs.get("input_topic"),
+        "consumerGroup": inputs.get("consumer_group"),
+        "format": "json",
+        "offsetReset": "earliest"
+    });
+    let paths = |name: &str| -> Vec<String> {
+        inputs
+            .list(name)
+            .iter()
+            .map(|f| format!("$.{}", f.trim_start_matches("$.")))
+            .collect()
+    };
+    let mut transformations = Vec::new();
+    if !inputs.list("drop_fields").is_empty() {
+        transformations
+            .push(json!({ "type": "remove", "remove": { "fields": paths("drop_fields") } }));
+    }
+    if !inputs.list("pii_fields").is_empty() {
+        transformations.push(json!({ "type": "mask", "mask": { "fields": paths("pii_fields"), "maskChar": "*", "keepLength": true } }));
+    }
+    let sink = json!({ "brokers": brokers, "topic": inputs.get("output_topic"), "format": "json" });
+    Ok((
+        "kafka",
+        "kafka".to_string(),
+        source,
+        sink,
+        Value::Array(transformations),
+    ))
+}
+
+/// Generates the manifest of a template profile from its inputs; the flow name defaults to the profile's pattern
+/// (e.g. orders-to-clickhouse) and credentials default to secretKeyRefs to <name>-credentials.
+pub fn generate_from_template(
+    profile: &str,
+    inputs: &JsonMap<String, Value>,
+    name: Option<&str>,
+    namespace: Option<&str>,
+) -> Result<String, String> {
+    let Some(profile) = PROFILES.iter().find(|p| p.name == profile) else {
+        return Err(format!(
+            "Unknown template '{}'; templates:\n{}",
+            profile,
+            profiles_summary()
+        ));
+    };
+    let inputs = resolve_inputs(profile, inputs)?;
+    let name = name
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| dns_name(&inputs.fill(profile.flow_name)));
+    let (source_type, sink_type, source, sink, transformations) =
+        (profile.build)(&inputs, &secret_name(Some(&name)))?;
+    let generated = generate_dataflow_manifest(
+        None,
+        Some(source_type),
+        Some(&sink_type),
+        Some(&source),
+        Some(&sink),
+        Some(&transformations),
+        Some(&name),
+        namespace,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    let (first, rest) = generated.split_once('\n').unwrap_or((&generated, ""));
+    Ok(format!(
+        "{}\n# Template: {} ({})\n{}",
+        first, profile.name, profile.description, rest
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::manifest::validate_dataflow_manifest;
+
+    fn inputs(v: Value) -> JsonMap<String, Value> {
+        v.as_object().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_templates_produce_valid_manifests() {
+        let cases = [
+            (
+                "kafka-to-warehouse",
+                json!({ "brokers": "k1:9092,k2:9092", "topic": "orders" }),
+            ),
+            (
+                "kafka-to-warehouse",
+                json!({ "brokers": ["k:9092"], "topic": "orders", "warehouse": "trino" }),
+            ),
+            ("cdc-replication", json!({ "table": "orders" })),
+            (
+                "cdc-replication",
+                json!({ "table": "orders", "target": "postgresql", "key": "order_id" }),
+            ),
+            (
+                "pii-masking-pipeline",
+                json!({ "brokers": "k:9092", "input_topic": "users" }),
+            ),
+        ];
+        for (profile, given) in cases {
+            let yaml = generate_from_template(profile, &inputs(given), None, None).unwrap();
+            assert!(yaml.contains(&format!("# Template: {} (", profile)));
+            if let Err(errors) = validate_dataflow_manifest(&yaml) {
+                panic!("{}: {:?}", profile, errors);
+            }
+        }
+    }
+
+    #[test]
+    fn test_template_defaults_and_inputs() {
+        let yaml = generate_from_template(
+            "kafka-to-warehouse",
+            &inputs(json!({ "brokers": "k:9092", "topic": "shop.orders" })),
+            None,
+            None,
+        )
+        .unwrap();
+        let doc: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(doc["metadata"]["name"], "shop-orders-to-clickhouse");
+        let sink = &doc["spec"]["sink"]["clickhouse"];
+        assert_eq!(sink["table"], "shop_orders");
+        assert_eq!(
+            sink["connectionString"]["secretKeyRef"],
+            json!({ "name": "shop-orders-to-clickhouse-credentials", "key": "sinkConnectionString" })
+        );
+        let yaml = generate_from_template("pii-masking-pipeline", &inputs(json!({ "brokers": "k:9092", "input_topic": "users", "pii_fields": ["email", "$.card"] })), Some("mask"), None).unwrap();
+        let doc: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(
+            doc["spec"]["transformations"][1]["mask"]["fields"],
+            json!(["$.email", "$.card"])
+        );
+        assert_eq!(doc["spec"]["sink"]["kafka"]["topic"], "users-masked");
+        let err = generate_from_template(
+            "cdc-replication",
+            &inputs(json!({ "tables": "orders" })),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, "unknown input 'tables' for template cdc-replication; inputs: table, target, brokers, topic, target_table, key, slot_name, publication, source_connection, target_connection");
+        assert!(
+            generate_from_template("lakehouse", &JsonMap::new(), None, None)
+                .unwrap_err()
+                .contains("- pii-masking-pipeline (brokers, input_topic")
+        );
+    }
+}
@@ -0,0 +1,122 @@
+// Session workspace: manifests, templates and answers saved during a session, exportable as one JSON bundle.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Bundle format version; import rejects bundles from a newer format.
+const WORKSPACE_BUNDLE_VERSION: u32 = 1;
+
+pub const WORKSPACE_KINDS: [&str; 3] = ["manifest", "template", "answer"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    #[serde(default)]
+    pub manifests: BTreeMap<String, String>,
+    #[serde(default)]
+    pub templates: BTreeMap<String, String>,
+    #[serde(default)]
+    pub answers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WorkspaceBundle {
+    version: u32,
+    #[serde(flatten)]
+    workspace: Workspace,
+}
+
+impl Workspace {
+    fn entries_mut(&mut self, kind: &str) -> Result<&mut BTreeMap<String, String>, String> {
+        match kind {
+            "manifest" => Ok(&mut self.manifests),
+            "template" => Ok(&mut self.templates),
+            "answer" => Ok(&mut self.answers),
+            _ => Err(format!(
+                "kind must be one of: {}",
+                WORKSPACE_KINDS.join(", ")
+            )),
+        }
+    }
+
+    /// Saves (or overwrites) an entry; kind is manifest, template or answer.
+    pub fn save(&mut self, kind: &str, key: &str, content: &str) -> Result<(), String> {
+        if key.trim().is_empty() {
+            return Err("key must not be empty".to_string());
+        }
+        self.entries_mut(kind)?
+            .insert(key.to_string(), content.to_string());
+        Ok(())
+    }
+
+    /// Exports the whole workspace as a versioned JSON bundle.
+    pub fn export(&self) -> String {
+        let bundle = WorkspaceBundle {
+            version: WORKSPACE_BUNDLE_VERSION,
+            workspace: self.clone(),
+        };
+        serde_json::to_string_pretty(&bundle).unwrap_or_default()
+    }
+
+    /// Imports a bundle produced by export. With replace the current workspace is discarded,
+    /// otherwise entries are merged and bundle entries win on key conflicts. Returns a summary.
+    pub fn import(&mut self, bundle_json: &str, replace: bool) -> Result<String, String> {
+        let bundle: WorkspaceBundle = serde_json::from_str(bundle_json)
+            .map_err(|e| format!("Invalid workspace bundle: {}", e))?;
+        if bundle.version > WORKSPACE_BUNDLE_VERSION {
+            return Err(format!(
+                "Workspace bundle version {} is newer than supported version {}",
+                bundle.version, WORKSPACE_BUNDLE_VERSION
+            ));
+        }
+        if replace {
+            *self = Workspace::default();
+        }
+        let w = bundle.workspace;
+        let summary = format!(
+            "Imported {} manifest(s), {} template(s), {} answer(s)",
+            w.manifests.len(),
+            w.templates.len(),
+            w.answers.len()
+        );
+        self.manifests.extend(w.manifests);
+        self.templates.extend(w.templates);
+        self.answers.extend(w.answers);
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_export_import_roundtrip() {
+        let mut ws = Workspace::default();
+        ws.save(
+            "manifest",
+            "orders",
+            "apiVersion: dataflow.dataflow.io/v1\n",
+        )
+        .unwrap();
+        ws.save("answer", "target-namespace", "streaming").unwrap();
+        assert!(ws.save("secret", "x", "y").is_err());
+        let bundle = ws.export();
+
+        let mut other = Workspace::default();
+        other.save("template", "kafka-to-pg", "{}").unwrap();
+        let summary = other.import(&bundle, false).unwrap();
+        assert_eq!(
+            summary,
+            "Imported 1 manifest(s), 0 template(s), 1 answer(s)"
+        );
+        assert_eq!(other.templates.len(), 1);
+        assert_eq!(other.manifests["orders"], ws.manifests["orders"]);
+
+        other.import(&bundle, true).unwrap();
+        assert_eq!(other, ws);
+        assert!(other
+            .import(r#"{"version": 99}"#, false)
+            .unwrap_err()
+            .contains("newer"));
+    }
+}
@@ -2,29 +2,52 @@
 // Generation uses serde_json::Value maps for flexibility.
 
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ParsedDataFlow {
     #[serde(rename = "apiVersion")]
     pub api_version: Option<String>,
     pub kind: Option<String>,
-    #[allow(dead_code)]
     pub metadata: Option<ParsedMetadata>,
     pub spec: Option<ParsedSpec>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ParsedMetadata {
-    #[allow(dead_code)]
     pub name: Option<String>,
-    #[allow(dead_code)]
     pub namespace: Option<String>,
+    pub labels: Option<BTreeMap<String, serde_json::Value>>,
+    pub annotations: Option<BTreeMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ParsedSpec {
     pub source: Option<ParsedSource>,
     pub sink: Option<ParsedSink>,
+    pub transformations: Option<Vec<serde_json::Value>>,
+    pub branches: Option<BTreeMap<String, ParsedBranch>>,
+    #[serde(rename = "errorHandling")]
+    pub error_handling: Option<ParsedErrorHandling>,
+    /// Number of parallel workers (pods) processing the flow.
+    pub parallelism: Option<serde_json::Value>,
+    /// Pod resources: {requests, limits} of cpu / memory quantities.
+    pub resources: Option<serde_json::Value>,
+    /// Not a v1 field (v1alpha1 name of parallelism); reported with a pointer to parallelism.
+    pub replicas: Option<serde_json::Value>,
+}
+
+/// Shared error handling: the DLQ that transformations with onError: dlq send failed messages to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedErrorHandling {
+    pub dlq: Option<ParsedSink>,
+}
+
+/// Named branch: a router route can target it by name; its own transformations run before its sink.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedBranch {
+    pub transformations: Option<Vec<serde_json::Value>>,
+    pub sink: Option<ParsedSink>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -35,6 +58,7 @@ pub struct ParsedSource {
     pub postgresql: Option<serde_json::Value>,
     pub trino: Option<serde_json::Value>,
     pub clickhouse: Option<serde_json::Value>,
+    pub syslog: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -45,9 +69,137 @@ pub struct ParsedSink {
     pub postgresql: Option<serde_json::Value>,
     pub trino: Option<serde_json::Value>,
     pub clickhouse: Option<serde_json::Value>,
+    pub elasticsearch: Option<serde_json::Value>,
+    pub s3: Option<serde_json::Value>,
 }
 
 pub const DATAFLOW_API_VERSION: &str = "dataflow.dataflow.io/v1";
 pub const DATAFLOW_KIND: &str = "DataFlow";
-pub const SOURCE_TYPES: [&str; 4] = ["kafka", "postgresql", "trino", "clickhouse"];
-pub const SINK_TYPES: [&str; 4] = ["kafka", "postgresql", "trino", "clickhouse"];
+pub const DATAFLOW_CRD_NAME: &str = "dataflows.dataflow.dataflow.io";
+pub const SOURCE_TYPES: [&str; 5] = ["kafka", "postgresql", "trino", "clickhouse", "syslog"];
+pub const SINK_TYPES: [&str; 6] = [
+    "kafka",
+    "postgresql",
+    "trino",
+    "clickhouse",
+    "elasticsearch",
+    "s3",
+];
+pub const ON_ERROR_POLICIES: [&str; 3] = ["skip", "dlq", "fail"];
+pub const DLQ_TYPES: [&str; 2] = ["kafka", "postgresql"];
+pub const SCRIPT_LANGUAGES: [&str; 3] = ["lua", "javascript", "expr"];
+pub const SYSLOG_PROTOCOLS: [&str; 2] = ["udp", "tcp"];
+pub const SYSLOG_PARSERS: [&str; 2] = ["rfc3164", "rfc5424"];
+pub const ENRICH_BACKENDS: [&str; 2] = ["http", "grpc"];
+pub const RELATIONAL_SINK_TYPES: [&str; 3] = ["postgresql", "trino", "clickhouse"];
+pub const BATCH_TRANSFORMATION_TYPES: [&str; 3] = ["sort", "dedupe", "chunk"];
+pub const KAFKA_FORMATS: [&str; 5] = ["json", "avro", "string", "protobuf", "jsonschema"];
+pub const KAFKA_OFFSET_RESETS: [&str; 2] = ["earliest", "latest"];
+pub const CLOUDEVENTS_MODES: [&str; 2] = ["binary", "structured"];
+pub const PAYLOAD_MODES: [&str; 3] = ["columns", "jsonb", "bytea"];
+pub const PAYLOAD_COMPRESSIONS: [&str; 3] = ["none", "gzip", "zstd"];
+pub const ES_NULL_VALUE_BEHAVIORS: [&str; 3] = ["ignore", "delete", "fail"];
+pub const ES_DOCUMENT_ID_STRATEGIES: [&str; 2] = ["auto", "key"];
+pub const TRINO_INSERT_STRATEGIES: [&str; 3] = ["insert", "ctas", "merge"];
+pub const TRINO_CATALOG_TYPES: [&str; 2] = ["hive", "iceberg"];
+pub const TRINO_HIVE_FILE_FORMATS: [&str; 5] = ["ORC", "PARQUET", "AVRO", "JSON", "TEXTFILE"];
+pub const TRINO_ICEBERG_FILE_FORMATS: [&str; 3] = ["PARQUET", "ORC", "AVRO"];
+pub const S3_FORMATS: [&str; 3] = ["json", "avro", "parquet"];
+pub const S3_PARTITIONERS: [&str; 3] = ["default", "field", "time"];
+pub const SUBJECT_NAME_STRATEGIES: [&str; 3] = [
+    "TopicNameStrategy",
+    "RecordNameStrategy",
+    "TopicRecordNameStrategy",
+];
+pub const SCHEMA_REGISTRY_AUTH_TYPES: [&str; 2] = ["basic", "bearer"];
+/// Connectors with a schemaRegistry block and the formats that read their schema from it.
+pub const SCHEMA_REGISTRY_CONNECTORS: [(&str, &[&str]); 2] = [
+    ("kafka", &["avro", "protobuf", "jsonschema"]),
+    ("s3", &["avro", "parquet"]),
+];
+
+/// A cross-field dependency of a connector block: when `field` is set (to `equals`, if given), `requires` must be
+/// set too — every one of them, or at least one with `any`. Nested fields use dots; `when` names the trigger in
+/// error messages.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldDependency {
+    pub connector: &'static str,
+    pub field: &'static str,
+    pub equals: Option<&'static str>,
+    pub requires: &'static [&'static str],
+    pub any: bool,
+    pub when: &'static str,
+}
+
+pub const FIELD_DEPENDENCIES: [FieldDependency; 8] = [
+    FieldDependency {
+        connector: "kafka",
+        field: "format",
+        equals: Some("avro"),
+        requires: &["schemaRegistry", "avroSchema"],
+        any: true,
+        when: "format avro",
+    },
+    FieldDependency {
+        connector: "kafka",
+        field: "format",
+        equals: Some("protobuf"),
+        requires: &["schemaRegistry", "protoSchema"],
+        any: true,
+        when: "format protobuf",
+    },
+    FieldDependency {
+        connector: "kafka",
+        field: "format",
+        equals: Some("jsonschema"),
+        requires: &["schemaRegistry", "jsonSchema"],
+        any: true,
+        when: "format jsonschema",
+    },
+    FieldDependency {
+        connector: "kafka",
+        field: "protoSchema",
+        equals: None,
+        requires: &["messageType"],
+        any: false,
+        when: "an inline protoSchema",
+    },
+    FieldDependency {
+        connector: "kafka",
+        field: "schemaRegistry",
+        equals: None,
+        requires: &["schemaRegistry.url"],
+        any: false,
+        when: "schemaRegistry",
+    },
+    FieldDependency {
+        connector: "s3",
+        field: "schemaRegistry",
+        equals: None,
+        requires: &["schemaRegistry.url"],
+        any: false,
+        when: "schemaRegistry",
+    },
+    FieldDependency {
+        connector: "postgresql",
+        field: "upsertMode",
+        equals: Some("true"),
+        requires: &["conflictKey"],
+        any: false,
+        when: "upsertMode: true",
+    },
+    FieldDependency {
+        connector: "trino",
+        field: "keycloak",
+        equals: None,
+        requires: &["keycloak.clientId", "keycloak.clientSecret"],
+        any: false,
+        when: "keycloak authentication",
+    },
+];
+pub const PG_REPLICATION_PLUGINS: [&str; 2] = ["pgoutput", "wal2json"];
+pub const POLLING_SOURCE_TYPES: [&str; 3] = ["postgresql", "trino", "clickhouse"];
+pub const POLL_MODES: [&str; 2] = ["bulk", "incremental"];
+pub const CURSOR_STORAGES: [&str; 2] = ["memory", "configmap"];
+pub const MAX_PARALLELISM: i64 = 128;
+pub const FINDING_OUTPUT_FORMATS: [&str; 3] = ["text", "sarif", "json"];